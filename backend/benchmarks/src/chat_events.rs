@@ -31,6 +31,7 @@ fn push_simple_text_messages() -> BenchResult {
             message_id: MessageId::from(u64::MAX - i),
             content: MessageContentInternal::Text(TextContentInternal {
                 text: "1".repeat(i as usize),
+                components: Vec::new(),
             }),
             sender_context: None,
             mentioned: Vec::new(),
@@ -74,7 +75,10 @@ fn add_reactions() -> BenchResult {
             sender: canister_id_from_u64(1).into(),
             thread_root_message_index: None,
             message_id,
-            content: MessageContentInternal::Text(TextContentInternal { text: "1".to_string() }),
+            content: MessageContentInternal::Text(TextContentInternal {
+                text: "1".to_string(),
+                components: Vec::new(),
+            }),
             sender_context: None,
             mentioned: Vec::new(),
             replies_to: None,
@@ -67,6 +67,8 @@ async fn prepare_airdrop(config: AirdropConfig, user_index_canister_id: Canister
             public: None,
             messages_visible_to_non_members: None,
             external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     )
     .await
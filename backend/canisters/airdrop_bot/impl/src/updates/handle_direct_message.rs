@@ -13,7 +13,7 @@ fn handle_direct_message(_args: Args) -> Response {
         bot_display_name: None,
         messages: vec![BotMessage {
             thread_root_message_id: None,
-            content: MessageContentInitial::Text(TextContent { text }),
+            content: MessageContentInitial::Text(TextContent { text, components: None }),
             message_id: None,
             block_level_markdown: None,
             og_previews: None,
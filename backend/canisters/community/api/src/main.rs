@@ -14,11 +14,14 @@ fn main() {
     generate_ts_method!(community, events_by_index);
     generate_ts_method!(community, events_window);
     generate_ts_method!(community, events);
+    generate_ts_method!(community, export_history);
     generate_ts_method!(community, explore_channels);
     generate_ts_method!(community, invite_code);
     generate_ts_method!(community, local_user_index);
     generate_ts_method!(community, lookup_members);
+    generate_ts_method!(community, message_edit_history);
     generate_ts_method!(community, messages_by_message_index);
+    generate_ts_method!(community, outgoing_webhooks);
     generate_ts_method!(community, search_channel);
     generate_ts_method!(community, selected_channel_initial);
     generate_ts_method!(community, selected_channel_updates_v2);
@@ -44,6 +47,7 @@ fn main() {
     generate_ts_method!(community, delete_channel);
     generate_ts_method!(community, delete_channel_history);
     generate_ts_method!(community, delete_messages);
+    generate_ts_method!(community, delete_outgoing_webhook);
     generate_ts_method!(community, delete_user_groups);
     generate_ts_method!(community, delete_webhook);
     generate_ts_method!(community, disable_invite_code);
@@ -51,10 +55,13 @@ fn main() {
     generate_ts_method!(community, enable_invite_code);
     generate_ts_method!(community, follow_thread);
     generate_ts_method!(community, import_group);
+    generate_ts_method!(community, import_history);
     generate_ts_method!(community, join_video_call);
     generate_ts_method!(community, leave_channel);
     generate_ts_method!(community, pin_message);
+    generate_ts_method!(community, press_message_button);
     generate_ts_method!(community, regenerate_webhook);
+    generate_ts_method!(community, register_outgoing_webhook);
     generate_ts_method!(community, register_poll_vote);
     generate_ts_method!(community, register_proposal_vote);
     generate_ts_method!(community, register_proposal_vote_v2);
@@ -66,8 +73,10 @@ fn main() {
     generate_ts_method!(community, reset_invite_code);
     generate_ts_method!(community, send_message);
     generate_ts_method!(community, set_member_display_name);
+    generate_ts_method!(community, set_notification_filters);
     generate_ts_method!(community, set_video_call_presence);
     generate_ts_method!(community, toggle_mute_notifications);
+    generate_ts_method!(community, translate_message);
     generate_ts_method!(community, unblock_user);
     generate_ts_method!(community, undelete_messages);
     generate_ts_method!(community, unfollow_thread);
@@ -75,6 +84,7 @@ fn main() {
     generate_ts_method!(community, update_bot);
     generate_ts_method!(community, update_channel);
     generate_ts_method!(community, update_community);
+    generate_ts_method!(community, update_outgoing_webhook);
     generate_ts_method!(community, update_user_group);
     generate_ts_method!(community, update_webhook);
 }
@@ -0,0 +1,17 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use types::{BotEventsSinceResult, ChannelId, TimestampMillis, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub channel_id: ChannelId,
+    pub since: Option<TimestampMillis>,
+    pub max_events: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(BotEventsSinceResult),
+    Error(OCError),
+}
@@ -0,0 +1,19 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, ChatExportCursor, ChatExportPage};
+
+#[ts_export(community, export_history)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub after: Option<ChatExportCursor>,
+    pub max_events: u32,
+}
+
+#[ts_export(community, export_history)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(ChatExportPage),
+    Error(OCError),
+}
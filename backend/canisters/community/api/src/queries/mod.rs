@@ -3,6 +3,7 @@ pub mod c2c_active_proposal_tallies;
 pub mod c2c_bot_channel_details;
 pub mod c2c_bot_community_events;
 pub mod c2c_bot_community_summary;
+pub mod c2c_bot_events_since;
 pub mod c2c_bot_members;
 pub mod c2c_can_issue_access_token;
 pub mod c2c_events;
@@ -18,10 +19,13 @@ pub mod events;
 pub mod events_by_index;
 pub mod events_window;
 pub mod explore_channels;
+pub mod export_history;
 pub mod invite_code;
 pub mod local_user_index;
 pub mod lookup_members;
+pub mod message_edit_history;
 pub mod messages_by_message_index;
+pub mod outgoing_webhooks;
 pub mod search_channel;
 pub mod selected_channel_initial;
 pub mod selected_channel_updates_v2;
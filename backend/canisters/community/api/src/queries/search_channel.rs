@@ -2,12 +2,13 @@ use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use ts_export::ts_export;
-use types::{ChannelId, MessageMatch, UserId};
+use types::{ChannelId, MessageIndex, MessageMatch, UserId};
 
 #[ts_export(community, search_channel)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub channel_id: ChannelId,
+    pub thread_root_message_index: Option<MessageIndex>,
     pub search_term: String,
     pub max_results: u8,
     pub users: Option<HashSet<UserId>>,
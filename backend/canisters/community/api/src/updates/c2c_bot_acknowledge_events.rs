@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use types::{ChannelId, TimestampMillis, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub channel_id: ChannelId,
+    pub cursor: TimestampMillis,
+}
+
+pub type Response = UnitResult;
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use types::{ChannelId, UserId};
+use types::{ChannelId, UserId, WebhookMessage, WebhookSignature};
 
 use super::send_message;
 
@@ -7,8 +7,9 @@ use super::send_message;
 pub struct Args {
     pub id: UserId,
     pub channel_id: ChannelId,
-    pub secret: String,
-    pub message: String,
+    pub secret: Option<String>,
+    pub message: WebhookMessage,
+    pub signature: Option<WebhookSignature>,
 }
 
 pub type Response = send_message::Response;
@@ -0,0 +1,24 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, ImportedMessage, MessageIndex};
+
+#[ts_export(community, import_history)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub messages: Vec<ImportedMessage>,
+}
+
+#[ts_export(community, import_history)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(community, import_history)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub message_indexes: Vec<MessageIndex>,
+}
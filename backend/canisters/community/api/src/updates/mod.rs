@@ -2,6 +2,7 @@ pub mod accept_p2p_swap;
 pub mod add_members_to_channel;
 pub mod add_reaction;
 pub mod block_user;
+pub mod c2c_bot_acknowledge_events;
 pub mod c2c_bot_add_reaction;
 pub mod c2c_bot_change_channel_role;
 pub mod c2c_bot_create_channel;
@@ -45,6 +46,7 @@ pub mod decline_invitation;
 pub mod delete_channel;
 pub mod delete_channel_history;
 pub mod delete_messages;
+pub mod delete_outgoing_webhook;
 pub mod delete_user_groups;
 pub mod delete_webhook;
 pub mod disable_invite_code;
@@ -54,10 +56,13 @@ pub mod end_video_call_v2;
 pub mod follow_thread;
 pub mod handle_webhook;
 pub mod import_group;
+pub mod import_history;
 pub mod join_video_call;
 pub mod leave_channel;
 pub mod pin_message;
+pub mod press_message_button;
 pub mod regenerate_webhook;
+pub mod register_outgoing_webhook;
 pub mod register_poll_vote;
 pub mod register_proposal_vote;
 pub mod register_proposal_vote_v2;
@@ -69,9 +74,11 @@ pub mod report_message;
 pub mod reset_invite_code;
 pub mod send_message;
 pub mod set_member_display_name;
+pub mod set_notification_filters;
 pub mod set_video_call_presence;
 pub mod start_video_call_v2;
 pub mod toggle_mute_notifications;
+pub mod translate_message;
 pub mod unblock_user;
 pub mod undelete_messages;
 pub mod unfollow_thread;
@@ -79,5 +86,6 @@ pub mod unpin_message;
 pub mod update_bot;
 pub mod update_channel;
 pub mod update_community;
+pub mod update_outgoing_webhook;
 pub mod update_user_group;
 pub mod update_webhook;
@@ -0,0 +1,29 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_export::ts_export;
+use types::{ChannelId, ChatEventType, UserId};
+
+#[ts_export(community, register_outgoing_webhook)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub name: String,
+    pub url: String,
+    pub event_types: HashSet<ChatEventType>,
+}
+
+#[ts_export(community, register_outgoing_webhook)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(community, register_outgoing_webhook)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub id: UserId,
+    // The secret used to sign requests to the webhook. This is the only time it is returned.
+    pub secret: String,
+}
@@ -1,7 +1,7 @@
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{ChannelId, UserId};
+use types::{ChannelId, UserId, WebhookRateLimits};
 
 #[ts_export(community, register_webhook)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -9,6 +9,10 @@ pub struct Args {
     pub channel_id: ChannelId,
     pub name: String,
     pub avatar: Option<String>,
+    #[serde(default)]
+    pub rate_limits: Option<WebhookRateLimits>,
+    #[serde(default)]
+    pub require_signature: Option<bool>,
 }
 
 #[ts_export(community, register_webhook)]
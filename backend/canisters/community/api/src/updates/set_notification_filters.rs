@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChannelId, UnitResult};
+
+#[ts_export(community, set_notification_filters)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    // If not set, the filters are applied to all of the caller's channels
+    pub channel_id: Option<ChannelId>,
+    // If true, only notify of mentions, replies and keyword alerts, ignoring @everyone
+    pub mentions_only: Option<bool>,
+    // Keywords which trigger a notification even if the channel is muted
+    pub keyword_alerts: Option<Vec<String>>,
+}
+
+pub type Response = UnitResult;
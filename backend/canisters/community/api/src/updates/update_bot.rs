@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{BotPermissions, UnitResult, UserId};
+use types::{BotChannelPermissions, BotPermissions, OptionUpdate, UnitResult, UserId};
 
 #[ts_export(community, update_bot)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -8,6 +8,11 @@ pub struct Args {
     pub bot_id: UserId,
     pub granted_permissions: BotPermissions,
     pub granted_autonomous_permissions: Option<BotPermissions>,
+    // Restricts the bot to these channels, each with its own permissions. Replaces any existing
+    // restrictions, `SetToNone` removes them and `NoChange` leaves them as they are.
+    #[serde(default)]
+    #[ts(as = "types::OptionUpdateBotChannelPermissions")]
+    pub channel_permissions: OptionUpdate<Vec<BotChannelPermissions>>,
 }
 
 pub type Response = UnitResult;
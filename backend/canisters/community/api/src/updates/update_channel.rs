@@ -23,6 +23,10 @@ pub struct Args {
     pub messages_visible_to_non_members: Option<bool>,
     #[ts(as = "types::OptionUpdateString")]
     pub external_url: OptionUpdate<String>,
+    #[serde(default)]
+    pub edit_history_disabled: Option<bool>,
+    #[serde(default)]
+    pub e2ee_enabled: Option<bool>,
 }
 
 #[ts_export(community, update_channel)]
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_export::ts_export;
+use types::{ChannelId, ChatEventType, UnitResult, UserId};
+
+#[ts_export(community, update_outgoing_webhook)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub channel_id: ChannelId,
+    pub id: UserId,
+    pub url: Option<String>,
+    pub event_types: Option<HashSet<ChatEventType>>,
+    pub enabled: Option<bool>,
+}
+
+pub type Response = UnitResult;
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{ChannelId, OptionUpdate, UnitResult, UserId};
+use types::{ChannelId, OptionUpdate, UnitResult, UserId, WebhookRateLimits};
 
 #[ts_export(community, update_webhook)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -10,6 +10,10 @@ pub struct Args {
     pub name: Option<String>,
     #[ts(as = "types::OptionUpdateString")]
     pub avatar: OptionUpdate<String>,
+    #[serde(default)]
+    pub rate_limits: Option<WebhookRateLimits>,
+    #[serde(default)]
+    pub require_signature: Option<bool>,
 }
 
 pub type Response = UnitResult;
@@ -6,6 +6,7 @@ generate_c2c_call!(c2c_active_proposal_tallies);
 generate_c2c_call!(c2c_bot_channel_details);
 generate_c2c_call!(c2c_bot_community_events);
 generate_c2c_call!(c2c_bot_community_summary);
+generate_c2c_call!(c2c_bot_events_since);
 generate_c2c_call!(c2c_bot_members);
 generate_c2c_call!(c2c_can_issue_access_token);
 generate_c2c_call!(c2c_events);
@@ -17,6 +18,7 @@ generate_c2c_call!(summary);
 generate_c2c_call!(summary_updates);
 
 // Updates
+generate_c2c_call!(c2c_bot_acknowledge_events);
 generate_c2c_call!(c2c_bot_add_reaction);
 generate_c2c_call!(c2c_bot_change_channel_role);
 generate_c2c_call!(c2c_bot_create_channel);
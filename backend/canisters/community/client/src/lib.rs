@@ -0,0 +1,6 @@
+use canister_client::generate_update_call;
+use community_canister::*;
+
+// Updates
+generate_update_call!(create_channel);
+generate_update_call!(import_history);
@@ -3,6 +3,7 @@ use crate::model::channels::Channels;
 use crate::model::groups_being_imported::{GroupBeingImportedSummary, GroupsBeingImported};
 use crate::model::local_user_index_event_batch::LocalUserIndexEventBatch;
 use crate::model::members::CommunityMembers;
+use crate::model::outgoing_webhook_delivery_batch::{OutgoingWebhookDeliveryBatch, OutgoingWebhookEvent};
 use crate::timer_job_types::{DeleteFileReferencesJob, MakeTransferJob, RemoveExpiredEventsJob, RemoveOldEventsJob, TimerJob};
 use activity_notification_state::ActivityNotificationState;
 use canister_state_macros::canister_state;
@@ -16,7 +17,7 @@ use gated_groups::{GatePayment, calculate_gate_payments};
 use group_chat_core::{AccessRulesInternal, AddResult};
 use group_community_common::{
     Achievements, ExpiringMember, ExpiringMemberActions, ExpiringMembers, Members, PaymentReceipts, PendingPaymentsQueue,
-    UserCache,
+    UserCache, outgoing_webhooks,
 };
 use ic_principal::Principal;
 use installed_bots::InstalledBots;
@@ -28,9 +29,10 @@ use oc_error_codes::OCErrorCode;
 use rand::Rng;
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
 use stable_memory_map::{BaseKeyPrefix, ChatEventKeyPrefix};
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::Deref;
 use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue};
 use types::{
@@ -41,7 +43,7 @@ use types::{
     IdempotentEnvelope, MembersAdded, MessageId, MessageIndex, Milliseconds, Notification, PendingCryptoTransaction, Rules,
     TimestampMillis, Timestamped, UserId, UserNotification, UserType,
 };
-use types::{BotSubscriptions, CommunityId};
+use types::{BotChannelPermissions, BotChatEvent, BotEvent, BotSubscriptions, Chat, CommunityId, OCResult, OptionUpdate};
 use user_canister::CommunityCanisterEvent;
 use utils::env::Environment;
 use utils::idempotency_checker::IdempotencyChecker;
@@ -172,10 +174,66 @@ impl RuntimeState {
     }
 
     pub fn push_bot_notification(&mut self, notification: Option<BotNotification>) {
-        if let Some(notification) = notification
-            && !notification.recipients.is_empty()
-        {
-            self.push_notification_inner(Notification::Bot(notification));
+        if let Some(mut notification) = notification {
+            // The events of end-to-end encrypted channels are never sent to bots or outgoing
+            // webhooks, including any which subscribed before encryption was enabled
+            if let BotEvent::Chat(BotChatEvent {
+                chat: Chat::Channel(_, channel_id),
+                ..
+            }) = &notification.event
+                && self
+                    .data
+                    .channels
+                    .get(channel_id)
+                    .is_some_and(|c| c.chat.is_end_to_end_encrypted())
+            {
+                return;
+            }
+
+            self.queue_outgoing_webhook_deliveries(&mut notification);
+
+            if !notification.recipients.is_empty() {
+                self.push_notification_inner(Notification::Bot(notification));
+            }
+        }
+    }
+
+    // Outgoing webhooks are subscribed to channel events in the same way as bots, so here we take
+    // them out of the notification's recipients and queue up a delivery to each of them instead
+    fn queue_outgoing_webhook_deliveries(&mut self, notification: &mut BotNotification) {
+        let BotEvent::Chat(BotChatEvent {
+            chat: Chat::Channel(_, channel_id),
+            ..
+        }) = &notification.event
+        else {
+            return;
+        };
+        let channel_id = *channel_id;
+
+        let Some(channel) = self.data.channels.get_mut(&channel_id) else {
+            return;
+        };
+
+        let now = self.env.now();
+        let deliveries = channel.chat.start_outgoing_webhook_deliveries(notification, now);
+        if deliveries.is_empty() {
+            return;
+        }
+
+        let data = ByteBuf::from(outgoing_webhooks::serialize_event(
+            self.data.local_user_index_canister_id,
+            notification.event.clone(),
+            notification.timestamp,
+        ));
+
+        for (webhook_id, delivery_id) in deliveries {
+            self.data.outgoing_webhook_delivery_queue.push(
+                (channel_id, webhook_id),
+                OutgoingWebhookEvent {
+                    delivery_id,
+                    data: data.clone(),
+                },
+            );
         }
     }
 
@@ -473,6 +531,7 @@ impl RuntimeState {
             timer_jobs: self.data.timer_jobs.len() as u32,
             queued_user_events: self.data.user_event_sync_queue.len() as u32,
             queued_local_index_events: self.data.local_user_index_event_sync_queue.len() as u32,
+            queued_outgoing_webhook_deliveries: self.data.outgoing_webhook_delivery_queue.len() as u32,
             stable_memory_sizes: memory::memory_sizes(),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
@@ -539,6 +598,10 @@ fn init_instruction_counts_log() -> InstructionCountsLog {
     InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
+fn outgoing_webhook_delivery_queue() -> GroupedTimerJobQueue<OutgoingWebhookDeliveryBatch> {
+    GroupedTimerJobQueue::new(5, true)
+}
+
 #[derive(Serialize, Deserialize)]
 struct Data {
     is_public: Timestamped<bool>,
@@ -583,6 +646,8 @@ struct Data {
     user_cache: UserCache,
     user_event_sync_queue: GroupedTimerJobQueue<UserEventBatch>,
     local_user_index_event_sync_queue: BatchedTimerJobQueue<LocalUserIndexEventBatch>,
+    #[serde(default = "outgoing_webhook_delivery_queue")]
+    outgoing_webhook_delivery_queue: GroupedTimerJobQueue<OutgoingWebhookDeliveryBatch>,
     stable_memory_keys_to_garbage_collect: Vec<BaseKeyPrefix>,
     bots: InstalledBots,
     verified: Timestamped<bool>,
@@ -695,6 +760,7 @@ impl Data {
             user_cache: UserCache::default(),
             user_event_sync_queue: GroupedTimerJobQueue::new(5, true),
             local_user_index_event_sync_queue: BatchedTimerJobQueue::new(local_user_index_canister_id, true),
+            outgoing_webhook_delivery_queue: outgoing_webhook_delivery_queue(),
             stable_memory_keys_to_garbage_collect: Vec::new(),
             bots: InstalledBots::default(),
             verified: Timestamped::default(),
@@ -1033,36 +1099,44 @@ impl Data {
         command_permissions: BotPermissions,
         autonomous_permissions: Option<BotPermissions>,
         default_subscriptions: Option<BotSubscriptions>,
+        channel_permissions: Option<Vec<BotChannelPermissions>>,
         now: TimestampMillis,
     ) -> bool {
         if !self.bots.add(
             bot_id,
             owner_id,
             command_permissions,
-            autonomous_permissions.clone(),
+            autonomous_permissions,
             default_subscriptions.clone(),
+            channel_permissions,
             now,
         ) {
             return false;
         }
 
-        if let (Some(subscriptions), Some(permissions)) = (default_subscriptions, autonomous_permissions) {
-            // Subscribe to permitted community events
-            self.events.subscribe_bot_to_events(
-                bot_id,
-                subscriptions.community,
-                &permissions.permitted_community_event_categories_to_read(),
-            );
+        if let Some(subscriptions) = default_subscriptions {
+            let bot = self.bots.get(&bot_id).unwrap();
+
+            // Subscribe to permitted community events, unless the bot is restricted to specific channels
+            if let Some(permissions) = bot.autonomous_permissions_in(None) {
+                self.events.subscribe_bot_to_events(
+                    bot_id,
+                    subscriptions.community,
+                    &permissions.permitted_community_event_categories_to_read(),
+                );
+            }
 
-            // Subscribe to permitted chat events for all public channels
-            let permitted_categories = permissions.permitted_chat_event_categories_to_read();
+            // Subscribe to permitted chat events for all public channels the bot has access to
 
             for channel in self.channels.iter_mut() {
-                if channel.chat.is_public.value {
-                    channel
-                        .chat
-                        .events
-                        .subscribe_bot_to_events(bot_id, subscriptions.chat.clone(), &permitted_categories);
+                if channel.chat.is_public.value
+                    && let Some(permissions) = bot.autonomous_permissions_in(Some(channel.id))
+                {
+                    channel.chat.events.subscribe_bot_to_events(
+                        bot_id,
+                        subscriptions.chat.clone(),
+                        &permissions.permitted_chat_event_categories_to_read(),
+                    );
                 }
             }
         }
@@ -1070,6 +1144,20 @@ impl Data {
         true
     }
 
+    // Channel permissions must only reference channels which exist
+    pub fn validate_bot_channel_permissions(&self, channel_permissions: Option<&Vec<BotChannelPermissions>>) -> OCResult {
+        if let Some(channel_permissions) = channel_permissions {
+            let mut channel_ids = HashSet::new();
+            for c in channel_permissions {
+                if !channel_ids.insert(c.channel_id) {
+                    return Err(OCErrorCode::InvalidRequest.with_message("Duplicate channel"));
+                }
+                self.channels.get_or_err(&c.channel_id)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn handle_bot_definition_updated(&mut self, update: BotDefinitionUpdate, now: TimestampMillis) {
         let bot_id = update.bot_id;
         if self.bots.update_from_definition(update, now) {
@@ -1083,11 +1171,12 @@ impl Data {
         bot_id: UserId,
         command_permissions: BotPermissions,
         autonomous_permissions: Option<BotPermissions>,
+        channel_permissions: OptionUpdate<Vec<BotChannelPermissions>>,
         now: TimestampMillis,
     ) -> bool {
         if self
             .bots
-            .update_permissions(bot_id, command_permissions, autonomous_permissions, now)
+            .update_permissions(bot_id, command_permissions, autonomous_permissions, channel_permissions, now)
         {
             self.apply_bot_update(bot_id, owner_id, now);
             true
@@ -1108,17 +1197,23 @@ impl Data {
 
         // Re-apply event subscriptions given the changes to permissions and/or subscriptions
         let bot = self.bots.get(&bot_id).unwrap();
-        let permissions = &bot.autonomous_permissions.clone().unwrap_or_default();
         let subscriptions = bot.default_subscriptions.clone().unwrap_or_default();
-        let permitted_chat_categories = permissions.permitted_chat_event_categories_to_read();
-        let permitted_community_categories = permissions.permitted_community_event_categories_to_read();
+        let permitted_community_categories = bot
+            .autonomous_permissions_in(None)
+            .map(|p| p.permitted_community_event_categories_to_read())
+            .unwrap_or_default();
 
         // Update community event subscriptions
         self.events
             .subscribe_bot_to_events(bot_id, subscriptions.community.clone(), &permitted_community_categories);
 
-        // Update chat event subscriptions for all channels
+        // Update chat event subscriptions for all channels, taking into account any per-channel permissions
         for channel in self.channels.iter_mut() {
+            let permitted_chat_categories = bot
+                .autonomous_permissions_in(Some(channel.id))
+                .map(|p| p.permitted_chat_event_categories_to_read())
+                .unwrap_or_default();
+
             channel
                 .chat
                 .events
@@ -1185,13 +1280,22 @@ impl Data {
         initiator: &BotInitiator,
         channel_id: Option<ChannelId>,
     ) -> Option<BotPermissions> {
+        // Bots can't read or send messages in end-to-end encrypted channels
+        if channel_id
+            .and_then(|id| self.channels.get(&id))
+            .is_some_and(|c| c.chat.is_end_to_end_encrypted())
+        {
+            return None;
+        }
+
         // Try to get the installed bot
         let bot = self.bots.get(bot_id)?;
 
-        // Get the permissions granted to the bot when initiated by command or autonomously
+        // Get the permissions granted to the bot when initiated by command or autonomously, if the
+        // bot is restricted to specific channels these are the permissions granted in the channel
         let granted_to_bot = match initiator {
-            BotInitiator::Command(_) => &bot.permissions,
-            BotInitiator::Autonomous => bot.autonomous_permissions.as_ref()?,
+            BotInitiator::Command(_) | BotInitiator::Interaction(_) => bot.permissions_in(channel_id)?,
+            BotInitiator::Autonomous => bot.autonomous_permissions_in(channel_id)?,
         };
 
         let (bot_is_channel_owner, is_private_channel) = if let Some(channel) = channel_id.and_then(|id| self.channels.get(&id))
@@ -1212,11 +1316,11 @@ impl Data {
         };
 
         match initiator {
-            BotInitiator::Command(command) => {
-                let user_permissions = self.get_user_permissions(&command.initiator, channel_id)?;
+            BotInitiator::Command(_) | BotInitiator::Interaction(_) => {
+                let user_permissions = self.get_user_permissions(&initiator.user()?, channel_id)?;
                 Some(BotPermissions::union(
                     &BotPermissions::intersect(granted_to_bot, &user_permissions),
-                    &bot.autonomous_permissions.clone().unwrap_or_default(),
+                    &bot.autonomous_permissions_in(channel_id).cloned().unwrap_or_default(),
                 ))
             }
             BotInitiator::Autonomous => {
@@ -1323,6 +1427,7 @@ pub struct Metrics {
     pub timer_jobs: u32,
     pub queued_user_events: u32,
     pub queued_local_index_events: u32,
+    pub queued_outgoing_webhook_deliveries: u32,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
     pub canister_ids: CanisterIds,
 }
@@ -296,6 +296,8 @@ impl Channel {
             mentions: chat.most_recent_mentions(m, None),
             notifications_muted: m.notifications_muted().value,
             at_everyone_muted: m.at_everyone_muted().value,
+            mentions_only: m.mentions_only().value,
+            keyword_alerts: m.keyword_alerts().value.clone(),
             my_metrics: chat
                 .events
                 .user_metrics(&m.user_id(), None)
@@ -325,6 +327,8 @@ impl Channel {
             is_public: chat.is_public.value,
             history_visible_to_new_joiners: chat.history_visible_to_new_joiners,
             messages_visible_to_non_members: chat.messages_visible_to_non_members.value,
+            edit_history_disabled: chat.edit_history_disabled.value,
+            e2ee_key_epoch: chat.e2ee_key_epoch.value,
             min_visible_event_index,
             min_visible_message_index,
             latest_message,
@@ -391,6 +395,8 @@ impl Channel {
             mentions: updates.mentions,
             notifications_muted: m.notifications_muted().if_set_after(since).cloned(),
             at_everyone_muted: m.at_everyone_muted().if_set_after(since).cloned(),
+            mentions_only: m.mentions_only().if_set_after(since).cloned(),
+            keyword_alerts: m.keyword_alerts().if_set_after(since).cloned(),
             my_metrics: self.chat.events.user_metrics(&m.user_id(), Some(since)).map(|m| m.hydrate()),
             latest_threads: m
                 .followed_threads
@@ -416,6 +422,8 @@ impl Channel {
             avatar_id: updates.avatar_id,
             is_public: updates.is_public,
             messages_visible_to_non_members: updates.messages_visible_to_non_members,
+            edit_history_disabled: updates.edit_history_disabled,
+            e2ee_key_epoch: updates.e2ee_key_epoch,
             latest_message,
             latest_message_sender_display_name,
             latest_event_index: updates.latest_event_index,
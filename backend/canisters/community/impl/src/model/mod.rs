@@ -4,5 +4,6 @@ pub mod groups_being_imported;
 pub mod invited_users;
 pub mod local_user_index_event_batch;
 pub mod members;
+pub mod outgoing_webhook_delivery_batch;
 pub mod user_event_batch;
 pub mod user_groups;
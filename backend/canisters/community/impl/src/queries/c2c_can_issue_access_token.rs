@@ -13,6 +13,15 @@ fn c2c_can_issue_access_token(args: Args) -> Response {
 
 fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Response {
     if let AccessTypeArgs::BotActionByCommand(args) = &args_outer.access_type {
+        // Bots can't read or send messages in end-to-end encrypted channels
+        if args_outer
+            .channel_id
+            .and_then(|channel_id| state.data.channels.get(&channel_id))
+            .is_some_and(|c| c.chat.is_end_to_end_encrypted())
+        {
+            return Response::Failure;
+        }
+
         // Ensure the initiator has the necessary seniority according to required role
         if !state
             .data
@@ -21,8 +30,14 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
             return Response::Failure;
         }
 
-        // Get the permissions granted to the bot in this community
-        let Some(granted_to_bot) = state.data.bots.get(&args.bot_id).map(|b| &b.permissions) else {
+        // Get the permissions granted to the bot in this community, or in the channel if it is
+        // restricted to specific channels
+        let Some(granted_to_bot) = state
+            .data
+            .bots
+            .get(&args.bot_id)
+            .and_then(|b| b.permissions_in(args_outer.channel_id))
+        else {
             return Response::Failure;
         };
 
@@ -47,6 +62,7 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
         AccessTypeArgs::StartVideoCall(args) => args.initiator,
         AccessTypeArgs::JoinVideoCall(args) => args.initiator,
         AccessTypeArgs::MarkVideoCallAsEnded(args) => args.initiator,
+        AccessTypeArgs::ChatEncryptionKey(args) => args.initiator,
         _ => unreachable!(),
     };
 
@@ -56,6 +72,13 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
 
     match &args_outer.access_type {
         AccessTypeArgs::JoinVideoCall(_) | AccessTypeArgs::MarkVideoCallAsEnded(_) => Response::Success,
+        AccessTypeArgs::ChatEncryptionKey(args) => {
+            if args.key_epoch >= channel.chat.min_key_epoch(&member) && args.key_epoch <= channel.chat.key_epoch() {
+                Response::Success
+            } else {
+                Response::Failure
+            }
+        }
         AccessTypeArgs::StartVideoCall(_) => {
             if member.role().is_permitted(channel.chat.permissions.start_video_call) {
                 Response::Success
@@ -0,0 +1,19 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::export_history::{Response::*, *};
+use types::{ChatExportPage, OCResult};
+
+#[query(msgpack = true)]
+fn export_history(args: Args) -> Response {
+    match read_state(|state| export_history_impl(args, state)) {
+        Ok(page) => Success(page),
+        Err(error) => Error(error),
+    }
+}
+
+fn export_history_impl(args: Args, state: &RuntimeState) -> OCResult<ChatExportPage> {
+    let user_id = state.get_caller_user_id()?;
+    let channel = state.data.channels.get_or_err(&args.channel_id)?;
+
+    channel.chat.export_history(user_id, args.after, args.max_events)
+}
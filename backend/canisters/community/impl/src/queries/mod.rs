@@ -5,6 +5,7 @@ mod active_proposal_tallies;
 mod c2c_bot_channel_details;
 mod c2c_bot_community_events;
 mod c2c_bot_community_summary;
+mod c2c_bot_events_since;
 mod c2c_bot_members;
 mod c2c_can_issue_access_token;
 mod channel_summary;
@@ -15,11 +16,14 @@ mod events;
 mod events_by_index;
 mod events_window;
 mod explore_channels;
+mod export_history;
 mod http_request;
 mod invite_code;
 mod local_user_index;
 mod lookup_members;
+mod message_edit_history;
 mod messages_by_message_index;
+mod outgoing_webhooks;
 mod search_channel;
 mod selected_channel_initial;
 mod selected_channel_updates;
@@ -0,0 +1,27 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use community_canister::outgoing_webhooks::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[query(msgpack = true)]
+fn outgoing_webhooks(args: Args) -> Response {
+    match read_state(|state| outgoing_webhooks_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn outgoing_webhooks_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let user_id = state.get_calling_member(true)?.user_id;
+    let channel = state.data.channels.get_or_err(&args.channel_id)?;
+    let member = channel.chat.members.get_verified_member(user_id)?;
+
+    if !member.role().can_manage_outgoing_webhooks() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    Ok(SuccessResult {
+        webhooks: channel.chat.outgoing_webhooks.details(),
+    })
+}
@@ -14,7 +14,13 @@ fn search_channel(args: Args) -> Response {
 fn search_channel_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
     let user_id = state.get_caller_user_id()?;
     let channel = state.data.channels.get_or_err(&args.channel_id)?;
-    let matches = channel.chat.search(user_id, args.search_term, args.users, args.max_results)?;
+    let matches = channel.chat.search(
+        user_id,
+        args.thread_root_message_index,
+        args.search_term,
+        args.users,
+        args.max_results,
+    )?;
 
     Ok(SuccessResult { matches })
 }
@@ -106,6 +106,7 @@ fn selected_updates_impl(args: Args, state: &RuntimeState) -> Response {
                         added_by: bot.added_by,
                         permissions: bot.permissions.clone(),
                         autonomous_permissions: bot.autonomous_permissions.clone(),
+                        channel_permissions: bot.channel_permissions(),
                     });
                 }
             }
@@ -8,8 +8,10 @@ use chat_events::{EndPollResult, MessageContentInternal};
 use constants::{DAY_IN_MS, MINUTE_IN_MS, NANOS_PER_MILLISECOND, SECOND_IN_MS};
 use event_store_types::TimestampMillis;
 use group_chat_core::AddResult;
+use icrc_ledger_types::icrc1::account::Account;
 use ledger_utils::process_transaction;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::{error, info};
 use types::{
     BlobReference, CanisterId, ChannelId, ChatId, MessageId, MessageIndex, P2PSwapStatus, PendingCryptoTransaction, UserId,
@@ -271,6 +273,29 @@ impl Job for DeleteFileReferencesJob {
 
 impl Job for EndPollJob {
     fn execute(self) {
+        let token_weighted_voters = read_state(|state| {
+            state.data.channels.get(&self.channel_id).and_then(|c| {
+                c.chat
+                    .events
+                    .token_weighted_poll_voters(self.thread_root_message_index, self.message_index)
+            })
+        });
+
+        if let Some((ledger_canister_id, voters)) = token_weighted_voters {
+            // Read the voters' balances as the poll ends so that tokens which have been moved
+            // between voters only count towards one of their votes
+            ic_cdk::futures::spawn_migratory(async move {
+                let final_balances = read_poll_voter_balances(ledger_canister_id, voters).await;
+                self.end_poll(Some(final_balances));
+            });
+        } else {
+            self.end_poll(None);
+        }
+    }
+}
+
+impl EndPollJob {
+    fn end_poll(&self, final_balances: Option<HashMap<UserId, u128>>) {
         mutate_state(|state| {
             let now = state.env.now();
             if let Some(channel) = state.data.channels.get_mut(&self.channel_id)
@@ -278,7 +303,7 @@ impl Job for EndPollJob {
                     channel
                         .chat
                         .events
-                        .end_poll(self.thread_root_message_index, self.message_index, now)
+                        .end_poll(self.thread_root_message_index, self.message_index, final_balances, now)
             {
                 state.push_bot_notification(result.bot_notification);
                 handle_activity_notification(state);
@@ -287,6 +312,31 @@ impl Job for EndPollJob {
     }
 }
 
+async fn read_poll_voter_balances(ledger_canister_id: CanisterId, voters: Vec<UserId>) -> HashMap<UserId, u128> {
+    let mut balances = HashMap::new();
+    for batch in voters.chunks(50) {
+        let results = futures::future::join_all(batch.iter().map(|user_id| async move {
+            let result = icrc_ledger_canister_c2c_client::icrc1_balance_of(ledger_canister_id, &Account::from(*user_id)).await;
+            (*user_id, result)
+        }))
+        .await;
+
+        for (user_id, result) in results {
+            match result {
+                Ok(balance) => {
+                    balances.insert(user_id, balance.0.try_into().unwrap_or(u128::MAX));
+                }
+                Err(error) => {
+                    // Count the voter as having no balance, rather than leaving their weight uncapped
+                    error!(?error, ?user_id, "Failed to read balance of poll voter");
+                    balances.insert(user_id, 0);
+                }
+            }
+        }
+    }
+    balances
+}
+
 impl Job for RemoveExpiredEventsJob {
     fn execute(self) {
         mutate_state(|state| state.run_event_expiry_job());
@@ -0,0 +1,22 @@
+use crate::guards::caller_is_local_user_index;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::c2c_bot_acknowledge_events::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(guard = "caller_is_local_user_index", msgpack = true)]
+#[trace]
+fn c2c_bot_acknowledge_events(args: Args) -> Response {
+    execute_update(|state| c2c_bot_acknowledge_events_impl(args, state)).into()
+}
+
+fn c2c_bot_acknowledge_events_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    if state.data.bots.get(&args.bot_id).is_none() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    channel.chat.events.acknowledge_bot_events(args.bot_id, args.cursor)
+}
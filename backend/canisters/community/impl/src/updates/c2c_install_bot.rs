@@ -21,6 +21,10 @@ fn c2c_install_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         return Err(OCErrorCode::InitiatorNotAuthorized.into());
     }
 
+    state
+        .data
+        .validate_bot_channel_permissions(args.channel_permissions.as_ref())?;
+
     let now = state.env.now();
 
     if !state.data.install_bot(
@@ -29,6 +33,7 @@ fn c2c_install_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         args.granted_permissions.clone(),
         args.granted_autonomous_permissions.clone(),
         args.default_subscriptions,
+        args.channel_permissions,
         now,
     ) {
         return Err(OCErrorCode::AlreadyAdded.into());
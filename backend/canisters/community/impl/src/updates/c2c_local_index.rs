@@ -200,7 +200,7 @@ fn process_event<F: FnOnce() -> TimestampMillis>(
         }
         LocalIndexEvent::UserDeleted(user_id) => {
             for channel in state.data.channels.iter_mut() {
-                channel.chat.members.remove(user_id, **now);
+                channel.chat.remove_deleted_user(user_id, **now);
             }
             state.data.members.remove(user_id, None, **now);
         }
@@ -28,7 +28,10 @@ fn c2c_send_moderation_notice_impl(args: Args, state: &mut RuntimeState) -> OCRe
         &Caller::OCBot(OPENCHAT_BOT_USER_ID),
         None,
         args.message_id,
-        MessageContentInternal::Text(TextContentInternal { text: args.text }),
+        MessageContentInternal::Text(TextContentInternal {
+            text: args.text,
+            components: Vec::new(),
+        }),
         None,
         &[],
         false,
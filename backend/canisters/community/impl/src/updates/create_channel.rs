@@ -12,7 +12,9 @@ use community_canister::{c2c_bot_create_channel, c2c_join_community};
 use group_chat_core::GroupChatCore;
 use oc_error_codes::OCErrorCode;
 use rand::RngExt;
-use types::{BotCaller, BotPermissions, Caller, ChannelCreated, CommunityPermission, MultiUserChat, OCResult, UserType};
+use types::{
+    BotCaller, BotPermissions, Caller, ChannelCreated, ChannelId, CommunityPermission, MultiUserChat, OCResult, UserType,
+};
 use url::Url;
 use utils::document::validate_avatar;
 use utils::text_validation::{StringLengthValidationError, validate_channel_name, validate_description, validate_rules};
@@ -195,7 +197,7 @@ fn create_channel_impl(
     );
 
     if args.is_public {
-        subscribe_bots_to_events(state, &mut chat);
+        subscribe_bots_to_events(state, channel_id, &mut chat);
     }
 
     state.data.members.mark_member_joined_channel(caller.agent(), channel_id);
@@ -229,9 +231,12 @@ fn create_channel_impl(
     Ok(SuccessResult { channel_id })
 }
 
-fn subscribe_bots_to_events(state: &mut RuntimeState, chat: &mut GroupChatCore) {
+fn subscribe_bots_to_events(state: &mut RuntimeState, channel_id: ChannelId, chat: &mut GroupChatCore) {
     for (bot_id, bot) in state.data.bots.iter() {
-        if let (Some(subscriptions), Some(permissions)) = (&bot.default_subscriptions, &bot.autonomous_permissions) {
+        // Bots restricted to specific channels won't have permissions in the new channel
+        if let (Some(subscriptions), Some(permissions)) =
+            (&bot.default_subscriptions, bot.autonomous_permissions_in(Some(channel_id)))
+        {
             chat.events.subscribe_bot_to_events(
                 *bot_id,
                 subscriptions.chat.clone(),
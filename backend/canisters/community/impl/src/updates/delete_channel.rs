@@ -66,6 +66,7 @@ fn delete_channel_impl(channel_id: ChannelId, ext_caller: Option<Caller>, state:
 
     let now = state.env.now();
     let channel = state.data.channels.delete(channel_id, now).expect("Channel should exist");
+    state.data.bots.remove_channel(channel_id, now);
 
     state
         .data
@@ -24,6 +24,9 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
 
     let sender = member.user_id;
     let channel_member = channel.chat.members.get_verified_member(sender)?;
+    let content = args.content.into();
+
+    channel.chat.validate_message_content(&content)?;
 
     let result = channel.chat.events.edit_message(
         EditMessageArgs {
@@ -31,10 +34,11 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
             min_visible_event_index: channel_member.min_visible_event_index(),
             thread_root_message_index: args.thread_root_message_index,
             message_id: args.message_id,
-            content: args.content.into(),
+            content,
             block_level_markdown: args.block_level_markdown,
             og_previews: args.og_previews,
             finalise_bot_message: false,
+            record_edit_history: !channel.chat.edit_history_disabled.value,
             now,
         },
         Some(CommunityEventPusher {
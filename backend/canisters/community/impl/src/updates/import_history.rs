@@ -0,0 +1,35 @@
+use crate::activity_notifications::handle_activity_notification;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::import_history::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::{MessageIndex, OCResult};
+
+const MAX_MESSAGES_PER_BATCH: usize = 100;
+
+#[update(msgpack = true)]
+#[trace]
+fn import_history(args: Args) -> Response {
+    match execute_update(|state| import_history_impl(args, state)) {
+        Ok(message_indexes) => Success(SuccessResult { message_indexes }),
+        Err(error) => Error(error),
+    }
+}
+
+fn import_history_impl(args: Args, state: &mut RuntimeState) -> OCResult<Vec<MessageIndex>> {
+    state.data.verify_not_frozen()?;
+
+    if args.messages.len() > MAX_MESSAGES_PER_BATCH {
+        return Err(OCErrorCode::InvalidRequest.with_message(format!("max {MAX_MESSAGES_PER_BATCH} messages per batch")));
+    }
+
+    let member = state.get_calling_member(true)?;
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let now = state.env.now();
+
+    let message_indexes = channel.chat.import_messages(member.user_id, args.messages, now)?;
+
+    handle_activity_notification(state);
+    Ok(message_indexes)
+}
@@ -1,6 +1,7 @@
 pub mod accept_p2p_swap;
 pub mod add_members_to_channel;
 pub mod add_reaction;
+pub mod c2c_bot_acknowledge_events;
 pub mod c2c_claim_prize;
 pub mod c2c_delete_community;
 pub mod c2c_flag_message;
@@ -34,6 +35,7 @@ pub mod decline_invitation;
 pub mod delete_channel;
 pub mod delete_channel_history;
 pub mod delete_messages;
+pub mod delete_outgoing_webhook;
 pub mod delete_user_groups;
 pub mod disable_invite_code;
 pub mod edit_message;
@@ -41,9 +43,12 @@ pub mod enable_invite_code;
 pub mod end_video_call;
 pub mod follow_thread;
 pub mod import_group;
+pub mod import_history;
 pub mod join_video_call;
 pub mod leave_channel;
 pub mod pin_message;
+pub mod press_message_button;
+pub mod register_outgoing_webhook;
 pub mod register_poll_vote;
 pub mod register_proposal_vote;
 //pub mod register_proposal_vote_v2;
@@ -58,15 +63,18 @@ pub mod remove_reaction;
 pub mod report_message;
 pub mod send_message;
 pub mod set_member_display_name;
+pub mod set_notification_filters;
 pub mod set_video_call_presence;
 pub mod start_video_call;
 pub mod toggle_mute_notifications;
+pub mod translate_message;
 pub mod unblock_user;
 pub mod undelete_messages;
 pub mod unfollow_thread;
 pub mod update_bot;
 pub mod update_channel;
 pub mod update_community;
+pub mod update_outgoing_webhook;
 pub mod update_user_group;
 pub mod update_webhook;
 pub mod wallet_receive;
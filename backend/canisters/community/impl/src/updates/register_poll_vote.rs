@@ -1,21 +1,52 @@
 use crate::activity_notifications::handle_activity_notification;
-use crate::{RuntimeState, execute_update};
+use crate::{RuntimeState, execute_update_async, mutate_state, read_state};
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use community_canister::register_poll_vote::{Response::*, *};
-use types::{Achievement, Chat, EventIndex, OCResult, PollVotes, TotalVotes};
+use icrc_ledger_types::icrc1::account::Account;
+use types::{Achievement, CanisterId, Chat, EventIndex, OCResult, PollVotes, TotalVotes, UserId};
 use user_canister::{CommunityCanisterEvent, MessageActivity, MessageActivityEvent};
 
 #[update(msgpack = true)]
 #[trace]
-fn register_poll_vote(args: Args) -> Response {
-    match execute_update(|state| register_poll_vote_impl(args, state)) {
+async fn register_poll_vote(args: Args) -> Response {
+    execute_update_async(|| register_poll_vote_impl(args)).await
+}
+
+async fn register_poll_vote_impl(args: Args) -> Response {
+    // If the poll's votes are weighted by token balance, read the user's balance before voting
+    let token_balance = match read_state(|state| prepare(&args, state)) {
+        Ok(Some((user_id, ledger_canister_id))) => {
+            match icrc_ledger_canister_c2c_client::icrc1_balance_of(ledger_canister_id, &Account::from(user_id)).await {
+                Ok(balance) => Some(balance.0.try_into().unwrap_or(u128::MAX)),
+                Err(error) => return Error(error.into()),
+            }
+        }
+        Ok(None) => None,
+        Err(error) => return Error(error),
+    };
+
+    match mutate_state(|state| commit(args, token_balance, state)) {
         Ok(votes) => Success(votes),
         Err(error) => Error(error),
     }
 }
 
-fn register_poll_vote_impl(args: Args, state: &mut RuntimeState) -> OCResult<PollVotes> {
+fn prepare(args: &Args, state: &RuntimeState) -> OCResult<Option<(UserId, CanisterId)>> {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+    let channel = state.data.channels.get_or_err(&args.channel_id)?;
+
+    let ledger_canister_id =
+        channel
+            .chat
+            .poll_vote_weighting_ledger(member.user_id, args.thread_root_message_index, args.message_index)?;
+
+    Ok(ledger_canister_id.map(|l| (member.user_id, l)))
+}
+
+fn commit(args: Args, token_balance: Option<u128>, state: &mut RuntimeState) -> OCResult<PollVotes> {
     state.data.verify_not_frozen()?;
 
     let member = state.get_calling_member(true)?;
@@ -29,6 +60,7 @@ fn register_poll_vote_impl(args: Args, state: &mut RuntimeState) -> OCResult<Pol
         args.message_index,
         args.poll_option,
         args.operation,
+        token_balance,
         now,
     )?;
 
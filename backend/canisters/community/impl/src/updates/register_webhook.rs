@@ -34,6 +34,11 @@ fn register_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult<Succe
         Err(UsernameValidationError::Invalid) => return Err(OCErrorCode::InvalidRequest.with_message("name invalid")),
     };
 
+    let rate_limits = args.rate_limits.unwrap_or_default();
+    if !rate_limits.is_valid() {
+        return Err(OCErrorCode::InvalidRequest.with_message("rate limits must be greater than zero"));
+    }
+
     let avatar = args
         .avatar
         .map(|avatar_url| try_parse_data_url(&avatar_url))
@@ -42,7 +47,14 @@ fn register_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult<Succe
 
     let now = state.env.now();
 
-    let Some(webhook_id) = channel.chat.webhooks.register(args.name, avatar, state.env.rng(), now) else {
+    let Some(webhook_id) = channel.chat.webhooks.register(
+        args.name,
+        avatar,
+        rate_limits,
+        args.require_signature.unwrap_or_default(),
+        state.env.rng(),
+        now,
+    ) else {
         return Err(OCErrorCode::NameTaken.into());
     };
 
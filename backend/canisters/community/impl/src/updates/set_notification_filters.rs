@@ -0,0 +1,45 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::set_notification_filters::*;
+use group_chat_core::normalise_keyword_alerts;
+use types::OCResult;
+
+#[update(msgpack = true)]
+#[trace]
+fn set_notification_filters(args: Args) -> Response {
+    execute_update(|state| set_notification_filters_impl(args, state)).into()
+}
+
+fn set_notification_filters_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let user_id = state.get_caller_user_id()?;
+    let now = state.env.now();
+
+    let updated = if let Some(channel_id) = args.channel_id {
+        let channel = state.data.channels.get_mut_or_err(&channel_id)?;
+        channel
+            .chat
+            .set_notification_filters(user_id, args.mentions_only, args.keyword_alerts, now)?
+    } else {
+        // Validate up front so that invalid keywords aren't silently skipped for every channel
+        let keyword_alerts = args.keyword_alerts.map(normalise_keyword_alerts).transpose()?;
+
+        let mut updated = false;
+        for channel in state.data.channels.iter_mut() {
+            if let Ok(true) = channel
+                .chat
+                .set_notification_filters(user_id, args.mentions_only, keyword_alerts.clone(), now)
+            {
+                updated = true;
+            }
+        }
+        updated
+    };
+
+    if updated {
+        state.mark_activity_for_user(user_id);
+    }
+    Ok(())
+}
@@ -22,6 +22,10 @@ fn update_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         return Err(OCErrorCode::InitiatorNotAuthorized.into());
     }
 
+    state
+        .data
+        .validate_bot_channel_permissions(args.channel_permissions.as_ref().expand().flatten())?;
+
     let now = state.env.now();
 
     if !state.data.update_bot_permissions(
@@ -29,6 +33,7 @@ fn update_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         args.bot_id,
         args.granted_permissions.clone(),
         args.granted_autonomous_permissions.clone(),
+        args.channel_permissions,
         now,
     ) {
         return Err(OCErrorCode::BotNotFound.into());
@@ -58,6 +58,8 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> OCResult<Suc
         args.messages_visible_to_non_members,
         args.events_ttl,
         args.external_url,
+        args.edit_history_disabled,
+        args.e2ee_enabled,
         now,
     )?;
 
@@ -67,9 +69,10 @@ fn update_channel_impl(mut args: Args, state: &mut RuntimeState) -> OCResult<Suc
         for (bot_id, bot) in state.data.bots.iter() {
             if result.newly_public {
                 // The channel is now public so subscribe the bot to all default events
-                if let (Some(subscriptions), Some(permissions)) =
-                    (bot.default_subscriptions.as_ref(), bot.autonomous_permissions.as_ref())
-                {
+                if let (Some(subscriptions), Some(permissions)) = (
+                    bot.default_subscriptions.as_ref(),
+                    bot.autonomous_permissions_in(Some(channel.id)),
+                ) {
                     channel.chat.events.subscribe_bot_to_events(
                         *bot_id,
                         subscriptions.chat.clone(),
@@ -0,0 +1,28 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use community_canister::update_outgoing_webhook::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true)]
+#[trace]
+fn update_outgoing_webhook(args: Args) -> Response {
+    execute_update(|state| update_outgoing_webhook_impl(args, state)).into()
+}
+
+fn update_outgoing_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let user_id = state.get_calling_member(true)?.user_id;
+    let channel = state.data.channels.get_mut_or_err(&args.channel_id)?;
+    let member = channel.chat.members.get_verified_member(user_id)?;
+
+    if !member.role().can_manage_outgoing_webhooks() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    channel
+        .chat
+        .update_outgoing_webhook(args.id, args.url, args.event_types, args.enabled)
+}
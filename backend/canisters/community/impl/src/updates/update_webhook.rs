@@ -23,6 +23,10 @@ fn update_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         return Err(OCErrorCode::InitiatorNotAuthorized.into());
     }
 
+    if args.rate_limits.is_some_and(|r| !r.is_valid()) {
+        return Err(OCErrorCode::InvalidRequest.with_message("rate limits must be greater than zero"));
+    }
+
     let avatar = args
         .avatar
         .map(|avatar_url| try_parse_data_url(&avatar_url))
@@ -31,7 +35,11 @@ fn update_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult {
 
     let now = state.env.now();
 
-    if !channel.chat.webhooks.update(args.id, args.name, avatar, now) {
+    if !channel
+        .chat
+        .webhooks
+        .update(args.id, args.name, avatar, args.rate_limits, args.require_signature, now)
+    {
         return Err(OCErrorCode::WebhookNotFound.into());
     }
 
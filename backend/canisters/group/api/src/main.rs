@@ -12,9 +12,12 @@ fn main() {
     generate_ts_method!(group, events);
     generate_ts_method!(group, events_by_index);
     generate_ts_method!(group, events_window);
+    generate_ts_method!(group, export_history);
     generate_ts_method!(group, invite_code);
     generate_ts_method!(group, local_user_index);
+    generate_ts_method!(group, message_edit_history);
     generate_ts_method!(group, messages_by_message_index);
+    generate_ts_method!(group, outgoing_webhooks);
     generate_ts_method!(group, thread_previews);
     generate_ts_method!(group, public_summary);
     generate_ts_method!(group, rules);
@@ -35,6 +38,7 @@ fn main() {
     generate_ts_method!(group, convert_into_community);
     generate_ts_method!(group, decline_invitation);
     generate_ts_method!(group, delete_messages);
+    generate_ts_method!(group, delete_outgoing_webhook);
     generate_ts_method!(group, delete_history);
     generate_ts_method!(group, delete_webhook);
     generate_ts_method!(group, disable_invite_code);
@@ -43,6 +47,8 @@ fn main() {
     generate_ts_method!(group, follow_thread);
     generate_ts_method!(group, join_video_call);
     generate_ts_method!(group, pin_message_v2);
+    generate_ts_method!(group, press_message_button);
+    generate_ts_method!(group, register_outgoing_webhook);
     generate_ts_method!(group, regenerate_webhook);
     generate_ts_method!(group, register_poll_vote);
     generate_ts_method!(group, register_proposal_vote);
@@ -53,13 +59,16 @@ fn main() {
     generate_ts_method!(group, report_message);
     generate_ts_method!(group, reset_invite_code);
     generate_ts_method!(group, send_message_v2);
+    generate_ts_method!(group, set_notification_filters);
     generate_ts_method!(group, set_video_call_presence);
     generate_ts_method!(group, toggle_mute_notifications);
+    generate_ts_method!(group, translate_message);
     generate_ts_method!(group, unblock_user);
     generate_ts_method!(group, undelete_messages);
     generate_ts_method!(group, unfollow_thread);
     generate_ts_method!(group, unpin_message);
     generate_ts_method!(group, update_bot);
     generate_ts_method!(group, update_group_v2);
+    generate_ts_method!(group, update_outgoing_webhook);
     generate_ts_method!(group, update_webhook);
 }
@@ -0,0 +1,16 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use types::{BotEventsSinceResult, TimestampMillis, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub since: Option<TimestampMillis>,
+    pub max_events: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(BotEventsSinceResult),
+    Error(OCError),
+}
@@ -0,0 +1,18 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{ChatExportCursor, ChatExportPage};
+
+#[ts_export(group, export_history)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub after: Option<ChatExportCursor>,
+    pub max_events: u32,
+}
+
+#[ts_export(group, export_history)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(ChatExportPage),
+    Error(OCError),
+}
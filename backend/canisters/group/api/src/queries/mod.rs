@@ -1,5 +1,6 @@
 pub mod active_proposal_tallies;
 pub mod c2c_active_proposal_tallies;
+pub mod c2c_bot_events_since;
 pub mod c2c_bot_group_details;
 pub mod c2c_bot_members;
 pub mod c2c_can_issue_access_token_v2;
@@ -13,9 +14,12 @@ pub mod deleted_message;
 pub mod events;
 pub mod events_by_index;
 pub mod events_window;
+pub mod export_history;
 pub mod invite_code;
 pub mod local_user_index;
+pub mod message_edit_history;
 pub mod messages_by_message_index;
+pub mod outgoing_webhooks;
 pub mod public_summary;
 pub mod rules;
 pub mod search_messages;
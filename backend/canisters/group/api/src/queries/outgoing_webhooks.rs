@@ -0,0 +1,19 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Empty, OutgoingWebhookDetails};
+
+pub type Args = Empty;
+
+#[ts_export(group, outgoing_webhooks)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(group, outgoing_webhooks)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub webhooks: Vec<OutgoingWebhookDetails>,
+}
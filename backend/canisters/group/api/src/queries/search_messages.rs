@@ -2,11 +2,12 @@ use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use ts_export::ts_export;
-use types::{MessageMatch, UserId};
+use types::{MessageIndex, MessageMatch, UserId};
 
 #[ts_export(group, search_messages)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
+    pub thread_root_message_index: Option<MessageIndex>,
     pub search_term: String,
     pub max_results: u8,
     pub users: Option<HashSet<UserId>>,
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{UnitResult, UserId};
+
+#[ts_export(group, delete_outgoing_webhook)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub id: UserId,
+}
+
+pub type Response = UnitResult;
@@ -1,13 +1,14 @@
 use serde::{Deserialize, Serialize};
-use types::UserId;
+use types::{UserId, WebhookMessage, WebhookSignature};
 
 use super::send_message_v2;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub id: UserId,
-    pub secret: String,
-    pub message: String,
+    pub secret: Option<String>,
+    pub message: WebhookMessage,
+    pub signature: Option<WebhookSignature>,
 }
 
 pub type Response = send_message_v2::Response;
@@ -1,6 +1,7 @@
 pub mod accept_p2p_swap;
 pub mod add_reaction;
 pub mod block_user;
+pub mod c2c_bot_acknowledge_events;
 pub mod c2c_bot_add_reaction;
 pub mod c2c_bot_change_role;
 pub mod c2c_bot_delete_messages;
@@ -36,6 +37,7 @@ pub mod convert_into_community;
 pub mod decline_invitation;
 pub mod delete_history;
 pub mod delete_messages;
+pub mod delete_outgoing_webhook;
 pub mod delete_webhook;
 pub mod disable_invite_code;
 pub mod edit_message_v2;
@@ -45,7 +47,9 @@ pub mod follow_thread;
 pub mod handle_webhook;
 pub mod join_video_call;
 pub mod pin_message_v2;
+pub mod press_message_button;
 pub mod regenerate_webhook;
+pub mod register_outgoing_webhook;
 pub mod register_poll_vote;
 pub mod register_proposal_vote;
 pub mod register_proposal_vote_v2;
@@ -55,13 +59,16 @@ pub mod remove_reaction;
 pub mod report_message;
 pub mod reset_invite_code;
 pub mod send_message_v2;
+pub mod set_notification_filters;
 pub mod set_video_call_presence;
 pub mod start_video_call_v2;
 pub mod toggle_mute_notifications;
+pub mod translate_message;
 pub mod unblock_user;
 pub mod undelete_messages;
 pub mod unfollow_thread;
 pub mod unpin_message;
 pub mod update_bot;
 pub mod update_group_v2;
+pub mod update_outgoing_webhook;
 pub mod update_webhook;
@@ -0,0 +1,30 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{MessageId, MessageIndex, TimestampMillis};
+
+#[ts_export(group, press_message_button)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub component_id: String,
+    // The option chosen if the component is a select menu
+    pub value: Option<String>,
+}
+
+#[ts_export(group, press_message_button)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+// The token should be sent to `{endpoint}/interaction`. The bot can then use it to edit the message.
+#[ts_export(group, press_message_button)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub token: String,
+    pub endpoint: String,
+    pub expires: TimestampMillis,
+}
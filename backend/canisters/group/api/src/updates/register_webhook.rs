@@ -1,13 +1,17 @@
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::UserId;
+use types::{UserId, WebhookRateLimits};
 
 #[ts_export(group, register_webhook)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub name: String,
     pub avatar: Option<String>,
+    #[serde(default)]
+    pub rate_limits: Option<WebhookRateLimits>,
+    #[serde(default)]
+    pub require_signature: Option<bool>,
 }
 
 #[ts_export(group, register_webhook)]
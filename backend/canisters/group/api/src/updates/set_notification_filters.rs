@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::UnitResult;
+
+#[ts_export(group, set_notification_filters)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    // If true, only notify of mentions, replies and keyword alerts, ignoring @everyone
+    pub mentions_only: Option<bool>,
+    // Keywords which trigger a notification even if the chat is muted
+    pub keyword_alerts: Option<Vec<String>>,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,25 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{MessageId, MessageIndex};
+
+#[ts_export(group, translate_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub language: String,
+}
+
+#[ts_export(group, translate_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(group, translate_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub translation: String,
+}
@@ -18,6 +18,10 @@ pub struct Args {
     pub gate_config: OptionUpdate<AccessGateConfig>,
     pub public: Option<bool>,
     pub messages_visible_to_non_members: Option<bool>,
+    #[serde(default)]
+    pub edit_history_disabled: Option<bool>,
+    #[serde(default)]
+    pub e2ee_enabled: Option<bool>,
 }
 
 #[ts_export(group, update_group)]
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_export::ts_export;
+use types::{ChatEventType, UnitResult, UserId};
+
+#[ts_export(group, update_outgoing_webhook)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub id: UserId,
+    pub url: Option<String>,
+    pub event_types: Option<HashSet<ChatEventType>>,
+    pub enabled: Option<bool>,
+}
+
+pub type Response = UnitResult;
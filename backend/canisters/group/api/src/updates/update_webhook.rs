@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{OptionUpdate, UnitResult, UserId};
+use types::{OptionUpdate, UnitResult, UserId, WebhookRateLimits};
 
 #[ts_export(group, update_webhook)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -9,6 +9,10 @@ pub struct Args {
     pub name: Option<String>,
     #[ts(as = "types::OptionUpdateString")]
     pub avatar: OptionUpdate<String>,
+    #[serde(default)]
+    pub rate_limits: Option<WebhookRateLimits>,
+    #[serde(default)]
+    pub require_signature: Option<bool>,
 }
 
 pub type Response = UnitResult;
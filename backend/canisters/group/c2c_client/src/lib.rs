@@ -3,6 +3,7 @@ use group_canister::*;
 
 // Queries
 generate_c2c_call!(c2c_active_proposal_tallies);
+generate_c2c_call!(c2c_bot_events_since);
 generate_c2c_call!(c2c_bot_group_details);
 generate_c2c_call!(c2c_bot_members);
 generate_c2c_call!(c2c_can_issue_access_token_v2);
@@ -15,6 +16,7 @@ generate_c2c_call!(summary);
 generate_c2c_call!(summary_updates);
 
 // Updates
+generate_c2c_call!(c2c_bot_acknowledge_events);
 generate_c2c_call!(c2c_bot_add_reaction);
 generate_c2c_call!(c2c_bot_change_role);
 generate_c2c_call!(c2c_bot_delete_messages);
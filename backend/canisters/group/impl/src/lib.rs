@@ -13,11 +13,12 @@ use gated_groups::{GatePayment, calculate_gate_payments};
 use group_chat_core::{AddResult as AddMemberResult, GroupChatCore, GroupMemberInternal, InvitedUsersSuccess, UserInvitation};
 use group_community_common::{
     Achievements, ExpiringMemberActions, ExpiringMembers, PaymentReceipts, PaymentRecipient, PendingPayment,
-    PendingPaymentReason, PendingPaymentsQueue, UserCache,
+    PendingPaymentReason, PendingPaymentsQueue, UserCache, outgoing_webhooks,
 };
 use ic_principal::Principal;
 use installed_bots::InstalledBots;
 use instruction_counts_log::{InstructionCountEntry, InstructionCountFunctionId, InstructionCountsLog};
+use model::outgoing_webhook_delivery_batch::{OutgoingWebhookDeliveryBatch, OutgoingWebhookEvent};
 use model::user_event_batch::UserEventBatch;
 use msgpack::serialize_then_unwrap;
 use oc_error_codes::OCErrorCode;
@@ -33,11 +34,11 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::Deref;
 use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue};
 use types::{
-    AccessGateConfigInternal, Achievement, BotAdded, BotDefinitionUpdate, BotEventsCaller, BotInitiator, BotNotification,
-    BotPermissions, BotRemoved, BotSubscriptions, BotUpdated, BuildVersion, Caller, CanisterId, ChatId, ChatMetrics,
-    CommunityId, Cycles, Document, EventIndex, EventsCaller, FrozenGroupInfo, GroupCanisterGroupChatSummary,
+    AccessGateConfigInternal, Achievement, BotAdded, BotDefinitionUpdate, BotEvent, BotEventsCaller, BotInitiator,
+    BotNotification, BotPermissions, BotRemoved, BotSubscriptions, BotUpdated, BuildVersion, Caller, CanisterId, ChatId,
+    ChatMetrics, CommunityId, Cycles, Document, EventIndex, EventsCaller, FrozenGroupInfo, GroupCanisterGroupChatSummary,
     GroupChatUserNotificationPayload, GroupMembership, GroupPermissions, GroupSubtype, IdempotentEnvelope,
-    MAX_THREADS_IN_SUMMARY, MessageId, MessageIndex, Milliseconds, MultiUserChat, Notification, OCResult, Rules,
+    MAX_THREADS_IN_SUMMARY, MessageId, MessageIndex, Milliseconds, MultiUserChat, Notification, OCResult, OptionUpdate, Rules,
     TimestampMillis, Timestamped, UserId, UserNotification, UserType,
 };
 use user_canister::GroupCanisterEvent;
@@ -147,10 +148,44 @@ impl RuntimeState {
     }
 
     pub fn push_bot_notification(&mut self, notification: Option<BotNotification>) {
-        if let Some(notification) = notification
-            && !notification.recipients.is_empty()
-        {
-            self.push_notification_inner(Notification::Bot(notification));
+        if let Some(mut notification) = notification {
+            // The events of end-to-end encrypted chats are never sent to bots or outgoing webhooks,
+            // including any which subscribed before encryption was enabled
+            if matches!(notification.event, BotEvent::Chat(_)) && self.data.chat.is_end_to_end_encrypted() {
+                return;
+            }
+
+            self.queue_outgoing_webhook_deliveries(&mut notification);
+
+            if !notification.recipients.is_empty() {
+                self.push_notification_inner(Notification::Bot(notification));
+            }
+        }
+    }
+
+    // Outgoing webhooks are subscribed to events in the same way as bots, so here we take them
+    // out of the notification's recipients and queue up a delivery to each of them instead
+    fn queue_outgoing_webhook_deliveries(&mut self, notification: &mut BotNotification) {
+        let now = self.env.now();
+        let deliveries = self.data.chat.start_outgoing_webhook_deliveries(notification, now);
+        if deliveries.is_empty() {
+            return;
+        }
+
+        let data = ByteBuf::from(outgoing_webhooks::serialize_event(
+            self.data.local_user_index_canister_id,
+            notification.event.clone(),
+            notification.timestamp,
+        ));
+
+        for (webhook_id, delivery_id) in deliveries {
+            self.data.outgoing_webhook_delivery_queue.push(
+                webhook_id,
+                OutgoingWebhookEvent {
+                    delivery_id,
+                    data: data.clone(),
+                },
+            );
         }
     }
 
@@ -224,6 +259,8 @@ impl RuntimeState {
             mentions: chat.most_recent_mentions(member, None),
             notifications_muted: member.notifications_muted().value,
             at_everyone_muted: member.at_everyone_muted().value,
+            mentions_only: member.mentions_only().value,
+            keyword_alerts: member.keyword_alerts().value.clone(),
             my_metrics: chat
                 .events
                 .user_metrics(&member.user_id(), None)
@@ -254,6 +291,8 @@ impl RuntimeState {
             is_public: chat.is_public.value,
             history_visible_to_new_joiners: chat.history_visible_to_new_joiners,
             messages_visible_to_non_members: chat.messages_visible_to_non_members.value,
+            edit_history_disabled: chat.edit_history_disabled.value,
+            e2ee_key_epoch: chat.e2ee_key_epoch.value,
             min_visible_event_index,
             min_visible_message_index,
             latest_message: main_events_reader.latest_message_event(Some(member.user_id())),
@@ -518,6 +557,7 @@ impl RuntimeState {
             timer_jobs: self.data.timer_jobs.len() as u32,
             queued_user_events: self.data.user_event_sync_queue.len() as u32,
             queued_local_index_events: self.data.local_user_index_event_sync_queue.len() as u32,
+            queued_outgoing_webhook_deliveries: self.data.outgoing_webhook_delivery_queue.len() as u32,
             stable_memory_sizes: memory::memory_sizes(),
             canister_ids: CanisterIds {
                 user_index: self.data.user_index_canister_id,
@@ -600,6 +640,8 @@ struct Data {
     user_cache: UserCache,
     user_event_sync_queue: GroupedTimerJobQueue<UserEventBatch>,
     local_user_index_event_sync_queue: BatchedTimerJobQueue<LocalUserIndexEventBatch>,
+    #[serde(default = "outgoing_webhook_delivery_queue")]
+    outgoing_webhook_delivery_queue: GroupedTimerJobQueue<OutgoingWebhookDeliveryBatch>,
     stable_memory_keys_to_garbage_collect: Vec<BaseKeyPrefix>,
     verified: Timestamped<bool>,
     #[serde(default)]
@@ -612,6 +654,10 @@ fn init_instruction_counts_log() -> InstructionCountsLog {
     InstructionCountsLog::init(get_instruction_counts_index_memory(), get_instruction_counts_data_memory())
 }
 
+fn outgoing_webhook_delivery_queue() -> GroupedTimerJobQueue<OutgoingWebhookDeliveryBatch> {
+    GroupedTimerJobQueue::new(5, true)
+}
+
 #[expect(clippy::too_many_arguments)]
 impl Data {
     pub fn new(
@@ -695,6 +741,7 @@ impl Data {
             user_cache: UserCache::default(),
             user_event_sync_queue: GroupedTimerJobQueue::new(5, true),
             local_user_index_event_sync_queue: BatchedTimerJobQueue::new(local_user_index_canister_id, true),
+            outgoing_webhook_delivery_queue: outgoing_webhook_delivery_queue(),
             stable_memory_keys_to_garbage_collect: Vec::new(),
             verified: Timestamped::default(),
             moderation_flags: Timestamped::default(),
@@ -855,12 +902,17 @@ impl Data {
     }
 
     fn granted_bot_permissions(&self, bot_id: &UserId, initiator: &BotInitiator) -> Option<BotPermissions> {
+        // Bots can't read or send messages in end-to-end encrypted groups
+        if self.chat.is_end_to_end_encrypted() {
+            return None;
+        }
+
         // Try to get the installed bot
         let bot = self.bots.get(bot_id)?;
 
         match initiator {
-            BotInitiator::Command(command) => {
-                let user_permissions = self.get_user_permissions(&command.initiator)?;
+            BotInitiator::Command(_) | BotInitiator::Interaction(_) => {
+                let user_permissions = self.get_user_permissions(&initiator.user()?)?;
                 Some(BotPermissions::union(
                     &BotPermissions::intersect(&bot.permissions, &user_permissions),
                     &bot.autonomous_permissions.clone().unwrap_or_default(),
@@ -885,6 +937,7 @@ impl Data {
             permissions,
             autonomous_permissions.clone(),
             default_subscriptions.clone(),
+            None,
             now,
         ) {
             return false;
@@ -925,10 +978,13 @@ impl Data {
         autonomous_permissions: Option<BotPermissions>,
         now: TimestampMillis,
     ) -> bool {
-        if self
-            .bots
-            .update_permissions(bot_id, command_permissions, autonomous_permissions, now)
-        {
+        if self.bots.update_permissions(
+            bot_id,
+            command_permissions,
+            autonomous_permissions,
+            OptionUpdate::NoChange,
+            now,
+        ) {
             self.apply_bot_update(bot_id, owner_id, now);
             true
         } else {
@@ -1032,6 +1088,7 @@ pub struct Metrics {
     pub timer_jobs: u32,
     pub queued_user_events: u32,
     pub queued_local_index_events: u32,
+    pub queued_outgoing_webhook_deliveries: u32,
     pub stable_memory_sizes: BTreeMap<u8, u64>,
     pub canister_ids: CanisterIds,
 }
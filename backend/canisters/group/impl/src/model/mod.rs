@@ -1,2 +1,3 @@
 pub mod local_user_index_event_batch;
+pub mod outgoing_webhook_delivery_batch;
 pub mod user_event_batch;
@@ -0,0 +1,59 @@
+use crate::{mutate_state, read_state};
+use group_chat_core::compute_signature;
+use group_community_common::outgoing_webhooks::post_event;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use timer_job_queues::{TimerJobItem, grouped_timer_job_batch};
+use types::{BotEventPayload, Milliseconds, UserId};
+
+// Each delivery is a separate request, so the batch size is 1, but grouping by webhook means
+// deliveries to each endpoint are made in order and a failing endpoint doesn't block the others
+grouped_timer_job_batch!(OutgoingWebhookDeliveryBatch, UserId, OutgoingWebhookEvent, 1);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OutgoingWebhookEvent {
+    pub delivery_id: u64,
+    pub data: ByteBuf,
+}
+
+impl TimerJobItem for OutgoingWebhookDeliveryBatch {
+    async fn process(&self) -> Result<(), Option<Milliseconds>> {
+        let webhook_id = self.key;
+        let Some(event) = self.items.first() else {
+            return Ok(());
+        };
+
+        // The webhook may have been updated, disabled or deleted since the event was queued
+        let Some((url, secret, now)) = read_state(|state| {
+            state
+                .data
+                .chat
+                .outgoing_webhooks
+                .get(&webhook_id)
+                .filter(|webhook| webhook.enabled)
+                .map(|webhook| (webhook.url.clone(), webhook.secret.clone(), state.env.now()))
+        }) else {
+            return Ok(());
+        };
+
+        let nonce = event.delivery_id.to_string();
+        let payload = BotEventPayload {
+            signature: compute_signature(&secret, now, &nonce, &event.data),
+            data: event.data.clone(),
+        };
+
+        let attempt = post_event(url, payload, now, nonce).await;
+
+        match mutate_state(|state| {
+            let now = state.env.now();
+            state
+                .data
+                .chat
+                .outgoing_webhooks
+                .record_attempt(webhook_id, event.delivery_id, attempt, now)
+        }) {
+            Some(delay) => Err(Some(delay)),
+            None => Ok(()),
+        }
+    }
+}
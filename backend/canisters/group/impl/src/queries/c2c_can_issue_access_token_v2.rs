@@ -13,6 +13,11 @@ fn c2c_can_issue_access_token_v2(args: Args) -> Response {
 
 fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Response {
     if let AccessTypeArgs::BotActionByCommand(args) = &args_outer {
+        // Bots can't read or send messages in end-to-end encrypted chats
+        if state.data.chat.is_end_to_end_encrypted() {
+            return Response::Failure;
+        }
+
         // Ensure the initiator is a member
         let Some(member) = state.data.get_member(args.initiator.into()) else {
             return Response::Failure;
@@ -42,6 +47,7 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
         AccessTypeArgs::StartVideoCall(args) => args.initiator,
         AccessTypeArgs::JoinVideoCall(args) => args.initiator,
         AccessTypeArgs::MarkVideoCallAsEnded(args) => args.initiator,
+        AccessTypeArgs::ChatEncryptionKey(args) => args.initiator,
         _ => unreachable!(),
     };
 
@@ -51,6 +57,13 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
 
     match args_outer {
         AccessTypeArgs::JoinVideoCall(_) | AccessTypeArgs::MarkVideoCallAsEnded(_) => Response::Success,
+        AccessTypeArgs::ChatEncryptionKey(args) => {
+            if args.key_epoch >= state.data.chat.min_key_epoch(&member) && args.key_epoch <= state.data.chat.key_epoch() {
+                Response::Success
+            } else {
+                Response::Failure
+            }
+        }
         AccessTypeArgs::StartVideoCall(_) => {
             if member.role().is_permitted(state.data.chat.permissions.start_video_call) {
                 Response::Success
@@ -0,0 +1,22 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use group_canister::message_edit_history::{Response::*, *};
+use types::{MessageRevision, OCResult};
+
+#[query(msgpack = true)]
+fn message_edit_history(args: Args) -> Response {
+    match read_state(|state| message_edit_history_impl(args, state)) {
+        Ok(revisions) => Success(SuccessResult { revisions }),
+        Err(error) => Error(error),
+    }
+}
+
+fn message_edit_history_impl(args: Args, state: &RuntimeState) -> OCResult<Vec<MessageRevision>> {
+    let user_id = state.get_caller_user_id()?;
+    let now = state.env.now();
+
+    state
+        .data
+        .chat
+        .message_edit_history(user_id, args.thread_root_message_index, args.message_id, now)
+}
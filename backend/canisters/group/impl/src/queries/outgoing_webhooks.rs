@@ -0,0 +1,25 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use group_canister::outgoing_webhooks::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[query(msgpack = true)]
+fn outgoing_webhooks(_args: Args) -> Response {
+    match read_state(outgoing_webhooks_impl) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn outgoing_webhooks_impl(state: &RuntimeState) -> OCResult<SuccessResult> {
+    let member = state.get_calling_member(true)?;
+
+    if !member.role().can_manage_outgoing_webhooks() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    Ok(SuccessResult {
+        webhooks: state.data.chat.outgoing_webhooks.details(),
+    })
+}
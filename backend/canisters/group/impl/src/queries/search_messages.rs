@@ -13,10 +13,13 @@ fn search_messages(args: Args) -> Response {
 
 fn search_messages_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
     let user_id = state.get_caller_user_id()?;
-    let matches = state
-        .data
-        .chat
-        .search(user_id, args.search_term, args.users, args.max_results)?;
+    let matches = state.data.chat.search(
+        user_id,
+        args.thread_root_message_index,
+        args.search_term,
+        args.users,
+        args.max_results,
+    )?;
 
     Ok(SuccessResult { matches })
 }
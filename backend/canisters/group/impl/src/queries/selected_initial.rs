@@ -46,6 +46,7 @@ fn selected_initial_impl(state: &RuntimeState) -> OCResult<SuccessResult> {
             added_by: bot.added_by,
             permissions: bot.permissions.clone(),
             autonomous_permissions: bot.autonomous_permissions.clone(),
+            channel_permissions: None,
         })
         .collect();
 
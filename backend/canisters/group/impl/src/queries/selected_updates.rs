@@ -36,6 +36,7 @@ fn selected_updates_impl(args: Args, state: &RuntimeState) -> OCResult<Response>
                         added_by: bot.added_by,
                         permissions: bot.permissions.clone(),
                         autonomous_permissions: bot.autonomous_permissions.clone(),
+                        channel_permissions: None,
                     });
                 }
             }
@@ -51,6 +51,8 @@ fn summary_updates_impl(updates_since: TimestampMillis, on_behalf_of: Option<Pri
         mentions: updates.mentions,
         notifications_muted: member.notifications_muted().if_set_after(updates_since).cloned(),
         at_everyone_muted: member.at_everyone_muted().if_set_after(updates_since).cloned(),
+        mentions_only: member.mentions_only().if_set_after(updates_since).cloned(),
+        keyword_alerts: member.keyword_alerts().if_set_after(updates_since).cloned(),
         my_metrics: state
             .data
             .chat
@@ -93,6 +95,8 @@ fn summary_updates_impl(updates_since: TimestampMillis, on_behalf_of: Option<Pri
             metrics: Some(chat.events.metrics().hydrate()),
             is_public: updates.is_public,
             messages_visible_to_non_members: updates.messages_visible_to_non_members,
+            edit_history_disabled: updates.edit_history_disabled,
+            e2ee_key_epoch: updates.e2ee_key_epoch,
             frozen: state
                 .data
                 .frozen
@@ -14,6 +14,10 @@ fn c2c_install_bot(args: Args) -> Response {
 fn c2c_install_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     state.data.verify_not_frozen()?;
 
+    if state.data.chat.is_end_to_end_encrypted() {
+        return Err(OCErrorCode::EndToEndEncrypted.with_message("Bots can't be installed"));
+    }
+
     let member = state.data.chat.members.get_verified_member(args.caller)?;
 
     if !member.role().is_owner() {
@@ -201,7 +201,7 @@ fn process_event<F: FnOnce() -> TimestampMillis>(
             state.data.moderation_flags = Timestamped::new(ev.flags, **now);
         }
         LocalIndexEvent::UserDeleted(user_id) => {
-            state.data.chat.members.remove(user_id, **now);
+            state.data.chat.remove_deleted_user(user_id, **now);
             state.data.remove_user(user_id, None);
         }
         LocalIndexEvent::BotRemoved(bot_id) => {
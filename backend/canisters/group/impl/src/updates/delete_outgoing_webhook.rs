@@ -0,0 +1,24 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::delete_outgoing_webhook::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true)]
+#[trace]
+fn delete_outgoing_webhook(args: Args) -> Response {
+    execute_update(|state| delete_outgoing_webhook_impl(args, state)).into()
+}
+
+fn delete_outgoing_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+
+    if !member.role().can_manage_outgoing_webhooks() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    state.data.chat.remove_outgoing_webhook(args.id)
+}
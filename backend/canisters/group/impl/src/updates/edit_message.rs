@@ -19,16 +19,20 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
     let now = state.env.now();
     let sender = member.user_id();
     let is_bot = member.user_type().is_bot();
+    let content = args.content.into();
+
+    state.data.chat.validate_message_content(&content)?;
 
     let edit_message_args = EditMessageArgs {
         sender,
         min_visible_event_index: member.min_visible_event_index(),
         thread_root_message_index: args.thread_root_message_index,
         message_id: args.message_id,
-        content: args.content.into(),
+        content,
         block_level_markdown: args.block_level_markdown,
         og_previews: args.og_previews,
         finalise_bot_message: false,
+        record_edit_history: !state.data.chat.edit_history_disabled.value,
         now,
     };
 
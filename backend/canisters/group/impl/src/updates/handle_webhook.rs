@@ -1,9 +1,8 @@
 use crate::{RuntimeState, execute_update};
 use group_canister::handle_webhook::*;
 use group_canister::send_message_v2::SuccessResult;
-use oc_error_codes::OCErrorCode;
 use rand::RngExt;
-use types::{Caller, MessageContentInitial, OCResult, TextContent};
+use types::{Caller, OCResult, UserId, WebhookMessage};
 
 use super::send_message::send_message_impl;
 
@@ -15,29 +14,48 @@ pub(crate) fn handle_webhook(args: Args) -> Response {
 }
 
 fn handle_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult<SuccessResult> {
-    let Some(webhook) = state.data.chat.webhooks.get(&args.id) else {
-        return Err(OCErrorCode::WebhookNotFound.into());
-    };
+    let now = state.env.now();
 
-    if webhook.secret != args.secret {
-        return Err(OCErrorCode::InvalidWebhook.into());
-    }
+    state
+        .data
+        .chat
+        .webhooks
+        .verify_request(args.id, args.secret.as_deref(), args.signature.as_ref(), now)?;
+
+    let sender_name = state.data.chat.webhooks.get(&args.id).unwrap().name.clone();
+
+    let result = send_webhook_message(args.id, sender_name, args.message, state);
+
+    state.data.chat.webhooks.record_message(args.id, result.is_ok(), now);
+
+    result
+}
+
+fn send_webhook_message(
+    webhook_id: UserId,
+    sender_name: String,
+    mut message: WebhookMessage,
+    state: &mut RuntimeState,
+) -> OCResult<SuccessResult> {
+    let content = message.take_content()?;
+    let replies_to = message.replies_to();
+    let block_level_markdown = message.block_level_markdown();
 
     let send_message_args = group_canister::send_message_v2::Args {
-        thread_root_message_index: None,
+        thread_root_message_index: message.thread_root_message_index,
         message_id: state.env.rng().random::<u64>().into(),
-        content: MessageContentInitial::Text(TextContent { text: args.message }),
-        sender_name: webhook.name.clone(),
+        content,
+        sender_name,
         sender_display_name: None,
-        replies_to: None,
-        mentioned: vec![],
+        replies_to,
+        mentioned: message.mentioned,
         forwarding: false,
-        block_level_markdown: true,
+        block_level_markdown,
         rules_accepted: None,
         message_filter_failed: None,
         new_achievement: false,
         og_previews: Vec::new(),
     };
 
-    send_message_impl(send_message_args, Some(Caller::Webhook(args.id)), true, state)
+    send_message_impl(send_message_args, Some(Caller::Webhook(webhook_id)), true, state)
 }
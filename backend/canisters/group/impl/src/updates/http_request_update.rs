@@ -1,26 +1,35 @@
 use crate::updates::handle_webhook;
-use http_request::{Route, WebhookRoute, build_json_response, extract_route};
+use http_request::{
+    Route, WebhookRoute, build_json_response, extract_route, extract_webhook_message, extract_webhook_signature,
+};
 use ic_cdk::update;
 use types::{HttpRequest, HttpResponse};
 
 #[update]
 fn http_request_update(request: HttpRequest) -> HttpResponse {
-    fn handle_webhook(route: WebhookRoute, body: Vec<u8>) -> HttpResponse {
-        let Some(message) = String::from_utf8(body).ok() else {
-            return HttpResponse::bad_request("Invalid UTF-8");
+    fn handle_webhook(route: WebhookRoute, request: &HttpRequest) -> HttpResponse {
+        let message = match extract_webhook_message(request) {
+            Ok(message) => message,
+            Err(error) => return HttpResponse::bad_request(&error),
+        };
+
+        let signature = match extract_webhook_signature(request) {
+            Ok(signature) => signature,
+            Err(error) => return HttpResponse::bad_request(&error),
         };
 
         let response = handle_webhook::handle_webhook(group_canister::handle_webhook::Args {
             id: route.webhook_id,
             secret: route.secret,
             message,
+            signature,
         });
 
         build_json_response(&response)
     }
 
     match extract_route(&request.url) {
-        Route::Webhook(route) => handle_webhook(route, request.body),
+        Route::Webhook(route) => handle_webhook(route, &request),
         _ => HttpResponse::not_found(),
     }
 }
@@ -1,5 +1,6 @@
 pub mod accept_p2p_swap;
 pub mod add_reaction;
+pub mod c2c_bot_acknowledge_events;
 pub mod c2c_claim_prize;
 pub mod c2c_delete_group;
 pub mod c2c_export_group;
@@ -29,6 +30,7 @@ pub mod convert_into_community;
 pub mod decline_invitation;
 pub mod delete_history;
 pub mod delete_messages;
+pub mod delete_outgoing_webhook;
 pub mod delete_webhook;
 pub mod disable_invite_code;
 pub mod edit_message;
@@ -39,7 +41,9 @@ pub mod handle_webhook;
 pub mod http_request_update;
 pub mod join_video_call;
 pub mod pin_message;
+pub mod press_message_button;
 pub mod regenerate_webhook;
+pub mod register_outgoing_webhook;
 pub mod register_poll_vote;
 pub mod register_proposal_vote;
 pub mod register_proposal_vote_v2;
@@ -48,14 +52,17 @@ pub mod remove_participant;
 pub mod remove_reaction;
 pub mod report_message;
 pub mod send_message;
+pub mod set_notification_filters;
 pub mod set_video_call_presence;
 pub mod start_video_call;
 pub mod toggle_mute_notifications;
+pub mod translate_message;
 pub mod unblock_user;
 pub mod undelete_messages;
 pub mod unfollow_thread;
 pub mod unpin_message;
 pub mod update_bot;
 pub mod update_group_v2;
+pub mod update_outgoing_webhook;
 pub mod update_webhook;
 pub mod wallet_receive;
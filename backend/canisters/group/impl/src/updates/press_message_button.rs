@@ -0,0 +1,72 @@
+use crate::{RuntimeState, execute_update_async, read_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::press_message_button::{Response::*, *};
+use local_user_index_canister::c2c_issue_bot_interaction_token;
+use oc_error_codes::OCErrorCode;
+use types::{BotActionChatDetails, BotActionScope, BotInitiator, BotInteraction, CanisterId, Chat, OCResult};
+
+#[update(msgpack = true)]
+#[trace]
+async fn press_message_button(args: Args) -> Response {
+    execute_update_async(|| press_message_button_impl(args)).await
+}
+
+async fn press_message_button_impl(args: Args) -> Response {
+    let (c2c_args, local_user_index_canister_id) = match read_state(|state| prepare(args, state)) {
+        Ok(ok) => ok,
+        Err(error) => return Error(error),
+    };
+
+    match local_user_index_canister_c2c_client::c2c_issue_bot_interaction_token(local_user_index_canister_id, &c2c_args).await {
+        Ok(c2c_issue_bot_interaction_token::Response::Success(result)) => Success(SuccessResult {
+            token: result.token,
+            endpoint: result.endpoint,
+            expires: result.expires,
+        }),
+        Ok(c2c_issue_bot_interaction_token::Response::Error(error)) => Error(error),
+        Err(error) => Error(error.into()),
+    }
+}
+
+fn prepare(args: Args, state: &RuntimeState) -> OCResult<(c2c_issue_bot_interaction_token::Args, CanisterId)> {
+    state.data.verify_not_frozen()?;
+
+    let user_id = state.get_caller_user_id()?;
+    let bot_id = state.data.chat.validate_message_interaction(
+        user_id,
+        args.thread_root_message_index,
+        args.message_id,
+        &args.component_id,
+        args.value.as_deref(),
+    )?;
+
+    let interaction = BotInteraction {
+        message_id: args.message_id,
+        component_id: args.component_id,
+        value: args.value,
+        initiator: user_id,
+    };
+
+    // The bot must still be installed, the token carries whatever it would be granted had the
+    // user run one of its commands
+    let granted_permissions = state
+        .data
+        .granted_bot_permissions(&bot_id, &BotInitiator::Interaction(interaction.clone()))
+        .ok_or(OCErrorCode::BotNotFound)?;
+
+    Ok((
+        c2c_issue_bot_interaction_token::Args {
+            bot_id,
+            scope: BotActionScope::Chat(BotActionChatDetails {
+                chat: Chat::Group(state.env.canister_id().into()),
+                thread: args.thread_root_message_index,
+                message_id: args.message_id,
+                user_message_id: None,
+            }),
+            granted_permissions,
+            interaction,
+        },
+        state.data.local_user_index_canister_id,
+    ))
+}
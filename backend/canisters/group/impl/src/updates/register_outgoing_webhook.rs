@@ -0,0 +1,45 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::register_outgoing_webhook::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+use utils::text_validation::{UsernameValidationError, validate_username_custom};
+
+#[update(msgpack = true)]
+#[trace]
+fn register_outgoing_webhook(args: Args) -> Response {
+    match execute_update(|state| register_outgoing_webhook_impl(args, state)) {
+        Ok(result) => Response::Success(result),
+        Err(error) => Response::Error(error),
+    }
+}
+
+fn register_outgoing_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult<SuccessResult> {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+
+    if !member.role().can_manage_outgoing_webhooks() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    match validate_username_custom(&args.name, 3, 15, &[]) {
+        Ok(_) => {}
+        Err(UsernameValidationError::TooShort(_)) => return Err(OCErrorCode::InvalidRequest.with_message("name too short")),
+        Err(UsernameValidationError::TooLong(_)) => return Err(OCErrorCode::InvalidRequest.with_message("name too long")),
+        Err(UsernameValidationError::Invalid) => return Err(OCErrorCode::InvalidRequest.with_message("name invalid")),
+    };
+
+    let id = state
+        .data
+        .chat
+        .register_outgoing_webhook(args.name, args.url, args.event_types, state.env.rng())?;
+
+    let webhook = state.data.chat.outgoing_webhooks.get(&id).unwrap();
+
+    Ok(SuccessResult {
+        id,
+        secret: webhook.secret.clone(),
+    })
+}
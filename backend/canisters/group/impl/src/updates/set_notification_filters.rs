@@ -0,0 +1,24 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::set_notification_filters::*;
+use types::OCResult;
+
+#[update(msgpack = true)]
+#[trace]
+fn set_notification_filters(args: Args) -> Response {
+    execute_update(|state| set_notification_filters_impl(args, state)).into()
+}
+
+fn set_notification_filters_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let user_id = state.get_caller_user_id()?;
+    let now = state.env.now();
+    if state
+        .data
+        .chat
+        .set_notification_filters(user_id, args.mentions_only, args.keyword_alerts, now)?
+    {
+        state.mark_activity_for_user(user_id);
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::{RuntimeState, execute_update_async, mutate_state, read_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use chat_events::MessageTranslation;
+use group_canister::translate_message::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::{CanisterId, OCResult, UserId, normalize_language_code};
+
+#[update(msgpack = true)]
+#[trace]
+async fn translate_message(args: Args) -> Response {
+    execute_update_async(|| translate_message_impl(args)).await
+}
+
+async fn translate_message_impl(mut args: Args) -> Response {
+    let Some(language) = normalize_language_code(&args.language) else {
+        return Error(OCErrorCode::InvalidLanguage.into());
+    };
+    args.language = language;
+
+    let (text, user_id, local_user_index_canister_id) = match read_state(|state| prepare(&args, state)) {
+        Ok(PrepareResult::Cached(translation)) => return Success(SuccessResult { translation }),
+        Ok(PrepareResult::Required(text, user_id, local_user_index_canister_id)) => {
+            (text, user_id, local_user_index_canister_id)
+        }
+        Err(error) => return Error(error),
+    };
+
+    let c2c_args = local_user_index_canister::c2c_translate_text::Args {
+        user_id,
+        text: text.clone(),
+        target_language: args.language.clone(),
+    };
+    let translation =
+        match local_user_index_canister_c2c_client::c2c_translate_text(local_user_index_canister_id, &c2c_args).await {
+            Ok(local_user_index_canister::c2c_translate_text::Response::Success(translation)) => translation,
+            Ok(local_user_index_canister::c2c_translate_text::Response::Error(error)) => return Error(error),
+            Err(error) => return Error(error.into()),
+        };
+
+    mutate_state(|state| {
+        state.data.chat.cache_message_translation(
+            args.thread_root_message_index,
+            args.message_id,
+            &text,
+            args.language,
+            translation.clone(),
+        )
+    });
+
+    Success(SuccessResult { translation })
+}
+
+enum PrepareResult {
+    Cached(String),
+    Required(String, UserId, CanisterId),
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> OCResult<PrepareResult> {
+    state.data.verify_not_frozen()?;
+
+    let user_id = state.get_caller_user_id()?;
+
+    match state
+        .data
+        .chat
+        .message_translation(user_id, args.thread_root_message_index, args.message_id, &args.language)?
+    {
+        MessageTranslation::Cached(translation) => Ok(PrepareResult::Cached(translation)),
+        MessageTranslation::Required(text) => Ok(PrepareResult::Required(
+            text,
+            user_id,
+            state.data.local_user_index_canister_id,
+        )),
+    }
+}
@@ -121,6 +121,7 @@ fn prepare(args: &Args, state: &RuntimeState) -> OCResult<PrepareResult> {
         &args.avatar,
         permissions,
         &args.public,
+        &args.e2ee_enabled,
     )?;
 
     let avatar_update = args.avatar.as_ref().expand();
@@ -175,6 +176,8 @@ fn commit(my_user_id: UserId, args: Args, state: &mut RuntimeState) -> SuccessRe
         args.messages_visible_to_non_members,
         args.events_ttl,
         OptionUpdate::NoChange,
+        args.edit_history_disabled,
+        args.e2ee_enabled,
         now,
     );
 
@@ -0,0 +1,27 @@
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use group_canister::update_outgoing_webhook::*;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+
+#[update(msgpack = true)]
+#[trace]
+fn update_outgoing_webhook(args: Args) -> Response {
+    execute_update(|state| update_outgoing_webhook_impl(args, state)).into()
+}
+
+fn update_outgoing_webhook_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    state.data.verify_not_frozen()?;
+
+    let member = state.get_calling_member(true)?;
+
+    if !member.role().can_manage_outgoing_webhooks() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    state
+        .data
+        .chat
+        .update_outgoing_webhook(args.id, args.url, args.event_types, args.enabled)
+}
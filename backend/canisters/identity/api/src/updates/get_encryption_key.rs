@@ -1,10 +1,17 @@
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
+use types::Chat;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Args {
     pub key_type: KeyType,
     pub transport_public_key: Vec<u8>,
+    // Required for `KeyType::Chat`, obtained from `local_user_index::access_token_v2`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+    // The epoch of the chat key to derive, must match the epoch in the access token
+    #[serde(default)]
+    pub key_epoch: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -16,6 +23,8 @@ pub enum Response {
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 pub enum KeyType {
     User,
+    // The key shared by all members of the chat, used to wrap the content keys of encrypted files
+    Chat(Chat),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -5,17 +5,17 @@ use ic_cdk::call::RejectCode;
 use ic_cdk_management_canister::{VetKDCurve, VetKDDeriveKeyArgs, VetKDKeyId};
 use identity_canister::get_encryption_key::{Response::*, *};
 use oc_error_codes::{OCError, OCErrorCode};
-use types::{C2CError, CanisterId, OCResult, UserId};
+use types::{C2CError, CLAIM_TYPE_CHAT_ENCRYPTION_KEY, CanisterId, Chat, ChatEncryptionKeyClaims, OCResult, UserId};
 
 #[update(msgpack = true)]
 #[trace]
 async fn get_encryption_key(args: Args) -> Response {
-    let caller_user_id = match mutate_state(prepare) {
+    let caller_user_id = match mutate_state(|state| prepare(&args, state)) {
         Ok(id) => id,
         Err(error) => return Error(error),
     };
 
-    let ContextAndInput { context, input } = calculate_context_and_input(caller_user_id, args.key_type);
+    let ContextAndInput { context, input } = calculate_context_and_input(caller_user_id, args.key_type, args.key_epoch);
 
     let derive_key_result = ic_cdk_management_canister::vetkd_derive_key(&VetKDDeriveKeyArgs {
         key_id: VetKDKeyId {
@@ -49,7 +49,7 @@ async fn get_encryption_key(args: Args) -> Response {
     })
 }
 
-fn prepare(state: &mut RuntimeState) -> OCResult<UserId> {
+fn prepare(args: &Args, state: &mut RuntimeState) -> OCResult<UserId> {
     let caller = state.caller_auth_principal();
     let user_id = state
         .data
@@ -58,6 +58,13 @@ fn prepare(state: &mut RuntimeState) -> OCResult<UserId> {
         .and_then(|p| p.user_id)
         .ok_or(OCErrorCode::InitiatorNotFound)?;
 
+    if let KeyType::Chat(chat) = args.key_type {
+        let access_token = args.access_token.as_deref().ok_or(OCErrorCode::InvalidRequest)?;
+        if !is_chat_member(access_token, user_id, chat, args.key_epoch, state) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+    }
+
     state
         .data
         .encryption_key_requests
@@ -67,6 +74,24 @@ fn prepare(state: &mut RuntimeState) -> OCResult<UserId> {
     Ok(user_id)
 }
 
+// Chat membership is checked by the chat itself when the LocalUserIndex issues the access token,
+// so here we only need to verify that the token was issued to the caller for the requested chat
+// and key epoch
+fn is_chat_member(access_token: &str, user_id: UserId, chat: Chat, key_epoch: u32, state: &RuntimeState) -> bool {
+    jwt::verify_and_decode::<ChatEncryptionKeyClaims>(
+        access_token,
+        state.data.oc_key_pair.public_key_pem(),
+        CLAIM_TYPE_CHAT_ENCRYPTION_KEY,
+    )
+    .is_ok_and(|claims| {
+        let custom = claims.custom();
+        claims.exp_ms() > state.env.now()
+            && custom.user_id == user_id
+            && custom.chat_id == chat
+            && custom.key_epoch == key_epoch
+    })
+}
+
 fn mark_complete(caller_user_id: &UserId, key_type: KeyType, error: Option<OCError>, state: &mut RuntimeState) {
     state
         .data
@@ -80,7 +105,7 @@ struct ContextAndInput {
     input: Vec<u8>,
 }
 
-fn calculate_context_and_input(caller_user_id: UserId, key_type: KeyType) -> ContextAndInput {
+fn calculate_context_and_input(caller_user_id: UserId, key_type: KeyType, key_epoch: u32) -> ContextAndInput {
     let context;
     let input;
     let caller_user_id_bytes = caller_user_id.as_slice();
@@ -89,6 +114,75 @@ fn calculate_context_and_input(caller_user_id: UserId, key_type: KeyType) -> Con
             context = b"oc-user".to_vec();
             input = caller_user_id_bytes.to_vec();
         }
+        KeyType::Chat(chat) => {
+            context = b"oc-chat".to_vec();
+            input = chat_key_input(caller_user_id, chat, key_epoch);
+        }
     }
     ContextAndInput { context, input }
 }
+
+// Each kind of chat is prefixed with a distinct byte and each id with its length, so that the
+// inputs for different chats can never collide. A direct chat is identified by the other user's
+// id from each side, so the two user ids are sorted to ensure both users derive the same key.
+// The key epoch is only appended from epoch 1 onwards, so that keys derived before end-to-end
+// encryption was enabled remain unchanged.
+fn chat_key_input(caller_user_id: UserId, chat: Chat, key_epoch: u32) -> Vec<u8> {
+    fn push_id(input: &mut Vec<u8>, id: &[u8]) {
+        input.push(id.len() as u8);
+        input.extend_from_slice(id);
+    }
+
+    let mut input = Vec::new();
+    match chat {
+        Chat::Direct(chat_id) => {
+            let mut user_ids = [caller_user_id, chat_id.into()];
+            user_ids.sort();
+            input.push(0);
+            for user_id in user_ids {
+                push_id(&mut input, user_id.as_slice());
+            }
+        }
+        Chat::Group(chat_id) => {
+            input.push(1);
+            push_id(&mut input, chat_id.as_slice());
+        }
+        Chat::Channel(community_id, channel_id) => {
+            input.push(2);
+            push_id(&mut input, community_id.as_ref());
+            input.extend_from_slice(&channel_id.as_u32().to_be_bytes());
+        }
+    }
+    if key_epoch > 0 {
+        input.extend_from_slice(&key_epoch.to_be_bytes());
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+    use std::collections::HashSet;
+
+    #[test]
+    fn direct_chat_key_input_is_the_same_for_both_users() {
+        let user1 = UserId::from(Principal::from_slice(&[1, 1, 1, 1, 1, 1, 1, 1, 1, 1]));
+        let user2 = UserId::from(Principal::from_slice(&[2, 2, 2, 2, 2, 2, 2, 2, 2, 2]));
+
+        assert_eq!(
+            chat_key_input(user1, Chat::Direct(user2.into()), 0),
+            chat_key_input(user2, Chat::Direct(user1.into()), 0)
+        );
+    }
+
+    #[test]
+    fn each_key_epoch_has_a_distinct_key_input() {
+        let user = UserId::from(Principal::from_slice(&[1, 1, 1, 1, 1, 1, 1, 1, 1, 1]));
+        let chat = Chat::Group(Principal::from_slice(&[3, 3, 3, 3, 3, 3, 3, 3, 3, 3]).into());
+
+        let inputs: HashSet<_> = (0..3).map(|epoch| chat_key_input(user, chat, epoch)).collect();
+
+        assert_eq!(inputs.len(), 3);
+    }
+}
@@ -11,7 +11,7 @@ use types::{
     ClassifyMessageRequest, CommunityId, CyclesTopUp, DiamondMembershipPlanDuration, GroupChatUserNotificationPayload,
     MessageContentInitial, MessageId, MessageIndex, Notification, NotifyChit, PhoneNumber, ReferralType, SuspensionDuration,
     TimestampMillis, UniquePersonProof, UpdateUserPrincipalArgs, User, UserCanisterStreakInsuranceClaim,
-    UserCanisterStreakInsurancePayment, UserId, UserNotificationPayload, UserType, is_default,
+    UserCanisterStreakInsurancePayment, UserId, UserNotificationPayload, UserQuietHours, UserType, is_default,
 };
 
 mod lifecycle;
@@ -54,12 +54,14 @@ pub enum UserIndexEvent {
     SyncExistingUser(UserDetailsFull),
     UserBlocked(UserId, UserId),
     UserUnblocked(UserId, UserId),
+    UserQuietHoursSet(UserId, Option<UserQuietHours>),
     UpdateChitBalance(UserId, ChitBalance),
     SetPremiumItemCost(SetPremiumItemCost),
     UpdateBlockedUsernamePatterns(UpdateBlockedUsernamePatterns),
     SetOpenAIApiKey(SetOpenAIApiKey),
     SetModerationReferralConfig(SetModerationReferralConfig),
     SetMediaScanConfig(types::MediaScanConfig),
+    SetTranslationProviderConfig(SetTranslationProviderConfig),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -327,6 +329,7 @@ pub enum UserEvent<T = UserNotificationPayload> {
     UserUnblocked(UserId),
     UserSetProfileBackground(Option<u128>),
     SetMaxStreak(u16),
+    SetQuietHours(Option<UserQuietHours>),
     EventStoreEvent(Event),
     Notification(Box<Notification<T>>),
 }
@@ -443,3 +446,8 @@ pub struct SetOpenAIApiKey {
 pub struct SetModerationReferralConfig {
     pub config: Option<types::ModerationReferralConfig>,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SetTranslationProviderConfig {
+    pub config: Option<types::TranslationProviderConfig>,
+}
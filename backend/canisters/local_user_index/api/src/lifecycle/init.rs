@@ -1,6 +1,6 @@
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
-use types::{BuildVersion, CanisterId, MediaScanConfig, ModerationReferralConfig};
+use types::{BuildVersion, CanisterId, MediaScanConfig, ModerationReferralConfig, TranslationProviderConfig};
 
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
@@ -26,5 +26,7 @@ pub struct Args {
     pub moderation_referral_config: Option<ModerationReferralConfig>,
     #[serde(default)]
     pub media_scan_config: MediaScanConfig,
+    #[serde(default)]
+    pub translation_provider_config: Option<TranslationProviderConfig>,
     pub test_mode: bool,
 }
@@ -13,7 +13,9 @@ fn main() {
     generate_ts_method!(local_user_index, chat_events);
     generate_ts_method!(local_user_index, group_and_community_summary_updates_v2);
 
+    generate_ts_method!(local_user_index, bot_acknowledge_events);
     generate_ts_method!(local_user_index, bot_add_reaction);
+    generate_ts_method!(local_user_index, bot_autocomplete);
     generate_ts_method!(local_user_index, bot_change_role);
     generate_ts_method!(local_user_index, bot_remove_user);
     generate_ts_method!(local_user_index, bot_chat_events);
@@ -21,6 +23,7 @@ fn main() {
     generate_ts_method!(local_user_index, bot_community_events);
     generate_ts_method!(local_user_index, bot_community_summary);
     generate_ts_method!(local_user_index, bot_create_channel);
+    generate_ts_method!(local_user_index, bot_events_since);
     generate_ts_method!(local_user_index, bot_delete_channel);
     generate_ts_method!(local_user_index, bot_delete_messages);
     generate_ts_method!(local_user_index, bot_invite_users);
@@ -11,6 +11,7 @@ pub enum Args {
     MarkVideoCallAsEnded(MarkVideoCallAsEndedArgs),
     BotActionByCommand(BotActionByCommandArgs),
     Translate,
+    ChatEncryptionKey(ChatEncryptionKeyArgs),
 }
 
 #[ts_export(local_user_index, access_token_v2)]
@@ -41,6 +42,18 @@ pub struct MarkVideoCallAsEndedArgs {
     pub chat: Chat,
 }
 
+// The resulting token is passed to `identity::get_encryption_key` to prove membership of the chat.
+// Members of end-to-end encrypted chats may request the key of any epoch from the one which was
+// current when they joined up to the current one. If the chat's history is visible to new joiners
+// they may request the key of any earlier epoch.
+#[ts_export]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatEncryptionKeyArgs {
+    pub chat: Chat,
+    #[serde(default)]
+    pub key_epoch: u32,
+}
+
 #[ts_export]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BotActionByCommandArgs {
@@ -0,0 +1,32 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotActionScope, TimestampMillis, UserId};
+
+#[ts_export(local_user_index, bot_autocomplete)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub scope: BotActionScope,
+    pub command_name: String,
+    pub param_name: String,
+}
+
+#[ts_export(local_user_index, bot_autocomplete)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    NotAuthorized,
+    InternalError(String),
+    Error(OCError),
+}
+
+// The token should be sent to `{endpoint}/autocomplete` along with the partially entered value. It
+// can be reused for each keystroke until it expires.
+#[ts_export(local_user_index, bot_autocomplete)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub token: String,
+    pub endpoint: String,
+    pub expires: TimestampMillis,
+}
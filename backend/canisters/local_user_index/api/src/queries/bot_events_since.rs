@@ -0,0 +1,30 @@
+use candid::{CandidType, Deserialize};
+use oc_error_codes::OCError;
+use serde::Serialize;
+use ts_export::ts_export;
+use types::{BotEventWrapper, Chat, TimestampMillis};
+
+#[ts_export(local_user_index, bot_events_since)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub chat: Chat,
+    // Defaults to the cursor last acknowledged by the bot
+    pub since: Option<TimestampMillis>,
+    pub max_events: u32,
+}
+
+#[ts_export(local_user_index, bot_events_since)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(local_user_index, bot_events_since)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub events: Vec<BotEventWrapper>,
+    pub cursor: TimestampMillis,
+    pub complete: bool,
+    pub events_pruned: bool,
+}
@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Chat, TimestampMillis, UnitResult};
+
+#[ts_export(local_user_index, bot_acknowledge_events)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub chat: Chat,
+    pub cursor: TimestampMillis,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,24 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use types::{BotActionScope, BotInteraction, BotPermissions, TimestampMillis, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub scope: BotActionScope,
+    pub granted_permissions: BotPermissions,
+    pub interaction: BotInteraction,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub token: String,
+    pub endpoint: String,
+    pub expires: TimestampMillis,
+}
@@ -0,0 +1,17 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use types::UserId;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    // The user requesting the translation, whose rate limit it counts towards
+    pub user_id: UserId,
+    pub text: String,
+    pub target_language: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(String),
+    Error(OCError),
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{BotInstallationLocation, BotPermissions, UnitResult, UserId};
+use types::{BotChannelPermissions, BotInstallationLocation, BotPermissions, UnitResult, UserId};
 
 #[ts_export(local_user_index, install_bot)]
 #[derive(Serialize, Deserialize, Debug)]
@@ -9,6 +9,9 @@ pub struct Args {
     pub bot_id: UserId,
     pub granted_permissions: BotPermissions,
     pub granted_autonomous_permissions: Option<BotPermissions>,
+    // Only valid when installing into a community. Restricts the bot to the specified channels.
+    #[serde(default)]
+    pub channel_permissions: Option<Vec<BotChannelPermissions>>,
 }
 
 pub type Response = UnitResult;
@@ -19,6 +19,7 @@ generate_c2c_call!(c2c_delete_community);
 generate_c2c_call!(c2c_delete_group);
 generate_c2c_call!(c2c_group_canister, 300);
 generate_c2c_call!(c2c_group_index, 300);
+generate_c2c_call!(c2c_issue_bot_interaction_token);
 generate_c2c_call!(c2c_notifications_index, 300);
 generate_c2c_call!(c2c_notify_low_balance);
 generate_c2c_call!(c2c_notify_user_index_events);
@@ -27,6 +28,7 @@ generate_c2c_call!(c2c_set_community_upgrade_concurrency);
 generate_c2c_call!(c2c_set_group_upgrade_concurrency);
 generate_c2c_call!(c2c_set_max_concurrent_community_upgrades);
 generate_c2c_call!(c2c_set_max_concurrent_group_upgrades);
+generate_c2c_call!(c2c_translate_text, 60);
 generate_c2c_call!(c2c_trigger_upgrade);
 generate_c2c_call!(c2c_upgrade_community_canister_wasm);
 generate_c2c_call!(c2c_upgrade_group_canister_wasm);
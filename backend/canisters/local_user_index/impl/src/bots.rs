@@ -2,8 +2,8 @@ use crate::RuntimeState;
 use rand::RngExt;
 use types::{
     BotActionByCommandClaims, BotActionChatDetails, BotActionCommunityDetails, BotActionScope, BotChatContext,
-    BotCommunityOrGroupContext, BotInitiator, CLAIM_TYPE_BOT_ACTION_BY_COMMAND, Chat, CommunityOrGroup, MessageId, User,
-    UserId,
+    BotCommunityOrGroupContext, BotInitiator, BotInteractionClaims, CLAIM_TYPE_BOT_ACTION_BY_COMMAND,
+    CLAIM_TYPE_BOT_INTERACTION, Chat, CommunityOrGroup, MessageId, User, UserId,
 };
 
 pub struct BotAccessContext {
@@ -88,23 +88,38 @@ pub fn extract_access_context_from_community_or_group_context(
     })
 }
 
-fn extract_access_context_from_jwt(jwt: &str, bot: &User, state: &mut RuntimeState) -> Result<BotAccessContext, String> {
-    const INVALID_MESSAGE: &str = "Not a valid access token JWT";
+const INVALID_MESSAGE: &str = "Not a valid access token JWT";
 
-    let claims = jwt::verify_and_decode::<BotActionByCommandClaims>(
-        jwt,
-        state.data.oc_key_pair.public_key_pem(),
-        CLAIM_TYPE_BOT_ACTION_BY_COMMAND,
-    )
-    .map_err(|_| INVALID_MESSAGE.to_string())?;
+fn extract_access_context_from_jwt(jwt: &str, bot: &User, state: &mut RuntimeState) -> Result<BotAccessContext, String> {
+    let public_key_pem = state.data.oc_key_pair.public_key_pem();
+
+    let (exp_ms, bot_id, initiator, scope) =
+        match jwt::verify_and_decode::<BotActionByCommandClaims>(jwt, public_key_pem, CLAIM_TYPE_BOT_ACTION_BY_COMMAND) {
+            Ok(claims) => {
+                let exp_ms = claims.exp_ms();
+                let custom = claims.into_custom();
+                (exp_ms, custom.bot, BotInitiator::Command(custom.command), custom.scope)
+            }
+            // Tokens issued when a user interacts with one of the bot's messages can be used in
+            // the same way as command tokens
+            Err(_) => {
+                let claims = jwt::verify_and_decode::<BotInteractionClaims>(jwt, public_key_pem, CLAIM_TYPE_BOT_INTERACTION)
+                    .map_err(|_| INVALID_MESSAGE.to_string())?;
+                let exp_ms = claims.exp_ms();
+                let custom = claims.into_custom();
+                (
+                    exp_ms,
+                    custom.bot,
+                    BotInitiator::Interaction(custom.interaction),
+                    custom.scope,
+                )
+            }
+        };
 
-    if claims.exp_ms() < state.env.now() {
+    if exp_ms < state.env.now() {
         return Err("Access token expired".to_string());
     }
 
-    let bot_action_claims = claims.into_custom();
-    let bot_id = bot_action_claims.bot;
-
     if bot.user_id != bot_id {
         return Err(INVALID_MESSAGE.to_string());
     }
@@ -112,7 +127,7 @@ fn extract_access_context_from_jwt(jwt: &str, bot: &User, state: &mut RuntimeSta
     Ok(BotAccessContext {
         bot_id,
         bot_name: bot.username.clone(),
-        initiator: BotInitiator::Command(bot_action_claims.command),
-        scope: bot_action_claims.scope,
+        initiator,
+        scope,
     })
 }
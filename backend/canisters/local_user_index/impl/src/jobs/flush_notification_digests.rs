@@ -0,0 +1,54 @@
+use crate::{RuntimeState, mutate_state};
+use ic_cdk_timers::TimerId;
+use std::cell::Cell;
+use std::time::Duration;
+use tracing::trace;
+use types::{Notification, TimestampMillis, UserNotification, UserNotificationPayload};
+
+thread_local! {
+    static TIMER: Cell<Option<(TimerId, TimestampMillis)>> = Cell::default();
+}
+
+// Schedules the job for when the next digest is due, rescheduling it if a digest has been added
+// which is due before the time the job is currently scheduled for
+pub(crate) fn start_job_if_required(state: &RuntimeState) -> bool {
+    let Some(next_due) = state.data.quiet_hours.next_digest_due() else {
+        return false;
+    };
+
+    if let Some((timer_id, scheduled_for)) = TIMER.get() {
+        if scheduled_for <= next_due {
+            return false;
+        }
+        ic_cdk_timers::clear_timer(timer_id);
+    }
+
+    let delay = next_due.saturating_sub(state.env.now());
+    let timer_id = ic_cdk_timers::set_timer(Duration::from_millis(delay), async { run() });
+    TIMER.set(Some((timer_id, next_due)));
+    true
+}
+
+fn run() {
+    trace!("'flush_notification_digests' running");
+    TIMER.set(None);
+
+    mutate_state(|state| {
+        let now = state.env.now();
+        let this_canister_id = state.env.canister_id();
+
+        for (user_id, digest) in state.data.quiet_hours.take_due_digests(now) {
+            state.handle_notification(
+                Notification::User(UserNotification {
+                    sender: None,
+                    recipients: vec![user_id],
+                    notification: UserNotificationPayload::Digest(digest),
+                }),
+                this_canister_id,
+                now,
+            );
+        }
+
+        start_job_if_required(state);
+    });
+}
@@ -4,6 +4,7 @@ use tracing::info;
 
 pub mod check_media_scan_stall;
 pub mod delete_users;
+pub mod flush_notification_digests;
 pub mod moderate_messages;
 pub mod topup_canister_pool;
 pub mod topup_canisters;
@@ -14,6 +15,7 @@ pub mod upgrade_users;
 pub(crate) fn start(state: &RuntimeState) {
     check_media_scan_stall::start_job();
     delete_users::start_job_if_required(state, None);
+    flush_notification_digests::start_job_if_required(state);
     moderate_messages::start_job_if_required(state);
     topup_canister_pool::start_job_if_required(state, None);
     topup_canisters::start_job();
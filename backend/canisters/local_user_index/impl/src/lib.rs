@@ -5,7 +5,9 @@ use crate::model::local_group_map::LocalGroupMap;
 use crate::model::media_scan_job_log::MediaScanJobLog;
 use crate::model::moderation_queue::ModerationQueue;
 use crate::model::premium_items::PremiumItems;
+use crate::model::quiet_hours::QuietHoursMap;
 use crate::model::referral_codes::{ReferralCodes, ReferralTypeMetrics};
+use crate::model::translation_rate_limiter::TranslationRateLimiter;
 use crate::model::user_event_batch::UserEventBatch;
 use crate::model::user_index_event_batch::UserIndexEventBatch;
 use crate::model::web_push_subscriptions::WebPushSubscriptions;
@@ -40,8 +42,8 @@ use types::{
     CLAIM_TYPE_DIAMOND_MEMBERSHIP, CanisterId, ChannelLatestMessageIndex, ChatId, ChildCanisterWasms,
     CommunityCanisterChannelSummary, CommunityCanisterCommunitySummary, CommunityId, Cycles, DiamondMembershipDetails,
     IdempotentEnvelope, MediaScanConfig, MessageContentInitial, Milliseconds, ModerationReferralConfig, Notification,
-    NotificationEnvelope, ReferralType, TimestampMillis, Timestamped, UserId, UserNotificationEnvelope,
-    VerifiedCredentialGateArgs,
+    NotificationEnvelope, ReferralType, TimestampMillis, Timestamped, TranslationProviderConfig, UserId,
+    UserNotificationEnvelope, VerifiedCredentialGateArgs,
 };
 use user_canister::LocalUserIndexEvent as UserEvent;
 use user_ids_set::UserIdsSet;
@@ -352,15 +354,20 @@ impl RuntimeState {
                     .map(|s| self.data.blocked_users.all_linked_users(s))
                     .unwrap_or_default();
 
-                let filtered_recipients: Vec<_> = user_notification
-                    .recipients
-                    .into_iter()
-                    .filter(|u| {
-                        (self.data.web_push_subscriptions.any_for_user(u)
-                            || !self.data.fcm_token_store.get_for_user(u).is_empty())
-                            && !users_who_have_blocked_sender.contains(u)
-                    })
-                    .collect();
+                let mut filtered_recipients = Vec::new();
+                for user_id in user_notification.recipients {
+                    if (self.data.web_push_subscriptions.any_for_user(&user_id)
+                        || !self.data.fcm_token_store.get_for_user(&user_id).is_empty())
+                        && !users_who_have_blocked_sender.contains(&user_id)
+                        && self
+                            .data
+                            .quiet_hours
+                            .should_push(user_id, &user_notification.notification, now)
+                    {
+                        filtered_recipients.push(user_id);
+                    }
+                }
+                jobs::flush_notification_digests::start_job_if_required(self);
 
                 if !filtered_recipients.is_empty() {
                     self.data
@@ -369,7 +376,7 @@ impl RuntimeState {
                             recipients: filtered_recipients,
                             notification_bytes: ByteBuf::from(msgpack::serialize_then_unwrap(&user_notification.notification)),
                             timestamp: now,
-                            fcm_data: Some(user_notification.notification.into()),
+                            fcm_data: user_notification.notification.into(),
                         })));
                 }
             }
@@ -520,9 +527,12 @@ impl RuntimeState {
             web_push_subscriptions: self.data.web_push_subscriptions.total(),
             fcm_token_count: self.data.fcm_token_store.len(),
             blocked_user_pairs: self.data.blocked_users.len() as u64,
+            users_with_quiet_hours: self.data.quiet_hours.users_count(),
+            pending_notification_digests: self.data.quiet_hours.pending_digests_count(),
             oc_secret_key_initialized: self.data.oc_key_pair.is_initialised(),
             openai_api_key_set: self.data.openai_api_key.is_some(),
             moderation_referral_config: self.data.moderation_referral_config.clone(),
+            translation_provider_set: self.data.translation_provider_config.is_some(),
             message_moderation_queue_len: self.data.message_moderation_queue.len() as u32,
             media_scanning_enabled: self.data.media_scan_config.enabled,
             media_scan_job_log_len: self.data.media_scan_job_log.len() as u32,
@@ -620,6 +630,12 @@ struct Data {
     pub media_scan_config: MediaScanConfig,
     #[serde(default)]
     pub media_scan_job_log: MediaScanJobLog,
+    #[serde(default)]
+    pub translation_provider_config: Option<TranslationProviderConfig>,
+    #[serde(default)]
+    pub translation_rate_limiter: TranslationRateLimiter,
+    #[serde(default)]
+    pub quiet_hours: QuietHoursMap,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -657,6 +673,7 @@ impl Data {
         openai_api_key: Option<String>,
         moderation_referral_config: Option<ModerationReferralConfig>,
         media_scan_config: MediaScanConfig,
+        translation_provider_config: Option<TranslationProviderConfig>,
         test_mode: bool,
     ) -> Self {
         Data {
@@ -718,6 +735,9 @@ impl Data {
             message_moderation_queue: ModerationQueue::default(),
             media_scan_config,
             media_scan_job_log: MediaScanJobLog::default(),
+            translation_provider_config,
+            translation_rate_limiter: TranslationRateLimiter::default(),
+            quiet_hours: QuietHoursMap::default(),
         }
     }
 }
@@ -781,9 +801,12 @@ pub struct Metrics {
     pub web_push_subscriptions: u64,
     pub fcm_token_count: usize,
     pub blocked_user_pairs: u64,
+    pub users_with_quiet_hours: u32,
+    pub pending_notification_digests: u32,
     pub oc_secret_key_initialized: bool,
     pub openai_api_key_set: bool,
     pub moderation_referral_config: Option<ModerationReferralConfig>,
+    pub translation_provider_set: bool,
     pub message_moderation_queue_len: u32,
     pub media_scanning_enabled: bool,
     pub media_scan_job_log_len: u32,
@@ -37,6 +37,7 @@ fn init(args: Args) {
         args.openai_api_key,
         args.moderation_referral_config,
         args.media_scan_config,
+        args.translation_provider_config,
         args.test_mode,
     );
 
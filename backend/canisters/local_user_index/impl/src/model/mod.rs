@@ -8,7 +8,9 @@ pub mod local_user_map;
 pub mod media_scan_job_log;
 pub mod moderation_queue;
 pub mod premium_items;
+pub mod quiet_hours;
 pub mod referral_codes;
+pub mod translation_rate_limiter;
 pub mod user_event_batch;
 pub mod user_index_event_batch;
 pub mod web_push_subscriptions;
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use types::{
+    Chat, NotificationDigest, NotificationDigestChat, TimestampMillis, UserId, UserNotificationPayload, UserQuietHours,
+};
+
+// Caps the size of each digest, any further chats are still included in the total count
+const MAX_DIGEST_CHATS: usize = 10;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(from = "QuietHoursMapTrimmed")]
+pub struct QuietHoursMap {
+    users: HashMap<UserId, UserQuietHours>,
+    pending_digests: HashMap<UserId, PendingDigest>,
+    #[serde(skip)]
+    digests_by_due: BTreeSet<(TimestampMillis, UserId)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PendingDigest {
+    #[serde(rename = "d")]
+    due: TimestampMillis,
+    #[serde(rename = "n")]
+    notification_count: u32,
+    #[serde(rename = "c")]
+    chats: Vec<NotificationDigestChat>,
+}
+
+impl QuietHoursMap {
+    // Any pending digest is rescheduled for the end of the user's new quiet window, or is due
+    // immediately if they are no longer in quiet hours (including if they have turned them off)
+    pub fn set(&mut self, user_id: UserId, quiet_hours: Option<UserQuietHours>, now: TimestampMillis) {
+        let digest_due = quiet_hours
+            .as_ref()
+            .and_then(|q| q.quiet_hours.window_end(now, q.utc_offset_mins))
+            .unwrap_or(now);
+
+        if let Some(quiet_hours) = quiet_hours {
+            self.users.insert(user_id, quiet_hours);
+        } else {
+            self.users.remove(&user_id);
+        }
+
+        if let Some(digest) = self.pending_digests.get_mut(&user_id) {
+            self.digests_by_due.remove(&(digest.due, user_id));
+            self.digests_by_due.insert((digest_due, user_id));
+            digest.due = digest_due;
+        }
+    }
+
+    // Returns false if the notification should be held back due to the user's quiet hours, in
+    // which case, if the user has opted into digests, it is added to their pending digest
+    pub fn should_push(&mut self, user_id: UserId, notification: &UserNotificationPayload, now: TimestampMillis) -> bool {
+        let Some(user) = self.users.get(&user_id) else {
+            return true;
+        };
+        let Some(window_end) = user.quiet_hours.window_end(now, user.utc_offset_mins) else {
+            return true;
+        };
+
+        if user.quiet_hours.digest
+            && let Some((chat, name)) = notification.chat_and_name()
+        {
+            let digest = self.pending_digests.entry(user_id).or_insert_with(|| {
+                self.digests_by_due.insert((window_end, user_id));
+                PendingDigest {
+                    due: window_end,
+                    notification_count: 0,
+                    chats: Vec::new(),
+                }
+            });
+            digest.add(chat, name);
+        }
+        false
+    }
+
+    pub fn take_due_digests(&mut self, now: TimestampMillis) -> Vec<(UserId, NotificationDigest)> {
+        let mut digests = Vec::new();
+        while let Some(&(due, user_id)) = self.digests_by_due.first()
+            && due <= now
+        {
+            self.digests_by_due.pop_first();
+            if let Some(digest) = self.pending_digests.remove(&user_id) {
+                digests.push((user_id, digest.into()));
+            }
+        }
+        digests
+    }
+
+    pub fn next_digest_due(&self) -> Option<TimestampMillis> {
+        self.digests_by_due.first().map(|(due, _)| *due)
+    }
+
+    pub fn users_count(&self) -> u32 {
+        self.users.len() as u32
+    }
+
+    pub fn pending_digests_count(&self) -> u32 {
+        self.pending_digests.len() as u32
+    }
+}
+
+#[derive(Deserialize)]
+struct QuietHoursMapTrimmed {
+    users: HashMap<UserId, UserQuietHours>,
+    pending_digests: HashMap<UserId, PendingDigest>,
+}
+
+impl From<QuietHoursMapTrimmed> for QuietHoursMap {
+    fn from(value: QuietHoursMapTrimmed) -> Self {
+        let digests_by_due = value.pending_digests.iter().map(|(u, d)| (d.due, *u)).collect();
+
+        QuietHoursMap {
+            users: value.users,
+            pending_digests: value.pending_digests,
+            digests_by_due,
+        }
+    }
+}
+
+impl PendingDigest {
+    fn add(&mut self, chat: Chat, name: String) {
+        self.notification_count += 1;
+
+        if let Some(existing) = self.chats.iter_mut().find(|c| c.chat == chat) {
+            existing.notification_count += 1;
+        } else if self.chats.len() < MAX_DIGEST_CHATS {
+            self.chats.push(NotificationDigestChat {
+                chat,
+                name,
+                notification_count: 1,
+            });
+        }
+    }
+}
+
+impl From<PendingDigest> for NotificationDigest {
+    fn from(mut value: PendingDigest) -> Self {
+        value.chats.sort_by(|a, b| b.notification_count.cmp(&a.notification_count));
+
+        NotificationDigest {
+            notification_count: value.notification_count,
+            chats: value.chats,
+        }
+    }
+}
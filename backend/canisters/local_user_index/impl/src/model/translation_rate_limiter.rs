@@ -0,0 +1,84 @@
+use constants::{HOUR_IN_MS, MINUTE_IN_MS};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use types::{TimestampMillis, UserId};
+
+// Each translation which isn't already cached costs a request to the provider, so the number each
+// user can request is capped
+const MAX_TRANSLATIONS_PER_MINUTE: usize = 10;
+const MAX_TRANSLATIONS_PER_HOUR: usize = 100;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct TranslationRateLimiter {
+    recent_translations: HashMap<UserId, VecDeque<TimestampMillis>>,
+}
+
+impl TranslationRateLimiter {
+    // Records the translation and returns true if the user is within their limits, otherwise
+    // returns false
+    pub fn try_record(&mut self, user_id: UserId, now: TimestampMillis) -> bool {
+        self.prune(now);
+
+        let recent = self.recent_translations.entry(user_id).or_default();
+        let last_minute = recent.iter().rev().take_while(|ts| **ts + MINUTE_IN_MS > now).count();
+
+        if recent.len() >= MAX_TRANSLATIONS_PER_HOUR || last_minute >= MAX_TRANSLATIONS_PER_MINUTE {
+            return false;
+        }
+
+        recent.push_back(now);
+        true
+    }
+
+    fn prune(&mut self, now: TimestampMillis) {
+        self.recent_translations.retain(|_, recent| {
+            while recent.front().is_some_and(|ts| *ts + HOUR_IN_MS <= now) {
+                recent.pop_front();
+            }
+            !recent.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    #[test]
+    fn per_minute_limit_applied() {
+        let mut limiter = TranslationRateLimiter::default();
+        let user_id = UserId::from(Principal::from_slice(&[1]));
+
+        for _ in 0..MAX_TRANSLATIONS_PER_MINUTE {
+            assert!(limiter.try_record(user_id, 1000));
+        }
+        assert!(!limiter.try_record(user_id, 1000));
+        assert!(limiter.try_record(user_id, 1000 + MINUTE_IN_MS));
+    }
+
+    #[test]
+    fn per_hour_limit_applied() {
+        let mut limiter = TranslationRateLimiter::default();
+        let user_id = UserId::from(Principal::from_slice(&[1]));
+
+        for i in 0..MAX_TRANSLATIONS_PER_HOUR as u64 {
+            assert!(limiter.try_record(user_id, i * 10 * 1000));
+        }
+        assert!(!limiter.try_record(user_id, 1000 * 1000));
+        assert!(limiter.try_record(user_id, HOUR_IN_MS));
+    }
+
+    #[test]
+    fn limits_are_per_user() {
+        let mut limiter = TranslationRateLimiter::default();
+        let user1 = UserId::from(Principal::from_slice(&[1]));
+        let user2 = UserId::from(Principal::from_slice(&[2]));
+
+        for _ in 0..MAX_TRANSLATIONS_PER_MINUTE {
+            assert!(limiter.try_record(user1, 1000));
+        }
+        assert!(!limiter.try_record(user1, 1000));
+        assert!(limiter.try_record(user2, 1000));
+    }
+}
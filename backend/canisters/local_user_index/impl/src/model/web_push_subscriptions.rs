@@ -1,26 +1,53 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use types::{SubscriptionInfo, UserId};
+use types::{NotificationSubscription, PushBackend, SubscriptionInfo, UserId};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct WebPushSubscriptions {
     subscriptions: HashMap<UserId, Vec<SubscriptionInfo>>,
+    // Only holds the subscriptions whose backend is something other than `PushBackend::WebPush`.
+    // Keyed by user as well as endpoint so that one user can't change the backend (or the secret)
+    // used when pushing to another user's subscription by registering the same endpoint.
+    #[serde(default)]
+    backends: HashMap<(UserId, String), PushBackend>,
     total: u64,
 }
 
 impl WebPushSubscriptions {
-    pub fn get(&self, user_id: &UserId) -> Option<Vec<SubscriptionInfo>> {
-        self.subscriptions.get(user_id).cloned()
+    pub fn get(&self, user_id: &UserId) -> Vec<NotificationSubscription> {
+        self.subscriptions.get(user_id).map_or(Vec::new(), |subscriptions| {
+            subscriptions
+                .iter()
+                .map(|s| {
+                    let backend = self
+                        .backends
+                        .get(&(*user_id, s.endpoint.clone()))
+                        .cloned()
+                        .unwrap_or_default();
+                    NotificationSubscription::new(s.clone(), backend)
+                })
+                .collect()
+        })
     }
 
-    pub fn push(&mut self, user_id: UserId, subscription: SubscriptionInfo) {
+    pub fn push(&mut self, user_id: UserId, subscription: SubscriptionInfo, backend: PushBackend) {
+        let key = (user_id, subscription.endpoint.clone());
+        if backend == PushBackend::WebPush {
+            self.backends.remove(&key);
+        } else {
+            self.backends.insert(key, backend);
+        }
+
         match self.subscriptions.entry(user_id) {
             Occupied(e) => {
                 let subscriptions = e.into_mut();
-                if !subscriptions.contains(&subscription) {
-                    subscriptions.push(subscription);
+                // If the endpoint is already subscribed, overwrite its keys in case they've changed
+                if let Some(existing) = subscriptions.iter_mut().find(|s| s.endpoint == subscription.endpoint) {
+                    *existing = subscription;
+                    return;
                 }
+                subscriptions.push(subscription);
             }
             Vacant(e) => {
                 e.insert(vec![subscription]);
@@ -37,6 +64,9 @@ impl WebPushSubscriptions {
     pub fn remove_all(&mut self, user_id: UserId) {
         if let Some(removed) = self.subscriptions.remove(&user_id) {
             self.total = self.total.saturating_sub(removed.len() as u64);
+            for subscription in removed {
+                self.backends.remove(&(user_id, subscription.endpoint));
+            }
         }
     }
 
@@ -49,7 +79,8 @@ impl WebPushSubscriptions {
                 .find(|(_, s)| s.endpoint.as_str() == endpoint || s.keys.p256dh.as_str() == endpoint)
                 .map(|(i, _)| i)
             {
-                subs.remove(index);
+                let removed = subs.remove(index);
+                self.backends.remove(&(user_id, removed.endpoint));
                 if subs.is_empty() {
                     e.remove();
                 }
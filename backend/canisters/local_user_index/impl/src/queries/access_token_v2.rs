@@ -8,12 +8,13 @@ use rand::SeedableRng;
 use rand::rngs::StdRng;
 use serde::Serialize;
 use types::c2c_can_issue_access_token::{
-    AccessTypeArgs, BotActionByCommandArgs, JoinVideoCallArgs, MarkVideoCallAsEndedArgs, StartVideoCallArgs,
+    AccessTypeArgs, BotActionByCommandArgs, ChatEncryptionKeyArgs, JoinVideoCallArgs, MarkVideoCallAsEndedArgs,
+    StartVideoCallArgs,
 };
 use types::{
-    AutonomousBotScope, BotActionByCommandClaims, BotCommand, CLAIM_TYPE_BOT_ACTION_BY_COMMAND, CLAIM_TYPE_JOIN_VIDEO_CALL,
-    CLAIM_TYPE_MARK_VIDEO_CALL_AS_ENDED, CLAIM_TYPE_START_VIDEO_CALL, Chat, JoinOrEndVideoCallClaims, Milliseconds,
-    StartVideoCallClaims, TranslateClaims,
+    AutonomousBotScope, BotActionByCommandClaims, BotCommand, CLAIM_TYPE_BOT_ACTION_BY_COMMAND, CLAIM_TYPE_CHAT_ENCRYPTION_KEY,
+    CLAIM_TYPE_JOIN_VIDEO_CALL, CLAIM_TYPE_MARK_VIDEO_CALL_AS_ENDED, CLAIM_TYPE_START_VIDEO_CALL, Chat,
+    ChatEncryptionKeyClaims, JoinOrEndVideoCallClaims, Milliseconds, StartVideoCallClaims, TimestampMillis, TranslateClaims,
 };
 
 const DEFAULT_TOKEN_VALIDITY: Milliseconds = 5 * 60 * 1000;
@@ -35,9 +36,11 @@ async fn access_token_v2(args_wrapper: Args) -> Response {
         Err(response) => return response,
     };
 
-    if let Err(error_response) = can_issue_access_token(scope, &access_type_args).await {
-        return error_response;
-    };
+    match can_issue_access_token(scope, &access_type_args).await {
+        Ok(true) => {}
+        Ok(false) => return NotAuthorized,
+        Err(error) => return InternalError(error),
+    }
 
     let token_type_name = args_wrapper.type_name().to_string();
 
@@ -86,6 +89,14 @@ async fn access_token_v2(args_wrapper: Args) -> Response {
                 };
                 build_token(token_type_name, custom_claims, DEFAULT_TOKEN_VALIDITY, state)
             }
+            AccessTypeArgs::ChatEncryptionKey(args) => {
+                let custom_claims = ChatEncryptionKeyClaims {
+                    user_id: args.initiator,
+                    chat_id: chat.unwrap(),
+                    key_epoch: args.key_epoch,
+                };
+                build_token(token_type_name, custom_claims, DEFAULT_TOKEN_VALIDITY, state)
+            }
             _ => unreachable!(),
         }
     })
@@ -149,6 +160,13 @@ fn prepare(args_outer: &ArgsInternal, state: &RuntimeState) -> Result<PrepareRes
             scope: AutonomousBotScope::Chat(args.chat),
             access_type_args: AccessTypeArgs::MarkVideoCallAsEnded(MarkVideoCallAsEndedArgs { initiator: user_id }),
         },
+        ArgsInternal::ChatEncryptionKey(args) => PrepareResult {
+            scope: AutonomousBotScope::Chat(args.chat),
+            access_type_args: AccessTypeArgs::ChatEncryptionKey(ChatEncryptionKeyArgs {
+                initiator: user_id,
+                key_epoch: args.key_epoch,
+            }),
+        },
         _ => unreachable!(),
     };
 
@@ -187,18 +205,27 @@ fn build_token<T: Serialize>(
     validity: Milliseconds,
     state: &mut RuntimeState,
 ) -> Response {
+    match sign_token(token_type_name, custom_claims, state.env.now() + validity, state) {
+        Ok(token) => Success(token),
+        Err(error) => InternalError(error),
+    }
+}
+
+pub(crate) fn sign_token<T: Serialize>(
+    token_type_name: String,
+    custom_claims: T,
+    expiry: TimestampMillis,
+    state: &mut RuntimeState,
+) -> Result<String, String> {
     if !state.data.oc_key_pair.is_initialised() {
-        return InternalError("OC Secret not set".to_string());
+        return Err("OC Secret not set".to_string());
     };
 
     let mut rng = StdRng::from_seed(state.env.entropy());
 
-    let claims = Claims::new(state.env.now() + validity, token_type_name, custom_claims);
+    let claims = Claims::new(expiry, token_type_name, custom_claims);
 
-    match jwt::sign_and_encode_token(state.data.oc_key_pair.secret_key_der(), claims, &mut rng) {
-        Ok(token) => Success(token),
-        Err(err) => InternalError(format!("{err:?}")),
-    }
+    jwt::sign_and_encode_token(state.data.oc_key_pair.secret_key_der(), claims, &mut rng).map_err(|err| format!("{err:?}"))
 }
 
 #[derive(Debug)]
@@ -208,6 +235,7 @@ enum ArgsInternal {
     MarkVideoCallAsEnded(access_token_v2::MarkVideoCallAsEndedArgs),
     BotActionByCommand(access_token_v2::BotActionByCommandArgs),
     Translate,
+    ChatEncryptionKey(access_token_v2::ChatEncryptionKeyArgs),
 }
 
 impl ArgsInternal {
@@ -218,6 +246,7 @@ impl ArgsInternal {
             Args::MarkVideoCallAsEnded(args) => Ok(ArgsInternal::MarkVideoCallAsEnded(args)),
             Args::BotActionByCommand(args) => Ok(ArgsInternal::BotActionByCommand(args)),
             Args::Translate => Ok(ArgsInternal::Translate),
+            Args::ChatEncryptionKey(args) => Ok(ArgsInternal::ChatEncryptionKey(args)),
         }
     }
 
@@ -228,6 +257,7 @@ impl ArgsInternal {
             Self::MarkVideoCallAsEnded(_) => CLAIM_TYPE_MARK_VIDEO_CALL_AS_ENDED,
             Self::BotActionByCommand(_) => CLAIM_TYPE_BOT_ACTION_BY_COMMAND,
             Self::Translate => "Translate",
+            Self::ChatEncryptionKey(_) => CLAIM_TYPE_CHAT_ENCRYPTION_KEY,
         }
     }
 
@@ -238,11 +268,15 @@ impl ArgsInternal {
             Self::MarkVideoCallAsEnded(args) => Some(args.chat),
             Self::BotActionByCommand(args) => args.scope.chat(None),
             Self::Translate => None,
+            Self::ChatEncryptionKey(args) => Some(args.chat),
         }
     }
 }
 
-async fn can_issue_access_token(scope: AutonomousBotScope, access_type_args: &AccessTypeArgs) -> Result<(), Response> {
+pub(crate) async fn can_issue_access_token(
+    scope: AutonomousBotScope,
+    access_type_args: &AccessTypeArgs,
+) -> Result<bool, String> {
     let c2c_response = match scope {
         AutonomousBotScope::Chat(Chat::Direct(chat_id)) => {
             user_canister_c2c_client::c2c_can_issue_access_token_v2(chat_id.into(), access_type_args).await
@@ -273,8 +307,8 @@ async fn can_issue_access_token(scope: AutonomousBotScope, access_type_args: &Ac
     };
 
     match c2c_response {
-        Ok(c2c_can_issue_access_token::Response::Success) => Ok(()),
-        Ok(c2c_can_issue_access_token::Response::Failure) => Err(NotAuthorized),
-        Err(err) => Err(InternalError(format!("{err:?}"))),
+        Ok(c2c_can_issue_access_token::Response::Success) => Ok(true),
+        Ok(c2c_can_issue_access_token::Response::Failure) => Ok(false),
+        Err(err) => Err(format!("{err:?}")),
     }
 }
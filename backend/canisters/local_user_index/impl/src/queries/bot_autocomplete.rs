@@ -0,0 +1,104 @@
+use crate::queries::access_token_v2::{can_issue_access_token, sign_token};
+use crate::{RuntimeState, mutate_state, read_state};
+use canister_api_macros::query;
+use canister_tracing_macros::trace;
+use local_user_index_canister::bot_autocomplete::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::c2c_can_issue_access_token::{AccessTypeArgs, BotActionByCommandArgs};
+use types::{AutonomousBotScope, BotAutocompleteClaims, BotPermissions, CLAIM_TYPE_BOT_AUTOCOMPLETE, Milliseconds, UserId};
+
+// Short enough that a leaked token is of little use, long enough to cover a burst of typing
+const AUTOCOMPLETE_TOKEN_VALIDITY: Milliseconds = 60 * 1000;
+
+#[query(composite = true, msgpack = true)]
+#[trace]
+async fn bot_autocomplete(args: Args) -> Response {
+    let PrepareResult {
+        initiator,
+        endpoint,
+        access_type_args,
+    } = match read_state(|state| prepare(&args, state)) {
+        Ok(r) => r,
+        Err(response) => return response,
+    };
+
+    let scope: AutonomousBotScope = args.scope.clone().into();
+    match can_issue_access_token(scope, &access_type_args).await {
+        Ok(true) => {}
+        Ok(false) => return NotAuthorized,
+        Err(error) => return InternalError(error),
+    }
+
+    mutate_state(|state| {
+        let custom_claims = BotAutocompleteClaims {
+            bot_api_gateway: state.env.canister_id(),
+            bot: args.bot_id,
+            scope: args.scope,
+            initiator,
+            command_name: args.command_name,
+            param_name: args.param_name,
+        };
+        let expires = state.env.now() + AUTOCOMPLETE_TOKEN_VALIDITY;
+
+        match sign_token(CLAIM_TYPE_BOT_AUTOCOMPLETE.to_string(), custom_claims, expires, state) {
+            Ok(token) => Success(SuccessResult {
+                token,
+                endpoint,
+                expires,
+            }),
+            Err(error) => InternalError(error),
+        }
+    })
+}
+
+struct PrepareResult {
+    initiator: UserId,
+    endpoint: String,
+    access_type_args: AccessTypeArgs,
+}
+
+fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, Response> {
+    let Some(user) = state
+        .data
+        .global_users
+        .get_by_principal(&state.env.caller())
+        .filter(|u| !u.user_type.is_bot())
+    else {
+        return Err(NotAuthorized);
+    };
+
+    let bot = state
+        .data
+        .bots
+        .get(&args.bot_id)
+        .ok_or_else(|| Error(OCErrorCode::BotNotFound.into()))?;
+
+    let command = bot
+        .commands
+        .iter()
+        .find(|c| c.name == args.command_name)
+        .ok_or_else(|| Error(OCErrorCode::InvalidRequest.with_message("Command not found")))?;
+
+    if !command
+        .params
+        .iter()
+        .any(|p| p.name == args.param_name && p.param_type.supports_autocomplete())
+    {
+        return Err(Error(
+            OCErrorCode::InvalidRequest.with_message("Parameter does not support autocomplete"),
+        ));
+    }
+
+    // The token grants the bot no permissions, but the chat must still confirm that the bot is
+    // installed and that the user is allowed to run the command
+    Ok(PrepareResult {
+        initiator: user.user_id,
+        endpoint: bot.endpoint.clone(),
+        access_type_args: AccessTypeArgs::BotActionByCommand(BotActionByCommandArgs {
+            bot_id: args.bot_id,
+            initiator: user.user_id,
+            initiator_role: command.default_role.unwrap_or_default(),
+            requested_permissions: BotPermissions::default(),
+        }),
+    })
+}
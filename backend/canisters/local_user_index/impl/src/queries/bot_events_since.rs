@@ -0,0 +1,95 @@
+use crate::read_state;
+use canister_api_macros::{query, update};
+use canister_tracing_macros::trace;
+use local_user_index_canister::bot_events_since::{Response::*, *};
+use oc_error_codes::OCErrorCode;
+use types::{BotEvent, BotEventWrapper, BotEventsSinceResult, Chat, OCResult};
+
+#[update(candid = true, msgpack = true)]
+#[trace]
+async fn bot_events_since_c2c(args: Args) -> Response {
+    bot_events_since_impl(args).await
+}
+
+#[query(composite = true, candid = true, msgpack = true)]
+#[trace]
+async fn bot_events_since(args: Args) -> Response {
+    bot_events_since_impl(args).await
+}
+
+async fn bot_events_since_impl(args: Args) -> Response {
+    let Some((bot_id, api_gateway)) = read_state(|state| {
+        state
+            .data
+            .bots
+            .get_by_caller(&state.env.caller())
+            .map(|bot| (bot.bot_id, state.env.canister_id()))
+    }) else {
+        return Error(OCErrorCode::BotNotAuthenticated.into());
+    };
+
+    let result: OCResult<BotEventsSinceResult> = match args.chat {
+        Chat::Direct(chat_id) => match user_canister_c2c_client::c2c_bot_events_since(
+            chat_id.into(),
+            &user_canister::c2c_bot_events_since::Args {
+                bot_id,
+                since: args.since,
+                max_events: args.max_events,
+            },
+        )
+        .await
+        {
+            Ok(user_canister::c2c_bot_events_since::Response::Success(result)) => Ok(result),
+            Ok(user_canister::c2c_bot_events_since::Response::Error(error)) => Err(error),
+            Err(error) => Err(error.into()),
+        },
+        Chat::Group(chat_id) => match group_canister_c2c_client::c2c_bot_events_since(
+            chat_id.into(),
+            &group_canister::c2c_bot_events_since::Args {
+                bot_id,
+                since: args.since,
+                max_events: args.max_events,
+            },
+        )
+        .await
+        {
+            Ok(group_canister::c2c_bot_events_since::Response::Success(result)) => Ok(result),
+            Ok(group_canister::c2c_bot_events_since::Response::Error(error)) => Err(error),
+            Err(error) => Err(error.into()),
+        },
+        Chat::Channel(community_id, channel_id) => match community_canister_c2c_client::c2c_bot_events_since(
+            community_id.into(),
+            &community_canister::c2c_bot_events_since::Args {
+                bot_id,
+                channel_id,
+                since: args.since,
+                max_events: args.max_events,
+            },
+        )
+        .await
+        {
+            Ok(community_canister::c2c_bot_events_since::Response::Success(result)) => Ok(result),
+            Ok(community_canister::c2c_bot_events_since::Response::Error(error)) => Err(error),
+            Err(error) => Err(error.into()),
+        },
+    };
+
+    match result {
+        // Wrap the events the same way as those pushed to the bot so they can be handled identically
+        Ok(result) => Success(SuccessResult {
+            events: result
+                .events
+                .into_iter()
+                .map(|e| BotEventWrapper {
+                    api_gateway,
+                    event: BotEvent::Chat(e.event),
+                    timestamp: e.timestamp,
+                })
+                .collect(),
+            cursor: result.cursor,
+            complete: result.complete,
+            events_pruned: result.events_pruned,
+        }),
+        Err(error) => Error(error),
+    }
+}
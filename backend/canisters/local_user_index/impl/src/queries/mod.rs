@@ -1,9 +1,11 @@
 pub mod access_token_v2;
 pub mod active_proposal_tallies;
+pub mod bot_autocomplete;
 pub mod bot_chat_events;
 pub mod bot_chat_summary;
 pub mod bot_community_events;
 pub mod bot_community_summary;
+pub mod bot_events_since;
 pub mod bot_members;
 pub mod c2c_lookup_user;
 pub mod c2c_lookup_users;
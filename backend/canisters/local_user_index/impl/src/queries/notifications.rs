@@ -25,20 +25,13 @@ pub(crate) fn notifications_impl(args: Args, state: &RuntimeState) -> Response {
         match &notification.value {
             NotificationEnvelope::User(n) => {
                 for user_id in n.recipients.iter() {
-                    // Get web push and firebase subscriptions for the user
+                    // Get the endpoint based (web push, UnifiedPush, HTTPS) and firebase subscriptions for the user
                     let notification_subscriptions = get_notification_subscriptions(user_id, state);
                     has_subscriptions |= !notification_subscriptions.is_empty();
 
                     // If user's subscriptions are already processed, skip them
                     if !subscriptions.contains_key(user_id) {
-                        size_added_by_notification += notification_subscriptions
-                            .iter()
-                            .map(|s| match s {
-                                NotificationSubscription::WebPush(web_push) => web_push.approx_size(),
-                                // TODO add FCM push size calculation
-                                _ => 0,
-                            })
-                            .sum::<usize>();
+                        size_added_by_notification += notification_subscriptions.iter().map(|s| s.approx_size()).sum::<usize>();
 
                         subscriptions.insert(*user_id, notification_subscriptions);
                     }
@@ -85,14 +78,7 @@ pub(crate) fn notifications_impl(args: Args, state: &RuntimeState) -> Response {
 }
 
 fn get_notification_subscriptions(user_id: &UserId, state: &RuntimeState) -> Vec<NotificationSubscription> {
-    let mut web_push_subs_for_user: Vec<NotificationSubscription> = state
-        .data
-        .web_push_subscriptions
-        .get(user_id)
-        .unwrap_or_default()
-        .into_iter()
-        .map(NotificationSubscription::WebPush)
-        .collect();
+    let mut web_push_subs_for_user: Vec<NotificationSubscription> = state.data.web_push_subscriptions.get(user_id);
 
     let fcm_tokens_for_user: Vec<NotificationSubscription> = state
         .data
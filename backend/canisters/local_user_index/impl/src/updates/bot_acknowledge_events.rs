@@ -0,0 +1,45 @@
+use crate::read_state;
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use local_user_index_canister::bot_acknowledge_events::*;
+use oc_error_codes::OCErrorCode;
+use types::Chat;
+
+#[update(candid = true, json = true, msgpack = true)]
+#[trace]
+async fn bot_acknowledge_events(args: Args) -> Response {
+    let Some(bot_id) = read_state(|state| state.data.bots.get_by_caller(&state.env.caller()).map(|bot| bot.bot_id)) else {
+        return OCErrorCode::BotNotAuthenticated.into();
+    };
+
+    match args.chat {
+        Chat::Direct(chat_id) => user_canister_c2c_client::c2c_bot_acknowledge_events(
+            chat_id.into(),
+            &user_canister::c2c_bot_acknowledge_events::Args {
+                bot_id,
+                cursor: args.cursor,
+            },
+        )
+        .await
+        .into(),
+        Chat::Group(chat_id) => group_canister_c2c_client::c2c_bot_acknowledge_events(
+            chat_id.into(),
+            &group_canister::c2c_bot_acknowledge_events::Args {
+                bot_id,
+                cursor: args.cursor,
+            },
+        )
+        .await
+        .into(),
+        Chat::Channel(community_id, channel_id) => community_canister_c2c_client::c2c_bot_acknowledge_events(
+            community_id.into(),
+            &community_canister::c2c_bot_acknowledge_events::Args {
+                bot_id,
+                channel_id,
+                cursor: args.cursor,
+            },
+        )
+        .await
+        .into(),
+    }
+}
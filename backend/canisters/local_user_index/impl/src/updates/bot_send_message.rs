@@ -124,7 +124,7 @@ fn extract_message_access_context(
     let (chat, thread, message_id, user_message_id) = match context.scope {
         BotActionScope::Chat(details) => {
             if let Some(message_id) = message_id
-                && matches!(context.initiator, BotInitiator::Command(_))
+                && !matches!(context.initiator, BotInitiator::Autonomous)
                 && message_id != details.message_id
             {
                 return Err(
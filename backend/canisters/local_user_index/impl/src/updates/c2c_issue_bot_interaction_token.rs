@@ -0,0 +1,58 @@
+use crate::guards::caller_is_local_child_canister;
+use crate::queries::access_token_v2::sign_token;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use local_user_index_canister::c2c_issue_bot_interaction_token::*;
+use oc_error_codes::OCErrorCode;
+use types::{BotActionScope, BotInteractionClaims, CLAIM_TYPE_BOT_INTERACTION, Milliseconds, OCResult};
+
+const INTERACTION_TOKEN_VALIDITY: Milliseconds = 5 * 60 * 1000;
+
+// Group, community and user canisters check the interaction against the bot's message before
+// calling this, the token is signed here because this is where the OC key pair is held
+#[update(guard = "caller_is_local_child_canister", msgpack = true)]
+#[trace]
+fn c2c_issue_bot_interaction_token(args: Args) -> Response {
+    match mutate_state(|state| c2c_issue_bot_interaction_token_impl(args, state)) {
+        Ok(result) => Response::Success(result),
+        Err(error) => Response::Error(error),
+    }
+}
+
+fn c2c_issue_bot_interaction_token_impl(args: Args, state: &mut RuntimeState) -> OCResult<SuccessResult> {
+    let BotActionScope::Chat(details) = &args.scope else {
+        return Err(OCErrorCode::InvalidRequest.with_message("Interactions must be scoped to a chat"));
+    };
+
+    // Each chat canister can only issue tokens for interactions within its own chats
+    if details.chat.canister_id() != state.env.caller() {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+
+    let endpoint = state
+        .data
+        .bots
+        .get(&args.bot_id)
+        .ok_or(OCErrorCode::BotNotFound)?
+        .endpoint
+        .clone();
+    let expires = state.env.now() + INTERACTION_TOKEN_VALIDITY;
+
+    let custom_claims = BotInteractionClaims {
+        bot_api_gateway: state.env.canister_id(),
+        bot: args.bot_id,
+        scope: args.scope,
+        granted_permissions: args.granted_permissions,
+        interaction: args.interaction,
+    };
+
+    let token = sign_token(CLAIM_TYPE_BOT_INTERACTION.to_string(), custom_claims, expires, state)
+        .map_err(|error| OCErrorCode::Unknown.with_message(error))?;
+
+    Ok(SuccessResult {
+        token,
+        endpoint,
+        expires,
+    })
+}
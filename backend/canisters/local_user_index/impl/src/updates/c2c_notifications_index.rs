@@ -20,7 +20,7 @@ fn c2c_notifications_index_impl(args: Args, state: &mut RuntimeState) -> Respons
         ) {
             match event.value {
                 NotificationsIndexEvent::SubscriptionAdded(s) => {
-                    state.data.web_push_subscriptions.push(s.user_id, s.subscription);
+                    state.data.web_push_subscriptions.push(s.user_id, s.subscription, s.backend);
                 }
                 NotificationsIndexEvent::SubscriptionRemoved(s) => {
                     state.data.web_push_subscriptions.remove(s.user_id, &s.endpoint);
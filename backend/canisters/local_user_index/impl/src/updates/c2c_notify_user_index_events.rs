@@ -322,6 +322,10 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
         UserIndexEvent::UserUnblocked(user_id, unblocked) => {
             state.data.blocked_users.remove(&(unblocked, user_id));
         }
+        UserIndexEvent::UserQuietHoursSet(user_id, quiet_hours) => {
+            state.data.quiet_hours.set(user_id, quiet_hours, **now);
+            jobs::flush_notification_digests::start_job_if_required(state);
+        }
         UserIndexEvent::SetPremiumItemCost(ev) => state.data.premium_items.set(ev.item_id, ev.chit_cost),
         UserIndexEvent::UpdateBlockedUsernamePatterns(ev) => {
             if ev.add {
@@ -337,6 +341,9 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
         UserIndexEvent::SetModerationReferralConfig(ev) => {
             state.data.moderation_referral_config = ev.config;
         }
+        UserIndexEvent::SetTranslationProviderConfig(ev) => {
+            state.data.translation_provider_config = ev.config;
+        }
         UserIndexEvent::SetMediaScanConfig(config) => {
             state.data.media_scan_config = config;
         }
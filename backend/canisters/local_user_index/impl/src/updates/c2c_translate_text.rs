@@ -0,0 +1,38 @@
+use crate::guards::caller_is_local_child_canister;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use group_community_common::translation;
+use local_user_index_canister::c2c_translate_text::*;
+use oc_error_codes::OCErrorCode;
+use tracing::error;
+use types::{OCResult, TranslationProviderConfig};
+
+// Translations are requested by group, community and user canisters, which cache the results, so
+// the provider's API key only needs to be held here
+#[update(guard = "caller_is_local_child_canister", msgpack = true)]
+async fn c2c_translate_text(args: Args) -> Response {
+    let config = match mutate_state(|state| prepare(&args, state)) {
+        Ok(config) => config,
+        Err(error) => return Response::Error(error),
+    };
+
+    match translation::translate_text(&config, &args.text, &args.target_language).await {
+        Ok(translation) => Response::Success(translation),
+        Err(message) => {
+            error!(message, "Failed to translate text");
+            Response::Error(OCErrorCode::TranslationFailed.with_message(message))
+        }
+    }
+}
+
+fn prepare(args: &Args, state: &mut RuntimeState) -> OCResult<TranslationProviderConfig> {
+    let Some(config) = state.data.translation_provider_config.clone() else {
+        return Err(OCErrorCode::TranslationFailed.with_message("No translation provider configured"));
+    };
+
+    if !state.data.translation_rate_limiter.try_record(args.user_id, state.env.now()) {
+        return Err(OCErrorCode::Throttled.with_message("Translation rate limit reached"));
+    }
+
+    Ok(config)
+}
@@ -1,5 +1,5 @@
 use crate::guards::caller_is_local_user_canister;
-use crate::{RuntimeState, UserIndexEvent, mutate_state};
+use crate::{RuntimeState, UserIndexEvent, jobs, mutate_state};
 use canister_api_macros::update;
 use canister_time::now_millis;
 use canister_tracing_macros::trace;
@@ -85,6 +85,11 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
         UserEvent::SetMaxStreak(max_streak) => {
             state.push_event_to_user_index(UserIndexEvent::SetMaxStreak(user_id, max_streak), **now);
         }
+        UserEvent::SetQuietHours(quiet_hours) => {
+            state.data.quiet_hours.set(user_id, quiet_hours, **now);
+            jobs::flush_notification_digests::start_job_if_required(state);
+            state.push_event_to_user_index(UserIndexEvent::UserQuietHoursSet(user_id, quiet_hours), **now);
+        }
         UserEvent::EventStoreEvent(event) => state.data.event_store_client.push(event),
         UserEvent::Notification(mut notification) => {
             if let Notification::Bot(bot_notification) = &mut *notification
@@ -5,8 +5,8 @@ use canister_tracing_macros::trace;
 use local_user_index_canister::install_bot::*;
 use oc_error_codes::{OCError, OCErrorCode};
 use types::{
-    BotEvent, BotInstalledEvent, BotLifecycleEvent, BotNotification, BotRegistrationStatus, BotSubscriptions, OCResult, UserId,
-    c2c_install_bot,
+    BotEvent, BotInstallationLocation, BotInstalledEvent, BotLifecycleEvent, BotNotification, BotRegistrationStatus,
+    BotSubscriptions, OCResult, UserId, c2c_install_bot,
 };
 
 #[update(guard = "caller_is_openchat_user", msgpack = true)]
@@ -29,6 +29,7 @@ async fn install_bot_impl(args: Args) -> OCResult {
             granted_permissions: args.granted_permissions.clone(),
             granted_autonomous_permissions: args.granted_autonomous_permissions.clone(),
             default_subscriptions,
+            channel_permissions: args.channel_permissions.clone(),
         },
     )
     .await?;
@@ -80,6 +81,10 @@ fn prepare(args: &Args, state: &RuntimeState) -> Result<PrepareResult, OCError>
     let user = state.data.global_users.get(&caller).unwrap();
     let bot = state.data.bots.get(&args.bot_id).ok_or(OCErrorCode::BotNotFound)?;
 
+    if args.channel_permissions.is_some() && !matches!(args.location, BotInstallationLocation::Community(_)) {
+        return Err(OCErrorCode::InvalidRequest.with_message("Channel permissions can only be specified within a community"));
+    }
+
     match bot.registration_status {
         BotRegistrationStatus::Public => (),
         BotRegistrationStatus::Private(location) => {
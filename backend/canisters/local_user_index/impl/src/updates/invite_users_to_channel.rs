@@ -80,7 +80,7 @@ pub(crate) fn send_channel_invitation(
     let text = format!(
         "You have been invited to the channel [{channel_name}](/community/{community_id}/channel/{channel_id}) in the community [{community_name}](/community/{community_id}) by @UserId({invited_by})."
     );
-    let message = MessageContentInitial::Text(TextContent { text });
+    let message = MessageContentInitial::Text(TextContent { text, components: None });
 
     for user_id in invited_users {
         state.push_oc_bot_message_to_user(user_id, message.clone(), now);
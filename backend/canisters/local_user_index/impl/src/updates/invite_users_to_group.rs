@@ -55,7 +55,7 @@ pub(crate) fn send_group_invitation(
 ) {
     let now = state.env.now();
     let text = format!("You have been invited to the group [{group_name}](/group/{group_id}) by @UserId({invited_by}).");
-    let message = MessageContentInitial::Text(TextContent { text });
+    let message = MessageContentInitial::Text(TextContent { text, components: None });
 
     for user_id in invited_users {
         state.push_oc_bot_message_to_user(user_id, message.clone(), now);
@@ -1,3 +1,4 @@
+pub mod bot_acknowledge_events;
 pub mod bot_add_reaction;
 pub mod bot_change_role;
 pub mod bot_create_channel;
@@ -12,6 +13,7 @@ pub mod c2c_delete_community;
 pub mod c2c_delete_group;
 pub mod c2c_group_index;
 pub mod c2c_group_or_community_canister;
+pub mod c2c_issue_bot_interaction_token;
 pub mod c2c_notifications_index;
 pub mod c2c_notify_low_balance;
 pub mod c2c_notify_user_index_events;
@@ -20,6 +22,7 @@ pub mod c2c_set_community_upgrade_concurrency;
 pub mod c2c_set_group_upgrade_concurrency;
 pub mod c2c_set_max_concurrent_community_upgrades;
 pub mod c2c_set_max_concurrent_group_upgrades;
+pub mod c2c_translate_text;
 pub mod c2c_trigger_upgrade;
 pub mod c2c_upgrade_community_canister_wasm;
 pub mod c2c_upgrade_group_canister_wasm;
@@ -151,15 +151,22 @@ fn prepare(args: &Args, state: &mut RuntimeState) -> Result<PrepareOk, Response>
         vec![
             MessageContentInitial::Text(TextContent {
                 text: "Welcome to OpenChat!!".to_string(),
+                components: None,
             }),
             MessageContentInitial::Text(TextContent {
                 text: format!("Wait a moment {}, your SATS are coming below 👇", args.username),
+                components: None,
             }),
         ]
     } else {
         welcome_messages()
             .into_iter()
-            .map(|t| MessageContentInitial::Text(TextContent { text: t }))
+            .map(|t| {
+                MessageContentInitial::Text(TextContent {
+                    text: t,
+                    components: None,
+                })
+            })
             .collect()
     };
 
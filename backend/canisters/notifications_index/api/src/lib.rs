@@ -1,7 +1,7 @@
 use candid::Principal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use types::{FcmToken, SubscriptionInfo, UserId};
+use types::{FcmToken, PushBackend, SubscriptionInfo, UserId};
 
 mod lifecycle;
 mod queries;
@@ -37,6 +37,8 @@ pub enum UserIndexEvent {
 pub struct SubscriptionAdded {
     pub user_id: UserId,
     pub subscription: SubscriptionInfo,
+    #[serde(default)]
+    pub backend: PushBackend,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -1,17 +1,20 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::SubscriptionInfo;
+use types::{PushBackend, SubscriptionInfo};
 
 #[ts_export(notifications_index, push_subscription)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
     pub subscription: SubscriptionInfo,
+    #[serde(default)]
+    pub backend: PushBackend,
 }
 
 #[ts_export(notifications_index, push_subscription)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum Response {
     Success,
+    InvalidSecret,
     InternalError(String),
 }
@@ -1,5 +1,5 @@
 use crate::model::local_index_event_batch::LocalIndexEventBatch;
-use crate::model::subscriptions::{SubscriptionInfoInternal, Subscriptions};
+use crate::model::subscriptions::{PushSubscriptionResult, SubscriptionInfoInternal, Subscriptions};
 use candid::Principal;
 use canister_state_macros::canister_state;
 use notifications_index_canister::{NotificationsIndexEvent, SubscriptionAdded, SubscriptionRemoved};
@@ -10,7 +10,8 @@ use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use timer_job_queues::GroupedTimerJobQueue;
 use types::{
-    BuildVersion, CanisterId, Cycles, FcmToken, IdempotentEnvelope, SubscriptionInfo, TimestampMillis, Timestamped, UserId,
+    BuildVersion, CanisterId, Cycles, FcmToken, IdempotentEnvelope, PushBackend, SubscriptionInfo, TimestampMillis,
+    Timestamped, UserId,
 };
 use utils::env::Environment;
 use utils::fcm_token_store::{FcmTokenAddResult, FcmTokenStore};
@@ -48,18 +49,34 @@ impl RuntimeState {
         self.data.push_service_principals.contains(&self.env.caller())
     }
 
-    pub fn add_subscription(&mut self, user_id: UserId, subscription: SubscriptionInfo, now: TimestampMillis) {
-        let subscriptions_removed = self.data.subscriptions.push(
+    pub fn add_subscription(
+        &mut self,
+        user_id: UserId,
+        subscription: SubscriptionInfo,
+        backend: PushBackend,
+        now: TimestampMillis,
+    ) {
+        let subscriptions_removed = match self.data.subscriptions.push(
             user_id,
             SubscriptionInfoInternal {
                 added: now,
                 last_active: now,
                 endpoint: subscription.endpoint.clone(),
                 keys: subscription.keys.clone(),
+                backend: backend.clone(),
             },
-        );
-
-        let event = NotificationsIndexEvent::SubscriptionAdded(SubscriptionAdded { user_id, subscription });
+        ) {
+            PushSubscriptionResult::Added(removed) => removed,
+            PushSubscriptionResult::Updated => Vec::new(),
+            // Nothing has changed so there's no need to notify the local indexes
+            PushSubscriptionResult::Unchanged => return,
+        };
+
+        let event = NotificationsIndexEvent::SubscriptionAdded(SubscriptionAdded {
+            user_id,
+            subscription,
+            backend,
+        });
 
         self.push_event_to_local_indexes(event, now);
 
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use tracing::info;
-use types::{SubscriptionInfo, SubscriptionKeys, TimestampMillis, UserId};
+use types::{PushBackend, SubscriptionInfo, SubscriptionKeys, TimestampMillis, UserId, is_default};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct Subscriptions {
@@ -11,14 +11,20 @@ pub struct Subscriptions {
 }
 
 impl Subscriptions {
-    // Returns any subscriptions which were removed
-    pub fn push(&mut self, user_id: UserId, subscription: SubscriptionInfoInternal) -> Vec<SubscriptionInfoInternal> {
+    pub fn push(&mut self, user_id: UserId, subscription: SubscriptionInfoInternal) -> PushSubscriptionResult {
         let mut removed = Vec::new();
         match self.subscriptions.entry(user_id) {
             Occupied(e) => {
                 let subscriptions = e.into_mut();
-                if subscriptions.iter().any(|s| s.endpoint == subscription.endpoint) {
-                    return removed;
+                if let Some(existing) = subscriptions.iter_mut().find(|s| s.endpoint == subscription.endpoint) {
+                    existing.last_active = subscription.last_active;
+                    return if existing.keys == subscription.keys && existing.backend == subscription.backend {
+                        PushSubscriptionResult::Unchanged
+                    } else {
+                        existing.keys = subscription.keys;
+                        existing.backend = subscription.backend;
+                        PushSubscriptionResult::Updated
+                    };
                 }
                 while subscriptions.len() >= 10 {
                     let to_remove = subscriptions
@@ -39,7 +45,7 @@ impl Subscriptions {
         }
 
         self.total = self.total.saturating_add(1);
-        removed
+        PushSubscriptionResult::Added(removed)
     }
 
     pub fn remove_all(&mut self, user_id: UserId) {
@@ -129,6 +135,14 @@ impl Subscriptions {
     }
 }
 
+pub enum PushSubscriptionResult {
+    // Contains any subscriptions which were removed to make room for the new one
+    Added(Vec<SubscriptionInfoInternal>),
+    // The endpoint was already subscribed but its keys or backend have changed
+    Updated,
+    Unchanged,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SubscriptionInfoInternal {
     #[serde(rename = "a")]
@@ -139,6 +153,8 @@ pub struct SubscriptionInfoInternal {
     pub endpoint: String,
     #[serde(rename = "k")]
     pub keys: SubscriptionKeys,
+    #[serde(rename = "b", default, skip_serializing_if = "is_default")]
+    pub backend: PushBackend,
 }
 
 impl From<SubscriptionInfoInternal> for SubscriptionInfo {
@@ -30,9 +30,11 @@ fn notify_local_index_added_impl(args: Args, state: &mut RuntimeState) -> Respon
         .iter()
         .flat_map(|(user_id, subs)| subs.iter().map(|s| (*user_id, s.clone())))
     {
+        let backend = subscription.backend.clone();
         events.push(NotificationsIndexEvent::SubscriptionAdded(SubscriptionAdded {
             user_id,
             subscription: subscription.into(),
+            backend,
         }));
     }
 
@@ -7,9 +7,13 @@ use notifications_index_canister::push_subscription::{Response::*, *};
 #[update(msgpack = true)]
 #[trace]
 async fn push_subscription(args: Args) -> Response {
+    if !args.backend.is_valid() {
+        return InvalidSecret;
+    }
+
     match get_user_id().await {
         Ok(user_id) => {
-            mutate_state(|state| state.add_subscription(user_id, args.subscription, state.env.now()));
+            mutate_state(|state| state.add_subscription(user_id, args.subscription, args.backend, state.env.now()));
             Success
         }
         Err(err) => InternalError(format!("{err:?}")),
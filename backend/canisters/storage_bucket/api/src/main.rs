@@ -5,6 +5,7 @@ use ts_export::generate_ts_method;
 fn main() {
     generate_candid_method!(storage_bucket, file_info, query);
 
+    generate_candid_method!(storage_bucket, claim_file, update);
     generate_candid_method!(storage_bucket, delete_file, update);
     generate_candid_method!(storage_bucket, delete_files, update);
     generate_candid_method!(storage_bucket, forward_file, update);
@@ -18,7 +19,9 @@ fn main() {
     }
 
     generate_ts_method!(storage_bucket, file_info);
+    generate_ts_method!(storage_bucket, file_status);
 
+    generate_ts_method!(storage_bucket, claim_file);
     generate_ts_method!(storage_bucket, delete_file);
     generate_ts_method!(storage_bucket, delete_files);
     generate_ts_method!(storage_bucket, forward_file);
@@ -1,7 +1,7 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{FileId, Hash};
+use types::{FileEncryption, FileId, Hash};
 
 #[ts_export(storage_bucket, file_info)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -22,4 +22,5 @@ pub struct SuccessResult {
     pub is_owner: bool,
     pub file_size: u64,
     pub file_hash: Hash,
+    pub encryption: Option<FileEncryption>,
 }
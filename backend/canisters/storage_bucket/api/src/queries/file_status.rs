@@ -1,18 +1,22 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
 use types::{FileId, FileStatus};
 
+#[ts_export(storage_bucket, file_status)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
     pub file_id: FileId,
 }
 
+#[ts_export(storage_bucket, file_status)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum Response {
     Success(SuccessResult),
     NotFound,
 }
 
+#[ts_export(storage_bucket, file_status)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct SuccessResult {
     pub status: FileStatus,
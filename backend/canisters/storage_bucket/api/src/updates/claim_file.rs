@@ -0,0 +1,37 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AccessorId, FileEncryption, FileId, Hash, TimestampMillis};
+
+// Creates a file referencing a blob which already exists in this bucket, so that the bytes
+// don't need to be uploaded again. If the blob doesn't exist, the file must be uploaded via
+// `upload_chunk_v2` using the same file id.
+#[ts_export(storage_bucket, claim_file)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub file_id: FileId,
+    pub hash: Hash,
+    pub size: u64,
+    pub mime_type: String,
+    #[ts(as = "Vec<ts_export::TSPrincipal>")]
+    pub accessors: Vec<AccessorId>,
+    pub expiry: Option<TimestampMillis>,
+    #[serde(default)]
+    pub encryption: Option<FileEncryption>,
+}
+
+#[ts_export(storage_bucket, claim_file)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    AllowanceExceeded,
+    BlobNotFound,
+    // The blob is encrypted but `encryption` wasn't provided, or vice versa
+    EncryptionMismatch,
+    FileAlreadyExists,
+    FileExpired,
+    InvalidFileId,
+    UserNotFound,
+    // The hash matches content upheld as CSAM: the claim is refused and reported
+    Blocked,
+}
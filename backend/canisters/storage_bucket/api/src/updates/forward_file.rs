@@ -1,7 +1,7 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{AccessorId, FileId};
+use types::{AccessorId, FileId, WrappedFileKey};
 
 #[ts_export(storage_bucket, forward_file)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -9,6 +9,10 @@ pub struct Args {
     pub file_id: FileId,
     #[ts(as = "Vec<ts_export::TSPrincipal>")]
     pub accessors: Vec<AccessorId>,
+    // If the file is encrypted, its content key wrapped using the keys of the chats it is being
+    // forwarded to. Required for encrypted files and ignored for unencrypted ones.
+    #[serde(default)]
+    pub wrapped_keys: Option<Vec<WrappedFileKey>>,
 }
 
 #[ts_export(storage_bucket, forward_file)]
@@ -19,4 +23,6 @@ pub enum Response {
     NotFound,
     // The hash matches content upheld as CSAM: the forward is refused and reported
     Blocked,
+    // The file is encrypted but no wrapped keys were supplied for the chats it is being forwarded to
+    WrappedKeysRequired,
 }
@@ -1,5 +1,6 @@
 pub mod c2c_sync_index;
 pub mod c2c_vault_sync;
+pub mod claim_file;
 pub mod delete_file;
 pub mod delete_files;
 pub mod forward_file;
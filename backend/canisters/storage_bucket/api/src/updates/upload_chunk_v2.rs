@@ -2,7 +2,7 @@ use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 use ts_export::ts_export;
-use types::{AccessorId, FileId, Hash, TimestampMillis};
+use types::{AccessorId, FileEncryption, FileId, Hash, TimestampMillis};
 
 #[ts_export(storage_bucket, upload_chunk)]
 #[derive(CandidType, Serialize, Deserialize)]
@@ -18,6 +18,10 @@ pub struct Args {
     #[serde(with = "serde_bytes")]
     pub bytes: Vec<u8>,
     pub expiry: Option<TimestampMillis>,
+    // Set if the bytes are ciphertext, in which case the bucket never renders or derives
+    // thumbnails from them and `mime_type` describes the decrypted content
+    #[serde(default)]
+    pub encryption: Option<FileEncryption>,
 }
 
 #[ts_export(storage_bucket, upload_chunk)]
@@ -51,6 +55,7 @@ impl Debug for Args {
             .field("total_size", &self.total_size)
             .field("byte_length", &self.bytes.len())
             .field("expiry", &self.expiry)
+            .field("encrypted", &self.encryption.is_some())
             .finish()
     }
 }
@@ -2,7 +2,7 @@ use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 use ts_export::ts_export;
-use types::FileId;
+use types::{FileEncryption, FileId};
 
 #[ts_export(storage_bucket, vault_file_chunk)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -31,6 +31,9 @@ pub struct SuccessResult {
     pub chunk_count: u32,
     pub total_size: u64,
     pub mime_type: String,
+    // The blob is ciphertext: reviewers need the content key, unwrapped by a member of one of
+    // these chats (eg. the reporter), to view it
+    pub encryption: Option<FileEncryption>,
 }
 
 // Hand-written so that tracing never formats the quarantined bytes themselves (the trace
@@ -43,6 +46,7 @@ impl Debug for SuccessResult {
             .field("chunk_count", &self.chunk_count)
             .field("total_size", &self.total_size)
             .field("mime_type", &self.mime_type)
+            .field("encrypted", &self.encryption.is_some())
             .finish()
     }
 }
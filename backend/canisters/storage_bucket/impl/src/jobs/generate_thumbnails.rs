@@ -0,0 +1,47 @@
+use crate::model::thumbnails;
+use crate::{RuntimeState, mutate_state, read_state};
+use ic_cdk_timers::TimerId;
+use std::cell::Cell;
+use std::time::Duration;
+use tracing::info;
+use types::Hash;
+
+thread_local! {
+    static TIMER_ID: Cell<Option<TimerId>> = Cell::default();
+}
+
+// Derives thumbnails for newly uploaded images, each image being decoded in its own message
+pub(crate) fn start_job_if_required(state: &RuntimeState) -> bool {
+    if TIMER_ID.get().is_none() && state.data.files.has_pending_thumbnails() {
+        let timer_id = ic_cdk_timers::set_timer(Duration::ZERO, async { run() });
+        TIMER_ID.set(Some(timer_id));
+        true
+    } else {
+        false
+    }
+}
+
+fn run() {
+    match mutate_state(|state| state.data.files.pop_pending_thumbnail()) {
+        Some(hash) => {
+            // Decode the image in a separate message, and schedule the next run before doing so.
+            // The image is removed from the queue and the job re-armed as part of this message, so
+            // if decoding traps, the failing image is dropped and the job moves on to the next one.
+            ic_cdk_timers::set_timer(Duration::ZERO, async move { generate(hash) });
+            let timer_id = ic_cdk_timers::set_timer(Duration::ZERO, async { run() });
+            TIMER_ID.set(Some(timer_id));
+        }
+        None => TIMER_ID.set(None),
+    }
+}
+
+fn generate(hash: Hash) {
+    let generated = read_state(|state| state.data.files.blob_bytes(&hash))
+        .map(|bytes| thumbnails::generate(&bytes))
+        .unwrap_or_else(|| Ok(Vec::new()));
+
+    mutate_state(|state| match generated {
+        Ok(thumbnails) => state.data.files.add_thumbnails(hash, thumbnails),
+        Err(error) => info!(%error, "Failed to generate thumbnails"),
+    });
+}
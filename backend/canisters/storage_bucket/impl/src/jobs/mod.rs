@@ -1,12 +1,14 @@
 use crate::RuntimeState;
 
 pub mod check_cycles_balance;
+pub mod generate_thumbnails;
 pub mod remove_expired_files;
 pub mod remove_old_pending_files;
 pub mod vault_retention;
 
 pub(crate) fn start(state: &RuntimeState) {
     check_cycles_balance::start_job();
+    generate_thumbnails::start_job_if_required(state);
     remove_expired_files::start_job_if_required(state);
     remove_old_pending_files::start_job();
     vault_retention::start_job_if_required(state);
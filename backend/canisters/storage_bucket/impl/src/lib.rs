@@ -67,6 +67,8 @@ impl RuntimeState {
             total_file_bytes: file_metrics.total_file_bytes,
             index_sync_queue_length: self.data.index_event_sync_queue.len() as u32,
             expiration_queue_length: file_metrics.expiration_queue_len,
+            thumbnail_count: file_metrics.thumbnail_count,
+            pending_thumbnails: file_metrics.pending_thumbnails,
             vault_quarantined: vault_metrics.quarantined,
             vault_legal_holds: vault_metrics.legal_holds,
             vault_reviewers: vault_metrics.reviewers,
@@ -139,6 +141,8 @@ pub struct Metrics {
     pub total_file_bytes: u64,
     pub index_sync_queue_length: u32,
     pub expiration_queue_length: u64,
+    pub thumbnail_count: u64,
+    pub pending_thumbnails: u64,
     pub vault_quarantined: u64,
     pub vault_legal_holds: u64,
     pub vault_reviewers: u64,
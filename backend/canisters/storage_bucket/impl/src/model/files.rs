@@ -2,6 +2,7 @@ use crate::model::files_map::FilesMap;
 use crate::model::files_per_accessor_map::FilesPerAccessorStableMap;
 use crate::model::reference_counts::ReferenceCountsStableMap;
 use crate::model::stable_blob_storage::StableBlobStorage;
+use crate::model::thumbnails::{self, GeneratedThumbnail, Thumbnail, Thumbnails};
 use crate::{MAX_BLOB_SIZE_BYTES, calc_chunk_count};
 use candid::Principal;
 use serde::{Deserialize, Serialize};
@@ -10,7 +11,9 @@ use std::cmp::Ordering;
 use std::collections::btree_map::Entry::{Occupied, Vacant};
 use std::collections::{BTreeMap, BTreeSet};
 use storage_bucket_canister::upload_chunk_v2::Args as UploadChunkArgs;
-use types::{AccessorId, CanisterId, FileAdded, FileId, FileMetaData, FileRemoved, Hash, TimestampMillis};
+use types::{
+    AccessorId, CanisterId, FileAdded, FileEncryption, FileId, FileMetaData, FileRemoved, Hash, TimestampMillis, WrappedFileKey,
+};
 use utils::file_id::generate_file_id;
 use utils::hasher::hash_bytes;
 
@@ -32,6 +35,11 @@ pub struct Files {
     // can remove the blob while it is quarantined (see model::vault).
     #[serde(default)]
     vault_pins: BTreeSet<Hash>,
+    #[serde(default)]
+    thumbnails: Thumbnails,
+    // Hashes of the blobs which were uploaded as ciphertext
+    #[serde(default)]
+    encrypted_blobs: BTreeSet<Hash>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -46,6 +54,8 @@ pub struct File {
     pub hash: Hash,
     #[serde(rename = "m")]
     pub mime_type: String,
+    #[serde(rename = "x", default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<FileEncryption>,
 }
 
 impl File {
@@ -58,8 +68,13 @@ impl File {
         FileMetaData {
             owner: self.owner,
             created: self.created,
+            encrypted: self.is_encrypted(),
         }
     }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
 }
 
 impl Files {
@@ -75,6 +90,50 @@ impl Files {
         self.blobs.get(hash)
     }
 
+    // Returns the bytes and mime type of the thumbnail derived from the given blob which best
+    // matches the requested size
+    pub fn thumbnail(&self, hash: &Hash, size: Option<u32>) -> Option<(Vec<u8>, String)> {
+        let thumbnail = self.thumbnails.get(hash, size)?;
+        let bytes = self.blobs.get(&thumbnail.key)?;
+
+        Some((bytes, thumbnail.mime_type.clone()))
+    }
+
+    // Pops the next blob which is awaiting thumbnail generation, skipping any which have since
+    // been removed or quarantined
+    pub fn pop_pending_thumbnail(&mut self) -> Option<Hash> {
+        while let Some(hash) = self.thumbnails.pop_pending() {
+            if self.blobs.exists(&hash) && !self.is_vault_pinned(&hash) {
+                return Some(hash);
+            }
+        }
+        None
+    }
+
+    pub fn has_pending_thumbnails(&self) -> bool {
+        self.thumbnails.has_pending()
+    }
+
+    // Stores the thumbnails derived from the given blob. They share the lifetime of the source
+    // blob, so they are not reference counted separately and are removed along with it.
+    pub fn add_thumbnails(&mut self, hash: Hash, generated: Vec<GeneratedThumbnail>) {
+        if !self.blobs.exists(&hash) || generated.is_empty() {
+            return;
+        }
+
+        let mut thumbnails = Vec::new();
+        for thumbnail in generated {
+            let key = thumbnails::thumbnail_key(&hash, thumbnail.size);
+            self.add_blob_if_not_exists(key, thumbnail.bytes);
+            thumbnails.push(Thumbnail {
+                size: thumbnail.size,
+                key,
+                mime_type: thumbnail.mime_type,
+            });
+        }
+        self.thumbnails.insert(hash, thumbnails);
+    }
+
     pub fn owner(&self, file_id: &FileId) -> Option<Principal> {
         self.get(file_id)
             .map(|f| f.owner)
@@ -107,7 +166,10 @@ impl Files {
                     meta_data: FileMetaData {
                         owner: args.owner,
                         created: args.now,
+                        encrypted: args.encryption.is_some(),
                     },
+                    mime_type: Some(args.mime_type.clone()),
+                    accessors: Some(args.accessors.clone()),
                 });
                 let pending_file: PendingFile = args.into();
                 if pending_file.is_completed() {
@@ -140,6 +202,7 @@ impl Files {
                     meta_data: FileMetaData {
                         owner: completed_file.owner,
                         created: completed_file.created,
+                        encrypted: completed_file.encryption.is_some(),
                     },
                 });
             }
@@ -153,6 +216,71 @@ impl Files {
         })
     }
 
+    // Creates a new file referencing an existing blob, allowing clients to skip uploading the
+    // bytes of any blob which this bucket already holds. Only blobs which are currently referenced
+    // by an uploaded file can be claimed, never derived thumbnails or quarantined blobs.
+    //
+    // As with forwarded files, the claimed file holds its own reference to the blob, so the blob
+    // is kept alive until every file referencing it has been removed, even if the user who first
+    // uploaded it has since deleted their file. Claiming requires the hash and size of the
+    // content, but this is NOT proof that the caller holds the content, since `file_info` returns
+    // the hash and size of any file to any caller. That grants no more than is already granted by
+    // knowing the file's id, since files are served to anyone with their id, and encrypted blobs
+    // can only be claimed as encrypted files, so their content remains opaque.
+    pub fn claim(&mut self, args: ClaimFileArgs) -> ClaimFileResult {
+        if self.files.contains_key(&args.file_id) || self.pending_files.contains_key(&args.file_id) {
+            return ClaimFileResult::FileAlreadyExists;
+        }
+
+        if self.thumbnails.is_derived_key(&args.hash)
+            || self.is_vault_pinned(&args.hash)
+            || self.reference_counts.get(&args.hash).is_none_or(|count| count == 0)
+            || self.blobs.data_size(&args.hash) != Some(args.size)
+        {
+            return ClaimFileResult::BlobNotFound;
+        }
+
+        // A blob is either ciphertext or plaintext, so the claimed file must be in the same mode
+        // as the files already referencing it, otherwise ciphertext could be served as plaintext
+        // (or vice versa)
+        if args.encryption.is_some() != self.encrypted_blobs.contains(&args.hash) {
+            return ClaimFileResult::EncryptionMismatch;
+        }
+
+        let accessors: BTreeSet<_> = args.accessors.iter().copied().collect();
+
+        self.accessors_map.link(args.owner, args.file_id);
+        for accessor in accessors.iter().copied() {
+            self.accessors_map.link(accessor, args.file_id);
+        }
+        self.reference_counts.incr(args.hash);
+
+        if let Some(expiry) = args.expiry {
+            self.expiration_queue.insert((expiry, args.file_id));
+        }
+
+        let file = File {
+            owner: args.owner,
+            created: args.now,
+            accessors,
+            hash: args.hash,
+            mime_type: args.mime_type.clone(),
+            encryption: args.encryption,
+        };
+        let meta_data = file.meta_data();
+
+        self.files.insert(args.file_id, file);
+
+        ClaimFileResult::Success(FileAdded {
+            file_id: args.file_id,
+            hash: args.hash,
+            size: args.size,
+            meta_data,
+            mime_type: Some(args.mime_type),
+            accessors: Some(args.accessors),
+        })
+    }
+
     pub fn remove(&mut self, caller: Principal, file_id: FileId) -> RemoveFileResult {
         if let Some(file) = self.get(&file_id) {
             if file.can_be_removed_by(caller) {
@@ -175,6 +303,10 @@ impl Files {
         }
     }
 
+    // The content key of an encrypted file is wrapped per chat, so the wrapped keys of the
+    // original file are replaced by those supplied for the chats it is being forwarded to. Without
+    // them the forwarded file could never be decrypted, so forwarding is refused.
+    #[expect(clippy::too_many_arguments)]
     pub fn forward(
         &mut self,
         caller: Principal,
@@ -182,6 +314,7 @@ impl Files {
         canister_id: CanisterId,
         file_id_seed: u128,
         accessors: BTreeSet<AccessorId>,
+        wrapped_keys: Option<Vec<WrappedFileKey>>,
         now: TimestampMillis,
     ) -> ForwardFileResult {
         let (file, size) = match self.file_and_size(&file_id) {
@@ -189,6 +322,12 @@ impl Files {
             None => return ForwardFileResult::NotFound,
         };
 
+        let encryption = match (&file.encryption, wrapped_keys) {
+            (None, _) => None,
+            (Some(_), Some(wrapped_keys)) if !wrapped_keys.is_empty() => Some(FileEncryption { wrapped_keys }),
+            (Some(_), _) => return ForwardFileResult::WrappedKeysRequired,
+        };
+
         let hash = file.hash;
         let new_file_id = generate_file_id(canister_id, caller, hash, file_id_seed, now);
 
@@ -202,9 +341,10 @@ impl Files {
         let new_file = File {
             owner: caller,
             created: now,
-            accessors,
+            accessors: accessors.clone(),
             hash,
-            mime_type: file.mime_type,
+            mime_type: file.mime_type.clone(),
+            encryption,
         };
 
         self.files.insert(new_file_id, new_file);
@@ -214,6 +354,8 @@ impl Files {
             hash,
             size,
             meta_data,
+            mime_type: Some(file.mime_type),
+            accessors: Some(accessors.into_iter().collect()),
         })
     }
 
@@ -221,8 +363,9 @@ impl Files {
         self.pending_files.remove(file_id).is_some()
     }
 
-    pub fn remove_accessor(&mut self, accessor_id: &AccessorId) -> Vec<FileRemoved> {
+    pub fn remove_accessor(&mut self, accessor_id: &AccessorId) -> RemoveAccessorResult {
         let mut files_removed = Vec::new();
+        let mut files_updated = Vec::new();
 
         let file_ids = self.accessors_map.remove(*accessor_id);
         for file_id in file_ids {
@@ -239,6 +382,7 @@ impl Files {
                     }
                 } else {
                     self.files.insert(file_id, file);
+                    files_updated.push(file_id);
                 }
             }
 
@@ -247,7 +391,10 @@ impl Files {
             }
         }
 
-        files_removed
+        RemoveAccessorResult {
+            files_removed,
+            files_updated,
+        }
     }
 
     pub fn update_owner(&mut self, file_id: &FileId, new_owner: Principal) -> bool {
@@ -317,6 +464,8 @@ impl Files {
             pending_files: self.pending_files.len() as u64,
             total_file_bytes: self.total_file_bytes,
             expiration_queue_len: self.expiration_queue.len() as u64,
+            thumbnail_count: self.thumbnails.len() as u64,
+            pending_thumbnails: self.thumbnails.pending_len() as u64,
         }
     }
 
@@ -327,7 +476,14 @@ impl Files {
         }
 
         self.reference_counts.incr(completed_file.hash);
-        self.add_blob_if_not_exists(completed_file.hash, completed_file.bytes);
+        if self.add_blob_if_not_exists(completed_file.hash, completed_file.bytes) {
+            // Thumbnails are never derived from ciphertext, whatever the declared mime type
+            if completed_file.encryption.is_some() {
+                self.encrypted_blobs.insert(completed_file.hash);
+            } else if thumbnails::is_supported(&completed_file.mime_type) {
+                self.thumbnails.enqueue(completed_file.hash);
+            }
+        }
 
         if let Some(expiry) = completed_file.expiry {
             self.expiration_queue.insert((expiry, file_id));
@@ -341,6 +497,7 @@ impl Files {
                 accessors: completed_file.accessors,
                 hash: completed_file.hash,
                 mime_type: completed_file.mime_type,
+                encryption: completed_file.encryption,
             },
         );
     }
@@ -362,23 +519,25 @@ impl Files {
         })
     }
 
-    fn add_blob_if_not_exists(&mut self, hash: Hash, bytes: Vec<u8>) {
+    fn add_blob_if_not_exists(&mut self, hash: Hash, bytes: Vec<u8>) -> bool {
         if !self.blobs.exists(&hash) {
             self.total_file_bytes = self.total_file_bytes.saturating_add(bytes.len() as u64);
 
             self.blobs.insert(hash, bytes);
+            true
+        } else {
+            false
         }
     }
 
     // Takes a vault pin on the hash of the given file, incrementing the reference count so the
-    // blob survives every existing deletion path. Returns the hash and mime type, or None if
-    // the file is gone.
-    pub fn vault_pin(&mut self, file_id: &FileId) -> Option<(Hash, String)> {
+    // blob survives every existing deletion path. Returns the file, or None if it is gone.
+    pub fn vault_pin(&mut self, file_id: &FileId) -> Option<File> {
         let file = self.get(file_id)?;
         if self.vault_pins.insert(file.hash) {
             self.reference_counts.incr(file.hash);
         }
-        Some((file.hash, file.mime_type))
+        Some(file)
     }
 
     // Releases a vault pin, removing the blob if the pin held the last reference
@@ -403,9 +562,14 @@ impl Files {
     }
 
     fn remove_blob(&mut self, hash: &Hash) {
-        if let Some(size) = self.blobs.data_size(hash) {
-            self.blobs.remove(hash);
-            self.total_file_bytes = self.total_file_bytes.saturating_sub(size);
+        self.encrypted_blobs.remove(hash);
+        let derived_keys = self.thumbnails.remove(hash).into_iter().map(|t| t.key);
+
+        for key in [*hash].into_iter().chain(derived_keys) {
+            if let Some(size) = self.blobs.data_size(&key) {
+                self.blobs.remove(&key);
+                self.total_file_bytes = self.total_file_bytes.saturating_sub(size);
+            }
         }
     }
 
@@ -472,6 +636,8 @@ pub struct PendingFile {
     pub bytes: Vec<u8>,
     #[serde(rename = "e", alias = "expiry", skip_serializing_if = "Option::is_none")]
     pub expiry: Option<TimestampMillis>,
+    #[serde(rename = "x", default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<FileEncryption>,
 }
 
 impl PendingFile {
@@ -535,6 +701,7 @@ pub struct PutChunkArgs {
     total_size: u64,
     bytes: Vec<u8>,
     expiry: Option<TimestampMillis>,
+    encryption: Option<FileEncryption>,
     now: TimestampMillis,
 }
 
@@ -551,6 +718,7 @@ impl PutChunkArgs {
             total_size: upload_chunk_args.total_size,
             bytes: upload_chunk_args.bytes,
             expiry: upload_chunk_args.expiry,
+            encryption: upload_chunk_args.encryption,
             now,
         }
     }
@@ -571,6 +739,7 @@ impl From<PutChunkArgs> for PendingFile {
             remaining_chunks: (0..chunk_count).collect(),
             bytes: vec![0; args.total_size as usize],
             expiry: args.expiry,
+            encryption: args.encryption,
         };
         pending_file.add_chunk(args.chunk_index, args.bytes);
         pending_file
@@ -594,15 +763,41 @@ pub struct PutChunkResultSuccess {
     pub file_added: Option<FileAdded>,
 }
 
+pub struct ClaimFileArgs {
+    pub owner: Principal,
+    pub file_id: FileId,
+    pub hash: Hash,
+    pub size: u64,
+    pub mime_type: String,
+    pub accessors: Vec<AccessorId>,
+    pub expiry: Option<TimestampMillis>,
+    pub encryption: Option<FileEncryption>,
+    pub now: TimestampMillis,
+}
+
+pub enum ClaimFileResult {
+    Success(FileAdded),
+    FileAlreadyExists,
+    BlobNotFound,
+    EncryptionMismatch,
+}
+
 pub enum RemoveFileResult {
     Success(FileRemoved),
     NotAuthorized,
     NotFound,
 }
 
+pub struct RemoveAccessorResult {
+    pub files_removed: Vec<FileRemoved>,
+    // Files which are still shared with other accessors
+    pub files_updated: Vec<FileId>,
+}
+
 pub enum ForwardFileResult {
     Success(FileAdded),
     NotFound,
+    WrappedKeysRequired,
 }
 
 #[expect(dead_code)]
@@ -625,4 +820,150 @@ pub struct Metrics {
     pub pending_files: u64,
     pub total_file_bytes: u64,
     pub expiration_queue_len: u64,
+    pub thumbnail_count: u64,
+    pub pending_thumbnails: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::DefaultMemoryImpl;
+    use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+
+    fn init_files() -> Files {
+        let memory = MemoryManager::init(DefaultMemoryImpl::default());
+        stable_memory_map::init(memory.get(MemoryId::new(2)));
+        Files::new_with_blobs_memory(memory.get(MemoryId::new(1)))
+    }
+
+    fn user(n: u8) -> Principal {
+        Principal::from_slice(&[n; 8])
+    }
+
+    fn upload(files: &mut Files, owner: Principal, file_id: FileId, bytes: Vec<u8>) -> Hash {
+        upload_with_encryption(files, owner, file_id, bytes, None)
+    }
+
+    fn upload_with_encryption(
+        files: &mut Files,
+        owner: Principal,
+        file_id: FileId,
+        bytes: Vec<u8>,
+        encryption: Option<FileEncryption>,
+    ) -> Hash {
+        let hash = hash_bytes(&bytes);
+        files.put_chunk(PutChunkArgs {
+            owner,
+            file_id,
+            hash,
+            mime_type: "image/png".to_string(),
+            accessors: vec![owner],
+            chunk_index: 0,
+            chunk_size: bytes.len() as u32,
+            total_size: bytes.len() as u64,
+            bytes,
+            expiry: None,
+            encryption,
+            now: 1,
+        });
+        hash
+    }
+
+    fn claim(files: &mut Files, owner: Principal, file_id: FileId, hash: Hash, size: u64) -> ClaimFileResult {
+        claim_with_encryption(files, owner, file_id, hash, size, None)
+    }
+
+    fn claim_with_encryption(
+        files: &mut Files,
+        owner: Principal,
+        file_id: FileId,
+        hash: Hash,
+        size: u64,
+        encryption: Option<FileEncryption>,
+    ) -> ClaimFileResult {
+        files.claim(ClaimFileArgs {
+            owner,
+            file_id,
+            hash,
+            size,
+            mime_type: "image/png".to_string(),
+            accessors: vec![owner],
+            expiry: None,
+            encryption,
+            now: 2,
+        })
+    }
+
+    #[test]
+    fn claimed_file_keeps_blob_alive_after_original_removed() {
+        let mut files = init_files();
+        let hash = upload(&mut files, user(1), 1, vec![1; 10]);
+
+        assert!(matches!(claim(&mut files, user(2), 2, hash, 10), ClaimFileResult::Success(_)));
+
+        files.remove_file(1);
+        assert!(files.blob_bytes(&hash).is_some());
+
+        files.remove_file(2);
+        assert!(files.blob_bytes(&hash).is_none());
+        assert!(matches!(
+            claim(&mut files, user(3), 3, hash, 10),
+            ClaimFileResult::BlobNotFound
+        ));
+    }
+
+    #[test]
+    fn thumbnails_cannot_be_claimed() {
+        let mut files = init_files();
+        let hash = upload(&mut files, user(1), 1, vec![1; 10]);
+        files.add_thumbnails(
+            hash,
+            vec![GeneratedThumbnail {
+                size: 128,
+                mime_type: "image/jpeg".to_string(),
+                bytes: vec![2; 5],
+            }],
+        );
+        let thumbnail_key = thumbnails::thumbnail_key(&hash, 128);
+        assert!(files.blob_bytes(&thumbnail_key).is_some());
+
+        assert!(matches!(
+            claim(&mut files, user(2), 2, thumbnail_key, 5),
+            ClaimFileResult::BlobNotFound
+        ));
+    }
+
+    #[test]
+    fn claim_requires_matching_encryption_mode() {
+        let mut files = init_files();
+        let encryption = FileEncryption {
+            wrapped_keys: Vec::new(),
+        };
+        let encrypted_hash = upload_with_encryption(&mut files, user(1), 1, vec![1; 10], Some(encryption.clone()));
+        let plaintext_hash = upload(&mut files, user(1), 2, vec![2; 10]);
+
+        assert!(matches!(
+            claim(&mut files, user(2), 3, encrypted_hash, 10),
+            ClaimFileResult::EncryptionMismatch
+        ));
+        assert!(matches!(
+            claim_with_encryption(&mut files, user(2), 4, plaintext_hash, 10, Some(encryption.clone())),
+            ClaimFileResult::EncryptionMismatch
+        ));
+        assert!(matches!(
+            claim_with_encryption(&mut files, user(2), 5, encrypted_hash, 10, Some(encryption)),
+            ClaimFileResult::Success(_)
+        ));
+    }
+
+    #[test]
+    fn claim_requires_matching_size() {
+        let mut files = init_files();
+        let hash = upload(&mut files, user(1), 1, vec![1; 10]);
+
+        assert!(matches!(
+            claim(&mut files, user(2), 2, hash, 11),
+            ClaimFileResult::BlobNotFound
+        ));
+    }
 }
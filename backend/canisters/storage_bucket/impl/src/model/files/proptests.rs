@@ -81,6 +81,7 @@ fn execute_operation(files: &mut Files, op: Operation, timestamp: TimestampMilli
                 total_size: bytes.len() as u64,
                 bytes,
                 expiry: None,
+                encryption: None,
                 now: timestamp,
             });
         }
@@ -105,6 +106,7 @@ fn execute_operation(files: &mut Files, op: Operation, timestamp: TimestampMilli
                     CanisterId::from_slice(&[1]),
                     file_id_seed,
                     [owner].into_iter().collect(),
+                    None,
                     timestamp,
                 );
             }
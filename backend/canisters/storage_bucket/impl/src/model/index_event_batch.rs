@@ -14,6 +14,7 @@ pub enum EventToSync {
     FileRemoved(FileRemoved),
     CsamMatch(storage_index_canister::c2c_sync_bucket::CsamMatch),
     CsamHashDenylisted(storage_index_canister::c2c_sync_bucket::CsamHashDenylisted),
+    FileAccessorRemoved(storage_index_canister::c2c_sync_bucket::FileAccessorRemoved),
 }
 
 impl TimerJobItem for IndexEventBatch {
@@ -38,6 +39,9 @@ impl TimerJobItem for IndexEventBatch {
                 EventToSync::CsamHashDenylisted(denylisted) => {
                     args.csam_hashes_denylisted.push(denylisted.clone());
                 }
+                EventToSync::FileAccessorRemoved(removed) => {
+                    args.file_accessors_removed.push(removed.clone());
+                }
             }
             args.total_file_bytes = *total_file_bytes;
         }
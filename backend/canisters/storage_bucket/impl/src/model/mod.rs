@@ -4,6 +4,7 @@ pub mod files_per_accessor_map;
 pub mod index_event_batch;
 pub mod reference_counts;
 pub mod stable_blob_storage;
+pub mod thumbnails;
 pub mod users;
 pub mod users_map;
 pub mod vault;
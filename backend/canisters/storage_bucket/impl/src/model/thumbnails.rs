@@ -0,0 +1,227 @@
+use image::io::{Limits, Reader};
+use image::{DynamicImage, ImageOutputFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io::Cursor;
+use types::Hash;
+use utils::hasher::hash_bytes;
+
+// The bounding box sizes (in pixels) of the thumbnails derived from each image
+const THUMBNAIL_SIZES: [u32; 3] = [128, 320, 640];
+const MAX_SOURCE_SIZE_BYTES: usize = 20 * (1 << 20); // 20MB
+const MAX_SOURCE_DIMENSION: u32 = 8192;
+const MAX_DECODE_ALLOC_BYTES: u64 = 256 * (1 << 20); // 256MB
+const JPEG_QUALITY: u8 = 80;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Thumbnails {
+    // The thumbnails derived from each source blob, keyed by the source blob's hash and ordered
+    // by size. The thumbnails live in the blob storage under their own keys and are removed
+    // along with the source blob once its reference count reaches zero.
+    derived: BTreeMap<Hash, Vec<Thumbnail>>,
+    // The blob keys of all derived thumbnails, so that they can never be claimed as files
+    #[serde(default)]
+    derived_keys: BTreeSet<Hash>,
+    pending: VecDeque<Hash>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Thumbnail {
+    #[serde(rename = "s")]
+    pub size: u32,
+    #[serde(rename = "k")]
+    pub key: Hash,
+    #[serde(rename = "m")]
+    pub mime_type: String,
+}
+
+pub struct GeneratedThumbnail {
+    pub size: u32,
+    pub mime_type: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Thumbnails {
+    pub fn enqueue(&mut self, source: Hash) {
+        self.pending.push_back(source);
+    }
+
+    pub fn pop_pending(&mut self) -> Option<Hash> {
+        self.pending.pop_front()
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn insert(&mut self, source: Hash, thumbnails: Vec<Thumbnail>) {
+        self.derived_keys.extend(thumbnails.iter().map(|t| t.key));
+        self.derived.insert(source, thumbnails);
+    }
+
+    pub fn remove(&mut self, source: &Hash) -> Vec<Thumbnail> {
+        let removed = self.derived.remove(source).unwrap_or_default();
+        for thumbnail in removed.iter() {
+            self.derived_keys.remove(&thumbnail.key);
+        }
+        removed
+    }
+
+    pub fn is_derived_key(&self, key: &Hash) -> bool {
+        self.derived_keys.contains(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.derived.values().map(|t| t.len()).sum()
+    }
+
+    // Returns the smallest thumbnail which is at least as large as the requested size, falling
+    // back to the largest thumbnail available. If no size is requested, the smallest is returned.
+    pub fn get(&self, source: &Hash, size: Option<u32>) -> Option<&Thumbnail> {
+        let thumbnails = self.derived.get(source)?;
+
+        match size {
+            Some(size) => thumbnails.iter().find(|t| t.size >= size).or_else(|| thumbnails.last()),
+            None => thumbnails.first(),
+        }
+    }
+}
+
+// Only still images are supported. Deriving poster frames from videos would mean decoding the
+// video within the canister, which isn't feasible, so clients continue to supply those themselves.
+pub fn is_supported(mime_type: &str) -> bool {
+    matches!(
+        mime_type.to_lowercase().as_str(),
+        "image/jpeg" | "image/jpg" | "image/png" | "image/webp"
+    )
+}
+
+// Thumbnails are stored alongside the uploaded blobs, so their keys are derived from the source
+// hash rather than from their contents. This ensures an uploaded file can never share a blob
+// with a thumbnail, since the thumbnail is removed whenever its source is removed.
+pub fn thumbnail_key(source: &Hash, size: u32) -> Hash {
+    let mut bytes = b"thumbnail".to_vec();
+    bytes.extend_from_slice(source);
+    bytes.extend_from_slice(&size.to_be_bytes());
+    hash_bytes(bytes)
+}
+
+// Decodes the image and derives a thumbnail for each size which is smaller than the image
+// itself, plus a re-encoded copy at the image's own size if it is smaller than the largest
+// thumbnail size. Images with an alpha channel are encoded as PNG, all others as JPEG.
+pub fn generate(bytes: &[u8]) -> Result<Vec<GeneratedThumbnail>, String> {
+    if bytes.len() > MAX_SOURCE_SIZE_BYTES {
+        return Err(format!("Image too large: {}", bytes.len()));
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC_BYTES);
+
+    let mut reader = Reader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    reader.limits(limits);
+
+    let image = reader.decode().map_err(|e| e.to_string())?;
+    let source_size = image.width().max(image.height());
+    let has_alpha = image.color().has_alpha();
+
+    let mut thumbnails = Vec::new();
+    for size in THUMBNAIL_SIZES {
+        if source_size <= size {
+            thumbnails.push(encode(&image, source_size, has_alpha)?);
+            break;
+        }
+        thumbnails.push(encode(&image.thumbnail(size, size), size, has_alpha)?);
+    }
+    Ok(thumbnails)
+}
+
+fn encode(image: &DynamicImage, size: u32, has_alpha: bool) -> Result<GeneratedThumbnail, String> {
+    let mut bytes = Vec::new();
+    let mut cursor = Cursor::new(&mut bytes);
+
+    let mime_type = if has_alpha {
+        image
+            .write_to(&mut cursor, ImageOutputFormat::Png)
+            .map_err(|e| e.to_string())?;
+        "image/png"
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_to(&mut cursor, ImageOutputFormat::Jpeg(JPEG_QUALITY))
+            .map_err(|e| e.to_string())?;
+        "image/jpeg"
+    };
+
+    Ok(GeneratedThumbnail {
+        size,
+        mime_type: mime_type.to_string(),
+        bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+    #[test]
+    fn thumbnails_are_bounded_by_each_size() {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(1000, 500, Rgb([10, 20, 30])));
+        let thumbnails = generate(&encode(&image, 1000, false).unwrap().bytes).unwrap();
+
+        assert_eq!(thumbnails.iter().map(|t| t.size).collect::<Vec<_>>(), THUMBNAIL_SIZES);
+
+        for thumbnail in thumbnails {
+            assert_eq!(thumbnail.mime_type, "image/jpeg");
+            let decoded = image::load_from_memory(&thumbnail.bytes).unwrap();
+            assert_eq!(decoded.width(), thumbnail.size);
+            assert_eq!(decoded.height(), thumbnail.size / 2);
+        }
+    }
+
+    #[test]
+    fn small_images_are_not_upscaled() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(200, 100, Rgba([10, 20, 30, 40])));
+        let thumbnails = generate(&encode(&image, 200, true).unwrap().bytes).unwrap();
+
+        assert_eq!(thumbnails.iter().map(|t| t.size).collect::<Vec<_>>(), vec![128, 200]);
+        assert!(thumbnails.iter().all(|t| t.mime_type == "image/png"));
+
+        let decoded = image::load_from_memory(&thumbnails[1].bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (200, 100));
+    }
+
+    #[test]
+    fn derived_keys_tracked_until_source_removed() {
+        let mut thumbnails = Thumbnails::default();
+        let source = [1; 32];
+        let key = thumbnail_key(&source, 128);
+        thumbnails.insert(
+            source,
+            vec![Thumbnail {
+                size: 128,
+                key,
+                mime_type: "image/jpeg".to_string(),
+            }],
+        );
+
+        assert!(thumbnails.is_derived_key(&key));
+        assert!(!thumbnails.is_derived_key(&source));
+
+        thumbnails.remove(&source);
+
+        assert!(!thumbnails.is_derived_key(&key));
+    }
+
+    #[test]
+    fn invalid_images_are_rejected() {
+        assert!(generate(&[1, 2, 3, 4]).is_err());
+    }
+}
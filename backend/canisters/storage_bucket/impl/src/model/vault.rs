@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use storage_bucket_canister::c2c_vault_sync::VaultCaptureMetadata;
 use storage_bucket_canister::c2c_vault_sync::VaultReviewer;
-use types::{FileId, Hash, TimestampMillis, UserId};
+use types::{FileEncryption, FileId, Hash, TimestampMillis, UserId};
 use utils::hasher::hash_bytes;
 
 // Evidence vault: metadata, retention state and the append-only access log for quarantined
@@ -56,6 +56,10 @@ pub struct VaultRecord {
     // Captured at quarantine time since the file record may be deleted while the blob is vaulted
     #[serde(default)]
     pub mime_type: String,
+    // Quarantine is keyed by hash, so encrypted blobs are captured like any other. The wrapped
+    // content keys are captured alongside so that the evidence remains viewable once unwrapped.
+    #[serde(default)]
+    pub encryption: Option<FileEncryption>,
     pub metadata: VaultCaptureMetadata,
     pub quarantined_at: TimestampMillis,
     pub retention_until: Option<TimestampMillis>,
@@ -196,6 +200,7 @@ impl Vault {
         file_id: FileId,
         hash: Hash,
         mime_type: String,
+        encryption: Option<FileEncryption>,
         metadata: VaultCaptureMetadata,
         now: TimestampMillis,
     ) {
@@ -209,6 +214,7 @@ impl Vault {
             hash,
             original_file_id: file_id,
             mime_type,
+            encryption,
             metadata,
             quarantined_at: now,
             retention_until: None,
@@ -665,7 +671,7 @@ mod tests {
     #[test]
     fn legal_hold_records_its_preservation_reference_in_the_log() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(7), 100);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(7), 100);
 
         vault.set_legal_hold(1, true, Some("POLICE-REF-99".to_string()), 200);
         vault.set_legal_hold(1, false, Some("POLICE-REF-99".to_string()), 300);
@@ -686,7 +692,7 @@ mod tests {
     #[test]
     fn log_is_hash_chained_and_verifiable() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(0), 100);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(0), 100);
         assert!(vault.authorize_view(1, reviewer(1), 0, 1, 200));
         vault.apply_verdict(1, 999, Some(Principal::from_slice(&[9; 8]).into()), false, Some(0), 300);
 
@@ -710,7 +716,7 @@ mod tests {
     #[test]
     fn view_attribution_is_captured_at_event_time() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(0), 100);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(0), 100);
         assert!(vault.authorize_view(1, reviewer(1), 0, 1, 200));
 
         // Replacing the reviewer set afterwards must not change what was recorded
@@ -723,7 +729,7 @@ mod tests {
     #[test]
     fn chunk_sessions_must_be_ordered() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "video/mp4".to_string(), metadata(0), 100);
+        vault.quarantine(1, [1u8; 32], "video/mp4".to_string(), None, metadata(0), 100);
 
         // Chunk 0 starts a session; later chunks only in order
         assert!(vault.authorize_view(1, reviewer(1), 0, 3, 200));
@@ -746,7 +752,7 @@ mod tests {
     #[test]
     fn legal_hold_blocks_release() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(0), 100);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(0), 100);
         vault.set_legal_hold(1, true, Some("REF-1".to_string()), 200);
         assert!(matches!(vault.unquarantine(1, None, Some(0), 300), VaultOpOutcome::Blocked));
         assert!(vault.record_for_file(&1).is_some());
@@ -763,8 +769,8 @@ mod tests {
     fn shared_hash_release_waits_for_the_last_report() {
         let mut vault = vault_with_reviewer();
         // The same blob (one hash) quarantined via two files, each held by a different report
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(7), 100);
-        vault.quarantine(2, [1u8; 32], "image/png".to_string(), metadata(8), 101);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(7), 100);
+        vault.quarantine(2, [1u8; 32], "image/png".to_string(), None, metadata(8), 101);
 
         // Dismissing report 8 keeps the record: report 7's evidence must survive
         assert!(matches!(vault.unquarantine(2, None, Some(8), 200), VaultOpOutcome::Retained));
@@ -788,8 +794,8 @@ mod tests {
 
         // A release with no report index (legacy sender) keeps whole-record semantics
         let mut vault = vault_with_reviewer();
-        vault.quarantine(3, [2u8; 32], "image/png".to_string(), metadata(9), 100);
-        vault.quarantine(4, [2u8; 32], "image/png".to_string(), metadata(10), 101);
+        vault.quarantine(3, [2u8; 32], "image/png".to_string(), None, metadata(9), 100);
+        vault.quarantine(4, [2u8; 32], "image/png".to_string(), None, metadata(10), 101);
         assert!(matches!(
             vault.unquarantine(3, None, None, 200),
             VaultOpOutcome::ReleasePin(_)
@@ -800,8 +806,8 @@ mod tests {
     #[test]
     fn log_page_filters_by_file() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(0), 100);
-        vault.quarantine(2, [2u8; 32], "image/png".to_string(), metadata(1), 101);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(0), 100);
+        vault.quarantine(2, [2u8; 32], "image/png".to_string(), None, metadata(1), 101);
         assert!(vault.authorize_view(2, reviewer(1), 0, 1, 200));
 
         let (total_all, _) = vault.log_page(0, 100, None);
@@ -814,7 +820,7 @@ mod tests {
     #[test]
     fn reanchor_extends_retention_without_resolving() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(0), 100);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(0), 100);
         let operator: UserId = Principal::from_slice(&[9; 8]).into();
 
         // An honest-unverified filing re-anchors retention but the record stays unresolved
@@ -840,8 +846,8 @@ mod tests {
     #[test]
     fn claim_released_under_legal_hold_defers_the_release_to_hold_clear() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(7), 100);
-        vault.quarantine(2, [1u8; 32], "image/png".to_string(), metadata(8), 101);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(7), 100);
+        vault.quarantine(2, [1u8; 32], "image/png".to_string(), None, metadata(8), 101);
         vault.set_legal_hold(1, true, Some("REF-1".to_string()), 150);
 
         // Claim bookkeeping proceeds under the hold; only the physical release is blocked
@@ -862,8 +868,8 @@ mod tests {
     #[test]
     fn expiry_and_metrics_respect_unverdicted_sibling_claims() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(7), 100);
-        vault.quarantine(2, [1u8; 32], "image/png".to_string(), metadata(8), 101);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(7), 100);
+        vault.quarantine(2, [1u8; 32], "image/png".to_string(), None, metadata(8), 101);
 
         // Report 7's verdict starts the clock, but report 8 still awaits one: the record must
         // stay on the unresolved metric and must NOT expire out from under report 8
@@ -883,7 +889,7 @@ mod tests {
     fn csam_hash_denylisted_by_verdict_and_survives_release() {
         let mut vault = vault_with_reviewer();
         let hash = [1u8; 32];
-        vault.quarantine(1, hash, "image/png".to_string(), metadata(7), 100);
+        vault.quarantine(1, hash, "image/png".to_string(), None, metadata(7), 100);
         assert!(!vault.is_csam_hash(&hash));
 
         vault.apply_verdict(1, 999, None, false, Some(7), 200);
@@ -896,7 +902,7 @@ mod tests {
 
         // A dismissal-driven release never denylists
         let other = [2u8; 32];
-        vault.quarantine(2, other, "image/png".to_string(), metadata(8), 300);
+        vault.quarantine(2, other, "image/png".to_string(), None, metadata(8), 300);
         assert!(matches!(
             vault.unquarantine(2, None, Some(8), 400),
             VaultOpOutcome::ReleasePin(_)
@@ -908,7 +914,7 @@ mod tests {
     fn verdict_reports_the_hash_for_cross_bucket_denylisting() {
         let mut vault = vault_with_reviewer();
         let hash = [1u8; 32];
-        vault.quarantine(1, hash, "image/png".to_string(), metadata(7), 100);
+        vault.quarantine(1, hash, "image/png".to_string(), None, metadata(7), 100);
 
         // The first verdict has to be propagated: the same content uploaded to any other
         // bucket would otherwise be stored and served publicly
@@ -918,7 +924,7 @@ mod tests {
         ));
 
         // A second verdict on the same hash is already denylisted, so nothing to propagate
-        vault.quarantine(2, hash, "image/png".to_string(), metadata(8), 300);
+        vault.quarantine(2, hash, "image/png".to_string(), None, metadata(8), 300);
         assert!(matches!(
             vault.apply_verdict(2, 999, None, false, Some(8), 400),
             VaultOpOutcome::Applied
@@ -935,7 +941,7 @@ mod tests {
     fn claiming_an_already_quarantined_blob_records_the_second_report() {
         let mut vault = vault_with_reviewer();
         let hash = [1u8; 32];
-        vault.quarantine(1, hash, "image/png".to_string(), metadata(7), 100);
+        vault.quarantine(1, hash, "image/png".to_string(), None, metadata(7), 100);
 
         // Two messages can carry the same blob, so a second report quarantines the same file
         // id. The claim has to be registered even though the record already exists, or
@@ -961,8 +967,8 @@ mod tests {
     #[test]
     fn retention_timer_rearms_once_a_blocking_claim_resolves() {
         let mut vault = vault_with_reviewer();
-        vault.quarantine(1, [1u8; 32], "image/png".to_string(), metadata(7), 100);
-        vault.quarantine(2, [1u8; 32], "image/png".to_string(), metadata(8), 101);
+        vault.quarantine(1, [1u8; 32], "image/png".to_string(), None, metadata(7), 100);
+        vault.quarantine(2, [1u8; 32], "image/png".to_string(), None, metadata(8), 101);
 
         // Report 7's verdict sets the clock but report 8's claim blocks expiry. The expiry must
         // not be advertised meanwhile: the timer would fire, remove nothing, and never re-arm.
@@ -996,7 +1002,7 @@ mod tests {
         // The machine detection (report #7) pins the hash first; an assertion made later on an
         // OLDER report (#3, lower index) claims it afterwards. The anchor must stay #7 (I13):
         // report indexes are creation order, not claim order.
-        vault.quarantine(1, hash, "image/png".to_string(), metadata(7), 100);
+        vault.quarantine(1, hash, "image/png".to_string(), None, metadata(7), 100);
         assert!(vault.claim_if_quarantined(1, 3, 200));
         assert_eq!(vault.pinned_report_index(&hash), Some(7));
         // Releasing the machine claim promotes the next-arrived claim
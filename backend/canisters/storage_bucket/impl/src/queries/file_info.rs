@@ -17,6 +17,7 @@ fn file_info_impl(args: Args, state: &RuntimeState) -> Response {
             is_owner: file.owner == state.env.caller(),
             file_hash: file.hash,
             file_size,
+            encryption: file.encryption,
         });
     }
 
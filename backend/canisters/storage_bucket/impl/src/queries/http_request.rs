@@ -34,6 +34,7 @@ fn http_request(request: HttpRequest) -> HttpResponse {
 
     match extract_route(&request.url) {
         Route::File(file_id) => read_state(|state| start_streaming_file(file_id, &request.headers, state)),
+        Route::FileThumbnail(file_id, size) => read_state(|state| get_thumbnail(file_id, size, state)),
         Route::Errors(since) => get_errors_impl(since),
         Route::Logs(since) => get_logs_impl(since),
         Route::Traces(since) => get_traces_impl(since),
@@ -58,16 +59,11 @@ fn start_streaming_file(file_id: FileId, request_headers: &[(String, String)], s
         && let Some(file_bytes) = state.data.files.blob_bytes(&file.hash)
     {
         let file_bytes_len = file_bytes.len();
-        let mut response_headers = vec![
-            HeaderField("Content-Type".to_string(), file.mime_type.clone()),
-            HeaderField("Cache-Control".to_string(), CACHE_HEADER_VALUE.to_string()),
-            HeaderField("X-Cacheable-Resource".to_string(), "true".to_string()),
-            HeaderField("Access-Control-Allow-Origin".to_string(), "*".to_string()),
-            HeaderField(
-                "Content-Security-Policy".to_string(),
-                "default-src 'none'; img-src *; media-src *; style-src 'unsafe-inline'".to_string(),
-            ),
-        ];
+        let mut response_headers = if file.is_encrypted() {
+            build_encrypted_response_headers()
+        } else {
+            build_response_headers(file.mime_type.clone())
+        };
 
         return if let Some(range) = extract_range_from_headers(request_headers) {
             let (start, end) = match range {
@@ -141,6 +137,51 @@ fn start_streaming_file(file_id: FileId, request_headers: &[(String, String)], s
     HttpResponse::not_found()
 }
 
+// Thumbnails are small enough to always fit within a single response, so they are never streamed
+fn get_thumbnail(file_id: FileId, size: Option<u32>, state: &RuntimeState) -> HttpResponse {
+    if let Some(file) = state.data.files.get(&file_id)
+        && !file.is_encrypted()
+        && !state.data.files.is_vault_pinned(&file.hash)
+        && !state.data.vault.is_csam_hash(&file.hash)
+        && let Some((bytes, mime_type)) = state.data.files.thumbnail(&file.hash, size)
+    {
+        let mut response_headers = build_response_headers(mime_type);
+        response_headers.push(HeaderField("Content-Length".to_string(), bytes.len().to_string()));
+
+        return HttpResponse {
+            status_code: 200,
+            headers: response_headers,
+            body: bytes,
+            streaming_strategy: None,
+            upgrade: None,
+        };
+    }
+
+    HttpResponse::not_found()
+}
+
+fn build_response_headers(mime_type: String) -> Vec<HeaderField> {
+    vec![
+        HeaderField("Content-Type".to_string(), mime_type),
+        HeaderField("Cache-Control".to_string(), CACHE_HEADER_VALUE.to_string()),
+        HeaderField("X-Cacheable-Resource".to_string(), "true".to_string()),
+        HeaderField("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+        HeaderField(
+            "Content-Security-Policy".to_string(),
+            "default-src 'none'; img-src *; media-src *; style-src 'unsafe-inline'".to_string(),
+        ),
+    ]
+}
+
+// Encrypted blobs are ciphertext, so they are only ever served as opaque downloads for the client
+// to decrypt, never with the declared mime type as media for the browser to render
+fn build_encrypted_response_headers() -> Vec<HeaderField> {
+    let mut headers = build_response_headers("application/octet-stream".to_string());
+    headers.push(HeaderField("Content-Disposition".to_string(), "attachment".to_string()));
+    headers.push(HeaderField("X-Content-Type-Options".to_string(), "nosniff".to_string()));
+    headers
+}
+
 fn continue_streaming_file(token: Token, state: &RuntimeState) -> StreamingCallbackHttpResponse {
     if let Route::File(file_id) = extract_route(&token.key) {
         let chunk_index = token.index.0.to_u32().unwrap();
@@ -5,6 +5,7 @@ use crate::{MAX_EVENTS_TO_SYNC_PER_BATCH, RuntimeState, check_cycles_balance, mu
 use canister_tracing_macros::trace;
 use ic_cdk::update;
 use storage_bucket_canister::c2c_sync_index::{Response::*, *};
+use storage_index_canister::c2c_sync_bucket::FileAccessorRemoved;
 use types::FileRemoved;
 
 #[update(guard = "caller_is_storage_index_canister")]
@@ -33,7 +34,16 @@ fn c2c_sync_index_impl(args: Args, state: &mut RuntimeState) -> Response {
     }
 
     for accessor_id in args.accessors_removed {
-        files_removed.extend(state.data.files.remove_accessor(&accessor_id));
+        let result = state.data.files.remove_accessor(&accessor_id);
+        files_removed.extend(result.files_removed);
+
+        // The index records each file's accessors, so it needs to know about files which remain
+        // but are no longer shared with the removed accessor
+        for file_id in result.files_updated {
+            state
+                .data
+                .push_event_to_index(EventToSync::FileAccessorRemoved(FileAccessorRemoved { file_id, accessor_id }));
+        }
     }
 
     for file_id in args.files_to_remove {
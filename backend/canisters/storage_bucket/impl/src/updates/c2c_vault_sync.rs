@@ -28,8 +28,11 @@ fn c2c_vault_sync_impl(args: Args, state: &mut RuntimeState) -> Response {
                 // and taking a fresh pin would fail.
                 if state.data.vault.claim_if_quarantined(q.file_id, q.metadata.report_index, now) {
                     info!(file_id = %q.file_id, "Vault: already quarantined, claim recorded");
-                } else if let Some((hash, mime_type)) = state.data.files.vault_pin(&q.file_id) {
-                    state.data.vault.quarantine(q.file_id, hash, mime_type, q.metadata, now);
+                } else if let Some(file) = state.data.files.vault_pin(&q.file_id) {
+                    state
+                        .data
+                        .vault
+                        .quarantine(q.file_id, file.hash, file.mime_type, file.encryption, q.metadata, now);
                     info!(file_id = %q.file_id, "Vault: quarantined");
                 } else {
                     // Evidence capture failed (eg. the file was deleted before the op arrived).
@@ -0,0 +1,70 @@
+use crate::guards::caller_is_known_user;
+use crate::model::files::{ClaimFileArgs, ClaimFileResult};
+use crate::model::index_event_batch::EventToSync;
+use crate::model::users::{FileStatusInternal, IndexSyncComplete};
+use crate::updates::upload_chunk::is_blocked;
+use crate::{RuntimeState, check_cycles_balance, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use storage_bucket_canister::claim_file::{Response::*, *};
+use types::RejectedReason;
+use utils::file_id::validate_file_id;
+
+#[update(guard = "caller_is_known_user", candid = true, json = true, msgpack = true)]
+#[trace]
+fn claim_file(args: Args) -> Response {
+    check_cycles_balance();
+
+    mutate_state(|state| claim_file_impl(args, state))
+}
+
+fn claim_file_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let user_id = state.env.caller();
+    let now = state.env.now();
+    let file_id = args.file_id;
+
+    if !validate_file_id(file_id, state.env.canister_id()) {
+        return InvalidFileId;
+    }
+
+    if is_blocked(user_id, file_id, args.hash, state) {
+        return Blocked;
+    }
+
+    let user = state.data.users.get(&user_id).unwrap();
+    if let Some(status) = user.file_status(&file_id) {
+        return match status {
+            FileStatusInternal::Rejected(RejectedReason::AllowanceExceeded) => AllowanceExceeded,
+            FileStatusInternal::Rejected(RejectedReason::UserNotFound) => UserNotFound,
+            FileStatusInternal::Rejected(RejectedReason::FileExpired) => FileExpired,
+            _ => FileAlreadyExists,
+        };
+    } else if args.expiry.is_some_and(|e| e < now) {
+        return FileExpired;
+    }
+
+    match state.data.files.claim(ClaimFileArgs {
+        owner: user_id,
+        file_id,
+        hash: args.hash,
+        size: args.size,
+        mime_type: args.mime_type,
+        accessors: args.accessors,
+        expiry: args.expiry,
+        encryption: args.encryption,
+        now,
+    }) {
+        ClaimFileResult::Success(file_added) => {
+            state
+                .data
+                .users
+                .set_file_status(user_id, user, file_id, FileStatusInternal::Complete(IndexSyncComplete::No));
+            state.data.push_event_to_index(EventToSync::FileAdded(file_added));
+            crate::jobs::remove_expired_files::start_job_if_required(state);
+            Success
+        }
+        ClaimFileResult::FileAlreadyExists => FileAlreadyExists,
+        ClaimFileResult::BlobNotFound => BlobNotFound,
+        ClaimFileResult::EncryptionMismatch => EncryptionMismatch,
+    }
+}
@@ -60,11 +60,15 @@ fn forward_file_impl(args: Args, state: &mut RuntimeState) -> Response {
 
     let accessors = args.accessors.into_iter().collect();
 
-    match state
-        .data
-        .files
-        .forward(caller, args.file_id, canister_id, file_id_seed, accessors, now)
-    {
+    match state.data.files.forward(
+        caller,
+        args.file_id,
+        canister_id,
+        file_id_seed,
+        accessors,
+        args.wrapped_keys,
+        now,
+    ) {
         ForwardFileResult::Success(f) => {
             let user = state.data.users.get(&caller).unwrap();
             let file_id = f.file_id;
@@ -79,5 +83,6 @@ fn forward_file_impl(args: Args, state: &mut RuntimeState) -> Response {
         // TODO Add this back in once we support access tokens
         // ForwardFileResult::NotAuthorized => NotAuthorized,
         ForwardFileResult::NotFound => NotFound,
+        ForwardFileResult::WrappedKeysRequired => WrappedKeysRequired,
     }
 }
@@ -1,5 +1,6 @@
 mod c2c_sync_index;
 mod c2c_vault_sync;
+mod claim_file;
 mod delete_file;
 mod delete_files;
 mod forward_file;
@@ -3,11 +3,12 @@ use crate::model::files::{PutChunkArgs, PutChunkResult};
 use crate::model::index_event_batch::EventToSync;
 use crate::model::users::{FileStatusInternal, IndexSyncComplete};
 use crate::{RuntimeState, check_cycles_balance, mutate_state};
+use candid::Principal;
 use canister_api_macros::update;
 use canister_tracing_macros::trace;
 use storage_bucket_canister::upload_chunk_v2::{Response::*, *};
 use storage_index_canister::c2c_sync_bucket::{CsamMatch, CsamMatchKind};
-use types::{FileRemoved, RejectedReason};
+use types::{FileId, FileRemoved, Hash, RejectedReason};
 use utils::file_id::validate_file_id;
 
 #[update(guard = "caller_is_known_user", candid = true, json = true, msgpack = true)]
@@ -21,52 +22,17 @@ fn upload_chunk_v2(args: Args) -> Response {
 fn upload_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
     let user_id = state.env.caller();
     let now = state.env.now();
-    let user = state.data.users.get(&user_id).unwrap();
     let file_id = args.file_id;
 
     if !validate_file_id(file_id, state.env.canister_id()) {
         return InvalidFileId;
     }
 
-    // Content previously upheld as CSAM can never be uploaded again: the upload is refused
-    // outright - so no message referencing it can ever be created - and the user_index is
-    // told so the uploader receives the same sanction as the original sender. Checking the
-    // declared hash is airtight: an upload only ever completes if the bytes hash to the
-    // declared value (see the HashMismatch arm below).
-    if let Some(report_index) = state.data.vault.known_csam_report_index(&args.hash) {
-        // Report once per file id: chunks upload in parallel and each is refused here, and a
-        // retry of the same attempt reuses the file id - only the first sighting is reported
-        if state.data.vault.record_blocked_attempt(user_id, file_id, args.hash) {
-            state.data.push_event_to_index(EventToSync::CsamMatch(CsamMatch {
-                uploader: user_id,
-                file_id,
-                hash: args.hash,
-                csam_report_index: report_index,
-                kind: CsamMatchKind::UploadAttempt,
-            }));
-        }
+    if is_blocked(user_id, file_id, args.hash, state) {
         return Blocked;
     }
 
-    // Content quarantined pending a verdict is refused the same way: the bucket will not
-    // serve it, so handing out a fresh reference would only create a message nobody can view
-    // while the re-share attempt itself went unrecorded. Reported against the pending report;
-    // a pin retained only by a legal hold has no active claim, so the upload is refused
-    // without reporting or sanctioning anyone against the already-resolved report.
-    if state.data.files.is_vault_pinned(&args.hash) {
-        if let Some(report_index) = state.data.vault.pinned_report_index(&args.hash)
-            && state.data.vault.record_blocked_attempt(user_id, file_id, args.hash)
-        {
-            state.data.push_event_to_index(EventToSync::CsamMatch(CsamMatch {
-                uploader: user_id,
-                file_id,
-                hash: args.hash,
-                csam_report_index: report_index,
-                kind: CsamMatchKind::PendingQuarantineAttempt,
-            }));
-        }
-        return Blocked;
-    }
+    let user = state.data.users.get(&user_id).unwrap();
 
     let mut index_sync_complete = IndexSyncComplete::No;
     let mut status = None;
@@ -90,6 +56,7 @@ fn upload_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
         PutChunkResult::Success(r) => {
             if r.file_completed {
                 status = Some(FileStatusInternal::Complete(index_sync_complete));
+                crate::jobs::generate_thumbnails::start_job_if_required(state);
             }
             if let Some(file_added) = r.file_added {
                 state.data.push_event_to_index(EventToSync::FileAdded(file_added));
@@ -133,3 +100,50 @@ fn upload_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
 
     response
 }
+
+// Content which is quarantined or upheld as CSAM can never be uploaded or claimed. Returns true
+// if the upload must be refused, reporting the attempt to the index if it is the first one.
+pub(crate) fn is_blocked(user_id: Principal, file_id: FileId, hash: Hash, state: &mut RuntimeState) -> bool {
+    // Content previously upheld as CSAM can never be uploaded again: the upload is refused
+    // outright - so no message referencing it can ever be created - and the user_index is
+    // told so the uploader receives the same sanction as the original sender. Checking the
+    // declared hash is airtight: an upload only ever completes if the bytes hash to the
+    // declared value (see the HashMismatch arm above), and a claim only succeeds if the bucket
+    // already holds a blob with the declared hash.
+    if let Some(report_index) = state.data.vault.known_csam_report_index(&hash) {
+        // Report once per file id: chunks upload in parallel and each is refused here, and a
+        // retry of the same attempt reuses the file id - only the first sighting is reported
+        if state.data.vault.record_blocked_attempt(user_id, file_id, hash) {
+            state.data.push_event_to_index(EventToSync::CsamMatch(CsamMatch {
+                uploader: user_id,
+                file_id,
+                hash,
+                csam_report_index: report_index,
+                kind: CsamMatchKind::UploadAttempt,
+            }));
+        }
+        return true;
+    }
+
+    // Content quarantined pending a verdict is refused the same way: the bucket will not
+    // serve it, so handing out a fresh reference would only create a message nobody can view
+    // while the re-share attempt itself went unrecorded. Reported against the pending report;
+    // a pin retained only by a legal hold has no active claim, so the upload is refused
+    // without reporting or sanctioning anyone against the already-resolved report.
+    if state.data.files.is_vault_pinned(&hash) {
+        if let Some(report_index) = state.data.vault.pinned_report_index(&hash)
+            && state.data.vault.record_blocked_attempt(user_id, file_id, hash)
+        {
+            state.data.push_event_to_index(EventToSync::CsamMatch(CsamMatch {
+                uploader: user_id,
+                file_id,
+                hash,
+                csam_report_index: report_index,
+                kind: CsamMatchKind::PendingQuarantineAttempt,
+            }));
+        }
+        return true;
+    }
+
+    false
+}
@@ -24,11 +24,11 @@ fn vault_file_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
         return NotAuthorized;
     }
 
-    let Some((hash, mime_type)) = state
+    let Some((hash, mime_type, encryption)) = state
         .data
         .vault
         .record_for_file(&args.file_id)
-        .map(|r| (r.hash, r.mime_type.clone()))
+        .map(|r| (r.hash, r.mime_type.clone(), r.encryption.clone()))
     else {
         return NotFound;
     };
@@ -60,5 +60,6 @@ fn vault_file_chunk_impl(args: Args, state: &mut RuntimeState) -> Response {
         chunk_count,
         total_size,
         mime_type,
+        encryption,
     })
 }
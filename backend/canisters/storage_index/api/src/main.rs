@@ -5,9 +5,12 @@ use ts_export::generate_ts_method;
 fn main() {
     generate_candid_method!(storage_index, allocated_bucket_v2, query);
     generate_candid_method!(storage_index, can_forward, query);
+    generate_candid_method!(storage_index, storage_usage, query);
     generate_candid_method!(storage_index, user, query);
     generate_candid_method!(storage_index, vault_buckets, query);
 
+    generate_candid_method!(storage_index, delete_files, update);
+
     let directory = env::current_dir().unwrap().join("tsBindings/storageIndex");
     if directory.exists() {
         std::fs::remove_dir_all(&directory).unwrap();
@@ -15,6 +18,8 @@ fn main() {
 
     generate_ts_method!(storage_index, allocated_bucket_v2);
     generate_ts_method!(storage_index, can_forward);
+    generate_ts_method!(storage_index, delete_files);
+    generate_ts_method!(storage_index, storage_usage);
     generate_ts_method!(storage_index, user);
     generate_ts_method!(storage_index, vault_buckets);
 
@@ -31,4 +31,7 @@ pub struct SuccessResult {
     pub bytes_used: u64,
     pub bytes_used_after_upload: u64,
     pub projected_allowance: ProjectedAllowance,
+    // If true, the bucket already holds a blob with this hash, so rather than uploading the
+    // bytes, the file can be created by calling `claim_file` on the bucket
+    pub blob_exists: bool,
 }
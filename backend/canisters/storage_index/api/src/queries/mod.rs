@@ -1,4 +1,5 @@
 pub mod allocated_bucket_v2;
 pub mod can_forward;
+pub mod storage_usage;
 pub mod user;
 pub mod vault_buckets;
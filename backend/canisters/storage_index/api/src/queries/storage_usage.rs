@@ -0,0 +1,76 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AccessorId, CanisterId, FileId, TimestampMillis};
+
+// Files are checked in batches from oldest to newest, so if `next_cursor` is returned the call
+// should be repeated passing it as `cursor` to get the breakdown of the next batch of files
+#[ts_export(storage_index, storage_usage)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    // Defaults to 10, capped at 100
+    pub largest_files_count: Option<u32>,
+    #[serde(default)]
+    pub cursor: Option<Cursor>,
+}
+
+#[ts_export(storage_index, storage_usage)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    UserNotFound,
+}
+
+// `byte_limit` and `bytes_used` are the user's overall totals, whereas the remaining fields only
+// cover the batch of files checked by this call, so they must be combined across batches. Each
+// file is counted at its full size, so where a user holds several files referencing the same
+// blob, the totals can exceed `bytes_used`, which counts each blob once. Files uploaded before
+// mime types and chats were recorded are grouped under an empty mime type and no chat.
+#[ts_export(storage_index, storage_usage)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub byte_limit: u64,
+    pub bytes_used: u64,
+    pub file_count: u32,
+    pub by_chat: Vec<ChatStorageUsage>,
+    pub by_mime_type: Vec<MimeTypeStorageUsage>,
+    pub largest_files: Vec<FileStorageUsage>,
+    pub next_cursor: Option<Cursor>,
+}
+
+// The position of the next file to check
+#[ts_export(storage_index, storage_usage)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct Cursor {
+    pub created: TimestampMillis,
+    pub file_id: FileId,
+}
+
+#[ts_export(storage_index, storage_usage)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct ChatStorageUsage {
+    #[ts(as = "ts_export::TSPrincipal")]
+    pub accessor_id: AccessorId,
+    pub file_count: u32,
+    pub bytes: u64,
+}
+
+#[ts_export(storage_index, storage_usage)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct MimeTypeStorageUsage {
+    pub mime_type: String,
+    pub file_count: u32,
+    pub bytes: u64,
+}
+
+#[ts_export(storage_index, storage_usage)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct FileStorageUsage {
+    pub file_id: FileId,
+    pub bucket: CanisterId,
+    pub size: u64,
+    pub mime_type: String,
+    #[ts(as = "Vec<ts_export::TSPrincipal>")]
+    pub accessors: Vec<AccessorId>,
+    pub created: TimestampMillis,
+}
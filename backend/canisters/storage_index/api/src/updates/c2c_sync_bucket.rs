@@ -1,6 +1,6 @@
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
-use types::{FileAdded, FileId, FileRejected, FileRemoved, Hash};
+use types::{AccessorId, FileAdded, FileId, FileRejected, FileRemoved, Hash};
 
 #[derive(CandidType, Serialize, Deserialize, Debug, Default)]
 pub struct Args {
@@ -20,6 +20,15 @@ pub struct Args {
     // every other bucket so the denylist is platform-wide rather than per-bucket
     #[serde(default)]
     pub csam_hashes_denylisted: Vec<CsamHashDenylisted>,
+    // Files which are no longer shared with an accessor but are still shared with others
+    #[serde(default)]
+    pub file_accessors_removed: Vec<FileAccessorRemoved>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct FileAccessorRemoved {
+    pub file_id: FileId,
+    pub accessor_id: AccessorId,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
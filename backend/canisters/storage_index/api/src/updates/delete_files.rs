@@ -0,0 +1,45 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{AccessorId, FileId, TimestampMillis};
+
+// Deletes each of the caller's files which match every filter specified. At least one filter
+// must be specified. Files are checked in batches from oldest to newest, so if `next_cursor` is
+// returned the call should be repeated passing it as `cursor` to continue.
+#[ts_export(storage_index, delete_files)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub older_than: Option<TimestampMillis>,
+    #[ts(as = "Option<ts_export::TSPrincipal>")]
+    pub accessor_id: Option<AccessorId>,
+    // Matches any mime type starting with this value, so "image/" matches all images
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub cursor: Option<Cursor>,
+}
+
+#[ts_export(storage_index, delete_files)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    FilterRequired,
+    UserNotFound,
+}
+
+// The files are deleted by their buckets asynchronously, so `bytes_used` will only reflect the
+// deletions once each bucket has synced them back to the index
+#[ts_export(storage_index, delete_files)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub file_count: u32,
+    pub total_bytes: u64,
+    pub next_cursor: Option<Cursor>,
+}
+
+// The position of the next file to check
+#[ts_export(storage_index, delete_files)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct Cursor {
+    pub created: TimestampMillis,
+    pub file_id: FileId,
+}
@@ -4,6 +4,7 @@ pub mod c2c_notify_low_balance;
 pub mod c2c_sync_bucket;
 pub mod c2c_update_user_principal;
 pub mod c2c_vault_ops;
+pub mod delete_files;
 pub mod remove_accessors;
 pub mod remove_users;
 pub mod set_bucket_full;
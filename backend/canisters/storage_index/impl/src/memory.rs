@@ -10,6 +10,7 @@ const BLOB_REFERENCE_COUNTS: MemoryId = MemoryId::new(2);
 const BLOB_SIZES: MemoryId = MemoryId::new(3);
 const TOTAL_FILE_BYTES: MemoryId = MemoryId::new(4);
 const TOTAL_BLOB_BYTES: MemoryId = MemoryId::new(5);
+const FILE_DETAILS: MemoryId = MemoryId::new(6);
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
@@ -42,8 +43,12 @@ pub fn get_total_blob_bytes_memory() -> Memory {
     get_memory(TOTAL_BLOB_BYTES)
 }
 
+pub fn get_file_details_memory() -> Memory {
+    get_memory(FILE_DETAILS)
+}
+
 pub fn memory_sizes() -> BTreeMap<u8, u64> {
-    (0u8..=5).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
+    (0u8..=6).map(|id| (id, get_memory(MemoryId::new(id)).size())).collect()
 }
 
 fn get_memory(id: MemoryId) -> Memory {
@@ -1,3 +1,4 @@
+use crate::mutate_state;
 use candid::Principal;
 use serde::{Deserialize, Serialize};
 use timer_job_queues::{TimerJobItem, grouped_timer_job_batch};
@@ -31,7 +32,17 @@ impl TimerJobItem for BucketEventBatch {
         let response = storage_bucket_canister_c2c_client::c2c_sync_index(self.key, &args).await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(storage_bucket_canister::c2c_sync_index::Response::Success(result)) => {
+                // Files removed as a result of this batch are returned here rather than synced
+                // back via `c2c_sync_bucket`, unless there were too many to fit in the response
+                let bucket = self.key;
+                mutate_state(|state| {
+                    for file in result.files_removed {
+                        state.data.remove_file_reference(bucket, file);
+                    }
+                });
+                Ok(())
+            }
             Err(error) => {
                 let delay_if_should_retry = delay_if_should_retry_failed_c2c_call(&error);
                 Err(delay_if_should_retry)
@@ -1,13 +1,18 @@
 use crate::memory::{
-    Memory, get_blob_reference_counts_memory, get_blob_sizes_memory, get_files_by_user_memory, get_total_blob_bytes_memory,
-    get_total_file_bytes_memory,
+    Memory, get_blob_reference_counts_memory, get_blob_sizes_memory, get_file_details_memory, get_files_by_user_memory,
+    get_total_blob_bytes_memory, get_total_file_bytes_memory,
 };
 use candid::Principal;
+use constants::DAY_IN_MS;
 use ic_stable_structures::storable::Bound;
 use ic_stable_structures::{StableBTreeMap, StableCell, Storable};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use types::{CanisterId, FileAdded, FileId, FileRemoved, Hash, TimestampMillis};
+use std::collections::HashMap;
+use types::{AccessorId, CanisterId, FileAdded, FileId, FileRemoved, Hash, Milliseconds, TimestampMillis};
+
+// If a bucket hasn't removed a file within this time of being asked to, it can be asked again
+const PENDING_DELETION_EXPIRY: Milliseconds = DAY_IN_MS;
 
 #[derive(Serialize, Deserialize)]
 pub struct Files {
@@ -21,6 +26,13 @@ pub struct Files {
     total_file_bytes: StableCell<u64, Memory>,
     #[serde(skip, default = "init_total_blob_bytes")]
     total_blob_bytes: StableCell<u64, Memory>,
+    // Only populated for files synced by buckets which report each file's mime type and accessors
+    #[serde(skip, default = "init_file_details")]
+    file_details: StableBTreeMap<FileId, FileDetails, Memory>,
+    // Files which buckets have been asked to delete but which they haven't yet synced as removed,
+    // along with when the bucket was asked
+    #[serde(default)]
+    files_pending_deletion: HashMap<FileId, TimestampMillis>,
 }
 
 impl Files {
@@ -36,6 +48,16 @@ impl Files {
             }
         }
 
+        if file.mime_type.is_some() || file.accessors.is_some() {
+            self.file_details.insert(
+                file.file_id,
+                FileDetails {
+                    mime_type: file.mime_type.unwrap_or_default(),
+                    accessors: file.accessors.unwrap_or_default(),
+                },
+            );
+        }
+
         let blob_reference = BlobReference {
             hash: file.hash,
             user_id: file.meta_data.owner,
@@ -61,7 +83,11 @@ impl Files {
     }
 
     pub fn remove(&mut self, file: FileRemoved, bucket: CanisterId) -> Result<RemoveFileSuccess, ()> {
+        self.files_pending_deletion.remove(&file.file_id);
+
         if let Some(HashAndBucket { hash, .. }) = self.files_by_user.remove(&(&file).into()) {
+            self.file_details.remove(&file.file_id);
+
             let blob_reference = BlobReference {
                 hash,
                 user_id: file.meta_data.owner,
@@ -102,6 +128,34 @@ impl Files {
         self.blob_sizes.get(hash)
     }
 
+    pub fn file_details(&self, file_id: &FileId) -> Option<FileDetails> {
+        self.file_details.get(file_id)
+    }
+
+    pub fn remove_file_accessor(&mut self, file_id: &FileId, accessor_id: AccessorId) {
+        if let Some(mut details) = self.file_details.get(file_id)
+            && let Some(index) = details.accessors.iter().position(|a| *a == accessor_id)
+        {
+            details.accessors.remove(index);
+            self.file_details.insert(*file_id, details);
+        }
+    }
+
+    pub fn is_pending_deletion(&self, file_id: &FileId, now: TimestampMillis) -> bool {
+        self.files_pending_deletion
+            .get(file_id)
+            .is_some_and(|ts| now.saturating_sub(*ts) < PENDING_DELETION_EXPIRY)
+    }
+
+    pub fn mark_pending_deletion(&mut self, file_id: FileId, now: TimestampMillis) {
+        self.files_pending_deletion.insert(file_id, now);
+    }
+
+    pub fn remove_expired_pending_deletions(&mut self, now: TimestampMillis) {
+        self.files_pending_deletion
+            .retain(|_, ts| now.saturating_sub(*ts) < PENDING_DELETION_EXPIRY);
+    }
+
     pub fn user_owns_blob(&self, user_id: Principal, hash: Hash) -> bool {
         self.iter_blob_reference_counts(hash, Some(user_id)).next().is_some()
     }
@@ -111,12 +165,23 @@ impl Files {
     }
 
     pub fn iter_user_files_from_oldest(&self, user_id: Principal) -> impl Iterator<Item = UserFile> + '_ {
-        self.iter_user_files_from_oldest_internal(user_id).map(|(k, v)| UserFile {
-            file_id: k.file_id,
-            created: k.created,
-            hash: v.hash,
-            bucket: v.bucket,
-        })
+        self.iter_user_files_from(user_id, 0, 0)
+    }
+
+    // Iterates over the user's files from oldest to newest, starting at the given position
+    pub fn iter_user_files_from(
+        &self,
+        user_id: Principal,
+        created: TimestampMillis,
+        file_id: FileId,
+    ) -> impl Iterator<Item = UserFile> + '_ {
+        self.iter_user_files_from_internal(user_id, created, file_id)
+            .map(|(k, v)| UserFile {
+                file_id: k.file_id,
+                created: k.created,
+                hash: v.hash,
+                bucket: v.bucket,
+            })
     }
 
     pub fn update_user_principal(&mut self, old_principal: Principal, new_principal: Principal) {
@@ -148,14 +213,16 @@ impl Files {
         }
     }
 
-    fn iter_user_files_from_oldest_internal(
+    fn iter_user_files_from_internal(
         &self,
         user_id: Principal,
+        created: TimestampMillis,
+        file_id: FileId,
     ) -> impl Iterator<Item = (FileIdByUserThenCreated, HashAndBucket)> + '_ {
         let range_start = FileIdByUserThenCreated {
             user_id,
-            created: 0,
-            file_id: 0,
+            created,
+            file_id,
         };
         self.files_by_user
             .range(range_start..)
@@ -249,12 +316,35 @@ impl FileIdByUserThenCreated {
 
 pub struct UserFile {
     pub file_id: FileId,
-    #[allow(dead_code)]
     pub created: TimestampMillis,
     pub hash: Hash,
     pub bucket: CanisterId,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct FileDetails {
+    #[serde(rename = "m")]
+    pub mime_type: String,
+    #[serde(rename = "a")]
+    pub accessors: Vec<AccessorId>,
+}
+
+impl Storable for FileDetails {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(msgpack::serialize_then_unwrap(self))
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        msgpack::serialize_then_unwrap(&self)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        msgpack::deserialize_then_unwrap(&bytes)
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
 pub struct HashAndBucket {
     pub hash: Hash,
     pub bucket: CanisterId,
@@ -360,6 +450,8 @@ impl Default for Files {
             blob_sizes: init_blob_sizes(),
             total_file_bytes: init_total_file_bytes(),
             total_blob_bytes: init_total_blob_bytes(),
+            file_details: init_file_details(),
+            files_pending_deletion: HashMap::new(),
         }
     }
 }
@@ -394,6 +486,12 @@ fn init_total_blob_bytes() -> StableCell<u64, Memory> {
     StableCell::init(memory, 0)
 }
 
+fn init_file_details() -> StableBTreeMap<FileId, FileDetails, Memory> {
+    let memory = get_file_details_memory();
+
+    StableBTreeMap::init(memory)
+}
+
 pub struct Metrics {
     pub file_count: u64,
     pub total_file_bytes: u64,
@@ -421,7 +519,10 @@ mod tests {
                     meta_data: FileMetaData {
                         owner: user_id,
                         created: i.into(),
+                        encrypted: false,
                     },
+                    mime_type: None,
+                    accessors: None,
                 },
                 bucket,
             );
@@ -450,7 +551,10 @@ mod tests {
                         meta_data: FileMetaData {
                             owner: user_id,
                             created: i.into(),
+                            encrypted: false,
                         },
+                        mime_type: None,
+                        accessors: None,
                     },
                     bucket,
                 );
@@ -465,6 +569,81 @@ mod tests {
         assert_eq!(created_dates, (30u64..40).collect::<Vec<_>>())
     }
 
+    #[test]
+    fn iter_user_files_from_starts_at_position() {
+        let mut files = Files::default();
+        let user_id = Principal::from_slice(&[1]);
+        let bucket = CanisterId::from_slice(&[2]);
+
+        for i in 0u8..10 {
+            files.add(file_added(i, user_id, None), bucket);
+        }
+
+        let created_dates: Vec<_> = files.iter_user_files_from(user_id, 4, 4).map(|f| f.created).collect();
+
+        assert_eq!(created_dates, (4u64..10).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn remove_file_accessor_updates_details() {
+        let mut files = Files::default();
+        let user_id = Principal::from_slice(&[1]);
+        let bucket = CanisterId::from_slice(&[2]);
+        let accessor1 = Principal::from_slice(&[3]);
+        let accessor2 = Principal::from_slice(&[4]);
+
+        files.add(file_added(1, user_id, Some(vec![accessor1, accessor2])), bucket);
+        files.remove_file_accessor(&1, accessor1);
+
+        assert_eq!(files.file_details(&1).unwrap().accessors, vec![accessor2]);
+    }
+
+    #[test]
+    fn remove_clears_pending_deletion() {
+        let mut files = Files::default();
+        let user_id = Principal::from_slice(&[1]);
+        let bucket = CanisterId::from_slice(&[2]);
+
+        files.add(file_added(1, user_id, None), bucket);
+        files.mark_pending_deletion(1, 0);
+        assert!(files.is_pending_deletion(&1, 0));
+
+        files
+            .remove(
+                FileRemoved {
+                    file_id: 1,
+                    meta_data: FileMetaData {
+                        owner: user_id,
+                        created: 1,
+                        encrypted: false,
+                    },
+                },
+                bucket,
+            )
+            .unwrap();
+
+        assert!(!files.is_pending_deletion(&1, 0));
+    }
+
+    #[test]
+    fn pending_deletions_expire() {
+        let mut files = Files::default();
+        let user_id = Principal::from_slice(&[1]);
+        let bucket = CanisterId::from_slice(&[2]);
+
+        files.add(file_added(1, user_id, None), bucket);
+        files.add(file_added(2, user_id, None), bucket);
+        files.mark_pending_deletion(1, 0);
+        files.mark_pending_deletion(2, 1000);
+
+        let now = PENDING_DELETION_EXPIRY;
+        assert!(!files.is_pending_deletion(&1, now));
+        assert!(files.is_pending_deletion(&2, now));
+
+        files.remove_expired_pending_deletions(now);
+        assert_eq!(files.files_pending_deletion.keys().copied().collect::<Vec<_>>(), vec![2]);
+    }
+
     #[test]
     fn add_then_remove_leaves_empty() {
         let mut files = Files::default();
@@ -480,7 +659,10 @@ mod tests {
                     meta_data: FileMetaData {
                         owner: user_id,
                         created: i.into(),
+                        encrypted: false,
                     },
+                    mime_type: Some("image/png".to_string()),
+                    accessors: Some(vec![Principal::from_slice(&[3])]),
                 },
                 bucket,
             );
@@ -494,6 +676,7 @@ mod tests {
                         meta_data: FileMetaData {
                             owner: user_id,
                             created: i.into(),
+                            encrypted: false,
                         },
                     },
                     bucket,
@@ -504,7 +687,23 @@ mod tests {
         assert!(files.files_by_user.is_empty());
         assert!(files.blob_reference_counts.is_empty());
         assert!(files.blob_sizes.is_empty());
+        assert!(files.file_details.is_empty());
         assert_eq!(*files.total_file_bytes.get(), 0);
         assert_eq!(*files.total_blob_bytes.get(), 0);
     }
+
+    fn file_added(i: u8, owner: Principal, accessors: Option<Vec<AccessorId>>) -> FileAdded {
+        FileAdded {
+            file_id: i.into(),
+            hash: [i; 32],
+            size: i.into(),
+            meta_data: FileMetaData {
+                owner,
+                created: i.into(),
+                encrypted: false,
+            },
+            mime_type: Some("image/png".to_string()),
+            accessors,
+        }
+    }
 }
@@ -34,11 +34,8 @@ fn allocated_bucket_impl(args: Args, state: &RuntimeState) -> Response {
         }
 
         let now = state.env.now();
-        let bucket = state
-            .data
-            .files
-            .bucket_for_blob(args.file_hash)
-            .or_else(|| state.data.buckets.allocate(args.file_hash, now));
+        let existing_bucket = state.data.files.bucket_for_blob(args.file_hash);
+        let bucket = existing_bucket.or_else(|| state.data.buckets.allocate(args.file_hash, now));
 
         if let Some(canister_id) = bucket {
             Success(SuccessResult {
@@ -60,6 +57,7 @@ fn allocated_bucket_impl(args: Args, state: &RuntimeState) -> Response {
                     bytes_used_after_upload,
                     bytes_used_after_operation: bytes_used_after_upload,
                 },
+                blob_exists: existing_bucket.is_some(),
             })
         } else {
             BucketUnavailable
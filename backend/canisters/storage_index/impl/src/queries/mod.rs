@@ -1,5 +1,6 @@
 pub mod allocated_bucket;
 pub mod can_forward;
 pub mod http_request;
+pub mod storage_usage;
 pub mod user;
 mod vault_buckets;
@@ -0,0 +1,106 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use canister_tracing_macros::trace;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use storage_index_canister::storage_usage::{Response::*, *};
+use types::AccessorId;
+
+const DEFAULT_LARGEST_FILES_COUNT: u32 = 10;
+const MAX_LARGEST_FILES_COUNT: u32 = 100;
+const MAX_FILES_CHECKED_PER_BATCH: usize = 1000;
+
+#[query(candid = true, msgpack = true)]
+#[trace]
+fn storage_usage(args: Args) -> Response {
+    read_state(|state| storage_usage_impl(args, state))
+}
+
+fn storage_usage_impl(args: Args, state: &RuntimeState) -> Response {
+    let user_id = state.env.caller();
+    let Some(user) = state.data.users.get(&user_id) else {
+        return UserNotFound;
+    };
+
+    let largest_files_count = args
+        .largest_files_count
+        .unwrap_or(DEFAULT_LARGEST_FILES_COUNT)
+        .min(MAX_LARGEST_FILES_COUNT) as usize;
+
+    let (created, file_id) = args.cursor.map_or((0, 0), |c| (c.created, c.file_id));
+    let mut file_count = 0;
+    let mut by_chat: HashMap<AccessorId, (u32, u64)> = HashMap::new();
+    let mut by_mime_type: HashMap<String, (u32, u64)> = HashMap::new();
+    let mut files = Vec::new();
+    let mut next_cursor = None;
+
+    for (index, file) in state.data.files.iter_user_files_from(user_id, created, file_id).enumerate() {
+        if index == MAX_FILES_CHECKED_PER_BATCH {
+            next_cursor = Some(Cursor {
+                created: file.created,
+                file_id: file.file_id,
+            });
+            break;
+        }
+
+        let size = state.data.files.blob_size(&file.hash).unwrap_or_default();
+        let (mime_type, accessors) = state
+            .data
+            .files
+            .file_details(&file.file_id)
+            .map(|d| (d.mime_type, d.accessors))
+            .unwrap_or_default();
+
+        file_count += 1;
+        for accessor_id in accessors.iter() {
+            let entry = by_chat.entry(*accessor_id).or_default();
+            entry.0 += 1;
+            entry.1 = entry.1.saturating_add(size);
+        }
+        let entry = by_mime_type.entry(mime_type.clone()).or_default();
+        entry.0 += 1;
+        entry.1 = entry.1.saturating_add(size);
+
+        files.push(FileStorageUsage {
+            file_id: file.file_id,
+            bucket: file.bucket,
+            size,
+            mime_type,
+            accessors,
+            created: file.created,
+        });
+    }
+
+    files.sort_unstable_by_key(|f| Reverse(f.size));
+    files.truncate(largest_files_count);
+
+    let mut by_chat: Vec<_> = by_chat
+        .into_iter()
+        .map(|(accessor_id, (file_count, bytes))| ChatStorageUsage {
+            accessor_id,
+            file_count,
+            bytes,
+        })
+        .collect();
+    by_chat.sort_unstable_by_key(|c| Reverse(c.bytes));
+
+    let mut by_mime_type: Vec<_> = by_mime_type
+        .into_iter()
+        .map(|(mime_type, (file_count, bytes))| MimeTypeStorageUsage {
+            mime_type,
+            file_count,
+            bytes,
+        })
+        .collect();
+    by_mime_type.sort_unstable_by_key(|m| Reverse(m.bytes));
+
+    Success(SuccessResult {
+        byte_limit: user.byte_limit,
+        bytes_used: user.bytes_used,
+        file_count,
+        by_chat,
+        by_mime_type,
+        largest_files: files,
+        next_cursor,
+    })
+}
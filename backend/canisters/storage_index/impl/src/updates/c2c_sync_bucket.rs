@@ -39,6 +39,10 @@ fn c2c_sync_bucket_impl(args: Args, state: &mut RuntimeState) -> Response {
         state.data.remove_file_reference(bucket, file);
     }
 
+    for removed in args.file_accessors_removed {
+        state.data.files.remove_file_accessor(&removed.file_id, removed.accessor_id);
+    }
+
     for denylisted in args.csam_hashes_denylisted {
         state
             .data
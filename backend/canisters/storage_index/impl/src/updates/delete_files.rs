@@ -0,0 +1,77 @@
+use crate::model::bucket_event_batch::EventToSync;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use storage_index_canister::delete_files::{Response::*, *};
+
+const MAX_FILES_CHECKED_PER_BATCH: usize = 1000;
+
+#[update(candid = true, msgpack = true)]
+#[trace]
+fn delete_files(args: Args) -> Response {
+    mutate_state(|state| delete_files_impl(args, state))
+}
+
+fn delete_files_impl(args: Args, state: &mut RuntimeState) -> Response {
+    let user_id = state.env.caller();
+    if !state.data.users.contains_key(&user_id) {
+        return UserNotFound;
+    }
+
+    if args.older_than.is_none() && args.accessor_id.is_none() && args.mime_type.is_none() {
+        return FilterRequired;
+    }
+
+    let now = state.env.now();
+    state.data.files.remove_expired_pending_deletions(now);
+
+    let files = &state.data.files;
+    let (created, file_id) = args.cursor.map_or((0, 0), |c| (c.created, c.file_id));
+    let mut files_to_delete = Vec::new();
+    let mut next_cursor = None;
+
+    for (index, file) in files
+        .iter_user_files_from(user_id, created, file_id)
+        .take_while(|f| args.older_than.is_none_or(|ts| f.created < ts))
+        .enumerate()
+    {
+        if index == MAX_FILES_CHECKED_PER_BATCH {
+            next_cursor = Some(Cursor {
+                created: file.created,
+                file_id: file.file_id,
+            });
+            break;
+        }
+
+        // Skip files which an earlier call has recently asked their bucket to delete
+        if files.is_pending_deletion(&file.file_id, now) {
+            continue;
+        }
+
+        let matches_filters = (args.accessor_id.is_none() && args.mime_type.is_none())
+            || files.file_details(&file.file_id).is_some_and(|details| {
+                args.accessor_id.is_none_or(|a| details.accessors.contains(&a))
+                    && args.mime_type.as_ref().is_none_or(|m| details.mime_type.starts_with(m))
+            });
+
+        if matches_filters {
+            files_to_delete.push((file.file_id, file.bucket, files.blob_size(&file.hash).unwrap_or_default()));
+        }
+    }
+
+    let mut total_bytes = 0u64;
+    for (file_id, bucket, size) in files_to_delete.iter().copied() {
+        state.data.files.mark_pending_deletion(file_id, now);
+        state
+            .data
+            .bucket_event_sync_queue
+            .push(bucket, EventToSync::FileToRemove(file_id));
+        total_bytes = total_bytes.saturating_add(size);
+    }
+
+    Success(SuccessResult {
+        file_count: files_to_delete.len() as u32,
+        total_bytes,
+        next_cursor,
+    })
+}
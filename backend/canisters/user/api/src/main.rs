@@ -14,10 +14,13 @@ fn main() {
     generate_ts_method!(user, events);
     generate_ts_method!(user, events_by_index);
     generate_ts_method!(user, events_window);
+    generate_ts_method!(user, export_history);
     generate_ts_method!(user, hot_group_exclusions);
     generate_ts_method!(user, initial_state);
+    generate_ts_method!(user, list_scheduled_messages);
     generate_ts_method!(user, local_user_index);
     generate_ts_method!(user, message_activity_feed);
+    generate_ts_method!(user, message_edit_history);
     generate_ts_method!(user, messages_by_message_index);
     generate_ts_method!(user, public_profile);
     generate_ts_method!(user, search_messages);
@@ -34,6 +37,7 @@ fn main() {
     generate_ts_method!(user, block_user);
     generate_ts_method!(user, cancel_message_reminder);
     generate_ts_method!(user, cancel_p2p_swap);
+    generate_ts_method!(user, cancel_scheduled_message);
     generate_ts_method!(user, claim_daily_chit);
     generate_ts_method!(user, configure_wallet);
     generate_ts_method!(user, create_community);
@@ -56,9 +60,11 @@ fn main() {
     generate_ts_method!(user, mute_notifications);
     generate_ts_method!(user, pay_for_streak_insurance);
     generate_ts_method!(user, pin_chat_v2);
+    generate_ts_method!(user, press_message_button);
     generate_ts_method!(user, remove_reaction);
     generate_ts_method!(user, report_message);
     generate_ts_method!(user, save_crypto_account);
+    generate_ts_method!(user, schedule_message);
     generate_ts_method!(user, send_message_with_transfer_to_channel);
     generate_ts_method!(user, send_message_with_transfer_to_group);
     generate_ts_method!(user, send_message_v2);
@@ -69,8 +75,10 @@ fn main() {
     generate_ts_method!(user, set_message_reminder_v2);
     generate_ts_method!(user, set_pin_number);
     generate_ts_method!(user, set_profile_background);
+    generate_ts_method!(user, set_quiet_hours);
     generate_ts_method!(user, swap_tokens);
     generate_ts_method!(user, tip_message);
+    generate_ts_method!(user, translate_message);
     generate_ts_method!(user, unblock_user);
     generate_ts_method!(user, undelete_messages);
     generate_ts_method!(user, unmute_notifications);
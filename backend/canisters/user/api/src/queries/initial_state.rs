@@ -2,7 +2,7 @@ use crate::{MessageActivitySummary, Referral, WalletConfig};
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
 use types::{
-    CanisterId, Chat, ChitEvent, DirectChatSummary, Empty, InstalledBotDetails, PinNumberSettings, StreakInsurance,
+    CanisterId, Chat, ChitEvent, DirectChatSummary, Empty, InstalledBotDetails, PinNumberSettings, QuietHours, StreakInsurance,
     TimestampMillis, UserId,
 };
 
@@ -45,6 +45,7 @@ pub struct SuccessResult {
     pub one_sec_address: Option<String>,
     pub premium_items: Vec<u32>,
     pub pinned_chats: Vec<Chat>,
+    pub quiet_hours: Option<QuietHours>,
 }
 
 #[ts_export(user, initial_state)]
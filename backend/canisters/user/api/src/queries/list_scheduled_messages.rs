@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Chat, GroupReplyContext, MessageContentInitial, MessageId, MessageIndex, TimestampMillis, User};
+
+#[ts_export(user, list_scheduled_messages)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub chat: Option<Chat>,
+}
+
+#[ts_export(user, list_scheduled_messages)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[ts_export(user, list_scheduled_messages)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub scheduled_messages: Vec<ScheduledMessage>,
+}
+
+#[ts_export(user, list_scheduled_messages)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ScheduledMessage {
+    pub scheduled_message_id: u64,
+    pub chat: Chat,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub content: MessageContentInitial,
+    pub replies_to: Option<GroupReplyContext>,
+    pub mentioned: Vec<User>,
+    pub block_level_markdown: bool,
+    pub send_at: TimestampMillis,
+    pub created: TimestampMillis,
+}
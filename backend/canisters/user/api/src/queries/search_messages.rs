@@ -1,12 +1,13 @@
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{MessageMatch, UserId};
+use types::{MessageIndex, MessageMatch, UserId};
 
 #[ts_export(user, search_messages)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Args {
     pub user_id: UserId,
+    pub thread_root_message_index: Option<MessageIndex>,
     pub search_term: String,
     pub max_results: u8,
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
 use types::{
     Chat, ChatId, ChitEvent, CommunityId, DirectChatSummary, DirectChatSummaryUpdates, InstalledBotDetails, OptionUpdate,
-    PinNumberSettings, StreakInsurance, TimestampMillis, UserId,
+    PinNumberSettings, QuietHours, StreakInsurance, TimestampMillis, UserId,
 };
 
 #[ts_export(user, updates)]
@@ -77,6 +77,9 @@ pub struct SuccessResult {
     pub one_sec_address: Option<String>,
     pub premium_items: Option<Vec<u32>>,
     pub pinned_chats: Option<Vec<Chat>>,
+    #[serde(default, skip_serializing_if = "OptionUpdate::is_empty")]
+    #[ts(as = "Option<types::OptionUpdateQuietHours>", optional)]
+    pub quiet_hours: OptionUpdate<QuietHours>,
 }
 
 #[ts_export(user, updates)]
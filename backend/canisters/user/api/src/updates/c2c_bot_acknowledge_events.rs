@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use types::{TimestampMillis, UnitResult, UserId};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub cursor: TimestampMillis,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::UnitResult;
+
+#[ts_export(user, cancel_scheduled_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub scheduled_message_id: u64,
+}
+
+pub type Response = UnitResult;
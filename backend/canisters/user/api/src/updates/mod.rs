@@ -5,6 +5,7 @@ pub mod approve_transfer;
 pub mod archive_unarchive_chats;
 pub mod block_user;
 pub mod c2c_accept_p2p_swap;
+pub mod c2c_bot_acknowledge_events;
 pub mod c2c_bot_add_reaction;
 pub mod c2c_bot_send_message;
 pub mod c2c_charge_user_account;
@@ -28,6 +29,7 @@ pub mod c2c_vote_on_proposal;
 pub mod c2c_withdraw_from_icpswap;
 pub mod cancel_message_reminder;
 pub mod cancel_p2p_swap;
+pub mod cancel_scheduled_message;
 pub mod claim_daily_chit;
 pub mod configure_wallet;
 pub mod create_community;
@@ -51,9 +53,11 @@ pub mod mark_read;
 pub mod mute_notifications;
 pub mod pay_for_streak_insurance;
 pub mod pin_chat_v2;
+pub mod press_message_button;
 pub mod remove_reaction;
 pub mod report_message;
 pub mod save_crypto_account;
+pub mod schedule_message;
 pub mod send_message_v2;
 pub mod send_message_with_transfer_to_channel;
 pub mod send_message_with_transfer_to_group;
@@ -64,9 +68,11 @@ pub mod set_contact;
 pub mod set_message_reminder_v2;
 pub mod set_pin_number;
 pub mod set_profile_background;
+pub mod set_quiet_hours;
 pub mod start_video_call_v2;
 pub mod swap_tokens;
 pub mod tip_message;
+pub mod translate_message;
 pub mod unblock_user;
 pub mod undelete_messages;
 pub mod unmute_notifications;
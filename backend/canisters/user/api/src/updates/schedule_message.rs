@@ -0,0 +1,30 @@
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Chat, GroupReplyContext, MessageContentInitial, MessageId, MessageIndex, TimestampMillis, User};
+
+#[ts_export(user, schedule_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub chat: Chat,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub content: MessageContentInitial,
+    pub replies_to: Option<GroupReplyContext>,
+    pub mentioned: Vec<User>,
+    pub block_level_markdown: bool,
+    pub send_at: TimestampMillis,
+}
+
+#[ts_export(user, schedule_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(user, schedule_message)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    pub scheduled_message_id: u64,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{QuietHours, UnitResult};
+
+#[ts_export(user, set_quiet_hours)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub quiet_hours: Option<QuietHours>,
+}
+
+pub type Response = UnitResult;
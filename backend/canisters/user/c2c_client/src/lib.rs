@@ -3,10 +3,12 @@ use user_canister::*;
 
 // Queries
 generate_c2c_call!(c2c_bot_chat_summary);
+generate_c2c_call!(c2c_bot_events_since);
 generate_c2c_call!(c2c_can_issue_access_token_v2);
 generate_c2c_call!(c2c_groups_and_communities);
 
 // Updates
+generate_c2c_call!(c2c_bot_acknowledge_events);
 generate_c2c_call!(c2c_bot_add_reaction);
 generate_c2c_call!(c2c_bot_send_message);
 generate_c2c_call!(c2c_accept_p2p_swap);
@@ -41,8 +41,8 @@ use timer_job_queues::{BatchedTimerJobQueue, GroupedTimerJobQueue};
 use types::{
     Achievement, BotDefinitionUpdate, BotInitiator, BotNotification, BotPermissions, BotUpdated, BuildVersion, CanisterId,
     Chat, ChatId, ChatMetrics, ChitEvent, ChitEventType, CommunityId, Cycles, DirectChatUserNotificationPayload, Document,
-    IdempotentEnvelope, Notification, NotifyChit, TimestampMillis, Timestamped, UniquePersonProof,
-    UserCanisterStreakInsuranceClaim, UserCanisterStreakInsurancePayment, UserId, UserNotification,
+    IdempotentEnvelope, Notification, NotifyChit, OptionUpdate, QuietHours, TimestampMillis, Timestamped, UniquePersonProof,
+    UserCanisterStreakInsuranceClaim, UserCanisterStreakInsurancePayment, UserId, UserNotification, UserQuietHours,
 };
 use user_canister::{MessageActivityEvent, NamedAccount, UserCanisterEvent, WalletConfig};
 use utils::env::Environment;
@@ -276,6 +276,16 @@ Your streak is now {new_streak} days and you have {days_remaining_text} of strea
         );
     }
 
+    // The local user index needs the user's UTC offset alongside their quiet hours, so this must
+    // be called whenever either of them changes
+    pub fn sync_quiet_hours_to_local_user_index(&mut self, now: TimestampMillis) {
+        let quiet_hours = self.data.quiet_hours.value.map(|quiet_hours| UserQuietHours {
+            quiet_hours,
+            utc_offset_mins: self.data.streak.utc_offset_mins(),
+        });
+        self.push_local_user_index_canister_event(LocalUserIndexEvent::SetQuietHours(quiet_hours), now);
+    }
+
     pub fn award_achievements_and_notify(&mut self, achievements: Vec<Achievement>, now: TimestampMillis) {
         let mut awarded = false;
 
@@ -496,6 +506,8 @@ struct Data {
     pub idempotency_checker: IdempotencyChecker,
     pub bots: InstalledBots,
     pub premium_items: PremiumItems,
+    #[serde(default)]
+    pub quiet_hours: Timestamped<Option<QuietHours>>,
 }
 
 impl Data {
@@ -566,6 +578,7 @@ impl Data {
             idempotency_checker: IdempotencyChecker::default(),
             bots: InstalledBots::default(),
             premium_items: PremiumItems::default(),
+            quiet_hours: Timestamped::default(),
         }
     }
 
@@ -626,24 +639,23 @@ impl Data {
     }
 
     pub fn is_bot_permitted(&self, bot_id: &UserId, initiator: &BotInitiator, required: BotPermissions) -> bool {
+        // The permissions required must be a subset of the permissions granted to the bot
+        self.granted_bot_permissions(bot_id, initiator)
+            .is_some_and(|granted| required.is_subset(&granted))
+    }
+
+    pub fn granted_bot_permissions(&self, bot_id: &UserId, initiator: &BotInitiator) -> Option<BotPermissions> {
         // Try to get the installed bot
-        let Some(bot) = self.bots.get(bot_id) else {
-            return false;
-        };
+        let bot = self.bots.get(bot_id)?;
 
         // Get the granted permissions when initiated by command or API key
-        let granted = match initiator {
-            BotInitiator::Command(_) => {
-                &BotPermissions::union(&bot.permissions, &bot.autonomous_permissions.clone().unwrap_or_default())
-            }
-            BotInitiator::Autonomous => match bot.autonomous_permissions.as_ref() {
-                Some(permissions) => permissions,
-                None => return false,
-            },
-        };
-
-        // The permissions required must be a subset of the permissions granted to the bot
-        required.is_subset(granted)
+        match initiator {
+            BotInitiator::Command(_) | BotInitiator::Interaction(_) => Some(BotPermissions::union(
+                &bot.permissions,
+                &bot.autonomous_permissions.clone().unwrap_or_default(),
+            )),
+            BotInitiator::Autonomous => bot.autonomous_permissions.clone(),
+        }
     }
 
     pub fn flush_pending_events(&mut self) {
@@ -666,10 +678,13 @@ impl Data {
         autonomous_permissions: Option<BotPermissions>,
         now: TimestampMillis,
     ) -> bool {
-        if self
-            .bots
-            .update_permissions(bot_id, command_permissions, autonomous_permissions, now)
-        {
+        if self.bots.update_permissions(
+            bot_id,
+            command_permissions,
+            autonomous_permissions,
+            OptionUpdate::NoChange,
+            now,
+        ) {
             self.apply_bot_update(bot_id, None, now);
             true
         } else {
@@ -136,7 +136,10 @@ pub(crate) fn send_text_message(
     mute_notification: bool,
     state: &mut RuntimeState,
 ) -> EventWrapper<Message> {
-    let content = MessageContentInternal::Text(TextContentInternal { text });
+    let content = MessageContentInternal::Text(TextContentInternal {
+        text,
+        components: Vec::new(),
+    });
     send_message(content, mentioned, mute_notification, state)
 }
 
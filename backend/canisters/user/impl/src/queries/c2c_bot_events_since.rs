@@ -0,0 +1,34 @@
+use crate::guards::caller_is_local_user_index;
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use oc_error_codes::OCErrorCode;
+use types::{BotEventsSinceResult, BotInitiator, OCResult};
+use user_canister::c2c_bot_events_since::{Response::*, *};
+
+#[query(guard = "caller_is_local_user_index", msgpack = true)]
+fn c2c_bot_events_since(args: Args) -> Response {
+    match read_state(|state| c2c_bot_events_since_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn c2c_bot_events_since_impl(args: Args, state: &RuntimeState) -> OCResult<BotEventsSinceResult> {
+    let permissions = state
+        .data
+        .granted_bot_permissions(&args.bot_id, &BotInitiator::Autonomous)
+        .ok_or(OCErrorCode::InitiatorNotAuthorized)?;
+
+    let chat = state
+        .data
+        .direct_chats
+        .get(&args.bot_id.into())
+        .ok_or(OCErrorCode::ChatNotFound)?;
+
+    chat.events.bot_events_since(
+        args.bot_id,
+        args.since,
+        &permissions.permitted_chat_event_categories_to_read(),
+        args.max_events as usize,
+    )
+}
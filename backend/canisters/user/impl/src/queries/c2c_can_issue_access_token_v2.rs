@@ -24,6 +24,7 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
         AccessTypeArgs::StartVideoCall(args) => args.initiator,
         AccessTypeArgs::JoinVideoCall(args) => args.initiator,
         AccessTypeArgs::MarkVideoCallAsEnded(args) => args.initiator,
+        AccessTypeArgs::ChatEncryptionKey(args) => args.initiator,
         _ => unreachable!(),
     };
 
@@ -31,6 +32,13 @@ fn c2c_can_issue_access_token_impl(args_outer: Args, state: &RuntimeState) -> Re
         return Response::Failure;
     }
 
+    // Direct chats have no key rotation so only ever use epoch 0
+    if let AccessTypeArgs::ChatEncryptionKey(args) = &args_outer
+        && args.key_epoch > 0
+    {
+        return Response::Failure;
+    }
+
     if let AccessTypeArgs::BotActionByCommand(_) = &args_outer {
         return Response::Success;
     }
@@ -0,0 +1,29 @@
+use crate::guards::caller_is_owner;
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use types::{ChatExportPage, EventIndex, OCResult};
+use user_canister::export_history::{Response::*, *};
+
+const MAX_EVENTS_PER_PAGE: u32 = 1000;
+
+#[query(guard = "caller_is_owner", msgpack = true)]
+fn export_history(args: Args) -> Response {
+    match read_state(|state| export_history_impl(args, state)) {
+        Ok(page) => Success(page),
+        Err(error) => Error(error),
+    }
+}
+
+fn export_history_impl(args: Args, state: &RuntimeState) -> OCResult<ChatExportPage> {
+    let my_user_id = state.env.canister_id().into();
+    let chat = state.data.direct_chats.get_or_err(&args.user_id.into())?;
+
+    let (events, next) = chat.events.export_events(
+        EventIndex::default(),
+        args.after,
+        args.max_events.clamp(1, MAX_EVENTS_PER_PAGE) as usize,
+        Some(my_user_id),
+    );
+
+    Ok(ChatExportPage::new(Vec::new(), events, next))
+}
@@ -45,6 +45,7 @@ fn initial_state_impl(state: &RuntimeState) -> Response {
             added_by: bot.added_by,
             permissions: bot.permissions.clone(),
             autonomous_permissions: bot.autonomous_permissions.clone(),
+            channel_permissions: None,
         })
         .collect();
 
@@ -77,5 +78,6 @@ fn initial_state_impl(state: &RuntimeState) -> Response {
         one_sec_address: state.data.one_sec_address.as_ref().map(|a| a.value.clone()),
         premium_items: state.data.premium_items.item_ids(),
         pinned_chats: merged_pinned,
+        quiet_hours: state.data.quiet_hours.value,
     })
 }
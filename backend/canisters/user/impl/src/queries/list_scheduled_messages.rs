@@ -0,0 +1,42 @@
+use crate::guards::caller_is_owner;
+use crate::timer_job_types::TimerJob;
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use user_canister::list_scheduled_messages::{Response::*, *};
+
+#[query(guard = "caller_is_owner", msgpack = true)]
+fn list_scheduled_messages(args: Args) -> Response {
+    read_state(|state| list_scheduled_messages_impl(args, state))
+}
+
+fn list_scheduled_messages_impl(args: Args, state: &RuntimeState) -> Response {
+    let mut scheduled_messages: Vec<_> = state
+        .data
+        .timer_jobs
+        .iter()
+        .filter_map(|(send_at, wrapper)| {
+            if let Some(TimerJob::SendScheduledMessage(job)) = wrapper.borrow().as_ref()
+                && args.chat.is_none_or(|c| c == job.chat)
+            {
+                Some(ScheduledMessage {
+                    scheduled_message_id: job.scheduled_message_id,
+                    chat: job.chat,
+                    thread_root_message_index: job.thread_root_message_index,
+                    message_id: job.message_id,
+                    content: job.content.clone(),
+                    replies_to: job.replies_to.clone(),
+                    mentioned: job.mentioned.clone(),
+                    block_level_markdown: job.block_level_markdown,
+                    send_at: *send_at,
+                    created: job.created,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    scheduled_messages.sort_by_key(|m| m.send_at);
+
+    Success(SuccessResult { scheduled_messages })
+}
@@ -0,0 +1,28 @@
+use crate::guards::caller_is_owner;
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use types::{EventIndex, OCResult};
+use user_canister::message_edit_history::{Response::*, *};
+
+#[query(guard = "caller_is_owner", msgpack = true)]
+fn message_edit_history(args: Args) -> Response {
+    match read_state(|state| message_edit_history_impl(args, state)) {
+        Ok(result) => Success(result),
+        Err(error) => Error(error),
+    }
+}
+
+fn message_edit_history_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let my_user_id = state.env.canister_id().into();
+    let chat = state.data.direct_chats.get_or_err(&args.user_id.into())?;
+
+    let revisions = chat.events.message_edit_history(
+        EventIndex::default(),
+        args.thread_root_message_index,
+        args.message_id,
+        Some(my_user_id),
+        state.env.now(),
+    )?;
+
+    Ok(SuccessResult { revisions })
+}
@@ -3,6 +3,7 @@ use types::TimestampMillis;
 
 pub mod bio;
 pub mod c2c_bot_chat_summary;
+pub mod c2c_bot_events_since;
 pub mod c2c_can_issue_access_token_v2;
 pub mod c2c_groups_and_communities;
 pub mod chit_events;
@@ -11,11 +12,14 @@ pub mod deleted_message;
 pub mod events;
 pub mod events_by_index;
 pub mod events_window;
+pub mod export_history;
 pub mod hot_group_exclusions;
 pub mod http_request;
 pub mod initial_state;
+pub mod list_scheduled_messages;
 pub mod local_user_index;
 pub mod message_activity_feed;
+pub mod message_edit_history;
 pub mod messages_by_message_index;
 pub mod public_profile;
 pub mod saved_crypto_accounts;
@@ -4,11 +4,11 @@ use canister_api_macros::query;
 use oc_error_codes::OCErrorCode;
 use search::simple::Query;
 use std::collections::HashSet;
-use types::{MessageIndex, OCResult};
+use types::{EventIndex, MessageIndex, OCResult};
 use user_canister::search_messages::{Response::*, *};
 
 const MIN_TERM_LENGTH: u8 = 3;
-const MAX_TERM_LENGTH: u8 = 30;
+const MAX_TERM_LENGTH: u8 = 100;
 
 #[query(guard = "caller_is_owner", msgpack = true)]
 fn search_messages(args: Args) -> Response {
@@ -31,9 +31,14 @@ fn search_messages_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessRes
 
     let direct_chat = state.data.direct_chats.get_or_err(&args.user_id.into())?;
     let query = Query::new(&args.search_term);
-    let matches = direct_chat
-        .events
-        .search_messages(MessageIndex::default(), query, HashSet::new(), args.max_results);
+    let matches = direct_chat.events.search_messages(
+        EventIndex::default(),
+        MessageIndex::default(),
+        args.thread_root_message_index,
+        query,
+        HashSet::new(),
+        args.max_results,
+    )?;
 
     Ok(SuccessResult { matches })
 }
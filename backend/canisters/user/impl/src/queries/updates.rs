@@ -58,6 +58,11 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
         .filter(|a| a.timestamp > updates_since)
         .map(|a| a.value.clone());
     let premium_items_updated = state.data.premium_items.last_updated() > updates_since;
+    let quiet_hours = state
+        .data
+        .quiet_hours
+        .if_set_after(updates_since)
+        .map_or(OptionUpdate::NoChange, |update| OptionUpdate::from_update(*update));
 
     let has_any_updates = username.is_some()
         || display_name.has_update()
@@ -73,6 +78,7 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
         || btc_address_if_updated.is_some()
         || one_sec_address_if_updated.is_some()
         || premium_items_updated
+        || quiet_hours.has_update()
         || state.data.direct_chats.any_updated(updates_since)
         || state.data.group_chats.any_updated(updates_since)
         || state.data.favourite_chats.any_updated(updates_since)
@@ -200,6 +206,7 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
                         added_by: bot.added_by,
                         permissions: bot.permissions.clone(),
                         autonomous_permissions: bot.autonomous_permissions.clone(),
+                        channel_permissions: None,
                     });
                 }
             }
@@ -242,5 +249,6 @@ fn updates_impl(updates_since: TimestampMillis, state: &RuntimeState) -> Respons
         one_sec_address: one_sec_address_if_updated,
         premium_items,
         pinned_chats: merged_pinned,
+        quiet_hours,
     })
 }
@@ -1,13 +1,18 @@
 use crate::model::token_swaps::TokenSwap;
 use crate::updates::end_video_call::end_video_call_impl;
+use crate::updates::send_message::send_message_v2_impl;
 use crate::updates::swap_tokens::process_token_swap;
-use crate::{can_borrow_state, flush_pending_events, mutate_state, openchat_bot, read_state, run_regular_jobs};
+use crate::{RuntimeState, can_borrow_state, flush_pending_events, mutate_state, openchat_bot, read_state, run_regular_jobs};
 use canister_timer_jobs::Job;
-use chat_events::{MessageContentInternal, MessageReminderContentInternal};
+use chat_events::{MessageContentInternal, MessageReminderContentInternal, ValidateNewMessageContentResult};
 use constants::{MINUTE_IN_MS, OPENCHAT_BOT_USER_ID, SECOND_IN_MS};
+use oc_error_codes::OCErrorCode;
 use serde::{Deserialize, Serialize};
 use tracing::error;
-use types::{BlobReference, Chat, ChatId, CommunityId, EventIndex, MessageId, MessageIndex, P2PSwapStatus};
+use types::{
+    BlobReference, Chat, ChatId, CommunityId, EventIndex, GroupReplyContext, MessageContentInitial, MessageId, MessageIndex,
+    OCResult, P2PSwapStatus, ReplyContext, TimestampMillis, User, UserType,
+};
 use user_canister::C2CReplyContext;
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -24,6 +29,7 @@ pub enum TimerJob {
     SendMessageToChannel(Box<SendMessageToChannelJob>),
     MarkVideoCallEnded(MarkVideoCallEndedJob),
     ClaimOrResetStreakInsurance(ClaimOrResetStreakInsuranceJob),
+    SendScheduledMessage(Box<SendScheduledMessageJob>),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -113,6 +119,19 @@ pub struct MarkVideoCallEndedJob(pub user_canister::end_video_call_v2::Args);
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ClaimOrResetStreakInsuranceJob;
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SendScheduledMessageJob {
+    pub scheduled_message_id: u64,
+    pub chat: Chat,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub content: MessageContentInitial,
+    pub replies_to: Option<GroupReplyContext>,
+    pub mentioned: Vec<User>,
+    pub block_level_markdown: bool,
+    pub created: TimestampMillis,
+}
+
 impl Job for TimerJob {
     fn execute(self) {
         let can_borrow_state = can_borrow_state();
@@ -133,6 +152,7 @@ impl Job for TimerJob {
             TimerJob::SendMessageToChannel(job) => job.execute(),
             TimerJob::MarkVideoCallEnded(job) => job.execute(),
             TimerJob::ClaimOrResetStreakInsurance(job) => job.execute(),
+            TimerJob::SendScheduledMessage(job) => job.execute(),
         }
 
         if can_borrow_state {
@@ -376,3 +396,117 @@ impl Job for ClaimOrResetStreakInsuranceJob {
         });
     }
 }
+
+impl Job for SendScheduledMessageJob {
+    fn execute(self) {
+        if let Chat::Direct(chat_id) = self.chat {
+            let args = user_canister::send_message_v2::Args {
+                recipient: chat_id.into(),
+                thread_root_message_index: self.thread_root_message_index,
+                message_id: self.message_id,
+                content: self.content,
+                replies_to: self.replies_to.map(|r| ReplyContext {
+                    chat_if_other: None,
+                    event_index: r.event_index,
+                }),
+                forwarding: false,
+                block_level_markdown: self.block_level_markdown,
+                message_filter_failed: None,
+                pin: None,
+                og_previews: Vec::new(),
+            };
+            ic_cdk::futures::spawn_migratory(async move {
+                if let user_canister::send_message_v2::Response::Error(error) = send_message_v2_impl(args).await {
+                    error!(?error, "Failed to send scheduled message");
+                }
+            });
+            return;
+        }
+
+        let (sender_name, sender_display_name, content) =
+            match read_state(|state| prepare_scheduled_message(self.chat, self.content, state)) {
+                Ok(ok) => ok,
+                Err(error) => {
+                    error!(?error, chat = ?self.chat, "Failed to send scheduled message");
+                    return;
+                }
+            };
+
+        match self.chat {
+            Chat::Group(chat_id) => SendMessageToGroupJob {
+                chat_id,
+                args: group_canister::c2c_send_message::Args {
+                    thread_root_message_index: self.thread_root_message_index,
+                    message_id: self.message_id,
+                    content,
+                    sender_name,
+                    sender_display_name,
+                    replies_to: self.replies_to,
+                    mentioned: self.mentioned,
+                    forwarding: false,
+                    block_level_markdown: self.block_level_markdown,
+                    rules_accepted: None,
+                    message_filter_failed: None,
+                    og_previews: Vec::new(),
+                },
+                p2p_swap_id: None,
+                attempt: 0,
+            }
+            .execute(),
+            Chat::Channel(community_id, channel_id) => SendMessageToChannelJob {
+                community_id,
+                args: community_canister::c2c_send_message::Args {
+                    channel_id,
+                    thread_root_message_index: self.thread_root_message_index,
+                    message_id: self.message_id,
+                    content,
+                    sender_name,
+                    sender_display_name,
+                    replies_to: self.replies_to,
+                    mentioned: self.mentioned,
+                    forwarding: false,
+                    block_level_markdown: self.block_level_markdown,
+                    og_previews: Vec::new(),
+                    community_rules_accepted: None,
+                    channel_rules_accepted: None,
+                    message_filter_failed: None,
+                },
+                p2p_swap_id: None,
+                attempt: 0,
+            }
+            .execute(),
+            Chat::Direct(_) => unreachable!(),
+        }
+    }
+}
+
+fn prepare_scheduled_message(
+    chat: Chat,
+    content: MessageContentInitial,
+    state: &RuntimeState,
+) -> OCResult<(String, Option<String>, MessageContentInternal)> {
+    // The user may have been suspended or have left the chat since the message was scheduled, so
+    // check again before sending. The group / community then re-checks that the user is still a
+    // member with permission to send this type of message, since their role or the chat's
+    // permissions may also have changed, rejecting the message if not.
+    state.data.verify_not_suspended()?;
+
+    let is_member = match chat {
+        Chat::Group(chat_id) => state.data.group_chats.exists(&chat_id),
+        Chat::Channel(community_id, _) => state.data.communities.exists(&community_id),
+        Chat::Direct(_) => true,
+    };
+    if !is_member {
+        return Err(OCErrorCode::InitiatorNotInChat.into());
+    }
+
+    match MessageContentInternal::validate_new_message(content, false, UserType::User, false, state.env.now()) {
+        ValidateNewMessageContentResult::Success(content) => Ok((
+            state.data.username.value.clone(),
+            state.data.display_name.value.clone(),
+            content,
+        )),
+        ValidateNewMessageContentResult::Error(error) => Err(OCErrorCode::InvalidMessageContent.with_json(&error)),
+        _ => Err(OCErrorCode::InvalidMessageType.into()),
+    }
+}
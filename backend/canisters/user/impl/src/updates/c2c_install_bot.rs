@@ -29,6 +29,7 @@ fn c2c_install_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
         args.granted_permissions.clone(),
         args.granted_autonomous_permissions.clone(),
         args.default_subscriptions.clone(),
+        None,
         now,
     ) {
         return Err(OCErrorCode::AlreadyAdded.into());
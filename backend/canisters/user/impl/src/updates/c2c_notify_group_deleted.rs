@@ -101,10 +101,14 @@ fn migrate_group_references_to_channel_references(
     );
 
     for (_, job) in data.timer_jobs.iter() {
-        if let Some(TimerJob::MessageReminder(mr)) = job.borrow_mut().as_mut()
-            && mr.chat == Chat::Group(group_id)
-        {
-            mr.chat = Chat::Channel(community_id, channel_id);
+        match job.borrow_mut().as_mut() {
+            Some(TimerJob::MessageReminder(mr)) if mr.chat == Chat::Group(group_id) => {
+                mr.chat = Chat::Channel(community_id, channel_id);
+            }
+            Some(TimerJob::SendScheduledMessage(sm)) if sm.chat == Chat::Group(group_id) => {
+                sm.chat = Chat::Channel(community_id, channel_id);
+            }
+            _ => {}
         }
     }
 }
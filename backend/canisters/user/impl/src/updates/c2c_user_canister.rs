@@ -183,7 +183,7 @@ fn send_messages(args: SendMessagesArgs, sender: UserId, state: &mut RuntimeStat
 
             if chat
                 .events
-                .message_already_finalised(thread_root_message_index, message.message_id, false)
+                .message_already_finalised(thread_root_message_index, message.message_id, None)
             {
                 continue;
             }
@@ -231,6 +231,7 @@ fn edit_message(args: user_canister::EditMessageArgs, caller_user_id: UserId, st
                 block_level_markdown: args.block_level_markdown,
                 og_previews: args.og_previews,
                 finalise_bot_message: false,
+                record_edit_history: true,
                 now,
             },
             None,
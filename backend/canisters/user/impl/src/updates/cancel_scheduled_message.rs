@@ -0,0 +1,26 @@
+use crate::guards::caller_is_owner;
+use crate::timer_job_types::TimerJob;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+use user_canister::cancel_scheduled_message::*;
+
+#[update(guard = "caller_is_owner", msgpack = true)]
+#[trace]
+fn cancel_scheduled_message(args: Args) -> Response {
+    execute_update(|state| cancel_scheduled_message_impl(args.scheduled_message_id, state)).into()
+}
+
+fn cancel_scheduled_message_impl(scheduled_message_id: u64, state: &mut RuntimeState) -> OCResult {
+    let cancelled = state.data.timer_jobs.cancel_job(|j| {
+        if let TimerJob::SendScheduledMessage(job) = j {
+            job.scheduled_message_id == scheduled_message_id
+        } else {
+            false
+        }
+    });
+
+    if cancelled.is_some() { Ok(()) } else { Err(OCErrorCode::ItemNotFound.into()) }
+}
@@ -29,6 +29,10 @@ fn claim_daily_chit_impl(args: Args, state: &mut RuntimeState) -> Response {
         if utc_offset_updated {
             // Claim again in case the timezone change has made this possible
             _ = state.data.streak.claim(now);
+
+            if state.data.quiet_hours.value.is_some() {
+                state.sync_quiet_hours_to_local_user_index(now);
+            }
         }
     }
 
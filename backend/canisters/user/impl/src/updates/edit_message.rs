@@ -33,6 +33,7 @@ fn edit_message_impl(args: Args, state: &mut RuntimeState) -> OCResult {
             block_level_markdown: args.block_level_markdown,
             og_previews: args.og_previews.clone(),
             finalise_bot_message: false,
+            record_edit_history: true,
             now,
         };
 
@@ -0,0 +1,71 @@
+use crate::guards::caller_is_owner;
+use crate::{RuntimeState, execute_update_async, read_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use local_user_index_canister::c2c_issue_bot_interaction_token;
+use oc_error_codes::OCErrorCode;
+use types::{BotActionChatDetails, BotActionScope, BotInitiator, BotInteraction, CanisterId, Chat, EventIndex, OCResult};
+use user_canister::press_message_button::{Response::*, *};
+
+#[update(guard = "caller_is_owner", msgpack = true)]
+#[trace]
+async fn press_message_button(args: Args) -> Response {
+    execute_update_async(|| press_message_button_impl(args)).await
+}
+
+async fn press_message_button_impl(args: Args) -> Response {
+    let (c2c_args, local_user_index_canister_id) = match read_state(|state| prepare(args, state)) {
+        Ok(ok) => ok,
+        Err(error) => return Error(error),
+    };
+
+    match local_user_index_canister_c2c_client::c2c_issue_bot_interaction_token(local_user_index_canister_id, &c2c_args).await {
+        Ok(c2c_issue_bot_interaction_token::Response::Success(result)) => Success(SuccessResult {
+            token: result.token,
+            endpoint: result.endpoint,
+            expires: result.expires,
+        }),
+        Ok(c2c_issue_bot_interaction_token::Response::Error(error)) => Error(error),
+        Err(error) => Error(error.into()),
+    }
+}
+
+fn prepare(args: Args, state: &RuntimeState) -> OCResult<(c2c_issue_bot_interaction_token::Args, CanisterId)> {
+    state.data.verify_not_suspended()?;
+
+    let chat = state.data.direct_chats.get_or_err(&args.user_id.into())?;
+    let bot_id = chat.events.validate_message_interaction(
+        EventIndex::default(),
+        args.thread_root_message_index,
+        args.message_id,
+        &args.component_id,
+        args.value.as_deref(),
+    )?;
+
+    let interaction = BotInteraction {
+        message_id: args.message_id,
+        component_id: args.component_id,
+        value: args.value,
+        initiator: state.env.canister_id().into(),
+    };
+
+    let granted_permissions = state
+        .data
+        .granted_bot_permissions(&bot_id, &BotInitiator::Interaction(interaction.clone()))
+        .ok_or(OCErrorCode::BotNotFound)?;
+
+    Ok((
+        c2c_issue_bot_interaction_token::Args {
+            bot_id,
+            scope: BotActionScope::Chat(BotActionChatDetails {
+                chat: Chat::Direct(state.env.canister_id().into()),
+                thread: args.thread_root_message_index,
+                message_id: args.message_id,
+                user_message_id: None,
+            }),
+            granted_permissions,
+            interaction,
+        },
+        state.data.local_user_index_canister_id,
+    ))
+}
@@ -0,0 +1,92 @@
+use crate::guards::caller_is_owner;
+use crate::timer_job_types::{SendScheduledMessageJob, TimerJob};
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use chat_events::{MessageContentInternal, ValidateNewMessageContentResult};
+use oc_error_codes::OCErrorCode;
+use rand::Rng;
+use types::{Chat, OCResult, UserType};
+use user_canister::schedule_message::{Response::*, *};
+
+const MAX_SCHEDULED_MESSAGES: usize = 100;
+
+#[update(guard = "caller_is_owner", msgpack = true)]
+#[trace]
+fn schedule_message(args: Args) -> Response {
+    match execute_update(|state| schedule_message_impl(args, state)) {
+        Ok(scheduled_message_id) => Success(SuccessResult { scheduled_message_id }),
+        Err(error) => Error(error),
+    }
+}
+
+fn schedule_message_impl(args: Args, state: &mut RuntimeState) -> OCResult<u64> {
+    state.data.verify_not_suspended()?;
+
+    let now = state.env.now();
+    if args.send_at <= now {
+        return Err(OCErrorCode::DateInThePast.into());
+    }
+
+    let is_member = match args.chat {
+        Chat::Direct(_) => true,
+        Chat::Group(chat_id) => state.data.group_chats.exists(&chat_id),
+        Chat::Channel(community_id, _) => state.data.communities.exists(&community_id),
+    };
+    if !is_member {
+        return Err(OCErrorCode::InitiatorNotInChat.into());
+    }
+
+    // Messages containing crypto transfers can't be scheduled since the transfer must be made
+    // (and the PIN verified) at the point the message is sent
+    if args.content.contains_crypto_transfer() {
+        return Err(OCErrorCode::InvalidMessageType.into());
+    }
+
+    // Validate the content as if it were being sent now, but treating `send_at` as the current time
+    // so that eg. poll end dates are checked against the time the message will actually be sent
+    let is_direct_chat = matches!(args.chat, Chat::Direct(_));
+    match MessageContentInternal::validate_new_message(
+        args.content.clone(),
+        is_direct_chat,
+        UserType::User,
+        false,
+        args.send_at,
+    ) {
+        ValidateNewMessageContentResult::Success(_) => {}
+        ValidateNewMessageContentResult::Error(error) => {
+            return Err(OCErrorCode::InvalidMessageContent.with_json(&error));
+        }
+        _ => return Err(OCErrorCode::InvalidMessageType.into()),
+    }
+
+    let scheduled_count = state
+        .data
+        .timer_jobs
+        .iter()
+        .filter(|(_, job)| matches!(job.borrow().as_ref(), Some(TimerJob::SendScheduledMessage(_))))
+        .count();
+    if scheduled_count >= MAX_SCHEDULED_MESSAGES {
+        return Err(OCErrorCode::MaxScheduledMessagesReached.into());
+    }
+
+    let scheduled_message_id = state.env.rng().next_u64();
+
+    state.data.timer_jobs.enqueue_job(
+        TimerJob::SendScheduledMessage(Box::new(SendScheduledMessageJob {
+            scheduled_message_id,
+            chat: args.chat,
+            thread_root_message_index: args.thread_root_message_index,
+            message_id: args.message_id,
+            content: args.content,
+            replies_to: args.replies_to,
+            mentioned: args.mentioned,
+            block_level_markdown: args.block_level_markdown,
+            created: now,
+        })),
+        args.send_at,
+        now,
+    );
+
+    Ok(scheduled_message_id)
+}
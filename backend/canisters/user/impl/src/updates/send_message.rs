@@ -30,13 +30,13 @@ async fn send_message_v2(args: Args) -> Response {
     execute_update_async(|| send_message_v2_impl(args)).await
 }
 
-async fn send_message_v2_impl(mut args: Args) -> Response {
+pub(crate) async fn send_message_v2_impl(mut args: Args) -> Response {
     let PrepareOk {
         my_user_id,
         now,
         local_user_index_canister_id,
         maybe_recipient_type,
-    } = match read_state(|state| prepare(&args, false, state)) {
+    } = match read_state(|state| prepare(&args, None, state)) {
         Ok(ok) => ok,
         Err(error) => return Error(error),
     };
@@ -195,7 +195,7 @@ fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut Runti
         return c2c_bot_send_message::Response::Error(OCErrorCode::InitiatorNotAuthorized.into());
     }
 
-    let result = match prepare(&args, true, state) {
+    let result = match prepare(&args, Some(&bot_caller), state) {
         Ok(ok) => ok,
         Err(error) => return c2c_bot_send_message::Response::Error(error),
     };
@@ -219,14 +219,12 @@ fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut Runti
             chat.events
                 .message_internal(EventIndex::default(), args.thread_root_message_index, args.message_id.into())
     {
-        // If the message id of a bot message matches an existing unfinalised bot message
-        // then edit this message instead of pushing a new one
-        if let Some(bot_message) = message.bot_context()
-            && bot_caller.bot == message.sender
-            && bot_caller.initiator.user() == bot_message.command.as_ref().map(|c| c.initiator)
-            && bot_caller.initiator.command() == bot_message.command.as_ref()
-            && !bot_message.finalised
-        {
+        // If the message id of a bot message matches an existing unfinalised bot message, or the
+        // message the bot is responding to an interaction with, then edit this message instead of
+        // pushing a new one
+        if message.is_open_to_bot(&bot_caller) {
+            // Don't notify the user again if the message was already finalised
+            let finalised = finalised && message.bot_context().is_some_and(|b| !b.finalised);
             let edit_message_args = EditMessageArgs {
                 sender: bot_caller.bot,
                 min_visible_event_index: EventIndex::default(),
@@ -236,6 +234,7 @@ fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut Runti
                 block_level_markdown: Some(args.block_level_markdown),
                 og_previews: args.og_previews,
                 finalise_bot_message: finalised,
+                record_edit_history: false,
                 now,
             };
 
@@ -301,7 +300,10 @@ fn c2c_bot_send_message_impl(args: c2c_bot_send_message::Args, state: &mut Runti
                 thread_root_message_index: args.thread_root_message_index,
                 message_id,
                 sender: my_user_id,
-                content: MessageContentInternal::Text(TextContentInternal { text }),
+                content: MessageContentInternal::Text(TextContentInternal {
+                    text,
+                    components: Vec::new(),
+                }),
                 mentioned: Vec::new(),
                 replies_to: None,
                 forwarded: false,
@@ -384,7 +386,7 @@ struct PrepareOk {
     maybe_recipient_type: Option<RecipientType>,
 }
 
-fn prepare(args: &Args, is_v2_bot: bool, state: &RuntimeState) -> OCResult<PrepareOk> {
+fn prepare(args: &Args, bot_caller: Option<&BotCaller>, state: &RuntimeState) -> OCResult<PrepareOk> {
     state.data.verify_not_suspended()?;
 
     if state.data.blocked_users.contains(&args.recipient) {
@@ -399,7 +401,7 @@ fn prepare(args: &Args, is_v2_bot: bool, state: &RuntimeState) -> OCResult<Prepa
     let maybe_recipient_type = if let Some(chat) = state.data.direct_chats.get(&args.recipient.into()) {
         if chat
             .events
-            .message_already_finalised(args.thread_root_message_index, args.message_id, is_v2_bot)
+            .message_already_finalised(args.thread_root_message_index, args.message_id, bot_caller)
         {
             return Err(OCErrorCode::MessageIdAlreadyExists.into());
         }
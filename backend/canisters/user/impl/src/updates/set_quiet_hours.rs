@@ -0,0 +1,28 @@
+use crate::guards::caller_is_owner;
+use crate::{RuntimeState, execute_update};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::{OCResult, Timestamped};
+use user_canister::set_quiet_hours::*;
+
+#[update(guard = "caller_is_owner", msgpack = true)]
+#[trace]
+fn set_quiet_hours(args: Args) -> Response {
+    execute_update(|state| set_quiet_hours_impl(args, state)).into()
+}
+
+fn set_quiet_hours_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    if args.quiet_hours.is_some_and(|q| !q.is_valid()) {
+        return Err(OCErrorCode::InvalidRequest.into());
+    }
+
+    if state.data.quiet_hours.value == args.quiet_hours {
+        return Ok(());
+    }
+
+    let now = state.env.now();
+    state.data.quiet_hours = Timestamped::new(args.quiet_hours, now);
+    state.sync_quiet_hours_to_local_user_index(now);
+    Ok(())
+}
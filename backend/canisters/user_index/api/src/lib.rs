@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use types::{
     BotInstallationLocation, BotPermissions, CanisterId, ChannelLatestMessageIndex, ChatId, CommunityId, MessageContentInitial,
     MessageId, MessageIndex, Milliseconds, NotifyChit, PremiumItemPurchase, StreakInsuranceClaim, StreakInsurancePayment,
-    TimestampMillis, UniquePersonProof, User, UserId,
+    TimestampMillis, UniquePersonProof, User, UserId, UserQuietHours,
 };
 
 mod lifecycle;
@@ -30,6 +30,7 @@ pub enum LocalUserIndexEvent {
     BotUninstalled(Box<BotUninstalled>),
     UserBlocked(UserId, UserId),
     UserUnblocked(UserId, UserId),
+    UserQuietHoursSet(UserId, Option<UserQuietHours>),
     SetMaxStreak(UserId, u16),
     NotifyOfUserDeleted(CanisterId, UserId),
     MediaScanStalled(Box<MediaScanStalled>),
@@ -3,6 +3,7 @@ use std::env;
 use ts_export::generate_ts_method;
 
 fn main() {
+    generate_candid_method!(user_index, bot_reviews, query);
     generate_candid_method!(user_index, bot_updates, query);
     generate_candid_method!(user_index, check_username, query);
     generate_candid_method!(user_index, chit_leaderboard, query);
@@ -24,7 +25,9 @@ fn main() {
     generate_candid_method!(user_index, award_external_achievement, update);
     generate_candid_method!(user_index, register_external_achievement, update);
     generate_candid_method!(user_index, publish_bot, update);
+    generate_candid_method!(user_index, rate_bot, update);
     generate_candid_method!(user_index, remove_bot, update);
+    generate_candid_method!(user_index, report_bot, update);
     generate_candid_method!(user_index, update_bot, update);
 
     candid::export_service!();
@@ -36,6 +39,8 @@ fn main() {
     }
 
     generate_ts_method!(user_index, bot_installation_events);
+    generate_ts_method!(user_index, bot_reports);
+    generate_ts_method!(user_index, bot_reviews);
     generate_ts_method!(user_index, bot_updates);
     generate_ts_method!(user_index, check_username);
     generate_ts_method!(user_index, chit_leaderboard);
@@ -59,8 +64,10 @@ fn main() {
     generate_ts_method!(user_index, update_diamond_membership_subscription);
 
     generate_ts_method!(user_index, pay_for_diamond_membership);
+    generate_ts_method!(user_index, rate_bot);
     generate_ts_method!(user_index, register_bot);
     generate_ts_method!(user_index, remove_bot);
+    generate_ts_method!(user_index, report_bot);
     generate_ts_method!(user_index, set_diamond_membership_fees);
     generate_ts_method!(user_index, set_display_name);
     generate_ts_method!(user_index, set_hide_online_status);
@@ -85,7 +92,9 @@ fn main() {
     generate_ts_method!(user_index, contest_moderation_sanction);
     generate_ts_method!(user_index, record_authority_report_filed);
     generate_ts_method!(user_index, resolve_moderation_report);
+    generate_ts_method!(user_index, resolve_bot_reports);
     generate_ts_method!(user_index, accept_terms);
     generate_ts_method!(user_index, set_moderation_referral_config);
     generate_ts_method!(user_index, set_vault_legal_hold);
+    generate_ts_method!(user_index, set_translation_provider_config);
 }
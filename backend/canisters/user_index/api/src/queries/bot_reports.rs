@@ -0,0 +1,37 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{Empty, TimestampMillis, UserId};
+
+pub type Args = Empty;
+
+#[ts_export(user_index, bot_reports)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+}
+
+#[ts_export(user_index, bot_reports)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    // Bots with unresolved reports, most reported first
+    pub bots: Vec<ReportedBot>,
+}
+
+#[ts_export(user_index, bot_reports)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct ReportedBot {
+    pub bot_id: UserId,
+    pub name: String,
+    pub owner: UserId,
+    // Most recent first
+    pub reports: Vec<BotReport>,
+}
+
+#[ts_export(user_index, bot_reports)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct BotReport {
+    pub reporter: UserId,
+    pub reason: String,
+    pub timestamp: TimestampMillis,
+}
@@ -0,0 +1,29 @@
+use candid::CandidType;
+use oc_error_codes::OCError;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{BotReview, UserId};
+
+#[ts_export(user_index, bot_reviews)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub page_index: u32,
+    pub page_size: u8,
+}
+
+#[ts_export(user_index, bot_reviews)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(SuccessResult),
+    Error(OCError),
+}
+
+#[ts_export(user_index, bot_reviews)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct SuccessResult {
+    // Most recent first
+    pub reviews: Vec<BotReview>,
+    pub total: u32,
+    pub average_rating: Option<f32>,
+}
@@ -2,7 +2,7 @@ use candid::CandidType;
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::{BotInstallationLocation, BotMatch};
+use types::{BotCategory, BotInstallationLocation, BotMatch};
 
 #[ts_export(user_index, explore_bots)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
@@ -12,6 +12,19 @@ pub struct Args {
     pub page_size: u8,
     pub installation_location: Option<BotInstallationLocation>,
     pub exclude_installed: bool,
+    // If not specified, bots are sorted by relevance to the search term, or by popularity if there is no search term
+    #[serde(default)]
+    pub sort: Option<BotSortOrder>,
+    #[serde(default)]
+    pub category: Option<BotCategory>,
+}
+
+#[ts_export(user_index, explore_bots)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BotSortOrder {
+    Popular,
+    Newest,
+    TopRated,
 }
 
 #[ts_export(user_index, explore_bots)]
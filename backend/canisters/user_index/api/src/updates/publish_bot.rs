@@ -3,12 +3,16 @@ use human_readable::{HumanReadablePrincipal, ToHumanReadable};
 use oc_error_codes::OCError;
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
-use types::UserId;
+use types::{BotCategory, UserId};
 
 #[ts_export(user_index, register_bot)]
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct Args {
     pub bot_id: UserId,
+    #[serde(default)]
+    pub categories: Option<Vec<BotCategory>>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
 }
 
 #[ts_export(user_index, register_bot)]
@@ -23,6 +27,8 @@ pub enum Response {
 #[derive(Serialize)]
 pub struct HumanReadableArgs {
     bot_id: HumanReadablePrincipal,
+    categories: Vec<BotCategory>,
+    tags: Vec<String>,
 }
 
 impl ToHumanReadable for Args {
@@ -31,6 +37,8 @@ impl ToHumanReadable for Args {
     fn to_human_readable(&self) -> Self::Target {
         HumanReadableArgs {
             bot_id: Principal::from(self.bot_id).into(),
+            categories: self.categories.clone().unwrap_or_default(),
+            tags: self.tags.clone().unwrap_or_default(),
         }
     }
 }
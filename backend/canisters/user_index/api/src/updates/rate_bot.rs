@@ -0,0 +1,15 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{UnitResult, UserId};
+
+#[ts_export(user_index, rate_bot)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    // From 1 to 5
+    pub rating: u8,
+    pub review: Option<String>,
+}
+
+pub type Response = UnitResult;
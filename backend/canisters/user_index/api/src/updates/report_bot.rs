@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{UnitResult, UserId};
+
+#[ts_export(user_index, report_bot)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+    pub reason: String,
+}
+
+pub type Response = UnitResult;
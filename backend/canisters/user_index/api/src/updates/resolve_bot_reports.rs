@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{UnitResult, UserId};
+
+#[ts_export(user_index, resolve_bot_reports)]
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub bot_id: UserId,
+}
+
+pub type Response = UnitResult;
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+use types::{TranslationProviderConfig, UnitResult};
+
+#[ts_export(user_index, set_translation_provider_config)]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Args {
+    pub config: Option<TranslationProviderConfig>,
+}
+
+pub type Response = UnitResult;
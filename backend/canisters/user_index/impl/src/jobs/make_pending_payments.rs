@@ -81,6 +81,7 @@ fn inform_referrer(pending_payment: &PendingPayment, block_index: BlockIndex, st
         text: format!(
             "You have received a referral reward of {amount_text}. This is because one of the users you referred has made a Diamond membership payment."
         ),
+        components: None,
     });
 
     state.push_event_to_local_user_index(
@@ -34,7 +34,8 @@ use std::time::Duration;
 use timer_job_queues::BatchedTimerJobQueue;
 use types::{
     BuildVersion, CanisterId, ChannelId, ChatId, ChildCanisterWasms, CommunityId, Cycles, DiamondMembershipFees,
-    MediaScanConfig, Milliseconds, ModerationReferralConfig, TimestampMillis, Timestamped, UserId, UserType,
+    MediaScanConfig, Milliseconds, ModerationReferralConfig, TimestampMillis, Timestamped, TranslationProviderConfig, UserId,
+    UserType,
 };
 use user_ids_set::UserIdsSet;
 use user_index_canister::ChildCanisterType;
@@ -280,6 +281,7 @@ impl RuntimeState {
             moderation_referral_config_set: self.data.moderation_referral_config.is_some(),
             media_scanning_enabled: self.data.media_scan_config.enabled,
             media_scanners: self.data.media_scan_config.scanners.len() as u32,
+            translation_provider_set: self.data.translation_provider_config.is_some(),
             moderation_referral_categories: self
                 .data
                 .moderation_referral_config
@@ -426,6 +428,8 @@ struct Data {
     #[serde(default)]
     pub media_scan_config: MediaScanConfig,
     #[serde(default)]
+    pub translation_provider_config: Option<TranslationProviderConfig>,
+    #[serde(default)]
     pub internal_moderation_channel: Option<(CommunityId, ChannelId)>,
     // Per-(report, uploader) notice throttle for blocked re-post attempts:
     // (last posted, suppressed count)
@@ -520,6 +524,7 @@ impl Data {
             openai_api_key: None,
             moderation_referral_config: None,
             media_scan_config: MediaScanConfig::default(),
+            translation_provider_config: None,
             internal_moderation_channel: None,
             blocked_attempt_notice_throttle: HashMap::new(),
         };
@@ -641,6 +646,7 @@ impl Default for Data {
             openai_api_key: None,
             moderation_referral_config: None,
             media_scan_config: MediaScanConfig::default(),
+            translation_provider_config: None,
             internal_moderation_channel: None,
             blocked_attempt_notice_throttle: HashMap::new(),
         }
@@ -686,6 +692,7 @@ pub struct Metrics {
     pub moderation_referral_categories: u32,
     pub media_scanning_enabled: bool,
     pub media_scanners: u32,
+    pub translation_provider_set: bool,
     pub oc_public_key: String,
     pub empty_users: usize,
     pub deleted_users: usize,
@@ -127,7 +127,10 @@ pub fn notify_other_platform_operators(text: String, state: &mut RuntimeState) {
             UserIndexEvent::OpenChatBotMessageV2(Box::new(OpenChatBotMessageV2 {
                 user_id,
                 thread_root_message_id: None,
-                content: MessageContentInitial::Text(TextContent { text: text.clone() }),
+                content: MessageContentInitial::Text(TextContent {
+                    text: text.clone(),
+                    components: None,
+                }),
                 mentioned: Vec::new(),
             })),
         );
@@ -1042,7 +1042,7 @@ fn build_oc_bot_message(text: String, user_id: UserId) -> UserIndexEvent {
     UserIndexEvent::OpenChatBotMessageV2(Box::new(OpenChatBotMessageV2 {
         user_id,
         thread_root_message_id: None,
-        content: MessageContentInitial::Text(TextContent { text }),
+        content: MessageContentInitial::Text(TextContent { text, components: None }),
         mentioned: Vec::new(),
     }))
 }
@@ -5,14 +5,16 @@ use crate::model::user::User;
 use candid::Principal;
 use search::weighted::{Document as SearchDocument, Query};
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ops::RangeFrom;
 use tracing::info;
 use types::{
-    BotDefinition, BotInstallationLocation, BotMatch, BotPermissions, BotRegistrationStatus, CanisterId, CyclesTopUp, Document,
-    Milliseconds, OptionUpdate, SuspensionDuration, TimestampMillis, UniquePersonProof, UserId, UserType,
+    BotCategory, BotDefinition, BotInstallationLocation, BotMatch, BotPermissions, BotRegistrationStatus, BotReview,
+    CanisterId, CyclesTopUp, Document, Milliseconds, OptionUpdate, SuspensionDuration, TimestampMillis, UniquePersonProof,
+    UserId, UserType,
 };
 use user_index_canister::bot_updates::BotDetails;
+use user_index_canister::explore_bots::{self, BotSortOrder};
 use utils::case_insensitive_hash_map::CaseInsensitiveHashMap;
 use utils::time::MonthKey;
 
@@ -53,6 +55,34 @@ pub struct Bot {
     #[serde(default)]
     pub installation_events: Vec<BotInstallationEvent>,
     pub registration_status: BotRegistrationStatus,
+    #[serde(default)]
+    pub published_at: Option<TimestampMillis>,
+    #[serde(default)]
+    pub categories: Vec<BotCategory>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub reviews: HashMap<UserId, BotReviewInternal>,
+    #[serde(default)]
+    pub reports: HashMap<UserId, BotReportInternal>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BotReviewInternal {
+    #[serde(rename = "r")]
+    pub rating: u8,
+    #[serde(rename = "t", default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(rename = "ts")]
+    pub timestamp: TimestampMillis,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BotReportInternal {
+    #[serde(rename = "r")]
+    pub reason: String,
+    #[serde(rename = "ts")]
+    pub timestamp: TimestampMillis,
 }
 
 impl Bot {
@@ -67,7 +97,65 @@ impl Bot {
             avatar_id: self.avatar.as_ref().map(|a| a.id),
             commands: self.definition.commands.clone(),
             autonomous_config: self.definition.autonomous_config.clone(),
+            categories: self.categories.clone(),
+            tags: self.tags.clone(),
+            install_count: self.installations.len() as u32,
+            lifetime_install_count: self.lifetime_install_count(),
+            average_rating: self.average_rating(),
+            rating_count: self.reviews.len() as u32,
+            published_at: self.published_at,
+        }
+    }
+
+    // Each location is counted once per installation, ignoring re-installs which only updated the
+    // granted permissions
+    pub fn lifetime_install_count(&self) -> u32 {
+        let mut installed = HashSet::new();
+        let mut count = 0;
+        for event in self.installation_events.iter() {
+            match event {
+                BotInstallationEvent::Installed(e) => {
+                    if installed.insert(e.location) {
+                        count += 1;
+                    }
+                }
+                BotInstallationEvent::Uninstalled(e) => {
+                    installed.remove(&e.location);
+                }
+            }
         }
+        count
+    }
+
+    pub fn average_rating(&self) -> Option<f32> {
+        if self.reviews.is_empty() {
+            None
+        } else {
+            let total: u32 = self.reviews.values().map(|r| r.rating as u32).sum();
+            Some(total as f32 / self.reviews.len() as f32)
+        }
+    }
+
+    pub fn reviews(&self) -> Vec<BotReview> {
+        let mut reviews: Vec<_> = self
+            .reviews
+            .iter()
+            .map(|(user_id, r)| BotReview {
+                reviewer: *user_id,
+                rating: r.rating,
+                text: r.text.clone(),
+                timestamp: r.timestamp,
+            })
+            .collect();
+
+        reviews.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+        reviews
+    }
+
+    pub fn has_been_installed_by(&self, user_id: UserId) -> bool {
+        self.installation_events
+            .iter()
+            .any(|e| matches!(e, BotInstallationEvent::Installed(i) if i.installed_by == user_id))
     }
 
     pub fn add_installation(
@@ -425,19 +513,83 @@ impl UserMap {
         }
     }
 
-    pub fn publish_bot(&mut self, bot_id: UserId, now: TimestampMillis) -> bool {
+    pub fn publish_bot(
+        &mut self,
+        bot_id: UserId,
+        categories: Option<Vec<BotCategory>>,
+        tags: Option<Vec<String>>,
+        now: TimestampMillis,
+    ) -> bool {
         let Some(bot) = self.bots.get_mut(&bot_id) else {
             return false;
         };
 
         if !matches!(bot.registration_status, BotRegistrationStatus::Public) {
             bot.registration_status = BotRegistrationStatus::Public;
+            bot.published_at.get_or_insert(now);
             self.bot_updates.insert((now, BotUpdate::Updated(bot_id)));
         }
 
+        if let Some(categories) = categories {
+            bot.categories = categories;
+        }
+        if let Some(tags) = tags {
+            bot.tags = tags;
+        }
+
+        true
+    }
+
+    // Adds the user's review of the bot, replacing any previous review from the same user
+    pub fn rate_bot(
+        &mut self,
+        bot_id: UserId,
+        reviewer: UserId,
+        rating: u8,
+        text: Option<String>,
+        now: TimestampMillis,
+    ) -> bool {
+        let Some(bot) = self.bots.get_mut(&bot_id) else {
+            return false;
+        };
+
+        bot.reviews.insert(
+            reviewer,
+            BotReviewInternal {
+                rating,
+                text,
+                timestamp: now,
+            },
+        );
         true
     }
 
+    // Returns false if the user has already reported the bot
+    pub fn report_bot(&mut self, bot_id: UserId, reporter: UserId, reason: String, now: TimestampMillis) -> bool {
+        let Some(bot) = self.bots.get_mut(&bot_id) else {
+            return false;
+        };
+
+        if bot.reports.contains_key(&reporter) {
+            false
+        } else {
+            bot.reports.insert(reporter, BotReportInternal { reason, timestamp: now });
+            true
+        }
+    }
+
+    pub fn reported_bots(&self) -> impl Iterator<Item = (&UserId, &Bot)> {
+        self.bots.iter().filter(|(_, bot)| !bot.reports.is_empty())
+    }
+
+    // Clears the bot's reports, returning how many were resolved
+    pub fn resolve_bot_reports(&mut self, bot_id: UserId) -> usize {
+        self.bots
+            .get_mut(&bot_id)
+            .map(|bot| std::mem::take(&mut bot.reports).len())
+            .unwrap_or_default()
+    }
+
     pub fn get(&self, user_id_or_principal: &Principal) -> Option<&User> {
         let user_id = self
             .principal_to_user_id
@@ -883,15 +1035,16 @@ impl UserMap {
         self.unique_person_proofs_submitted
     }
 
-    pub fn search_bots(
-        &self,
-        search_term: Option<String>,
-        page_index: u32,
-        page_size: u8,
-        caller: Option<UserId>,
-        installation_location: Option<BotInstallationLocation>,
-        exclude_installed: bool,
-    ) -> (Vec<BotMatch>, u32) {
+    pub fn search_bots(&self, args: explore_bots::Args, caller: Option<UserId>) -> (Vec<BotMatch>, u32) {
+        let explore_bots::Args {
+            search_term,
+            page_index,
+            page_size,
+            installation_location,
+            exclude_installed,
+            sort,
+            category,
+        } = args;
         let query = search_term.map(Query::parse);
 
         let mut matches: Vec<_> = self
@@ -917,12 +1070,17 @@ impl UserMap {
                     None => false,
                 },
             })
+            .filter(|(_, bot)| category.is_none_or(|c| bot.categories.contains(&c)))
             .map(|(user_id, bot)| {
                 let score = if let Some(query) = &query {
-                    SearchDocument::default()
+                    let mut document = SearchDocument::default();
+                    document
                         .add_field(bot.name.clone(), 5.0, true)
-                        .add_field(bot.definition.description.clone(), 1.0, true)
-                        .calculate_score(query)
+                        .add_field(bot.definition.description.clone(), 1.0, true);
+                    for tag in bot.tags.iter() {
+                        document.add_field(tag.clone(), 2.0, true);
+                    }
+                    document.calculate_score(query)
                 } else {
                     (bot.installations.len() + 1) as u32
                 };
@@ -932,12 +1090,23 @@ impl UserMap {
 
         let total = matches.len() as u32;
 
-        matches.sort_by_key(|(score, _, _)| *score);
+        matches.retain(|&(s, _, _)| s > 0);
+
+        match sort {
+            None => matches.sort_by_key(|(score, _, _)| *score),
+            Some(BotSortOrder::Popular) => matches.sort_by_key(|(score, _, bot)| (bot.installations.len(), *score)),
+            Some(BotSortOrder::Newest) => {
+                matches.sort_by_key(|(score, _, bot)| (bot.published_at.unwrap_or(bot.last_updated), *score))
+            }
+            // Average ratings are never negative, so ordering by their bits orders them numerically
+            Some(BotSortOrder::TopRated) => matches.sort_by_cached_key(|(score, _, bot)| {
+                (bot.average_rating().unwrap_or_default().to_bits(), bot.reviews.len(), *score)
+            }),
+        }
 
         let matches = matches
             .into_iter()
             .rev()
-            .filter(|&(s, _, _)| s > 0)
             .map(|(s, id, b)| b.to_match(*id, s))
             .skip(page_index as usize * page_size as usize)
             .take(page_size as usize)
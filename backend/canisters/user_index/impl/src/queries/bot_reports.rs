@@ -0,0 +1,40 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use user_index_canister::bot_reports::{Response::*, *};
+
+#[query(guard = "caller_is_platform_moderator", msgpack = true)]
+fn bot_reports(_args: Args) -> Response {
+    read_state(bot_reports_impl)
+}
+
+fn bot_reports_impl(state: &RuntimeState) -> Response {
+    let mut bots: Vec<_> = state
+        .data
+        .users
+        .reported_bots()
+        .map(|(bot_id, bot)| {
+            let mut reports: Vec<_> = bot
+                .reports
+                .iter()
+                .map(|(reporter, report)| BotReport {
+                    reporter: *reporter,
+                    reason: report.reason.clone(),
+                    timestamp: report.timestamp,
+                })
+                .collect();
+            reports.sort_unstable_by_key(|r| std::cmp::Reverse(r.timestamp));
+
+            ReportedBot {
+                bot_id: *bot_id,
+                name: bot.name.clone(),
+                owner: bot.owner,
+                reports,
+            }
+        })
+        .collect();
+
+    bots.sort_by_key(|b| std::cmp::Reverse(b.reports.len()));
+
+    Success(SuccessResult { bots })
+}
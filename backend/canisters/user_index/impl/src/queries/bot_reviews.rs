@@ -0,0 +1,28 @@
+use crate::{RuntimeState, read_state};
+use canister_api_macros::query;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+use user_index_canister::bot_reviews::*;
+
+#[query(candid = true, msgpack = true)]
+fn bot_reviews(args: Args) -> Response {
+    match read_state(|state| bot_reviews_impl(args, state)) {
+        Ok(result) => Response::Success(result),
+        Err(error) => Response::Error(error),
+    }
+}
+
+fn bot_reviews_impl(args: Args, state: &RuntimeState) -> OCResult<SuccessResult> {
+    let bot = state.data.users.get_bot(&args.bot_id).ok_or(OCErrorCode::BotNotFound)?;
+    let reviews = bot.reviews();
+
+    Ok(SuccessResult {
+        total: reviews.len() as u32,
+        average_rating: bot.average_rating(),
+        reviews: reviews
+            .into_iter()
+            .skip(args.page_index as usize * args.page_size as usize)
+            .take(args.page_size as usize)
+            .collect(),
+    })
+}
@@ -24,14 +24,7 @@ fn explore_bots_impl(args: Args, state: &RuntimeState) -> Response {
         }
     }
 
-    let (matches, total) = state.data.users.search_bots(
-        args.search_term,
-        args.page_index,
-        args.page_size,
-        user_id,
-        args.installation_location,
-        args.exclude_installed,
-    );
+    let (matches, total) = state.data.users.search_bots(args, user_id);
 
     Success(SuccessResult { matches, total })
 }
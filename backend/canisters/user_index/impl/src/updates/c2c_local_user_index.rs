@@ -201,6 +201,9 @@ fn handle_event<F: FnOnce() -> TimestampMillis>(
             state.data.blocked_users.remove(&(unblocked, user_id));
             state.push_event_to_all_local_user_indexes(UserIndexEvent::UserUnblocked(user_id, unblocked), Some(caller));
         }
+        LocalUserIndexEvent::UserQuietHoursSet(user_id, quiet_hours) => {
+            state.push_event_to_all_local_user_indexes(UserIndexEvent::UserQuietHoursSet(user_id, quiet_hours), Some(caller));
+        }
         LocalUserIndexEvent::SetMaxStreak(user_id, max_streak) => state.data.users.set_max_streak(&user_id, max_streak),
     }
 }
@@ -276,7 +279,8 @@ fn process_new_user(
                 content: MessageContentInitial::Text(TextContent {
                     text: format!("Unfortunately the username \"{original_username}\" was taken so your username has been changed to \"{username}\".
 
-You can change your username at any time by clicking \"Profile settings\" from the main menu.")
+You can change your username at any time by clicking \"Profile settings\" from the main menu."),
+                    components: None,
                 }),
                 mentioned: Vec::new(),
             })),
@@ -18,7 +18,10 @@ fn c2c_send_openchat_bot_messages_impl(args: Args, state: &mut RuntimeState) ->
             UserIndexEvent::OpenChatBotMessageV2(Box::new(OpenChatBotMessageV2 {
                 user_id: message.recipient,
                 thread_root_message_id: None,
-                content: MessageContentInitial::Text(TextContent { text: message.text }),
+                content: MessageContentInitial::Text(TextContent {
+                    text: message.text,
+                    components: None,
+                }),
                 mentioned: Vec::new(),
             })),
         );
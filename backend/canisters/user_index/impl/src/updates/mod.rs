@@ -23,12 +23,15 @@ pub mod mark_suspected_bot;
 pub mod pay_for_diamond_membership;
 mod propose_protected_action;
 pub mod publish_bot;
+pub mod rate_bot;
 pub mod record_authority_report_filed;
 pub mod register_bot;
 pub mod register_external_achievement;
 mod remove_bot;
 pub mod remove_platform_moderator;
 pub mod remove_platform_operator;
+pub mod report_bot;
+pub mod resolve_bot_reports;
 pub mod resolve_moderation_report;
 pub mod set_diamond_membership_fees;
 pub mod set_display_name;
@@ -40,6 +43,7 @@ pub mod set_moderation_flags;
 pub mod set_moderation_referral_config;
 pub(crate) mod set_openai_api_key;
 pub mod set_premium_item_cost;
+mod set_translation_provider_config;
 pub mod set_user_upgrade_concurrency;
 pub mod set_username;
 mod set_vault_legal_hold;
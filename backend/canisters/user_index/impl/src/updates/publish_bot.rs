@@ -3,8 +3,13 @@ use crate::{RuntimeState, mutate_state};
 use canister_api_macros::{proposal, update};
 use canister_tracing_macros::trace;
 use local_user_index_canister::{BotPublished, UserIndexEvent};
+use oc_error_codes::OCErrorCode;
+use std::collections::HashSet;
 use user_index_canister::publish_bot::{Args, Response};
 
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LENGTH: usize = 25;
+
 #[proposal(guard = "caller_is_governance_principal")]
 #[trace]
 fn publish_bot(args: Args) -> Response {
@@ -22,7 +27,16 @@ fn publish_bot(args: Args) -> Response {
 }
 
 fn publish_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
-    if !state.data.users.publish_bot(args.bot_id, state.env.now()) {
+    let tags = match args.tags.map(validate_tags).transpose() {
+        Ok(tags) => tags,
+        Err(message) => return Response::Error(OCErrorCode::InvalidRequest.with_message(message)),
+    };
+    let categories = args.categories.map(|c| {
+        let mut seen = HashSet::new();
+        c.into_iter().filter(|c| seen.insert(*c)).collect()
+    });
+
+    if !state.data.users.publish_bot(args.bot_id, categories, tags, state.env.now()) {
         return Response::NotFound;
     }
 
@@ -30,3 +44,24 @@ fn publish_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
 
     Response::Success
 }
+
+// Tags are trimmed, lowercased and deduplicated
+fn validate_tags(tags: Vec<String>) -> Result<Vec<String>, String> {
+    let mut validated: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Err("Tags cannot be empty".to_string());
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(format!("Tags cannot be longer than {MAX_TAG_LENGTH} characters"));
+        }
+        if !validated.contains(&tag) {
+            validated.push(tag);
+        }
+    }
+    if validated.len() > MAX_TAGS {
+        return Err(format!("Bots cannot have more than {MAX_TAGS} tags"));
+    }
+    Ok(validated)
+}
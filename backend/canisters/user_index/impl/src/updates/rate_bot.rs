@@ -0,0 +1,54 @@
+use crate::guards::caller_is_openchat_user;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::{BotRegistrationStatus, FieldTooLongResult, OCResult};
+use user_index_canister::rate_bot::*;
+
+const MAX_REVIEW_LENGTH: u32 = 1000;
+
+#[update(guard = "caller_is_openchat_user", candid = true, msgpack = true)]
+#[trace]
+fn rate_bot(args: Args) -> Response {
+    mutate_state(|state| rate_bot_impl(args, state)).into()
+}
+
+fn rate_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let user = state
+        .data
+        .users
+        .get_by_principal(&caller)
+        .ok_or(OCErrorCode::InitiatorNotFound)?;
+    if user.suspension_details.is_some() {
+        return Err(OCErrorCode::InitiatorSuspended.into());
+    }
+    let reviewer = user.user_id;
+
+    let bot = state.data.users.get_bot(&args.bot_id).ok_or(OCErrorCode::BotNotFound)?;
+    if !matches!(bot.registration_status, BotRegistrationStatus::Public) {
+        return Err(OCErrorCode::InvalidRequest.with_message("Bot has not been published"));
+    }
+    // Only users who have installed the bot somewhere can review it, and owners can't review their own bots
+    if bot.owner == reviewer || !bot.has_been_installed_by(reviewer) {
+        return Err(OCErrorCode::InitiatorNotAuthorized.into());
+    }
+    if !(1..=5).contains(&args.rating) {
+        return Err(OCErrorCode::InvalidRequest.with_message("Rating must be between 1 and 5"));
+    }
+
+    let review = args.review.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if let Some(length_provided) = review.as_ref().map(|r| r.chars().count() as u32)
+        && length_provided > MAX_REVIEW_LENGTH
+    {
+        return Err(OCErrorCode::TextTooLong.with_json(&FieldTooLongResult {
+            length_provided,
+            max_length: MAX_REVIEW_LENGTH,
+        }));
+    }
+
+    let now = state.env.now();
+    state.data.users.rate_bot(args.bot_id, reviewer, args.rating, review, now);
+    Ok(())
+}
@@ -75,6 +75,11 @@ fn register_bot_impl(args: Args, state: &mut RuntimeState) -> Response {
             installations: HashMap::new(),
             installation_events: Vec::new(),
             registration_status: BotRegistrationStatus::Private(args.permitted_install_location),
+            published_at: None,
+            categories: Vec::new(),
+            tags: Vec::new(),
+            reviews: HashMap::new(),
+            reports: HashMap::new(),
         }),
     );
 
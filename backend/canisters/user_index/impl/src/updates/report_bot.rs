@@ -0,0 +1,58 @@
+use crate::guards::caller_is_openchat_user;
+use crate::model::moderation;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::{FieldTooLongResult, OCResult};
+use user_index_canister::report_bot::*;
+
+const MAX_REASON_LENGTH: u32 = 1000;
+
+#[update(guard = "caller_is_openchat_user", candid = true, msgpack = true)]
+#[trace]
+fn report_bot(args: Args) -> Response {
+    mutate_state(|state| report_bot_impl(args, state)).into()
+}
+
+// Records the report against the bot and raises it with the platform moderators in the internal
+// moderation channel. The moderators can list outstanding reports via `bot_reports`, remove the
+// bot or sanction its owner, and then mark the reports as dealt with via `resolve_bot_reports`
+fn report_bot_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let user = state
+        .data
+        .users
+        .get_by_principal(&caller)
+        .ok_or(OCErrorCode::InitiatorNotFound)?;
+    if user.suspension_details.is_some() {
+        return Err(OCErrorCode::InitiatorSuspended.into());
+    }
+    let reporter = user.user_id;
+
+    let reason = args.reason.trim();
+    if reason.is_empty() {
+        return Err(OCErrorCode::InvalidRequest.with_message("A reason must be given"));
+    }
+    let length_provided = reason.chars().count() as u32;
+    if length_provided > MAX_REASON_LENGTH {
+        return Err(OCErrorCode::TextTooLong.with_json(&FieldTooLongResult {
+            length_provided,
+            max_length: MAX_REASON_LENGTH,
+        }));
+    }
+
+    let bot = state.data.users.get_bot(&args.bot_id).ok_or(OCErrorCode::BotNotFound)?;
+    let text = format!(
+        "Bot \"{}\" ({}), owned by user {}, was reported by user {}.\n\nReason: {reason}",
+        bot.name, args.bot_id, bot.owner, reporter
+    );
+
+    let now = state.env.now();
+    if !state.data.users.report_bot(args.bot_id, reporter, reason.to_string(), now) {
+        return Err(OCErrorCode::AlreadyReported.into());
+    }
+
+    moderation::post_moderation_notice(text, state);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::guards::caller_is_platform_moderator;
+use crate::model::moderation;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use oc_error_codes::OCErrorCode;
+use types::OCResult;
+use user_index_canister::resolve_bot_reports::*;
+
+#[update(guard = "caller_is_platform_moderator", msgpack = true)]
+#[trace]
+fn resolve_bot_reports(args: Args) -> Response {
+    mutate_state(|state| resolve_bot_reports_impl(args, state)).into()
+}
+
+// Marks the bot's reports as dealt with once the moderators have reviewed them (and taken any
+// action against the bot or its owner), so that the bot drops out of `bot_reports`. Users can
+// report the bot again if it continues to misbehave.
+fn resolve_bot_reports_impl(args: Args, state: &mut RuntimeState) -> OCResult {
+    let caller = state.env.caller();
+    let moderator = state
+        .data
+        .users
+        .get_by_principal(&caller)
+        .map(|u| u.user_id)
+        .ok_or(OCErrorCode::InitiatorNotFound)?;
+
+    let bot = state.data.users.get_bot(&args.bot_id).ok_or(OCErrorCode::BotNotFound)?;
+    if bot.owner == moderator {
+        return Err(OCErrorCode::InitiatorNotAuthorized.with_message("Cannot resolve reports against your own bot"));
+    }
+    let bot_name = bot.name.clone();
+
+    let resolved = state.data.users.resolve_bot_reports(args.bot_id);
+    if resolved == 0 {
+        return Err(OCErrorCode::NoChange.with_message("Bot has no unresolved reports"));
+    }
+
+    moderation::post_moderation_notice(
+        format!(
+            "{resolved} report(s) against bot \"{bot_name}\" ({}) were resolved by moderator {moderator}.",
+            args.bot_id
+        ),
+        state,
+    );
+    Ok(())
+}
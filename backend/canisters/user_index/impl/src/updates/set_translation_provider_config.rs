@@ -0,0 +1,32 @@
+use crate::guards::caller_is_platform_operator;
+use crate::{RuntimeState, mutate_state};
+use canister_api_macros::update;
+use canister_tracing_macros::trace;
+use local_user_index_canister::{SetTranslationProviderConfig, UserIndexEvent};
+use oc_error_codes::OCErrorCode;
+use types::TranslationProvider;
+use user_index_canister::set_translation_provider_config::*;
+
+#[update(guard = "caller_is_platform_operator", msgpack = true)]
+#[trace]
+fn set_translation_provider_config(args: Args) -> Response {
+    mutate_state(|state| set_translation_provider_config_impl(args, state))
+}
+
+fn set_translation_provider_config_impl(args: Args, state: &mut RuntimeState) -> Response {
+    if let Some(config) = args.config.as_ref()
+        && let TranslationProvider::LibreTranslate(url) = &config.provider
+        && !url.starts_with("https://")
+    {
+        return Response::Error(OCErrorCode::InvalidRequest.with_message("LibreTranslate URL must use HTTPS"));
+    }
+
+    state.data.translation_provider_config = args.config.clone();
+
+    state.push_event_to_all_local_user_indexes(
+        UserIndexEvent::SetTranslationProviderConfig(SetTranslationProviderConfig { config: args.config }),
+        None,
+    );
+
+    Response::Success
+}
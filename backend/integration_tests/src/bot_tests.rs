@@ -15,12 +15,14 @@ use std::time::Duration;
 use test_case::test_case;
 use testing::rng::{random_from_u128, random_string};
 use types::{
-    AutonomousConfig, BotActionChatDetails, BotActionScope, BotChatContext, BotCommandArg, BotCommandArgValue,
-    BotCommandDefinition, BotCommandParam, BotCommandParamType, BotDefinition, BotInstallationLocation, BotMessageContent,
-    BotPermissions, BotSubscriptions, CanisterId, Chat, ChatEvent, ChatEventType, ChatPermission, ChatType, CommunityEventType,
-    CommunityPermission, EventIndex, MessageContent, MessageId, MessagePermission, NotificationEnvelope, OptionUpdate, Rules,
-    StringParam, TextContent, UpdatedRules, UserId,
+    AutonomousConfig, BotActionChatDetails, BotActionScope, BotChannelPermissions, BotChatContext, BotCommandArg,
+    BotCommandArgValue, BotCommandDefinition, BotCommandParam, BotCommandParamType, BotDefinition, BotEvent,
+    BotInstallationLocation, BotMessageContent, BotPermissions, BotSubscriptions, CanisterId, Chat, ChatEvent, ChatEventType,
+    ChatPermission, ChatType, CommunityEventType, CommunityPermission, Empty, EventIndex, MessageButton, MessageButtonStyle,
+    MessageComponent, MessageContent, MessageId, MessagePermission, MessageSelect, MessageSelectOption, NotificationEnvelope,
+    OptionUpdate, Rules, StringParam, TextContent, TimestampMillis, UnitResult, UpdatedRules, UserId,
 };
+use user_index_canister::explore_bots::BotSortOrder;
 
 #[test]
 fn e2e_command_bot_test() {
@@ -143,7 +145,10 @@ fn e2e_command_bot_test() {
             thread: None,
             message_id: None,
             replies_to: None,
-            content: BotMessageContent::Text(TextContent { text: text.clone() }),
+            content: BotMessageContent::Text(TextContent {
+                text: text.clone(),
+                components: None,
+            }),
             block_level_markdown: false,
             finalised: false,
             og_previews: None,
@@ -181,7 +186,10 @@ fn e2e_command_bot_test() {
             thread: None,
             message_id: None,
             replies_to: None,
-            content: BotMessageContent::Text(TextContent { text: text.clone() }),
+            content: BotMessageContent::Text(TextContent {
+                text: text.clone(),
+                components: None,
+            }),
             block_level_markdown: false,
             finalised: true,
             og_previews: None,
@@ -334,7 +342,10 @@ fn e2e_autonomous_bot_test() {
             thread: None,
             message_id: None,
             replies_to: None,
-            content: BotMessageContent::Text(TextContent { text: text.clone() }),
+            content: BotMessageContent::Text(TextContent {
+                text: text.clone(),
+                components: None,
+            }),
             block_level_markdown: false,
             finalised: true,
             og_previews: None,
@@ -444,6 +455,7 @@ fn create_channel_autonomously() {
             replies_to: None,
             content: BotMessageContent::Text(TextContent {
                 text: "I'm sorry, Dave. I'm afraid I can't do that.".to_string(),
+                components: None,
             }),
             block_level_markdown: false,
             finalised: true,
@@ -540,6 +552,105 @@ fn read_messages_autonomously(authorized: bool) {
     }
 }
 
+#[test]
+fn bot_restricted_to_channel_cannot_access_other_channels() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let community_id =
+        client::user::happy_path::create_community(env, &owner, &random_string(), true, vec!["General".to_string()]);
+    let permitted_channel_id =
+        client::community::happy_path::create_channel(env, owner.principal, community_id, true, random_string());
+    let other_channel_id =
+        client::community::happy_path::create_channel(env, owner.principal, community_id, true, random_string());
+
+    // Register a bot
+    let bot_name = random_string();
+    let command_name = random_string();
+    let (bot_id, bot_principal) = register_bot(env, &owner, canister_ids.user_index, bot_name.clone(), command_name.clone());
+
+    // Install the bot, restricting it to a single channel
+    let read_messages = BotPermissions::default().with_chat(&HashSet::from_iter([ChatPermission::ReadMessages]));
+    let local_user_index = canister_ids.local_user_index(env, community_id);
+    let response = client::local_user_index::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        &local_user_index_canister::install_bot::Args {
+            location: BotInstallationLocation::Community(community_id),
+            bot_id,
+            granted_permissions: BotPermissions::text_only(),
+            granted_autonomous_permissions: Some(read_messages.clone()),
+            channel_permissions: Some(vec![BotChannelPermissions {
+                channel_id: permitted_channel_id,
+                permissions: BotPermissions::text_only(),
+                autonomous_permissions: Some(read_messages),
+            }]),
+        },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    for (channel_id, permitted) in [(permitted_channel_id, true), (other_channel_id, false)] {
+        let send_message_response = client::community::happy_path::send_text_message(
+            env,
+            &owner,
+            community_id,
+            channel_id,
+            None,
+            random_string(),
+            None,
+        );
+
+        let response = client::local_user_index::bot_chat_events(
+            env,
+            bot_principal,
+            local_user_index,
+            &local_user_index_canister::bot_chat_events::Args {
+                chat_context: BotChatContext::Autonomous(Chat::Channel(community_id, channel_id)),
+                thread: None,
+                events: EventsSelectionCriteria::ByIndex(EventsByIndexArgs {
+                    events: vec![send_message_response.event_index],
+                }),
+            },
+        );
+
+        match response {
+            local_user_index_canister::bot_chat_events::Response::Success(result) => {
+                assert!(permitted);
+                assert_eq!(result.events.len(), 1);
+            }
+            local_user_index_canister::bot_chat_events::Response::Error(_) => assert!(!permitted),
+        }
+    }
+
+    // Updating the bot's permissions without specifying channel permissions leaves them unchanged
+    client::community::happy_path::update_bot(env, owner.principal, community_id, bot_id, BotPermissions::text_only());
+
+    let channel_permissions = |env: &PocketIc| {
+        client::community::happy_path::selected_initial(env, owner.principal, community_id)
+            .bots
+            .into_iter()
+            .find(|b| b.user_id == bot_id)
+            .and_then(|b| b.channel_permissions)
+            .map(|c| c.into_iter().map(|c| c.channel_id).collect::<Vec<_>>())
+    };
+    assert_eq!(channel_permissions(env), Some(vec![permitted_channel_id]));
+
+    // Deleting the channel removes it from the bot's channel permissions
+    client::community::happy_path::delete_channel(env, owner.principal, community_id, permitted_channel_id);
+    assert_eq!(channel_permissions(env), Some(Vec::new()));
+}
+
 #[test]
 fn read_messages_by_command() {
     let mut wrapper = ENV.deref().get();
@@ -689,6 +800,7 @@ fn send_direct_message() {
                         max_length: 10000,
                         choices: vec![],
                         multi_line: true,
+                        autocomplete: None,
                     }),
                     placeholder: None,
                 }],
@@ -756,6 +868,7 @@ fn send_direct_message() {
             replies_to: None,
             content: BotMessageContent::Text(TextContent {
                 text: message_text.clone(),
+                components: None,
             }),
             block_level_markdown: false,
             finalised: true,
@@ -794,6 +907,374 @@ fn send_direct_message() {
     assert!(message.bot_context().is_none());
 }
 
+#[test]
+fn bot_autocomplete_only_issues_tokens_for_autocomplete_params() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let other_user = client::register_user(env, canister_ids);
+
+    let local_user_index = canister_ids.local_user_index(env, owner.user_id);
+    let endpoint = "https://my.bot.xyz/".to_string();
+
+    // Register a bot with one parameter which supports autocomplete and one which doesn't
+    let string_param = |name: &str, autocomplete: Option<bool>| BotCommandParam {
+        name: name.to_string(),
+        description: None,
+        required: true,
+        param_type: BotCommandParamType::StringParam(StringParam {
+            min_length: 1,
+            max_length: 100,
+            choices: vec![],
+            multi_line: false,
+            autocomplete,
+        }),
+        placeholder: None,
+    };
+    let (bot_id, _) = client::user_index::happy_path::register_bot(
+        env,
+        owner.principal,
+        canister_ids.user_index,
+        "TicketBot".to_string(),
+        endpoint.clone(),
+        BotDefinition {
+            description: "Comment on tickets".to_string(),
+            commands: vec![BotCommandDefinition {
+                name: "comment".to_string(),
+                description: None,
+                placeholder: None,
+                params: vec![string_param("ticket", Some(true)), string_param("comment", None)],
+                permissions: BotPermissions::text_only(),
+                default_role: None,
+                direct_messages: Some(true),
+            }],
+            autonomous_config: None,
+            default_subscriptions: None,
+            data_encoding: None,
+            restricted_locations: None,
+        },
+    );
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        BotInstallationLocation::User(owner.user_id.into()),
+        bot_id,
+        BotPermissions::text_only(),
+        None,
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    let args = |param_name: &str, user: &User| local_user_index_canister::bot_autocomplete::Args {
+        bot_id,
+        scope: BotActionScope::Chat(BotActionChatDetails {
+            chat: Chat::Direct(user.user_id.into()),
+            thread: None,
+            message_id: random_from_u128(),
+            user_message_id: None,
+        }),
+        command_name: "comment".to_string(),
+        param_name: param_name.to_string(),
+    };
+
+    let response = client::local_user_index::bot_autocomplete(env, owner.principal, local_user_index, &args("ticket", &owner));
+    match response {
+        local_user_index_canister::bot_autocomplete::Response::Success(result) => {
+            assert!(!result.token.is_empty());
+            assert_eq!(result.endpoint, endpoint);
+            assert!(result.expires > now_millis(env));
+        }
+        response => panic!("'bot_autocomplete' error: {response:?}"),
+    }
+
+    let response = client::local_user_index::bot_autocomplete(env, owner.principal, local_user_index, &args("comment", &owner));
+    assert!(matches!(
+        response,
+        local_user_index_canister::bot_autocomplete::Response::Error(_)
+    ));
+
+    // The bot isn't installed in the other user's direct chats
+    let response = client::local_user_index::bot_autocomplete(
+        env,
+        other_user.principal,
+        canister_ids.local_user_index(env, other_user.user_id),
+        &args("ticket", &other_user),
+    );
+    assert!(matches!(
+        response,
+        local_user_index_canister::bot_autocomplete::Response::NotAuthorized
+    ));
+}
+
+#[test]
+fn bot_message_components_issue_interaction_tokens() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let chat = Chat::Group(group_id);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+
+    // Register and install a bot
+    let command_name = random_string();
+    let (bot_id, bot_principal) = register_bot(env, &owner, canister_ids.user_index, random_string(), command_name.clone());
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        chat.into(),
+        bot_id,
+        BotPermissions::text_only(),
+        None,
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    let message_id = random_from_u128();
+    let access_token_args = access_token_v2::Args::BotActionByCommand(BotActionByCommandArgs {
+        bot_id,
+        command: BotCommandInitial {
+            name: command_name,
+            args: Vec::new(),
+            meta: None,
+        },
+        scope: BotActionScope::Chat(BotActionChatDetails {
+            chat,
+            thread: None,
+            message_id,
+            user_message_id: None,
+        }),
+    });
+    let access_token =
+        match client::local_user_index::access_token_v2(env, owner.principal, local_user_index, &access_token_args) {
+            local_user_index_canister::access_token_v2::Response::Success(access_token) => access_token,
+            response => panic!("'access_token' error: {response:?}"),
+        };
+
+    // The bot sends a finalised message with a button and a select menu
+    let components = vec![
+        MessageComponent::Button(MessageButton {
+            id: "approve".to_string(),
+            label: "Approve".to_string(),
+            style: MessageButtonStyle::Primary,
+        }),
+        MessageComponent::Select(MessageSelect {
+            id: "priority".to_string(),
+            placeholder: None,
+            options: ["low", "high"]
+                .into_iter()
+                .map(|v| MessageSelectOption {
+                    value: v.to_string(),
+                    label: v.to_string(),
+                })
+                .collect(),
+        }),
+    ];
+    let response = client::local_user_index::bot_send_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_send_message::Args {
+            chat_context: BotChatContext::Command(access_token),
+            thread: None,
+            message_id: None,
+            replies_to: None,
+            content: BotMessageContent::Text(TextContent {
+                text: "Approve this change?".to_string(),
+                components: Some(components.clone()),
+            }),
+            block_level_markdown: false,
+            finalised: true,
+            og_previews: None,
+        },
+    );
+
+    if !matches!(response, local_user_index_canister::bot_send_message::Response::Success(_)) {
+        panic!("'bot_send_message' error: {response:?}");
+    }
+
+    let response = client::group::happy_path::events(env, &owner, group_id, 0.into(), true, 5, 10);
+    let latest_event = response.events.last().expect("Expected some chat events");
+    let ChatEvent::Message(message) = &latest_event.event else {
+        panic!("Expected latest event to be a message: {latest_event:?}");
+    };
+    let MessageContent::Text(text_content) = &message.content else {
+        panic!("Expected message to be text");
+    };
+    assert_eq!(text_content.components, Some(components));
+
+    let press_args = |component_id: &str, value: Option<&str>| group_canister::press_message_button::Args {
+        thread_root_message_index: None,
+        message_id,
+        component_id: component_id.to_string(),
+        value: value.map(|v| v.to_string()),
+    };
+
+    // Selecting a value which isn't one of the options should fail
+    let response =
+        client::group::press_message_button(env, owner.principal, group_id.into(), &press_args("priority", Some("urgent")));
+    assert!(matches!(response, group_canister::press_message_button::Response::Error(_)));
+
+    // Pressing the button issues a token for the bot
+    let interaction_token =
+        match client::group::press_message_button(env, owner.principal, group_id.into(), &press_args("approve", None)) {
+            group_canister::press_message_button::Response::Success(result) => {
+                assert_eq!(result.endpoint, "https://my.bot.xyz/");
+                assert!(result.expires > now_millis(env));
+                result.token
+            }
+            response => panic!("'press_message_button' error: {response:?}"),
+        };
+
+    // The bot uses the token to edit the (already finalised) message, removing the components
+    let response = client::local_user_index::bot_send_message(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_send_message::Args {
+            chat_context: BotChatContext::Command(interaction_token),
+            thread: None,
+            message_id: None,
+            replies_to: None,
+            content: BotMessageContent::Text(TextContent {
+                text: "Approved".to_string(),
+                components: None,
+            }),
+            block_level_markdown: false,
+            finalised: true,
+            og_previews: None,
+        },
+    );
+
+    if !matches!(response, local_user_index_canister::bot_send_message::Response::Success(_)) {
+        panic!("'bot_send_message' error: {response:?}");
+    }
+
+    let response = client::group::happy_path::events(env, &owner, group_id, 0.into(), true, 5, 10);
+    let latest_event = response.events.last().expect("Expected some chat events");
+    let ChatEvent::Message(message) = &latest_event.event else {
+        panic!("Expected latest event to be a message: {latest_event:?}");
+    };
+    let MessageContent::Text(text_content) = &message.content else {
+        panic!("Expected message to be text");
+    };
+    assert_eq!(text_content.text, "Approved");
+    assert!(text_content.components.is_none());
+    assert!(message.edited);
+}
+
+#[test]
+fn bot_can_replay_missed_events_from_cursor() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let chat = Chat::Group(group_id);
+    let local_user_index = canister_ids.local_user_index(env, group_id);
+
+    // Register a bot which subscribes to messages and install it with permission to read them
+    let (bot_id, bot_principal) = register_bot(env, &owner, canister_ids.user_index, random_string(), random_string());
+    let permissions = BotPermissions::default()
+        .with_message(&HashSet::from_iter([MessagePermission::Text]))
+        .with_chat(&HashSet::from_iter([ChatPermission::ReadMessages]));
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        owner.principal,
+        local_user_index,
+        chat.into(),
+        bot_id,
+        permissions.clone(),
+        Some(permissions),
+    );
+
+    env.advance_time(Duration::from_millis(1000));
+    env.tick();
+
+    // Send a message and a reply in its thread
+    let root = client::group::happy_path::send_text_message(env, &owner, group_id, None, random_string(), None);
+    env.advance_time(Duration::from_millis(1000));
+    client::group::happy_path::send_text_message(env, &owner, group_id, Some(root.message_index), random_string(), None);
+
+    let events_since =
+        |env: &mut PocketIc, since: Option<TimestampMillis>, max_events: u32| match client::local_user_index::bot_events_since(
+            env,
+            bot_principal,
+            local_user_index,
+            &local_user_index_canister::bot_events_since::Args { chat, since, max_events },
+        ) {
+            local_user_index_canister::bot_events_since::Response::Success(result) => result,
+            response => panic!("'bot_events_since' error: {response:?}"),
+        };
+
+    // Both messages are replayed, one batch at a time
+    let first = events_since(env, None, 1);
+    assert_eq!(first.events.len(), 1);
+    assert!(!first.complete);
+    let BotEvent::Chat(event) = &first.events[0].event else {
+        panic!("Expected a chat event: {:?}", first.events[0]);
+    };
+    assert!(event.thread.is_none());
+    assert!(matches!(event.event, ChatEvent::Message(_)));
+
+    let second = events_since(env, Some(first.cursor), 1);
+    assert_eq!(second.events.len(), 1);
+    assert!(second.complete);
+    let BotEvent::Chat(event) = &second.events[0].event else {
+        panic!("Expected a chat event: {:?}", second.events[0]);
+    };
+    assert_eq!(event.thread, Some(root.message_index));
+
+    // Once acknowledged, nothing more is replayed by default
+    let response = client::local_user_index::bot_acknowledge_events(
+        env,
+        bot_principal,
+        local_user_index,
+        &local_user_index_canister::bot_acknowledge_events::Args {
+            chat,
+            cursor: second.cursor,
+        },
+    );
+    assert!(
+        matches!(response, UnitResult::Success),
+        "'bot_acknowledge_events' error: {response:?}"
+    );
+
+    let response = events_since(env, None, 10);
+    assert!(response.events.is_empty());
+    assert!(response.complete);
+    assert_eq!(response.cursor, second.cursor);
+}
+
 #[test_case(ChatType::Direct)]
 #[test_case(ChatType::Group)]
 #[test_case(ChatType::Channel)]
@@ -885,7 +1366,10 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             thread: None,
             message_id: None,
             replies_to: None,
-            content: BotMessageContent::Text(TextContent { text: text.clone() }),
+            content: BotMessageContent::Text(TextContent {
+                text: text.clone(),
+                components: None,
+            }),
             block_level_markdown: false,
             finalised: false,
             og_previews: None,
@@ -907,7 +1391,10 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             thread: None,
             message_id: None,
             replies_to: None,
-            content: BotMessageContent::Text(TextContent { text: text.clone() }),
+            content: BotMessageContent::Text(TextContent {
+                text: text.clone(),
+                components: None,
+            }),
             block_level_markdown: false,
             finalised: false,
             og_previews: None,
@@ -929,7 +1416,10 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             thread: None,
             message_id: None,
             replies_to: None,
-            content: BotMessageContent::Text(TextContent { text: text.clone() }),
+            content: BotMessageContent::Text(TextContent {
+                text: text.clone(),
+                components: None,
+            }),
             block_level_markdown: false,
             finalised: true,
             og_previews: None,
@@ -973,7 +1463,10 @@ fn send_multiple_updates_to_same_message(chat_type: ChatType) {
             thread: None,
             message_id: None,
             replies_to: None,
-            content: BotMessageContent::Text(TextContent { text: text.clone() }),
+            content: BotMessageContent::Text(TextContent {
+                text: text.clone(),
+                components: None,
+            }),
             block_level_markdown: false,
             finalised: true,
             og_previews: None,
@@ -1202,6 +1695,8 @@ fn changing_channel_visibility_should_affect_subscriptions() {
             events_ttl: OptionUpdate::NoChange,
             gate_config: OptionUpdate::NoChange,
             external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -1237,6 +1732,8 @@ fn changing_channel_visibility_should_affect_subscriptions() {
             events_ttl: OptionUpdate::NoChange,
             gate_config: OptionUpdate::NoChange,
             external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -1386,6 +1883,140 @@ fn updating_autonomous_permissions_should_propogate_to_installations(chat_type:
     assert!(notifications.notifications.is_empty());
 }
 
+#[test]
+fn users_can_rate_and_report_published_bots() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    env.advance_time(Duration::from_millis(1));
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let user = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &user, &random_string(), true, true);
+
+    // Register and publish a bot
+    let (bot_id, _) = register_bot(env, &owner, canister_ids.user_index, random_string(), random_string());
+    client::user_index::happy_path::publish_bot(env, *controller, canister_ids.user_index, bot_id);
+
+    let rate_bot_args = user_index_canister::rate_bot::Args {
+        bot_id,
+        rating: 4,
+        review: Some("Very useful".to_string()),
+    };
+
+    // Users can only rate bots which they have installed
+    let response = client::user_index::rate_bot(env, user.principal, canister_ids.user_index, &rate_bot_args);
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    client::local_user_index::happy_path::install_bot(
+        env,
+        user.principal,
+        canister_ids.local_user_index(env, group_id),
+        BotInstallationLocation::Group(group_id),
+        bot_id,
+        BotPermissions::text_only(),
+        None,
+    );
+    tick_many(env, 3);
+
+    let response = client::user_index::rate_bot(env, user.principal, canister_ids.user_index, &rate_bot_args);
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    // Owners can't rate their own bots
+    let response = client::user_index::rate_bot(env, owner.principal, canister_ids.user_index, &rate_bot_args);
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    let response = client::user_index::bot_reviews(
+        env,
+        user.principal,
+        canister_ids.user_index,
+        &user_index_canister::bot_reviews::Args {
+            bot_id,
+            page_index: 0,
+            page_size: 10,
+        },
+    );
+    let user_index_canister::bot_reviews::Response::Success(result) = response else {
+        panic!("'bot_reviews' error: {response:?}");
+    };
+    assert_eq!(result.total, 1);
+    assert_eq!(result.average_rating, Some(4.0));
+    assert_eq!(result.reviews[0].reviewer, user.user_id);
+    assert_eq!(result.reviews[0].text.as_deref(), Some("Very useful"));
+
+    // The install count and rating are returned by `explore_bots`
+    let response = client::user_index::explore_bots(
+        env,
+        user.principal,
+        canister_ids.user_index,
+        &user_index_canister::explore_bots::Args {
+            search_term: None,
+            page_index: 0,
+            page_size: 100,
+            installation_location: None,
+            exclude_installed: false,
+            sort: Some(BotSortOrder::TopRated),
+            category: None,
+        },
+    );
+    let user_index_canister::explore_bots::Response::Success(result) = response else {
+        panic!("'explore_bots' error: {response:?}");
+    };
+    let bot = result.matches.iter().find(|b| b.id == bot_id).unwrap();
+    assert_eq!(bot.install_count, 1);
+    assert_eq!(bot.lifetime_install_count, 1);
+    assert_eq!(bot.average_rating, Some(4.0));
+    assert_eq!(bot.rating_count, 1);
+
+    // Each user can only report a bot once
+    let report_bot_args = user_index_canister::report_bot::Args {
+        bot_id,
+        reason: "Spam".to_string(),
+    };
+    let response = client::user_index::report_bot(env, user.principal, canister_ids.user_index, &report_bot_args);
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let response = client::user_index::report_bot(env, user.principal, canister_ids.user_index, &report_bot_args);
+    assert!(matches!(response, UnitResult::Error(_)), "{response:?}");
+
+    // Platform moderators can list the outstanding reports
+    let moderator = client::register_user(env, canister_ids);
+    client::user_index::add_platform_moderator(
+        env,
+        *controller,
+        canister_ids.user_index,
+        &user_index_canister::add_platform_moderator::Args {
+            user_id: moderator.user_id,
+        },
+    );
+    tick_many(env, 3);
+
+    let user_index_canister::bot_reports::Response::Success(result) =
+        client::user_index::bot_reports(env, moderator.principal, canister_ids.user_index, &Empty {});
+    let reported = result.bots.iter().find(|b| b.bot_id == bot_id).expect("Bot not reported");
+    assert_eq!(reported.owner, owner.user_id);
+    assert_eq!(reported.reports.len(), 1);
+    assert_eq!(reported.reports[0].reporter, user.user_id);
+    assert_eq!(reported.reports[0].reason, "Spam");
+
+    // Once resolved the bot no longer appears in the list
+    let response = client::user_index::resolve_bot_reports(
+        env,
+        moderator.principal,
+        canister_ids.user_index,
+        &user_index_canister::resolve_bot_reports::Args { bot_id },
+    );
+    assert!(matches!(response, UnitResult::Success), "{response:?}");
+
+    let user_index_canister::bot_reports::Response::Success(result) =
+        client::user_index::bot_reports(env, moderator.principal, canister_ids.user_index, &Empty {});
+    assert!(result.bots.iter().all(|b| b.bot_id != bot_id));
+}
+
 fn register_bot(
     env: &mut PocketIc,
     owner: &User,
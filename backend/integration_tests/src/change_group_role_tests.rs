@@ -100,6 +100,8 @@ fn moderator_cannot_demote_admin() {
             gate_config: NoChange,
             public: None,
             messages_visible_to_non_members: None,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
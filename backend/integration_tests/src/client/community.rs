@@ -35,6 +35,7 @@ generate_msgpack_update_call!(delete_user_groups);
 generate_msgpack_update_call!(edit_message);
 generate_msgpack_update_call!(enable_invite_code);
 generate_msgpack_update_call!(import_group);
+generate_msgpack_update_call!(import_history);
 generate_msgpack_update_call!(leave_channel);
 generate_msgpack_update_call!(register_poll_vote);
 generate_msgpack_update_call!(register_webhook);
@@ -57,8 +58,8 @@ pub mod happy_path {
     use types::{
         AccessGate, BotPermissions, CanisterId, ChannelId, ChatId, CommunityCanisterChannelSummary,
         CommunityCanisterCommunitySummary, CommunityCanisterCommunitySummaryUpdates, CommunityId, CommunityRole, Empty,
-        EventIndex, EventsResponse, GroupReplyContext, GroupRole, MessageContentInitial, MessageId, MessageIndex, PollVotes,
-        Reaction, Rules, TextContent, TimestampMillis, UserId, VoteOperation,
+        EventIndex, EventsResponse, GroupReplyContext, GroupRole, MessageContentInitial, MessageId, MessageIndex, OptionUpdate,
+        PollVotes, Reaction, Rules, TextContent, TimestampMillis, UserId, VoteOperation,
     };
 
     pub fn create_channel(
@@ -185,7 +186,10 @@ pub mod happy_path {
                 channel_id,
                 thread_root_message_index,
                 message_id: message_id.unwrap_or_else(random_from_u128),
-                content: MessageContentInitial::Text(TextContent { text: text.to_string() }),
+                content: MessageContentInitial::Text(TextContent {
+                    text: text.to_string(),
+                    components: None,
+                }),
                 sender_name: sender.username(),
                 sender_display_name: None,
                 replies_to: None,
@@ -716,6 +720,7 @@ pub mod happy_path {
                 bot_id,
                 granted_permissions,
                 granted_autonomous_permissions: None,
+                channel_permissions: OptionUpdate::NoChange,
             },
         );
 
@@ -748,6 +753,8 @@ pub mod happy_path {
                 channel_id,
                 name,
                 avatar,
+                rate_limits: None,
+                require_signature: None,
             },
         );
 
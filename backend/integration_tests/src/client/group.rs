@@ -9,7 +9,10 @@ generate_msgpack_query_call!(deleted_message);
 generate_msgpack_query_call!(events);
 generate_msgpack_query_call!(events_by_index);
 generate_msgpack_query_call!(events_window);
+generate_msgpack_query_call!(export_history);
 generate_msgpack_query_call!(local_user_index);
+generate_msgpack_query_call!(message_edit_history);
+generate_msgpack_query_call!(outgoing_webhooks);
 generate_msgpack_query_call!(public_summary);
 generate_msgpack_query_call!(selected_initial);
 generate_msgpack_query_call!(selected_updates_v2);
@@ -31,14 +34,18 @@ generate_msgpack_update_call!(enable_invite_code);
 generate_update_call!(end_video_call_v2);
 generate_msgpack_update_call!(join_video_call);
 generate_msgpack_update_call!(pin_message_v2);
+generate_msgpack_update_call!(press_message_button);
+generate_msgpack_update_call!(register_outgoing_webhook);
 generate_msgpack_update_call!(register_poll_vote);
 generate_msgpack_update_call!(register_webhook);
 generate_msgpack_update_call!(remove_participant);
 generate_msgpack_update_call!(report_message);
 generate_msgpack_update_call!(remove_reaction);
 generate_msgpack_update_call!(send_message_v2);
+generate_msgpack_update_call!(set_notification_filters);
 generate_update_call!(start_video_call_v2);
 generate_msgpack_update_call!(toggle_mute_notifications);
+generate_msgpack_update_call!(translate_message);
 generate_msgpack_update_call!(unblock_user);
 generate_msgpack_update_call!(undelete_messages);
 generate_msgpack_update_call!(unpin_message);
@@ -73,7 +80,10 @@ pub mod happy_path {
             &group_canister::send_message_v2::Args {
                 thread_root_message_index,
                 message_id: message_id.unwrap_or_else(random_from_u128),
-                content: MessageContentInitial::Text(TextContent { text: text.to_string() }),
+                content: MessageContentInitial::Text(TextContent {
+                    text: text.to_string(),
+                    components: None,
+                }),
                 sender_name: sender.username(),
                 sender_display_name: None,
                 replies_to: None,
@@ -574,16 +584,27 @@ pub mod happy_path {
         local_user_index
     }
 
-    pub fn register_webhook(env: &mut PocketIc, caller: Principal, group_id: ChatId, name: String, avatar: Option<String>) {
+    pub fn register_webhook(
+        env: &mut PocketIc,
+        caller: Principal,
+        group_id: ChatId,
+        name: String,
+        avatar: Option<String>,
+    ) -> group_canister::register_webhook::SuccessResult {
         let response = super::register_webhook(
             env,
             caller,
             group_id.into(),
-            &group_canister::register_webhook::Args { name, avatar },
+            &group_canister::register_webhook::Args {
+                name,
+                avatar,
+                rate_limits: None,
+                require_signature: None,
+            },
         );
 
         match response {
-            group_canister::register_webhook::Response::Success(_) => (),
+            group_canister::register_webhook::Response::Success(result) => result,
             response => panic!("'register_webhook' error: {response:?}"),
         }
     }
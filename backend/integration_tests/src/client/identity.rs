@@ -12,6 +12,7 @@ generate_msgpack_update_call!(create_account_linking_code);
 generate_msgpack_update_call!(create_identity);
 generate_msgpack_update_call!(delete_user);
 generate_msgpack_update_call!(finalise_account_linking_with_code);
+generate_msgpack_update_call!(get_encryption_key);
 generate_msgpack_update_call!(initiate_identity_link);
 generate_msgpack_update_call!(initiate_identity_link_via_qr_code);
 generate_msgpack_update_call!(prepare_delegation);
@@ -3,8 +3,10 @@ use local_user_index_canister::*;
 
 // Queries
 generate_msgpack_query_call!(access_token_v2);
+generate_msgpack_query_call!(bot_autocomplete);
 generate_query_call!(bot_chat_events);
 generate_query_call!(bot_community_events);
+generate_query_call!(bot_events_since);
 generate_msgpack_query_call!(chat_events);
 generate_msgpack_query_call!(group_and_community_summary_updates_v2);
 generate_query_call!(latest_notification_index);
@@ -12,6 +14,7 @@ generate_query_call!(media_scan_jobs);
 generate_query_call!(notifications);
 
 // Updates
+generate_update_call!(bot_acknowledge_events);
 generate_update_call!(bot_create_channel);
 generate_update_call!(bot_delete_channel);
 generate_update_call!(bot_send_message);
@@ -290,6 +293,7 @@ pub mod happy_path {
                 granted_permissions,
                 location,
                 granted_autonomous_permissions,
+                channel_permissions: None,
             },
         );
 
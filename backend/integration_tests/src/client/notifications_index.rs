@@ -13,7 +13,7 @@ generate_msgpack_update_call!(push_subscription);
 pub mod happy_path {
     use candid::Principal;
     use pocket_ic::PocketIc;
-    use types::{CanisterId, SubscriptionInfo, SubscriptionKeys};
+    use types::{CanisterId, PushBackend, SubscriptionInfo, SubscriptionKeys};
 
     pub fn push_subscription(
         env: &mut PocketIc,
@@ -22,6 +22,26 @@ pub mod happy_path {
         auth: impl Into<String>,
         p256dh: impl Into<String>,
         endpoint: impl Into<String>,
+    ) {
+        push_subscription_with_backend(
+            env,
+            sender,
+            notifications_index_canister_id,
+            auth,
+            p256dh,
+            endpoint,
+            PushBackend::WebPush,
+        );
+    }
+
+    pub fn push_subscription_with_backend(
+        env: &mut PocketIc,
+        sender: Principal,
+        notifications_index_canister_id: CanisterId,
+        auth: impl Into<String>,
+        p256dh: impl Into<String>,
+        endpoint: impl Into<String>,
+        backend: PushBackend,
     ) {
         let response = super::push_subscription(
             env,
@@ -35,6 +55,7 @@ pub mod happy_path {
                     },
                     endpoint: endpoint.into(),
                 },
+                backend,
             },
         );
 
@@ -7,6 +7,7 @@ generate_query_call!(file_status);
 generate_query_call!(vault_log);
 
 // Updates
+generate_update_call!(claim_file);
 generate_update_call!(delete_file);
 generate_update_call!(delete_files);
 generate_update_call!(forward_file);
@@ -50,6 +51,7 @@ pub mod happy_path {
                     total_size,
                     bytes: chunk.to_vec(),
                     expiry,
+                    encryption: None,
                 },
             );
 
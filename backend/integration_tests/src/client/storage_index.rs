@@ -4,12 +4,14 @@ use storage_index_canister::*;
 // Queries
 generate_query_call!(allocated_bucket_v2);
 generate_query_call!(can_forward);
+generate_query_call!(storage_usage);
 generate_query_call!(user);
 generate_query_call!(vault_buckets);
 
 // Updates
 generate_update_call!(add_bucket_canister);
 generate_update_call!(add_or_update_users);
+generate_update_call!(delete_files);
 generate_update_call!(remove_accessors);
 generate_update_call!(remove_users);
 generate_update_call!(upgrade_bucket_canister_wasm);
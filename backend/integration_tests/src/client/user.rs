@@ -7,7 +7,9 @@ generate_msgpack_query_call!(events);
 generate_msgpack_query_call!(events_by_index);
 generate_msgpack_query_call!(events_window);
 generate_msgpack_query_call!(initial_state);
+generate_msgpack_query_call!(list_scheduled_messages);
 generate_msgpack_query_call!(message_activity_feed);
+generate_msgpack_query_call!(message_edit_history);
 generate_msgpack_query_call!(saved_crypto_accounts);
 generate_msgpack_query_call!(updates);
 
@@ -18,6 +20,7 @@ generate_msgpack_update_call!(add_reaction);
 generate_msgpack_update_call!(block_user);
 generate_msgpack_update_call!(cancel_message_reminder);
 generate_msgpack_update_call!(cancel_p2p_swap);
+generate_msgpack_update_call!(cancel_scheduled_message);
 generate_msgpack_update_call!(claim_daily_chit);
 generate_msgpack_update_call!(create_community);
 generate_msgpack_update_call!(create_group);
@@ -38,12 +41,14 @@ generate_msgpack_update_call!(c2c_pay_for_premium_item);
 generate_msgpack_update_call!(pay_for_streak_insurance);
 generate_msgpack_update_call!(remove_reaction);
 generate_msgpack_update_call!(save_crypto_account);
+generate_msgpack_update_call!(schedule_message);
 generate_msgpack_update_call!(send_message_v2);
 generate_msgpack_update_call!(send_message_with_transfer_to_channel);
 generate_msgpack_update_call!(send_message_with_transfer_to_group);
 generate_msgpack_update_call!(set_message_reminder_v2);
 generate_msgpack_update_call!(set_pin_number);
 generate_msgpack_update_call!(set_profile_background);
+generate_msgpack_update_call!(set_quiet_hours);
 generate_update_call!(start_video_call_v2);
 generate_msgpack_update_call!(tip_message);
 generate_msgpack_update_call!(unblock_user);
@@ -57,7 +62,8 @@ pub mod happy_path {
     use testing::rng::random_from_u128;
     use types::{
         CanisterId, Chat, ChatId, CommunityId, Empty, EventIndex, EventsResponse, MessageContentInitial, MessageId,
-        MessageIndex, Milliseconds, Reaction, ReplyContext, Rules, TextContent, TimestampMillis, UserId, VideoCallType,
+        MessageIndex, Milliseconds, QuietHours, Reaction, ReplyContext, Rules, TextContent, TimestampMillis, UserId,
+        VideoCallType,
     };
     use user_canister::NamedAccount;
     use user_canister::set_pin_number::PinNumberVerification;
@@ -77,7 +83,10 @@ pub mod happy_path {
                 recipient,
                 thread_root_message_index: None,
                 message_id: message_id.unwrap_or_else(random_from_u128),
-                content: MessageContentInitial::Text(TextContent { text: text.to_string() }),
+                content: MessageContentInitial::Text(TextContent {
+                    text: text.to_string(),
+                    components: None,
+                }),
                 replies_to: None,
                 forwarding: false,
                 block_level_markdown: false,
@@ -144,7 +153,10 @@ pub mod happy_path {
                 user_id,
                 thread_root_message_index: None,
                 message_id,
-                content: MessageContentInitial::Text(TextContent { text: text.to_string() }),
+                content: MessageContentInitial::Text(TextContent {
+                    text: text.to_string(),
+                    components: None,
+                }),
                 block_level_markdown,
                 og_previews: Vec::new(),
             },
@@ -156,6 +168,69 @@ pub mod happy_path {
         }
     }
 
+    pub fn schedule_text_message(
+        env: &mut PocketIc,
+        sender: &User,
+        chat: Chat,
+        message_id: MessageId,
+        text: impl ToString,
+        send_at: TimestampMillis,
+    ) -> u64 {
+        let response = super::schedule_message(
+            env,
+            sender.principal,
+            sender.canister(),
+            &user_canister::schedule_message::Args {
+                chat,
+                thread_root_message_index: None,
+                message_id,
+                content: MessageContentInitial::Text(TextContent {
+                    text: text.to_string(),
+                    components: None,
+                }),
+                replies_to: None,
+                mentioned: Vec::new(),
+                block_level_markdown: false,
+                send_at,
+            },
+        );
+
+        match response {
+            user_canister::schedule_message::Response::Success(result) => result.scheduled_message_id,
+            response => panic!("'schedule_message' error: {response:?}"),
+        }
+    }
+
+    pub fn cancel_scheduled_message(env: &mut PocketIc, sender: &User, scheduled_message_id: u64) {
+        let response = super::cancel_scheduled_message(
+            env,
+            sender.principal,
+            sender.canister(),
+            &user_canister::cancel_scheduled_message::Args { scheduled_message_id },
+        );
+
+        match response {
+            user_canister::cancel_scheduled_message::Response::Success => {}
+            response => panic!("'cancel_scheduled_message' error: {response:?}"),
+        }
+    }
+
+    pub fn list_scheduled_messages(
+        env: &PocketIc,
+        sender: &User,
+        chat: Option<Chat>,
+    ) -> Vec<user_canister::list_scheduled_messages::ScheduledMessage> {
+        let response = super::list_scheduled_messages(
+            env,
+            sender.principal,
+            sender.canister(),
+            &user_canister::list_scheduled_messages::Args { chat },
+        );
+
+        let user_canister::list_scheduled_messages::Response::Success(result) = response;
+        result.scheduled_messages
+    }
+
     pub fn create_group(
         env: &mut PocketIc,
         sender: &User,
@@ -612,6 +687,17 @@ pub mod happy_path {
         assert!(matches!(response, user_canister::set_profile_background::Response::Success));
     }
 
+    pub fn set_quiet_hours(env: &mut PocketIc, user: &User, quiet_hours: Option<QuietHours>) {
+        let response = super::set_quiet_hours(
+            env,
+            user.principal,
+            user.canister(),
+            &user_canister::set_quiet_hours::Args { quiet_hours },
+        );
+
+        assert!(matches!(response, user_canister::set_quiet_hours::Response::Success));
+    }
+
     pub fn save_crypto_account(env: &mut PocketIc, user: &User, name: impl Into<String>, account: impl Into<String>) {
         let response = super::save_crypto_account(
             env,
@@ -17,6 +17,8 @@ generate_msgpack_query_call!(users);
 generate_msgpack_query_call!(users_chit);
 generate_msgpack_query_call!(bot_updates);
 generate_msgpack_query_call!(explore_bots);
+generate_msgpack_query_call!(bot_reviews);
+generate_msgpack_query_call!(bot_reports);
 
 // Updates
 generate_update_call!(add_local_user_index_canister);
@@ -37,6 +39,7 @@ generate_msgpack_update_call!(set_moderation_referral_config);
 generate_update_call!(remove_platform_moderator);
 generate_msgpack_update_call!(set_display_name);
 generate_msgpack_update_call!(set_premium_item_cost);
+generate_msgpack_update_call!(set_translation_provider_config);
 generate_msgpack_update_call!(set_username);
 generate_msgpack_update_call!(suspend_user);
 generate_msgpack_update_call!(update_diamond_membership_subscription);
@@ -46,6 +49,9 @@ generate_update_call!(upgrade_user_canister_wasm);
 generate_update_call!(upload_wasm_chunk);
 generate_msgpack_update_call!(register_bot);
 generate_msgpack_update_call!(publish_bot);
+generate_msgpack_update_call!(rate_bot);
+generate_msgpack_update_call!(report_bot);
+generate_msgpack_update_call!(resolve_bot_reports);
 generate_msgpack_update_call!(update_bot);
 
 pub mod happy_path {
@@ -341,7 +347,11 @@ pub mod happy_path {
             env,
             caller,
             user_index_canister_id,
-            &user_index_canister::publish_bot::Args { bot_id },
+            &user_index_canister::publish_bot::Args {
+                bot_id,
+                categories: None,
+                tags: None,
+            },
         );
 
         match response {
@@ -428,6 +438,8 @@ pub mod happy_path {
                 page_index: 0,
                 page_size: 10,
                 exclude_installed: false,
+                sort: None,
+                category: None,
             },
         );
 
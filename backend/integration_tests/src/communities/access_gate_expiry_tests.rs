@@ -625,6 +625,8 @@ fn update_container_gate(
                 public: None,
                 messages_visible_to_non_members: None,
                 external_url: OptionUpdate::NoChange,
+                edit_history_disabled: None,
+                e2ee_enabled: None,
             };
 
             client::community::happy_path::update_channel(env, principal, *community_id, &args);
@@ -640,6 +642,8 @@ fn update_container_gate(
                 permissions_v2: None,
                 events_ttl: OptionUpdate::NoChange,
                 messages_visible_to_non_members: None,
+                edit_history_disabled: None,
+                e2ee_enabled: None,
             };
 
             client::group::happy_path::update_group(env, principal, *group_id, &args);
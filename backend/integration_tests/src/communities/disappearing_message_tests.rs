@@ -37,6 +37,8 @@ fn disappearing_messages_in_channel() {
             public: None,
             messages_visible_to_non_members: None,
             external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -86,6 +88,8 @@ fn disappearing_messages_in_channel() {
             public: None,
             messages_visible_to_non_members: None,
             external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -142,6 +146,8 @@ fn stable_memory_garbage_collected_after_messages_disappear() {
             public: None,
             messages_visible_to_non_members: None,
             external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -297,7 +297,10 @@ fn private_replies_to_group_updated_to_community() {
             recipient: user1.user_id,
             thread_root_message_index: None,
             message_id: random_from_u128(),
-            content: MessageContentInitial::Text(TextContent { text: random_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: random_string(),
+                components: None,
+            }),
             replies_to: Some(ReplyContext {
                 chat_if_other: Some((Chat::Group(group_id), None)),
                 event_index: send_message_response.event_index,
@@ -0,0 +1,224 @@
+use crate::env::ENV;
+use crate::utils::now_millis;
+use crate::{TestEnv, client};
+use constants::{DAY_IN_MS, IMPORTED_USER_ID};
+use oc_error_codes::OCErrorCode;
+use std::ops::Deref;
+use testing::rng::{random_from_u128, random_string};
+use types::{
+    ChatEvent, EventIndex, ImportSource, ImportedMessage, ImportedMessageContext, MessageContent, MessageIndex, OptionUpdate,
+    Rules, SenderContext, TimestampMillis,
+};
+
+const ORIGINAL_TIMESTAMP: TimestampMillis = 1_600_000_000_000;
+
+#[test]
+fn import_history_succeeds() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user = client::register_diamond_user(env, canister_ids, *controller);
+    let community_id = client::user::happy_path::create_community(env, &user, &random_string(), true, vec![random_string()]);
+    let channel_id = client::community::happy_path::create_channel(env, user.principal, community_id, true, random_string());
+
+    let root = imported_message(None, "Hello", "Alice");
+    let reply = imported_message(Some(MessageIndex::from(0)), "Hi!", "Bob");
+
+    let args = community_canister::import_history::Args {
+        channel_id,
+        messages: vec![root.clone()],
+    };
+    let response = client::community::import_history(env, user.principal, community_id.into(), &args);
+    let community_canister::import_history::Response::Success(result) = response else {
+        panic!("'import_history' error: {response:?}");
+    };
+    assert_eq!(result.message_indexes, vec![MessageIndex::from(0)]);
+
+    // Re-importing a message which has already been imported is a no-op
+    let args = community_canister::import_history::Args {
+        channel_id,
+        messages: vec![root, reply],
+    };
+    let response = client::community::import_history(env, user.principal, community_id.into(), &args);
+    let community_canister::import_history::Response::Success(result) = response else {
+        panic!("'import_history' error: {response:?}");
+    };
+    assert_eq!(result.message_indexes, vec![MessageIndex::from(0), MessageIndex::from(0)]);
+
+    let events = client::community::happy_path::events(env, &user, community_id, channel_id, EventIndex::from(0), true, 10, 10);
+    let messages: Vec<_> = events
+        .events
+        .into_iter()
+        .filter_map(|e| if let ChatEvent::Message(m) = e.event { Some((e.timestamp, *m)) } else { None })
+        .collect();
+
+    assert_eq!(messages.len(), 1);
+    let (timestamp, message) = &messages[0];
+    assert_eq!(*timestamp, now_millis(env));
+    assert_eq!(message.sender, IMPORTED_USER_ID);
+    assert_eq!(message.thread_summary.as_ref().map(|t| t.reply_count), Some(1));
+    assert!(matches!(&message.content, MessageContent::Text(t) if t.text == "Hello"));
+    assert!(matches!(
+        &message.sender_context,
+        Some(SenderContext::Imported(c))
+            if c.author_name == "Alice" && c.source == ImportSource::Slack && c.original_timestamp == ORIGINAL_TIMESTAMP
+    ));
+}
+
+#[test]
+fn imported_messages_expire_relative_to_import_time() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user = client::register_diamond_user(env, canister_ids, *controller);
+    let community_id = client::user::happy_path::create_community(env, &user, &random_string(), true, vec![random_string()]);
+    let response = client::community::create_channel(
+        env,
+        user.principal,
+        community_id.into(),
+        &community_canister::create_channel::Args {
+            is_public: true,
+            name: random_string(),
+            description: random_string(),
+            rules: Rules::default(),
+            subtype: None,
+            avatar: None,
+            history_visible_to_new_joiners: true,
+            messages_visible_to_non_members: None,
+            permissions_v2: None,
+            events_ttl: Some(DAY_IN_MS),
+            gate_config: None,
+            external_url: None,
+        },
+    );
+    let community_canister::create_channel::Response::Success(result) = response else {
+        panic!("'create_channel' error: {response:?}");
+    };
+    let channel_id = result.channel_id;
+
+    let response = client::community::import_history(
+        env,
+        user.principal,
+        community_id.into(),
+        &community_canister::import_history::Args {
+            channel_id,
+            messages: vec![imported_message(None, "Hello", "Alice")],
+        },
+    );
+    assert!(matches!(response, community_canister::import_history::Response::Success(_)));
+
+    let now = now_millis(env);
+    let events = client::community::happy_path::events(env, &user, community_id, channel_id, EventIndex::from(0), true, 10, 10);
+    let message_event = events
+        .events
+        .into_iter()
+        .find(|e| matches!(e.event, ChatEvent::Message(_)))
+        .expect("Imported message should not have expired");
+
+    assert_eq!(message_event.expires_at, Some(now + DAY_IN_MS));
+}
+
+#[test]
+fn import_history_by_non_owner_fails() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user1 = client::register_diamond_user(env, canister_ids, *controller);
+    let user2 = client::register_user(env, canister_ids);
+    let community_id = client::user::happy_path::create_community(env, &user1, &random_string(), true, vec![random_string()]);
+    let summary = client::community::happy_path::join_community(env, user2.principal, community_id);
+    let channel_id = summary.channels.first().unwrap().channel_id;
+
+    let response = client::community::import_history(
+        env,
+        user2.principal,
+        community_id.into(),
+        &community_canister::import_history::Args {
+            channel_id,
+            messages: vec![imported_message(None, "Hello", "Alice")],
+        },
+    );
+
+    assert!(matches!(
+        response,
+        community_canister::import_history::Response::Error(e) if e.matches_code(OCErrorCode::InitiatorNotAuthorized)
+    ));
+}
+
+#[test]
+fn import_history_into_end_to_end_encrypted_channel_fails() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user = client::register_diamond_user(env, canister_ids, *controller);
+    let community_id = client::user::happy_path::create_community(env, &user, &random_string(), true, vec![random_string()]);
+    let channel_id = client::community::happy_path::create_channel(env, user.principal, community_id, false, random_string());
+    client::community::happy_path::update_channel(
+        env,
+        user.principal,
+        community_id,
+        &community_canister::update_channel::Args {
+            channel_id,
+            name: None,
+            description: None,
+            rules: None,
+            avatar: OptionUpdate::NoChange,
+            permissions_v2: None,
+            events_ttl: OptionUpdate::NoChange,
+            gate_config: OptionUpdate::NoChange,
+            public: None,
+            messages_visible_to_non_members: None,
+            external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: Some(true),
+        },
+    );
+
+    let response = client::community::import_history(
+        env,
+        user.principal,
+        community_id.into(),
+        &community_canister::import_history::Args {
+            channel_id,
+            messages: vec![imported_message(None, "Hello", "Alice")],
+        },
+    );
+
+    assert!(matches!(
+        response,
+        community_canister::import_history::Response::Error(e) if e.matches_code(OCErrorCode::EndToEndEncrypted)
+    ));
+}
+
+fn imported_message(thread_root_message_index: Option<MessageIndex>, text: &str, author_name: &str) -> ImportedMessage {
+    ImportedMessage {
+        message_id: random_from_u128(),
+        thread_root_message_index,
+        text: text.to_string(),
+        context: ImportedMessageContext {
+            source: ImportSource::Slack,
+            author_name: author_name.to_string(),
+            original_timestamp: ORIGINAL_TIMESTAMP,
+        },
+    }
+}
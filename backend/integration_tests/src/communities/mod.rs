@@ -7,6 +7,7 @@ mod delete_community_tests;
 mod delete_history_tests;
 mod disappearing_message_tests;
 mod import_group_tests;
+mod import_history_tests;
 mod join_channel_tests;
 mod join_community_tests;
 mod leave_community_tests;
@@ -4,7 +4,7 @@ use candid::Principal;
 use pocket_ic::PocketIc;
 use std::ops::Deref;
 use testing::rng::random_string;
-use types::{ChannelId, CommunityId, MessageIndex};
+use types::{ChannelId, CommunityId, MessageIndex, MessageMatch};
 
 #[test]
 fn search_channel_returns_expected_message() {
@@ -29,6 +29,7 @@ fn search_channel_returns_expected_message() {
         community_id.into(),
         &community_canister::search_channel::Args {
             channel_id,
+            thread_root_message_index: None,
             search_term: "crue".to_string(),
             max_results: 10,
             users: None,
@@ -42,6 +43,103 @@ fn search_channel_returns_expected_message() {
 
     assert_eq!(matches.len(), 1);
     assert_eq!(matches[0].message_index, MessageIndex::from(1));
+    assert_eq!(matches[0].highlights, vec![(9, 13)]);
+}
+
+#[test]
+fn search_channel_with_phrase_and_prefix_returns_expected_message() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user1,
+        user2: _,
+        community_id,
+        channel_id,
+    } = init_test_data(env, canister_ids, *controller);
+
+    let matches = search(env, &user1, community_id, channel_id, None, "\"cruel world\"");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].message_index, MessageIndex::from(1));
+    assert_eq!(matches[0].highlights, vec![(9, 20)]);
+
+    let matches = search(env, &user1, community_id, channel_id, None, "wor*");
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].message_index, MessageIndex::from(1));
+    assert_eq!(matches[1].message_index, MessageIndex::from(0));
+
+    let matches = search(env, &user1, community_id, channel_id, None, "orld*");
+    assert!(matches.is_empty());
+
+    let matches = search(env, &user1, community_id, channel_id, None, "world has:image");
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn search_channel_thread_returns_expected_message() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData {
+        user1,
+        user2,
+        community_id,
+        channel_id,
+    } = init_test_data(env, canister_ids, *controller);
+
+    client::community::happy_path::send_text_message(
+        env,
+        &user2,
+        community_id,
+        channel_id,
+        Some(MessageIndex::from(0)),
+        "Hello again, world!",
+        None,
+    );
+
+    let matches = search(env, &user1, community_id, channel_id, Some(MessageIndex::from(0)), "again");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].message_index, MessageIndex::from(0));
+
+    let matches = search(env, &user1, community_id, channel_id, None, "again");
+    assert!(matches.is_empty());
+}
+
+fn search(
+    env: &PocketIc,
+    user: &User,
+    community_id: CommunityId,
+    channel_id: ChannelId,
+    thread_root_message_index: Option<MessageIndex>,
+    search_term: &str,
+) -> Vec<MessageMatch> {
+    let response = client::community::search_channel(
+        env,
+        user.principal,
+        community_id.into(),
+        &community_canister::search_channel::Args {
+            channel_id,
+            thread_root_message_index,
+            search_term: search_term.to_string(),
+            max_results: 10,
+            users: None,
+        },
+    );
+
+    match response {
+        community_canister::search_channel::Response::Success(result) => result.matches,
+        response => panic!("'search_channel' error: {response:?}"),
+    }
 }
 
 fn init_test_data(env: &mut PocketIc, canister_ids: &CanisterIds, controller: Principal) -> TestData {
@@ -75,7 +173,6 @@ fn init_test_data(env: &mut PocketIc, canister_ids: &CanisterIds, controller: Pr
     }
 }
 
-#[expect(dead_code)]
 struct TestData {
     user1: User,
     user2: User,
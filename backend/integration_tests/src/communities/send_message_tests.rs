@@ -705,7 +705,10 @@ fn send_dummy_message_with_rules(
             channel_id,
             thread_root_message_index: None,
             message_id: random_from_u128(),
-            content: MessageContentInitial::Text(TextContent { text: "123".to_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: "123".to_string(),
+                components: None,
+            }),
             sender_name: sender.username(),
             sender_display_name: None,
             replies_to: None,
@@ -778,6 +781,8 @@ fn set_channel_rules(env: &mut PocketIc, sender: Principal, community_id: Commun
         channel_id,
         messages_visible_to_non_members: None,
         external_url: OptionUpdate::NoChange,
+        edit_history_disabled: None,
+        e2ee_enabled: None,
     };
 
     client::community::happy_path::update_channel(env, sender, community_id, &args);
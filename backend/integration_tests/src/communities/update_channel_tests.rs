@@ -67,6 +67,8 @@ fn members_added_if_channel_made_public_or_gate_removed(make_public: bool) {
             public: make_public.then_some(true),
             messages_visible_to_non_members: None,
             external_url: OptionUpdate::NoChange,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
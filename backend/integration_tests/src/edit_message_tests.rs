@@ -1,10 +1,11 @@
 use crate::env::ENV;
 use crate::utils::tick_many;
 use crate::{TestEnv, client};
+use oc_error_codes::OCErrorCode;
 use std::ops::Deref;
 use test_case::test_case;
-use testing::rng::random_from_u128;
-use types::{ChatEvent, MessageContentInitial, TextContent};
+use testing::rng::{random_from_u128, random_string};
+use types::{ChatEvent, MessageContentInitial, TextContent, UnitResult};
 
 #[test]
 fn edit_message_succeeds() {
@@ -65,6 +66,7 @@ fn update_block_level_markdown_succeeds(starting_value: bool) {
             message_id,
             content: MessageContentInitial::Text(TextContent {
                 text: "TEXT".to_string(),
+                components: None,
             }),
             replies_to: None,
             forwarding: false,
@@ -102,3 +104,100 @@ fn update_block_level_markdown_succeeds(starting_value: bool) {
     assert!(m2.edited);
     assert_eq!(m2.block_level_markdown, new_value);
 }
+
+#[test]
+fn edit_history_returned_for_direct_messages() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::register_user(env, canister_ids);
+    let user2 = client::register_user(env, canister_ids);
+
+    let message_id = random_from_u128();
+
+    client::user::happy_path::send_text_message(env, &user1, user2.user_id, "TEXT", Some(message_id));
+    client::user::happy_path::edit_text_message(env, &user1, user2.user_id, message_id, "TEXT!", None);
+    client::user::happy_path::edit_text_message(env, &user1, user2.user_id, message_id, "TEXT!!", None);
+
+    tick_many(env, 3);
+
+    for (user, other) in [(&user1, &user2), (&user2, &user1)] {
+        let user_canister::message_edit_history::Response::Success(result) = client::user::message_edit_history(
+            env,
+            user.principal,
+            user.canister(),
+            &user_canister::message_edit_history::Args {
+                user_id: other.user_id,
+                thread_root_message_index: None,
+                message_id,
+            },
+        ) else {
+            panic!()
+        };
+
+        let texts: Vec<_> = result
+            .revisions
+            .iter()
+            .map(|r| r.content.text().unwrap().to_string())
+            .collect();
+        assert_eq!(texts, vec!["TEXT", "TEXT!", "TEXT!!"]);
+    }
+}
+
+#[test]
+fn edit_history_not_returned_if_disabled_for_group() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &user, &random_string(), false, true);
+
+    let message_id = random_from_u128();
+    client::group::happy_path::send_text_message(env, &user, group_id, None, "TEXT", Some(message_id));
+
+    let edit_response = client::group::edit_message_v2(
+        env,
+        user.principal,
+        group_id.into(),
+        &group_canister::edit_message_v2::Args {
+            thread_root_message_index: None,
+            message_id,
+            content: MessageContentInitial::Text(TextContent {
+                text: "TEXT!".to_string(),
+                components: None,
+            }),
+            block_level_markdown: None,
+            new_achievement: false,
+            og_previews: Vec::new(),
+        },
+    );
+    assert!(matches!(edit_response, UnitResult::Success));
+
+    let args = group_canister::message_edit_history::Args {
+        thread_root_message_index: None,
+        message_id,
+    };
+
+    let group_canister::message_edit_history::Response::Success(result) =
+        client::group::message_edit_history(env, user.principal, group_id.into(), &args)
+    else {
+        panic!()
+    };
+    assert_eq!(result.revisions.len(), 2);
+
+    client::group::happy_path::update_group(
+        env,
+        user.principal,
+        group_id,
+        &group_canister::update_group_v2::Args {
+            edit_history_disabled: Some(true),
+            ..Default::default()
+        },
+    );
+
+    let response = client::group::message_edit_history(env, user.principal, group_id.into(), &args);
+    assert!(matches!(
+        response,
+        group_canister::message_edit_history::Response::Error(e) if e.matches_code(OCErrorCode::EditHistoryDisabled)
+    ));
+}
@@ -0,0 +1,464 @@
+use crate::env::ENV;
+use crate::utils::tick_many;
+use crate::{CanisterIds, TestEnv, User, client};
+use candid::Principal;
+use oc_error_codes::OCErrorCode;
+use pocket_ic::PocketIc;
+use std::ops::Deref;
+use std::time::Duration;
+use testing::rng::{random_from_u128, random_string};
+use types::{
+    Chat, ChatEventType, ChatId, Empty, EncryptedContent, EncryptedMessageContentType, MessageContentInitial, TextContent,
+};
+
+#[test]
+fn key_epoch_rotates_when_member_removed() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids, *controller, true);
+
+    let summary = client::group::happy_path::summary(env, user1.principal, group_id);
+    assert_eq!(summary.e2ee_key_epoch, Some(1));
+
+    let remove_member_response = client::group::remove_participant(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::remove_participant::Args { user_id: user2.user_id },
+    );
+    assert!(matches!(
+        remove_member_response,
+        group_canister::remove_participant::Response::Success
+    ));
+
+    let summary = client::group::happy_path::summary(env, user1.principal, group_id);
+    assert_eq!(summary.e2ee_key_epoch, Some(2));
+}
+
+#[test]
+fn key_epoch_rotates_when_member_leaves() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids, *controller, true);
+
+    client::user::happy_path::leave_group(env, &user2, group_id);
+
+    let summary = client::group::happy_path::summary(env, user1.principal, group_id);
+    assert_eq!(summary.e2ee_key_epoch, Some(2));
+}
+
+#[test]
+fn new_member_cannot_get_keys_from_before_they_joined() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids, *controller, false);
+
+    client::user::happy_path::leave_group(env, &user2, group_id);
+    env.advance_time(Duration::from_secs(1));
+
+    let user3 = join_new_member(env, canister_ids, &user1, group_id);
+
+    // The new member joined during epoch 2, so they can't derive the key of epoch 1
+    assert!(!can_get_key_token(env, canister_ids, &user3, group_id, 1));
+    assert!(can_get_key_token(env, canister_ids, &user3, group_id, 2));
+    assert!(!can_get_key_token(env, canister_ids, &user3, group_id, 3));
+
+    // Whereas existing members can still derive the keys of earlier epochs
+    assert!(can_get_key_token(env, canister_ids, &user1, group_id, 1));
+}
+
+#[test]
+fn new_member_can_get_earlier_keys_if_history_visible() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids, *controller, true);
+
+    client::user::happy_path::leave_group(env, &user2, group_id);
+    env.advance_time(Duration::from_secs(1));
+
+    let user3 = join_new_member(env, canister_ids, &user1, group_id);
+
+    assert!(can_get_key_token(env, canister_ids, &user3, group_id, 1));
+    assert!(can_get_key_token(env, canister_ids, &user3, group_id, 2));
+}
+
+#[test]
+fn plaintext_messages_rejected() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, group_id, .. } = init_test_data(env, canister_ids, *controller, true);
+
+    let text_response = send_message(
+        env,
+        &user1,
+        group_id,
+        MessageContentInitial::Text(TextContent {
+            text: random_string(),
+            components: None,
+        }),
+    );
+    assert!(matches!(
+        text_response,
+        group_canister::send_message_v2::Response::Error(e) if e.matches_code(OCErrorCode::EndToEndEncrypted)
+    ));
+
+    let encrypted_response = send_message(
+        env,
+        &user1,
+        group_id,
+        MessageContentInitial::Encrypted(EncryptedContent {
+            content_type: EncryptedMessageContentType::Text,
+            version: 1,
+            encrypted_message_key: vec![1; 32].into(),
+            public_key: vec![2; 32].into(),
+            encrypted_data: vec![3; 100],
+        }),
+    );
+    assert!(matches!(
+        encrypted_response,
+        group_canister::send_message_v2::Response::Success(_)
+    ));
+}
+
+#[test]
+fn end_to_end_encrypted_group_cannot_be_made_public() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, group_id, .. } = init_test_data(env, canister_ids, *controller, true);
+
+    let response = client::group::update_group_v2(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::update_group_v2::Args {
+            public: Some(true),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(
+        response,
+        group_canister::update_group_v2::Response::Error(e) if e.matches_code(OCErrorCode::EndToEndEncrypted)
+    ));
+}
+
+#[test]
+fn chat_encryption_key_requires_valid_access_token() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, group_id, .. } = init_test_data(env, canister_ids, *controller, true);
+
+    let (member, member_auth) = client::register_user_and_include_auth(env, canister_ids);
+    client::local_user_index::happy_path::invite_users_to_group(
+        env,
+        &user1,
+        canister_ids.local_user_index(env, group_id),
+        group_id,
+        vec![member.user_id],
+    );
+    client::group::happy_path::join_group(env, member.principal, group_id);
+
+    let (_, non_member_auth) = client::register_user_and_include_auth(env, canister_ids);
+    let other_group_id = client::user::happy_path::create_group(env, &user1, &random_string(), false, true);
+
+    tick_many(env, 3);
+
+    let key_epoch = 1;
+    let access_token = key_token(env, canister_ids, &member, group_id, key_epoch);
+    let member_principal = member_auth.auth_principal();
+    let chat = Chat::Group(group_id);
+
+    // No token
+    let response = get_encryption_key(env, canister_ids, member_principal, chat, key_epoch, None);
+    assert!(
+        matches!(&response, identity_canister::get_encryption_key::Response::Error(e) if e.matches_code(OCErrorCode::InvalidRequest)),
+        "{response:?}"
+    );
+
+    // A token issued to another user
+    let response = get_encryption_key(
+        env,
+        canister_ids,
+        non_member_auth.auth_principal(),
+        chat,
+        key_epoch,
+        Some(access_token.clone()),
+    );
+    assert!(is_not_authorized(&response), "{response:?}");
+
+    // A token issued for another chat
+    let response = get_encryption_key(
+        env,
+        canister_ids,
+        member_principal,
+        Chat::Group(other_group_id),
+        key_epoch,
+        Some(access_token.clone()),
+    );
+    assert!(is_not_authorized(&response), "{response:?}");
+
+    // A token issued for another key epoch
+    let response = get_encryption_key(env, canister_ids, member_principal, chat, 0, Some(access_token.clone()));
+    assert!(is_not_authorized(&response), "{response:?}");
+
+    // A valid token passes the membership check. The test environment has no vetKD key, so whether
+    // the key itself is derived isn't asserted here.
+    let response = get_encryption_key(
+        env,
+        canister_ids,
+        member_principal,
+        chat,
+        key_epoch,
+        Some(access_token.clone()),
+    );
+    assert!(!is_not_authorized(&response), "{response:?}");
+
+    // An expired token
+    env.advance_time(Duration::from_secs(5 * 60 + 1));
+    let response = get_encryption_key(env, canister_ids, member_principal, chat, key_epoch, Some(access_token));
+    assert!(is_not_authorized(&response), "{response:?}");
+}
+
+#[test]
+fn outgoing_webhooks_receive_no_events_once_end_to_end_encrypted() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let user = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &user, &random_string(), false, true);
+
+    let register_args = group_canister::register_outgoing_webhook::Args {
+        name: random_string(),
+        url: format!("https://example.com/{}", random_string()),
+        event_types: [ChatEventType::Message].into_iter().collect(),
+    };
+    let register_response = client::group::register_outgoing_webhook(env, user.principal, group_id.into(), &register_args);
+    let group_canister::register_outgoing_webhook::Response::Success(webhook) = register_response else {
+        panic!("'register_outgoing_webhook' error: {register_response:?}");
+    };
+
+    client::group::happy_path::update_group(
+        env,
+        user.principal,
+        group_id,
+        &group_canister::update_group_v2::Args {
+            e2ee_enabled: Some(true),
+            ..Default::default()
+        },
+    );
+
+    let send_response = send_message(
+        env,
+        &user,
+        group_id,
+        MessageContentInitial::Encrypted(EncryptedContent {
+            content_type: EncryptedMessageContentType::Text,
+            version: 1,
+            encrypted_message_key: vec![1; 32].into(),
+            public_key: vec![2; 32].into(),
+            encrypted_data: vec![3; 100],
+        }),
+    );
+    assert!(matches!(send_response, group_canister::send_message_v2::Response::Success(_)));
+    tick_many(env, 3);
+
+    let response = client::group::outgoing_webhooks(env, user.principal, group_id.into(), &Empty {});
+    let group_canister::outgoing_webhooks::Response::Success(result) = response else {
+        panic!("'outgoing_webhooks' error: {response:?}");
+    };
+    let details = result.webhooks.iter().find(|w| w.id == webhook.id).unwrap();
+    assert!(details.deliveries.is_empty());
+
+    // New outgoing webhooks can't be registered
+    let register_response = client::group::register_outgoing_webhook(env, user.principal, group_id.into(), &register_args);
+    assert!(matches!(
+        register_response,
+        group_canister::register_outgoing_webhook::Response::Error(e) if e.matches_code(OCErrorCode::EndToEndEncrypted)
+    ));
+}
+
+fn send_message(
+    env: &mut PocketIc,
+    sender: &User,
+    group_id: ChatId,
+    content: MessageContentInitial,
+) -> group_canister::send_message_v2::Response {
+    client::group::send_message_v2(
+        env,
+        sender.principal,
+        group_id.into(),
+        &group_canister::send_message_v2::Args {
+            thread_root_message_index: None,
+            message_id: random_from_u128(),
+            content,
+            sender_name: sender.username(),
+            sender_display_name: None,
+            replies_to: None,
+            mentioned: Vec::new(),
+            forwarding: false,
+            block_level_markdown: false,
+            rules_accepted: None,
+            message_filter_failed: None,
+            new_achievement: false,
+            og_previews: Vec::new(),
+        },
+    )
+}
+
+fn join_new_member(env: &mut PocketIc, canister_ids: &CanisterIds, inviter: &User, group_id: ChatId) -> User {
+    let user = client::register_user(env, canister_ids);
+    client::local_user_index::happy_path::invite_users_to_group(
+        env,
+        inviter,
+        canister_ids.local_user_index(env, group_id),
+        group_id,
+        vec![user.user_id],
+    );
+    client::group::happy_path::join_group(env, user.principal, group_id);
+    user
+}
+
+fn can_get_key_token(env: &PocketIc, canister_ids: &CanisterIds, user: &User, group_id: ChatId, key_epoch: u32) -> bool {
+    let response = client::local_user_index::access_token_v2(
+        env,
+        user.principal,
+        canister_ids.local_user_index(env, group_id),
+        &local_user_index_canister::access_token_v2::Args::ChatEncryptionKey(
+            local_user_index_canister::access_token_v2::ChatEncryptionKeyArgs {
+                chat: Chat::Group(group_id),
+                key_epoch,
+            },
+        ),
+    );
+    matches!(response, local_user_index_canister::access_token_v2::Response::Success(_))
+}
+
+fn key_token(env: &PocketIc, canister_ids: &CanisterIds, user: &User, group_id: ChatId, key_epoch: u32) -> String {
+    let response = client::local_user_index::access_token_v2(
+        env,
+        user.principal,
+        canister_ids.local_user_index(env, group_id),
+        &local_user_index_canister::access_token_v2::Args::ChatEncryptionKey(
+            local_user_index_canister::access_token_v2::ChatEncryptionKeyArgs {
+                chat: Chat::Group(group_id),
+                key_epoch,
+            },
+        ),
+    );
+    match response {
+        local_user_index_canister::access_token_v2::Response::Success(token) => token,
+        response => panic!("'access_token_v2' error: {response:?}"),
+    }
+}
+
+fn get_encryption_key(
+    env: &mut PocketIc,
+    canister_ids: &CanisterIds,
+    auth_principal: Principal,
+    chat: Chat,
+    key_epoch: u32,
+    access_token: Option<String>,
+) -> identity_canister::get_encryption_key::Response {
+    client::identity::get_encryption_key(
+        env,
+        auth_principal,
+        canister_ids.identity,
+        &identity_canister::get_encryption_key::Args {
+            key_type: identity_canister::get_encryption_key::KeyType::Chat(chat),
+            transport_public_key: vec![0; 48],
+            access_token,
+            key_epoch,
+        },
+    )
+}
+
+fn is_not_authorized(response: &identity_canister::get_encryption_key::Response) -> bool {
+    matches!(response, identity_canister::get_encryption_key::Response::Error(e) if e.matches_code(OCErrorCode::InitiatorNotAuthorized))
+}
+
+fn init_test_data(
+    env: &mut PocketIc,
+    canister_ids: &CanisterIds,
+    controller: Principal,
+    history_visible_to_new_joiners: bool,
+) -> TestData {
+    let user1 = client::register_diamond_user(env, canister_ids, controller);
+    let user2 = client::register_user(env, canister_ids);
+
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), false, history_visible_to_new_joiners);
+
+    client::local_user_index::happy_path::invite_users_to_group(
+        env,
+        &user1,
+        canister_ids.local_user_index(env, group_id),
+        group_id,
+        vec![user2.user_id],
+    );
+    client::group::happy_path::join_group(env, user2.principal, group_id);
+
+    client::group::happy_path::update_group(
+        env,
+        user1.principal,
+        group_id,
+        &group_canister::update_group_v2::Args {
+            e2ee_enabled: Some(true),
+            ..Default::default()
+        },
+    );
+
+    tick_many(env, 3);
+
+    TestData { user1, user2, group_id }
+}
+
+struct TestData {
+    user1: User,
+    user2: User,
+    group_id: ChatId,
+}
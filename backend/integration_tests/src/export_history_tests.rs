@@ -0,0 +1,100 @@
+use crate::env::ENV;
+use crate::{TestEnv, client};
+use oc_error_codes::OCErrorCode;
+use std::ops::Deref;
+use testing::rng::random_string;
+use types::{ChatEvent, ChatExportRecord, GroupRole};
+
+#[test]
+fn export_history_pages_through_all_events() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::register_user(env, canister_ids);
+    let user2 = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), false, true);
+    client::group::happy_path::join_group(env, user2.principal, group_id);
+    // Enough members that they span multiple pages
+    for _ in 0..6 {
+        let user = client::register_user(env, canister_ids);
+        client::group::happy_path::join_group(env, user.principal, group_id);
+    }
+
+    for i in 0..10 {
+        client::group::happy_path::send_text_message(env, &user1, group_id, None, format!("TEXT {i}"), None);
+    }
+    let root_message = client::group::happy_path::send_text_message(env, &user2, group_id, None, "ROOT", None);
+    client::group::happy_path::send_text_message(env, &user1, group_id, Some(root_message.message_index), "REPLY", None);
+
+    let mut records = Vec::new();
+    let mut after = None;
+    loop {
+        let group_canister::export_history::Response::Success(page) = client::group::export_history(
+            env,
+            user1.principal,
+            group_id.into(),
+            &group_canister::export_history::Args { after, max_events: 5 },
+        ) else {
+            panic!()
+        };
+        assert!(page.records.len() <= 5);
+        records.extend(page.records);
+        match page.next {
+            Some(next) => after = Some(next),
+            None => break,
+        }
+    }
+
+    let members: Vec<_> = records
+        .iter()
+        .filter_map(|r| if let ChatExportRecord::Member(m) = r { Some(m) } else { None })
+        .collect();
+    assert_eq!(members.len(), 8);
+    assert!(
+        members
+            .iter()
+            .any(|m| m.user_id == user1.user_id && m.role == GroupRole::Owner)
+    );
+
+    let messages: Vec<_> = records
+        .iter()
+        .filter_map(|r| match r {
+            ChatExportRecord::Event(e) => match &e.event.event {
+                ChatEvent::Message(m) => Some((e.thread_root_message_index, m.content.text().unwrap().to_string())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(messages.len(), 12);
+    assert_eq!(messages.first().unwrap(), &(None, "TEXT 0".to_string()));
+    assert_eq!(
+        messages.last().unwrap(),
+        &(Some(root_message.message_index), "REPLY".to_string())
+    );
+}
+
+#[test]
+fn export_history_requires_permission() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::register_user(env, canister_ids);
+    let user2 = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), false, true);
+    client::group::happy_path::join_group(env, user2.principal, group_id);
+
+    let response = client::group::export_history(
+        env,
+        user2.principal,
+        group_id.into(),
+        &group_canister::export_history::Args {
+            after: None,
+            max_events: 100,
+        },
+    );
+    assert!(matches!(
+        response,
+        group_canister::export_history::Response::Error(e) if e.matches_code(OCErrorCode::InitiatorNotAuthorized)
+    ));
+}
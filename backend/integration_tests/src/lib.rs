@@ -24,8 +24,10 @@ mod delete_user_tests;
 mod diamond_membership_tests;
 mod disappearing_message_tests;
 mod edit_message_tests;
+mod end_to_end_encryption_tests;
 mod env;
 mod escrow_tests;
+mod export_history_tests;
 mod fire_and_forget_handler_tests;
 mod freeze_group_tests;
 mod gated_group_tests;
@@ -45,6 +47,7 @@ mod register_user_tests;
 mod registry_tests;
 mod remove_from_group_tests;
 mod save_crypto_account_tests;
+mod scheduled_message_tests;
 mod send_crypto_tests;
 mod send_direct_message_tests;
 mod set_message_reminder_tests;
@@ -55,6 +58,7 @@ mod storage;
 mod storage_tests;
 mod suspend_user_tests;
 mod tip_message_tests;
+mod translate_message_tests;
 mod update_group_tests;
 mod update_profile_tests;
 mod utils;
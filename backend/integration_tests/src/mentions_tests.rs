@@ -40,7 +40,7 @@ fn mention_users_succeeds(mention_everyone: bool) {
         &group_canister::send_message_v2::Args {
             thread_root_message_index: None,
             message_id,
-            content: MessageContentInitial::Text(TextContent { text }),
+            content: MessageContentInitial::Text(TextContent { text, components: None }),
             sender_name: user1.username(),
             sender_display_name: None,
             replies_to: None,
@@ -118,6 +118,7 @@ fn mention_everyone_only_succeeds_if_authorized(authorized: bool) {
             message_id,
             content: MessageContentInitial::Text(TextContent {
                 text: "Hello @everyone!".to_string(),
+                components: None,
             }),
             sender_name: user1.username(),
             sender_display_name: None,
@@ -179,6 +180,7 @@ fn mentioned_in_thread_adds_user_as_follower() {
             message_id: random_from_u128(),
             content: MessageContentInitial::Text(TextContent {
                 text: format!("Hello @UserId({})!", user2.user_id),
+                components: None,
             }),
             sender_name: user1.username(),
             sender_display_name: None,
@@ -11,8 +11,8 @@ use test_case::test_case;
 use testing::rng::{random_from_u128, random_string};
 use types::{
     Chat, ChatType, CryptoContent, CryptoTransaction, DiamondMembershipPlanDuration, GroupReplyContext, MessageContentInitial,
-    P2PSwapContentInitial, PendingCryptoTransaction, PollConfig, PollContent, PollVotes, TextContent, TimestampMillis,
-    TotalVotes,
+    P2PSwapContentInitial, PendingCryptoTransaction, PollConfig, PollContent, PollVotes, PollVotingSystem, TextContent,
+    TimestampMillis, TotalVotes,
 };
 use user_canister::MessageActivity;
 
@@ -103,7 +103,7 @@ fn mention_user_and_check_activity_feed(chat_type: ChatType) {
 
     let text = format!("Hello @UserId({})!", us.user_id);
     let message_id = random_from_u128();
-    let content = MessageContentInitial::Text(TextContent { text });
+    let content = MessageContentInitial::Text(TextContent { text, components: None });
     let mentioned = vec![(&us).into()];
 
     // Send a message from them mentioning us
@@ -274,7 +274,10 @@ fn quote_reply_to_message_and_check_activity_feed(chat_type: ChatType) {
     let TestData { them, us, chat } = init_test_data(env, canister_ids, *controller, chat_type);
 
     let text = "Hello World";
-    let content = MessageContentInitial::Text(TextContent { text: text.to_string() });
+    let content = MessageContentInitial::Text(TextContent {
+        text: text.to_string(),
+        components: None,
+    });
 
     match chat {
         Chat::Direct(_) => unreachable!(),
@@ -335,10 +338,14 @@ fn vote_on_poll_and_check_activity_feed(chat_type: ChatType) {
             show_votes_before_end_date: true,
             allow_multiple_votes_per_user: false,
             allow_user_to_change_vote: false,
+            voting_system: PollVotingSystem::Plurality,
+            vote_weighting: None,
+            quorum: None,
         },
         votes: PollVotes {
             total: TotalVotes::Visible(HashMap::new()),
             user: Vec::new(),
+            result: None,
         },
         ended: false,
     });
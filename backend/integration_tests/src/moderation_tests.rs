@@ -2494,6 +2494,7 @@ fn upload_declaring_hash_expecting(
             total_size: file.len() as u64,
             bytes: file.to_vec(),
             expiry: None,
+            encryption: None,
         },
     );
     assert!(expected(&response), "{response:?}");
@@ -3612,6 +3613,7 @@ fn post_verdict_forward_is_still_reported() {
         &storage_bucket_canister::forward_file::Args {
             file_id: reporter_bucket.file_id,
             accessors: vec![test_data.reporter.canister()],
+            wrapped_keys: None,
         },
     );
     assert!(
@@ -3648,6 +3650,7 @@ fn post_verdict_forward_is_still_reported() {
         &storage_bucket_canister::forward_file::Args {
             file_id: reporter_bucket.file_id,
             accessors: vec![test_data.reporter.canister()],
+            wrapped_keys: None,
         },
     );
     assert!(
@@ -4150,6 +4153,7 @@ fn post_verdict_reupload_of_same_file_id_is_still_reported() {
                 total_size: file.len() as u64,
                 bytes: file.clone(),
                 expiry: None,
+                encryption: None,
             },
         )
     };
@@ -2,7 +2,7 @@ use crate::env::ENV;
 use crate::utils::{now_millis, tick_many};
 use crate::{CanisterIds, TestEnv, User, client};
 use candid::Principal;
-use constants::{DAY_IN_MS, NANOS_PER_MILLISECOND};
+use constants::{DAY_IN_MS, MINUTE_IN_MS, NANOS_PER_MILLISECOND};
 use itertools::Itertools;
 use pocket_ic::{PocketIc, Time};
 use rand::{Rng, rng};
@@ -11,7 +11,12 @@ use std::ops::Deref;
 use std::time::Duration;
 use test_case::test_case;
 use testing::rng::{random_from_u128, random_string};
-use types::{Empty, MessageContentInitial, NotificationSubscription, TextContent};
+use types::{
+    Empty, MessageContentInitial, NotificationSubscription, PushBackend, QuietHours, SubscriptionInfo, SubscriptionKeys,
+    TextContent,
+};
+
+const HTTPS_PUSH_SECRET: &str = "0123456789abcdef0123456789abcdef";
 
 #[test]
 fn direct_message_notification_succeeds() {
@@ -184,7 +189,7 @@ fn group_message_notification_muted(case: u32) {
         &group_canister::send_message_v2::Args {
             thread_root_message_index: None,
             message_id: random_from_u128(),
-            content: MessageContentInitial::Text(TextContent { text }),
+            content: MessageContentInitial::Text(TextContent { text, components: None }),
             sender_name: user1.username(),
             sender_display_name: None,
             replies_to: None,
@@ -212,6 +217,84 @@ fn group_message_notification_muted(case: u32) {
     }
 }
 
+#[test_case(true)]
+#[test_case(false)]
+fn group_message_notification_filters(mentions_only: bool) {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2 } = init_test_data(env, canister_ids);
+
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), false, false);
+    let local_user_index_canister = canister_ids.local_user_index(env, group_id);
+    client::local_user_index::happy_path::add_users_to_group(
+        env,
+        &user1,
+        local_user_index_canister,
+        group_id,
+        vec![(user2.user_id, user2.principal)],
+    );
+
+    if !mentions_only {
+        client::group::toggle_mute_notifications(
+            env,
+            user2.principal,
+            group_id.into(),
+            &group_canister::toggle_mute_notifications::Args {
+                mute: Some(true),
+                mute_at_everyone: None,
+            },
+        );
+    }
+
+    let response = client::group::set_notification_filters(
+        env,
+        user2.principal,
+        group_id.into(),
+        &group_canister::set_notification_filters::Args {
+            mentions_only: Some(mentions_only),
+            keyword_alerts: Some(vec!["Outage".to_string()]),
+        },
+    );
+    assert!(matches!(
+        response,
+        group_canister::set_notification_filters::Response::Success
+    ));
+
+    let latest_notification_index =
+        client::local_user_index::happy_path::latest_notification_index(env, *controller, local_user_index_canister);
+
+    client::group::happy_path::send_text_message(env, &user1, group_id, None, random_string(), None);
+    client::group::happy_path::send_text_message(env, &user1, group_id, None, "@everyone", None);
+    client::group::happy_path::send_text_message(env, &user1, group_id, None, "Timeouts are not outages", None);
+
+    let notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    // Muted members are still notified of @everyone unless they only want mentions and replies
+    assert_eq!(notifications_response.notifications.len(), if mentions_only { 0 } else { 1 });
+
+    client::group::happy_path::send_text_message(env, &user1, group_id, None, "There is an OUTAGE!", None);
+
+    let notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    assert_eq!(notifications_response.notifications.len(), if mentions_only { 1 } else { 2 });
+}
+
 #[test]
 fn only_store_up_to_10_subscriptions_per_user() {
     let mut wrapper = ENV.deref().get();
@@ -391,6 +474,166 @@ fn inactive_subscriptions_removed() {
     ));
 }
 
+#[test]
+fn subscriptions_returned_with_their_push_backend() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2 } = init_test_data(env, canister_ids);
+
+    client::notifications_index::happy_path::push_subscription_with_backend(
+        env,
+        user2.principal,
+        canister_ids.notifications_index,
+        "abc",
+        "def",
+        "https://ntfy.example/up123",
+        PushBackend::UnifiedPush,
+    );
+    client::notifications_index::happy_path::push_subscription_with_backend(
+        env,
+        user2.principal,
+        canister_ids.notifications_index,
+        "ghi",
+        "jkl",
+        "https://desktop.example/push",
+        PushBackend::HttpsPush(HTTPS_PUSH_SECRET.to_string()),
+    );
+
+    tick_many(env, 3);
+
+    assert!(client::notifications_index::happy_path::subscription_exists(
+        env,
+        user2.principal,
+        canister_ids.notifications_index,
+        "https://ntfy.example/up123"
+    ));
+
+    let local_user_index_canister = canister_ids.local_user_index(env, user2.canister());
+    let latest_notification_index =
+        client::local_user_index::happy_path::latest_notification_index(env, *controller, local_user_index_canister);
+
+    client::user::happy_path::send_text_message(env, &user1, user2.user_id, random_string(), None);
+
+    tick_many(env, 3);
+
+    let mut notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    let subscriptions = notifications_response.subscriptions.remove(&user2.user_id).unwrap();
+
+    assert!(
+        subscriptions
+            .iter()
+            .any(|s| matches!(s, NotificationSubscription::WebPush(s) if s.endpoint == "https://xyz.com/"))
+    );
+    assert!(
+        subscriptions
+            .iter()
+            .any(|s| matches!(s, NotificationSubscription::UnifiedPush(s) if s.endpoint == "https://ntfy.example/up123"))
+    );
+    assert!(subscriptions.iter().any(|s| matches!(
+        s,
+        NotificationSubscription::HttpsPush(s) if s.subscription.endpoint == "https://desktop.example/push" && s.secret == HTTPS_PUSH_SECRET
+    )));
+}
+
+#[test]
+fn existing_subscription_updated_when_pushed_with_new_backend() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2 } = init_test_data(env, canister_ids);
+
+    // The subscription to "https://xyz.com/" was pushed with the default `WebPush` backend in
+    // `init_test_data`, now push it again as a UnifiedPush subscription
+    client::notifications_index::happy_path::push_subscription_with_backend(
+        env,
+        user2.principal,
+        canister_ids.notifications_index,
+        "123",
+        "456",
+        "https://xyz.com/",
+        PushBackend::UnifiedPush,
+    );
+
+    tick_many(env, 3);
+
+    let local_user_index_canister = canister_ids.local_user_index(env, user2.canister());
+    let latest_notification_index =
+        client::local_user_index::happy_path::latest_notification_index(env, *controller, local_user_index_canister);
+
+    client::user::happy_path::send_text_message(env, &user1, user2.user_id, random_string(), None);
+
+    tick_many(env, 3);
+
+    let mut notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    let subscriptions = notifications_response.subscriptions.remove(&user2.user_id).unwrap();
+
+    assert_eq!(subscriptions.len(), 1);
+    assert!(matches!(
+        &subscriptions[0],
+        NotificationSubscription::UnifiedPush(s) if s.endpoint == "https://xyz.com/" && s.keys.auth == "123"
+    ));
+}
+
+#[test_case("too_short"; "Too short")]
+#[test_case(&"a".repeat(257); "Too long")]
+#[test_case("0123456789abcdef 0123456789abcdef"; "Invalid character")]
+fn push_subscription_with_invalid_secret_fails(secret: &str) {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let TestData { user1: _, user2 } = init_test_data(env, canister_ids);
+
+    let response = client::notifications_index::push_subscription(
+        env,
+        user2.principal,
+        canister_ids.notifications_index,
+        &notifications_index_canister::push_subscription::Args {
+            subscription: SubscriptionInfo {
+                keys: SubscriptionKeys {
+                    auth: "abc".to_string(),
+                    p256dh: "def".to_string(),
+                },
+                endpoint: "https://desktop.example/push".to_string(),
+            },
+            backend: PushBackend::HttpsPush(secret.to_string()),
+        },
+    );
+
+    assert!(matches!(
+        response,
+        notifications_index_canister::push_subscription::Response::InvalidSecret
+    ));
+    assert!(!client::notifications_index::happy_path::subscription_exists(
+        env,
+        user2.principal,
+        canister_ids.notifications_index,
+        "https://desktop.example/push"
+    ));
+}
+
 #[test]
 fn notifications_blocked_from_blocked_users() {
     let mut wrapper = ENV.deref().get();
@@ -464,6 +707,135 @@ fn notifications_blocked_from_blocked_users() {
     assert!(notifications_response.subscriptions.contains_key(&user2.user_id));
 }
 
+#[test_case(true)]
+#[test_case(false)]
+fn notifications_held_back_during_quiet_hours(digest: bool) {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2 } = init_test_data(env, canister_ids);
+
+    // Start the quiet window at the current minute (UTC) and end it an hour later
+    let minute_of_day = ((now_millis(env) % DAY_IN_MS) / MINUTE_IN_MS) as u16;
+    client::user::happy_path::set_quiet_hours(
+        env,
+        &user2,
+        Some(QuietHours {
+            start: minute_of_day,
+            end: (minute_of_day + 60) % (24 * 60),
+            digest,
+        }),
+    );
+
+    tick_many(env, 10);
+
+    let local_user_index_canister = canister_ids.local_user_index(env, user2.canister());
+    let latest_notification_index =
+        client::local_user_index::happy_path::latest_notification_index(env, *controller, local_user_index_canister);
+
+    client::user::happy_path::send_text_message(env, &user1, user2.user_id, random_string(), None);
+    client::user::happy_path::send_text_message(env, &user1, user2.user_id, random_string(), None);
+
+    tick_many(env, 3);
+
+    let notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    assert!(notifications_response.notifications.is_empty());
+
+    env.advance_time(Duration::from_millis(61 * MINUTE_IN_MS));
+    tick_many(env, 3);
+
+    let notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    // If digests are enabled, the held back notifications are summarised in a single notification
+    assert_eq!(notifications_response.notifications.len(), if digest { 1 } else { 0 });
+
+    client::user::happy_path::send_text_message(env, &user1, user2.user_id, random_string(), None);
+
+    tick_many(env, 3);
+
+    let notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    assert_eq!(notifications_response.notifications.len(), if digest { 2 } else { 1 });
+}
+
+#[test]
+fn turning_off_quiet_hours_sends_pending_digest() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let TestData { user1, user2 } = init_test_data(env, canister_ids);
+
+    let minute_of_day = ((now_millis(env) % DAY_IN_MS) / MINUTE_IN_MS) as u16;
+    client::user::happy_path::set_quiet_hours(
+        env,
+        &user2,
+        Some(QuietHours {
+            start: minute_of_day,
+            end: (minute_of_day + 60) % (24 * 60),
+            digest: true,
+        }),
+    );
+
+    tick_many(env, 10);
+
+    let local_user_index_canister = canister_ids.local_user_index(env, user2.canister());
+    let latest_notification_index =
+        client::local_user_index::happy_path::latest_notification_index(env, *controller, local_user_index_canister);
+
+    client::user::happy_path::send_text_message(env, &user1, user2.user_id, random_string(), None);
+
+    tick_many(env, 3);
+
+    let notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    assert!(notifications_response.notifications.is_empty());
+
+    // Turning quiet hours off part way through the window sends the pending digest straight away
+    client::user::happy_path::set_quiet_hours(env, &user2, None);
+
+    tick_many(env, 10);
+
+    let notifications_response = client::local_user_index::happy_path::notifications(
+        env,
+        *controller,
+        local_user_index_canister,
+        latest_notification_index + 1,
+    );
+
+    assert_eq!(notifications_response.notifications.len(), 1);
+}
+
 #[test]
 fn notification_canisters_returns_correct_ids() {
     let mut wrapper = ENV.deref().get();
@@ -6,7 +6,9 @@ use std::collections::HashMap;
 use std::ops::Deref;
 use std::time::{Duration, SystemTime};
 use testing::rng::random_from_u128;
-use types::{ChatEvent, ChatId, MessageContent, MessageContentInitial, PollConfig, PollContent, PollVotes, TotalVotes};
+use types::{
+    ChatEvent, ChatId, MessageContent, MessageContentInitial, PollConfig, PollContent, PollVotes, PollVotingSystem, TotalVotes,
+};
 
 #[test]
 fn allow_multiple_votes_per_user() {
@@ -21,6 +23,9 @@ fn allow_multiple_votes_per_user() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: true,
         allow_user_to_change_vote: true,
+        voting_system: PollVotingSystem::Plurality,
+        vote_weighting: None,
+        quorum: None,
     };
 
     let TestData {
@@ -52,6 +57,9 @@ fn single_vote_per_user() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: false,
         allow_user_to_change_vote: true,
+        voting_system: PollVotingSystem::Plurality,
+        vote_weighting: None,
+        quorum: None,
     };
 
     let TestData {
@@ -86,6 +94,9 @@ fn polls_ended_correctly() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: false,
         allow_user_to_change_vote: true,
+        voting_system: PollVotingSystem::Plurality,
+        vote_weighting: None,
+        quorum: None,
     };
 
     let TestData {
@@ -103,6 +114,9 @@ fn polls_ended_correctly() {
         show_votes_before_end_date: false,
         allow_multiple_votes_per_user: false,
         allow_user_to_change_vote: true,
+        voting_system: PollVotingSystem::Plurality,
+        vote_weighting: None,
+        quorum: None,
     };
 
     let create_poll_result2 = client::group::send_message_v2(
@@ -117,6 +131,7 @@ fn polls_ended_correctly() {
                 votes: PollVotes {
                     total: TotalVotes::Anonymous(HashMap::default()),
                     user: Vec::new(),
+                    result: None,
                 },
                 ended: false,
             }),
@@ -245,6 +260,7 @@ fn init_test_data(env: &mut PocketIc, canister_ids: &CanisterIds, poll_config: P
                 votes: PollVotes {
                     total: TotalVotes::Anonymous(HashMap::default()),
                     user: Vec::new(),
+                    result: None,
                 },
                 ended: false,
             }),
@@ -0,0 +1,173 @@
+use crate::env::ENV;
+use crate::utils::{now_millis, tick_many};
+use crate::{CanisterIds, TestEnv, User, client};
+use pocket_ic::PocketIc;
+use std::ops::Deref;
+use std::time::Duration;
+use testing::rng::{random_from_u128, random_string};
+use types::{
+    Chat, ChatId, GroupPermissionRole, MessageContent, MessageId, OptionalGroupPermissions, OptionalMessagePermissions,
+};
+
+#[test]
+fn scheduled_group_message_sent_when_due() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let TestData {
+        user1: _,
+        user2,
+        group_id,
+    } = init_test_data(env, canister_ids);
+
+    let message_id = random_from_u128();
+    let text = random_string();
+    let send_at = now_millis(env) + 10_000;
+
+    client::user::happy_path::schedule_text_message(env, &user2, Chat::Group(group_id), message_id, &text, send_at);
+
+    assert_eq!(client::user::happy_path::list_scheduled_messages(env, &user2, None).len(), 1);
+
+    env.advance_time(Duration::from_millis(9_000));
+    tick_many(env, 3);
+
+    assert_ne!(latest_message_id(env, &user2, group_id), Some(message_id));
+
+    env.advance_time(Duration::from_millis(1_000));
+    tick_many(env, 3);
+
+    let summary = client::group::happy_path::summary(env, user2.principal, group_id);
+    let latest_message = summary.latest_message.unwrap().event;
+    assert_eq!(latest_message.message_id, message_id);
+    assert_eq!(latest_message.sender, user2.user_id);
+    assert!(matches!(latest_message.content, MessageContent::Text(t) if t.text == text));
+
+    assert!(client::user::happy_path::list_scheduled_messages(env, &user2, None).is_empty());
+}
+
+#[test]
+fn cancelled_scheduled_message_not_sent() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let TestData {
+        user1: _,
+        user2,
+        group_id,
+    } = init_test_data(env, canister_ids);
+
+    let message_id = random_from_u128();
+    let send_at = now_millis(env) + 10_000;
+
+    let scheduled_message_id = client::user::happy_path::schedule_text_message(
+        env,
+        &user2,
+        Chat::Group(group_id),
+        message_id,
+        random_string(),
+        send_at,
+    );
+
+    client::user::happy_path::cancel_scheduled_message(env, &user2, scheduled_message_id);
+
+    assert!(client::user::happy_path::list_scheduled_messages(env, &user2, None).is_empty());
+
+    env.advance_time(Duration::from_millis(10_000));
+    tick_many(env, 3);
+
+    assert_ne!(latest_message_id(env, &user2, group_id), Some(message_id));
+}
+
+#[test]
+fn scheduled_message_not_sent_if_sender_removed_from_group() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids);
+
+    let message_id = random_from_u128();
+    let send_at = now_millis(env) + 10_000;
+
+    client::user::happy_path::schedule_text_message(env, &user2, Chat::Group(group_id), message_id, random_string(), send_at);
+
+    let remove_member_response = client::group::remove_participant(
+        env,
+        user1.principal,
+        group_id.into(),
+        &group_canister::remove_participant::Args { user_id: user2.user_id },
+    );
+    assert!(matches!(
+        remove_member_response,
+        group_canister::remove_participant::Response::Success
+    ));
+
+    env.advance_time(Duration::from_millis(10_000));
+    tick_many(env, 3);
+
+    assert_ne!(latest_message_id(env, &user1, group_id), Some(message_id));
+}
+
+#[test]
+fn scheduled_message_not_sent_if_sender_no_longer_has_permission() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let TestData { user1, user2, group_id } = init_test_data(env, canister_ids);
+
+    let message_id = random_from_u128();
+    let send_at = now_millis(env) + 10_000;
+
+    client::user::happy_path::schedule_text_message(env, &user2, Chat::Group(group_id), message_id, random_string(), send_at);
+
+    client::group::happy_path::update_group(
+        env,
+        user1.principal,
+        group_id,
+        &group_canister::update_group_v2::Args {
+            permissions_v2: Some(OptionalGroupPermissions {
+                message_permissions: Some(OptionalMessagePermissions {
+                    default: Some(GroupPermissionRole::Admins),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+
+    env.advance_time(Duration::from_millis(10_000));
+    tick_many(env, 3);
+
+    assert_ne!(latest_message_id(env, &user2, group_id), Some(message_id));
+}
+
+fn latest_message_id(env: &PocketIc, user: &User, group_id: ChatId) -> Option<MessageId> {
+    client::group::happy_path::summary(env, user.principal, group_id)
+        .latest_message
+        .map(|m| m.event.message_id)
+}
+
+fn init_test_data(env: &mut PocketIc, canister_ids: &CanisterIds) -> TestData {
+    let user1 = client::register_user(env, canister_ids);
+    let user2 = client::register_user(env, canister_ids);
+
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), false, true);
+    client::local_user_index::happy_path::invite_users_to_group(
+        env,
+        &user1,
+        canister_ids.local_user_index(env, group_id),
+        group_id,
+        vec![user2.user_id],
+    );
+    client::group::happy_path::join_group(env, user2.principal, group_id);
+
+    tick_many(env, 3);
+
+    TestData { user1, user2, group_id }
+}
+
+struct TestData {
+    user1: User,
+    user2: User,
+    group_id: ChatId,
+}
@@ -43,7 +43,10 @@ fn empty_message_fails() {
         recipient: user2.user_id,
         thread_root_message_index: None,
         message_id: random_from_u128(),
-        content: MessageContentInitial::Text(TextContent { text: String::default() }),
+        content: MessageContentInitial::Text(TextContent {
+            text: String::default(),
+            components: None,
+        }),
         replies_to: None,
         forwarding: false,
         block_level_markdown: false,
@@ -72,6 +75,7 @@ fn text_too_long_fails() {
         message_id: random_from_u128(),
         content: MessageContentInitial::Text(TextContent {
             text: (0..10001).map(|_| '1').collect(),
+            components: None,
         }),
         replies_to: None,
         forwarding: false,
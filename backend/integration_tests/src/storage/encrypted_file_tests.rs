@@ -0,0 +1,194 @@
+use crate::env::ENV;
+use crate::utils::tick_many;
+use crate::{TestEnv, client};
+use candid::Principal;
+use std::ops::Deref;
+use storage_index_canister::add_or_update_users::UserConfig;
+use testing::rng::random_principal;
+use types::{Chat, FileEncryption, HeaderField, HttpRequest, WrappedFileKey};
+use utils::hasher::hash_bytes;
+
+#[test]
+fn encrypted_file_is_served_as_opaque_download() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    let group_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let file = vec![3u8; 1000];
+    let encryption = FileEncryption {
+        wrapped_keys: vec![WrappedFileKey {
+            chat: Chat::Group(group_id.into()),
+            key_epoch: 0,
+            wrapped_key: vec![4u8; 32],
+        }],
+    };
+
+    let allocated_bucket_response =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file);
+    let bucket = allocated_bucket_response.canister_id;
+    let file_id = allocated_bucket_response.file_id;
+
+    let upload_response = client::storage_bucket::upload_chunk_v2(
+        env,
+        user_id,
+        bucket,
+        &storage_bucket_canister::upload_chunk_v2::Args {
+            file_id,
+            hash: hash_bytes(&file),
+            mime_type: "image/png".to_string(),
+            accessors: vec![group_id],
+            chunk_index: 0,
+            chunk_size: 1000,
+            total_size: file.len() as u64,
+            bytes: file,
+            expiry: None,
+            encryption: Some(encryption.clone()),
+        },
+    );
+    assert!(
+        matches!(upload_response, storage_bucket_canister::upload_chunk_v2::Response::Success),
+        "{upload_response:?}"
+    );
+
+    tick_many(env, 10);
+
+    let file_info_response = client::storage_bucket::happy_path::file_info(env, user_id, bucket, file_id);
+    let wrapped_keys = file_info_response.encryption.unwrap().wrapped_keys;
+    assert_eq!(wrapped_keys.len(), 1);
+    assert_eq!(wrapped_keys[0].chat, encryption.wrapped_keys[0].chat);
+    assert_eq!(wrapped_keys[0].wrapped_key, encryption.wrapped_keys[0].wrapped_key);
+
+    let file_response = client::http_request(env, Principal::anonymous(), bucket, &get(format!("/files/{file_id}")));
+    assert_eq!(file_response.status_code, 200);
+    assert_eq!(
+        header(&file_response.headers, "Content-Type"),
+        Some("application/octet-stream")
+    );
+    assert_eq!(header(&file_response.headers, "Content-Disposition"), Some("attachment"));
+
+    // Thumbnails are never derived from ciphertext
+    let thumbnail_response = client::http_request(env, Principal::anonymous(), bucket, &get(format!("/files/{file_id}/thumb")));
+    assert_eq!(thumbnail_response.status_code, 404);
+}
+
+#[test]
+fn forwarding_encrypted_file_requires_wrapped_keys() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    let group_id = random_principal();
+    let other_group_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let file = vec![5u8; 1000];
+    let allocated_bucket_response =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file);
+    let bucket = allocated_bucket_response.canister_id;
+    let file_id = allocated_bucket_response.file_id;
+
+    let upload_response = client::storage_bucket::upload_chunk_v2(
+        env,
+        user_id,
+        bucket,
+        &storage_bucket_canister::upload_chunk_v2::Args {
+            file_id,
+            hash: hash_bytes(&file),
+            mime_type: "image/png".to_string(),
+            accessors: vec![group_id],
+            chunk_index: 0,
+            chunk_size: 1000,
+            total_size: file.len() as u64,
+            bytes: file,
+            expiry: None,
+            encryption: Some(FileEncryption {
+                wrapped_keys: vec![WrappedFileKey {
+                    chat: Chat::Group(group_id.into()),
+                    key_epoch: 0,
+                    wrapped_key: vec![4u8; 32],
+                }],
+            }),
+        },
+    );
+    assert!(
+        matches!(upload_response, storage_bucket_canister::upload_chunk_v2::Response::Success),
+        "{upload_response:?}"
+    );
+
+    for wrapped_keys in [None, Some(Vec::new())] {
+        let forward_response = client::storage_bucket::forward_file(
+            env,
+            user_id,
+            bucket,
+            &storage_bucket_canister::forward_file::Args {
+                file_id,
+                accessors: vec![other_group_id],
+                wrapped_keys,
+            },
+        );
+        assert!(
+            matches!(
+                forward_response,
+                storage_bucket_canister::forward_file::Response::WrappedKeysRequired
+            ),
+            "{forward_response:?}"
+        );
+    }
+
+    let wrapped_key = WrappedFileKey {
+        chat: Chat::Group(other_group_id.into()),
+        key_epoch: 0,
+        wrapped_key: vec![6u8; 32],
+    };
+    let forward_response = client::storage_bucket::forward_file(
+        env,
+        user_id,
+        bucket,
+        &storage_bucket_canister::forward_file::Args {
+            file_id,
+            accessors: vec![other_group_id],
+            wrapped_keys: Some(vec![wrapped_key.clone()]),
+        },
+    );
+    let storage_bucket_canister::forward_file::Response::Success(forwarded_file_id) = forward_response else {
+        panic!("'forward_file' error: {forward_response:?}");
+    };
+
+    let file_info_response = client::storage_bucket::happy_path::file_info(env, user_id, bucket, forwarded_file_id);
+    let wrapped_keys = file_info_response.encryption.unwrap().wrapped_keys;
+    assert_eq!(wrapped_keys.len(), 1);
+    assert_eq!(wrapped_keys[0].chat, wrapped_key.chat);
+    assert_eq!(wrapped_keys[0].wrapped_key, wrapped_key.wrapped_key);
+}
+
+fn get(url: String) -> HttpRequest {
+    HttpRequest {
+        method: "GET".to_string(),
+        url,
+        headers: Vec::new(),
+        body: Vec::new(),
+    }
+}
+
+fn header<'a>(headers: &'a [HeaderField], name: &str) -> Option<&'a str> {
+    headers.iter().find(|h| h.0 == name).map(|h| h.1.as_str())
+}
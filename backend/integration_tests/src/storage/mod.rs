@@ -1,3 +1,5 @@
 mod allocation_exceeded_tests;
+mod encrypted_file_tests;
 mod file_expiry_tests;
+mod storage_usage_tests;
 mod upload_file_tests;
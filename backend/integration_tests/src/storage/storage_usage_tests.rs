@@ -0,0 +1,257 @@
+use crate::env::ENV;
+use crate::utils::tick_many;
+use crate::{TestEnv, client};
+use candid::Principal;
+use pocket_ic::PocketIc;
+use rand::{Rng, rng};
+use std::ops::Deref;
+use storage_index_canister::add_or_update_users::UserConfig;
+use testing::rng::random_principal;
+use types::{AccessorId, CanisterId, FileId, SuccessOnly};
+use utils::hasher::hash_bytes;
+
+#[test]
+fn storage_usage_breaks_down_files_and_delete_files_removes_matches() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let chat1 = random_principal();
+    let chat2 = random_principal();
+
+    let (bucket1, file_id1) = upload(env, user_id, canister_ids.storage_index, 1000, "image/png", &[chat1]);
+    let (_, file_id2) = upload(env, user_id, canister_ids.storage_index, 2000, "video/mp4", &[chat2]);
+    let (bucket3, file_id3) = upload(env, user_id, canister_ids.storage_index, 500, "image/jpeg", &[chat1]);
+
+    tick_many(env, 10);
+
+    let storage_usage_response = client::storage_index::storage_usage(
+        env,
+        user_id,
+        canister_ids.storage_index,
+        &storage_index_canister::storage_usage::Args {
+            largest_files_count: Some(2),
+            cursor: None,
+        },
+    );
+    let storage_index_canister::storage_usage::Response::Success(usage) = storage_usage_response else {
+        panic!("'storage_usage' error");
+    };
+
+    assert_eq!(usage.bytes_used, 3500);
+    assert_eq!(usage.file_count, 3);
+    assert!(usage.next_cursor.is_none());
+    assert_eq!(
+        usage.largest_files.iter().map(|f| f.file_id).collect::<Vec<_>>(),
+        vec![file_id2, file_id1]
+    );
+    assert_eq!(
+        usage
+            .by_chat
+            .iter()
+            .map(|c| (c.accessor_id, c.file_count, c.bytes))
+            .collect::<Vec<_>>(),
+        vec![(chat2, 1, 2000), (chat1, 2, 1500)]
+    );
+    assert_eq!(
+        usage
+            .by_mime_type
+            .iter()
+            .map(|m| (m.mime_type.as_str(), m.bytes))
+            .collect::<Vec<_>>(),
+        vec![("video/mp4", 2000), ("image/png", 1000), ("image/jpeg", 500)]
+    );
+
+    let delete_files_response = client::storage_index::delete_files(
+        env,
+        user_id,
+        canister_ids.storage_index,
+        &storage_index_canister::delete_files::Args {
+            older_than: None,
+            accessor_id: Some(chat1),
+            mime_type: Some("image/".to_string()),
+            cursor: None,
+        },
+    );
+    let storage_index_canister::delete_files::Response::Success(result) = delete_files_response else {
+        panic!("'delete_files' error");
+    };
+
+    assert_eq!(result.file_count, 2);
+    assert_eq!(result.total_bytes, 1500);
+    assert!(result.next_cursor.is_none());
+
+    // Files which are already pending deletion are skipped
+    let delete_files_response = client::storage_index::delete_files(
+        env,
+        user_id,
+        canister_ids.storage_index,
+        &storage_index_canister::delete_files::Args {
+            older_than: None,
+            accessor_id: Some(chat1),
+            mime_type: None,
+            cursor: None,
+        },
+    );
+    let storage_index_canister::delete_files::Response::Success(result) = delete_files_response else {
+        panic!("'delete_files' error");
+    };
+
+    assert_eq!(result.file_count, 0);
+
+    tick_many(env, 10);
+
+    assert!(!client::storage_bucket::happy_path::file_exists(
+        env, user_id, bucket1, file_id1
+    ));
+    assert!(!client::storage_bucket::happy_path::file_exists(
+        env, user_id, bucket3, file_id3
+    ));
+
+    let user_response = client::storage_index::happy_path::user(env, user_id, canister_ids.storage_index);
+    assert_eq!(user_response.bytes_used, 2000);
+}
+
+#[test]
+fn delete_files_requires_a_filter() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let response = client::storage_index::delete_files(
+        env,
+        user_id,
+        canister_ids.storage_index,
+        &storage_index_canister::delete_files::Args {
+            older_than: None,
+            accessor_id: None,
+            mime_type: None,
+            cursor: None,
+        },
+    );
+
+    assert!(matches!(
+        response,
+        storage_index_canister::delete_files::Response::FilterRequired
+    ));
+}
+
+#[test]
+fn removed_accessors_are_removed_from_file_details() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let chat1 = random_principal();
+    let chat2 = random_principal();
+
+    upload(env, user_id, canister_ids.storage_index, 1000, "image/png", &[chat1, chat2]);
+
+    tick_many(env, 10);
+
+    let remove_accessors_response = client::storage_index::remove_accessors(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        &storage_index_canister::remove_accessors::Args {
+            accessor_ids: vec![chat1],
+        },
+    );
+    assert!(matches!(remove_accessors_response, SuccessOnly::Success));
+
+    tick_many(env, 10);
+
+    let storage_usage_response = client::storage_index::storage_usage(
+        env,
+        user_id,
+        canister_ids.storage_index,
+        &storage_index_canister::storage_usage::Args {
+            largest_files_count: None,
+            cursor: None,
+        },
+    );
+    let storage_index_canister::storage_usage::Response::Success(usage) = storage_usage_response else {
+        panic!("'storage_usage' error");
+    };
+
+    assert_eq!(usage.file_count, 1);
+    assert_eq!(
+        usage
+            .by_chat
+            .iter()
+            .map(|c| (c.accessor_id, c.file_count, c.bytes))
+            .collect::<Vec<_>>(),
+        vec![(chat2, 1, 1000)]
+    );
+}
+
+fn upload(
+    env: &mut PocketIc,
+    user_id: Principal,
+    storage_index_canister_id: CanisterId,
+    size: usize,
+    mime_type: &str,
+    accessors: &[AccessorId],
+) -> (CanisterId, FileId) {
+    let mut file = vec![0; size];
+    rng().fill_bytes(file.as_mut_slice());
+
+    let allocated_bucket_response =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, storage_index_canister_id, &file);
+    let bucket = allocated_bucket_response.canister_id;
+    let file_id = allocated_bucket_response.file_id;
+
+    let response = client::storage_bucket::upload_chunk_v2(
+        env,
+        user_id,
+        bucket,
+        &storage_bucket_canister::upload_chunk_v2::Args {
+            file_id,
+            hash: hash_bytes(&file),
+            mime_type: mime_type.to_string(),
+            accessors: accessors.to_vec(),
+            chunk_index: 0,
+            chunk_size: size as u32,
+            total_size: size as u64,
+            bytes: file,
+            expiry: None,
+            encryption: None,
+        },
+    );
+    assert!(matches!(
+        response,
+        storage_bucket_canister::upload_chunk_v2::Response::Success
+    ));
+
+    (bucket, file_id)
+}
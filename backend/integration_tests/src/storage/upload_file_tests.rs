@@ -1,8 +1,10 @@
 use crate::env::ENV;
+use crate::utils::tick_many;
 use crate::{TestEnv, client};
 use std::ops::Deref;
 use storage_index_canister::add_or_update_users::UserConfig;
 use testing::rng::random_principal;
+use types::FileStatus;
 use utils::hasher::hash_bytes;
 
 #[test]
@@ -42,3 +44,201 @@ fn upload_file() {
 
     assert_eq!(user_response.bytes_used, file_size);
 }
+
+#[test]
+fn claim_file_with_existing_blob() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id1 = random_principal();
+    let user_id2 = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![
+            UserConfig {
+                user_id: user_id1,
+                byte_limit: 10000,
+            },
+            UserConfig {
+                user_id: user_id2,
+                byte_limit: 10000,
+            },
+        ],
+    );
+
+    let file = vec![2u8; 1000];
+    let file_hash = hash_bytes(&file);
+    let file_size = file.len() as u64;
+
+    let allocated_bucket_response1 =
+        client::storage_index::happy_path::allocated_bucket(env, user_id1, canister_ids.storage_index, &file);
+    assert!(!allocated_bucket_response1.blob_exists);
+
+    let bucket = allocated_bucket_response1.canister_id;
+    client::storage_bucket::happy_path::upload_file(
+        env,
+        user_id1,
+        bucket,
+        allocated_bucket_response1.file_id,
+        file.clone(),
+        Vec::new(),
+        None,
+    );
+
+    let allocated_bucket_response2 =
+        client::storage_index::happy_path::allocated_bucket(env, user_id2, canister_ids.storage_index, &file);
+    assert!(allocated_bucket_response2.blob_exists);
+    assert_eq!(allocated_bucket_response2.canister_id, bucket);
+
+    let file_id = allocated_bucket_response2.file_id;
+    let claim_file_response = client::storage_bucket::claim_file(
+        env,
+        user_id2,
+        bucket,
+        &storage_bucket_canister::claim_file::Args {
+            file_id,
+            hash: file_hash,
+            size: file_size,
+            mime_type: "test_mime_type".to_string(),
+            accessors: Vec::new(),
+            expiry: None,
+            encryption: None,
+        },
+    );
+    assert!(matches!(
+        claim_file_response,
+        storage_bucket_canister::claim_file::Response::Success
+    ));
+
+    tick_many(env, 10);
+
+    let file_info_response = client::storage_bucket::happy_path::file_info(env, user_id2, bucket, file_id);
+
+    assert!(file_info_response.is_owner);
+    assert_eq!(file_info_response.file_hash, file_hash);
+    assert_eq!(file_info_response.file_size, file_size);
+
+    let user_response = client::storage_index::happy_path::user(env, user_id2, canister_ids.storage_index);
+
+    assert_eq!(user_response.bytes_used, file_size);
+}
+
+#[test]
+fn claim_file_fails_if_blob_not_found() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let file = vec![3u8; 1000];
+
+    let allocated_bucket_response =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file);
+    assert!(!allocated_bucket_response.blob_exists);
+
+    let claim_file_response = client::storage_bucket::claim_file(
+        env,
+        user_id,
+        allocated_bucket_response.canister_id,
+        &storage_bucket_canister::claim_file::Args {
+            file_id: allocated_bucket_response.file_id,
+            hash: hash_bytes(&file),
+            size: file.len() as u64,
+            mime_type: "test_mime_type".to_string(),
+            accessors: Vec::new(),
+            expiry: None,
+            encryption: None,
+        },
+    );
+    assert!(matches!(
+        claim_file_response,
+        storage_bucket_canister::claim_file::Response::BlobNotFound
+    ));
+}
+
+#[test]
+fn resume_interrupted_upload() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user_id = random_principal();
+    client::storage_index::happy_path::add_or_update_users(
+        env,
+        canister_ids.user_index,
+        canister_ids.storage_index,
+        vec![UserConfig {
+            user_id,
+            byte_limit: 10000,
+        }],
+    );
+
+    let file: Vec<u8> = (0..3000).map(|i| (i % 101) as u8).collect();
+    let hash = hash_bytes(&file);
+    let chunk_size = 1000;
+
+    let allocated_bucket_response =
+        client::storage_index::happy_path::allocated_bucket(env, user_id, canister_ids.storage_index, &file);
+    let bucket = allocated_bucket_response.canister_id;
+    let file_id = allocated_bucket_response.file_id;
+
+    let upload_chunk = |env: &mut pocket_ic::PocketIc, chunk_index: u32| {
+        let start = (chunk_index * chunk_size) as usize;
+        client::storage_bucket::upload_chunk_v2(
+            env,
+            user_id,
+            bucket,
+            &storage_bucket_canister::upload_chunk_v2::Args {
+                file_id,
+                hash,
+                mime_type: "test_mime_type".to_string(),
+                accessors: Vec::new(),
+                chunk_index,
+                chunk_size,
+                total_size: file.len() as u64,
+                bytes: file[start..start + chunk_size as usize].to_vec(),
+                expiry: None,
+                encryption: None,
+            },
+        )
+    };
+
+    // Upload the first chunk only, as if the upload was interrupted
+    upload_chunk(env, 0);
+
+    let file_status_response =
+        client::storage_bucket::file_status(env, user_id, bucket, &storage_bucket_canister::file_status::Args { file_id });
+    let chunks_remaining = match file_status_response {
+        storage_bucket_canister::file_status::Response::Success(result) => match result.status {
+            FileStatus::Uploading(uploading) => uploading.chunks_remaining,
+            status => panic!("Unexpected file status: {status:?}"),
+        },
+        response => panic!("'file_status' error: {response:?}"),
+    };
+    assert_eq!(chunks_remaining, vec![1, 2]);
+
+    for chunk_index in chunks_remaining {
+        let response = upload_chunk(env, chunk_index);
+        assert!(matches!(
+            response,
+            storage_bucket_canister::upload_chunk_v2::Response::Success
+        ));
+    }
+
+    tick_many(env, 10);
+
+    let file_info_response = client::storage_bucket::happy_path::file_info(env, user_id, bucket, file_id);
+
+    assert_eq!(file_info_response.file_hash, hash);
+    assert_eq!(file_info_response.file_size, file.len() as u64);
+}
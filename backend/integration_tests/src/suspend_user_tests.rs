@@ -53,7 +53,10 @@ fn suspend_user() {
             recipient: user2.user_id,
             thread_root_message_index: None,
             message_id: random_from_u128(),
-            content: MessageContentInitial::Text(TextContent { text: "123".to_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: "123".to_string(),
+                components: None,
+            }),
             replies_to: None,
             forwarding: false,
             block_level_markdown: false,
@@ -76,7 +79,10 @@ fn suspend_user() {
             message_id: random_from_u128(),
             sender_name: user1.username(),
             sender_display_name: None,
-            content: MessageContentInitial::Text(TextContent { text: "123".to_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: "123".to_string(),
+                components: None,
+            }),
             replies_to: None,
             mentioned: Vec::new(),
             forwarding: false,
@@ -102,7 +108,10 @@ fn suspend_user() {
             message_id: random_from_u128(),
             sender_name: user1.username(),
             sender_display_name: None,
-            content: MessageContentInitial::Text(TextContent { text: "123".to_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: "123".to_string(),
+                components: None,
+            }),
             replies_to: None,
             mentioned: Vec::new(),
             forwarding: false,
@@ -139,7 +148,10 @@ fn suspend_user() {
             recipient: user2.user_id,
             thread_root_message_index: None,
             message_id: random_from_u128(),
-            content: MessageContentInitial::Text(TextContent { text: "123".to_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: "123".to_string(),
+                components: None,
+            }),
             replies_to: None,
             forwarding: false,
             block_level_markdown: false,
@@ -162,7 +174,10 @@ fn suspend_user() {
             message_id: random_from_u128(),
             sender_name: user1.username(),
             sender_display_name: None,
-            content: MessageContentInitial::Text(TextContent { text: "123".to_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: "123".to_string(),
+                components: None,
+            }),
             replies_to: None,
             mentioned: Vec::new(),
             forwarding: false,
@@ -188,7 +203,10 @@ fn suspend_user() {
             message_id: random_from_u128(),
             sender_name: user1.username(),
             sender_display_name: None,
-            content: MessageContentInitial::Text(TextContent { text: "123".to_string() }),
+            content: MessageContentInitial::Text(TextContent {
+                text: "123".to_string(),
+                components: None,
+            }),
             replies_to: None,
             mentioned: Vec::new(),
             forwarding: false,
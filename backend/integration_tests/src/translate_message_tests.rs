@@ -0,0 +1,170 @@
+use crate::env::ENV;
+use crate::utils::tick_many;
+use crate::{TestEnv, client};
+use oc_error_codes::OCErrorCode;
+use pocket_ic::PocketIc;
+use pocket_ic::common::rest::{CanisterHttpReply, CanisterHttpRequest, CanisterHttpResponse, MockCanisterHttpResponse};
+use serde_json::{Value, json};
+use std::ops::Deref;
+use std::time::Duration;
+use test_case::test_case;
+use testing::rng::{random_from_u128, random_string};
+use types::{MessageId, TranslationProvider, TranslationProviderConfig};
+
+#[test]
+fn translate_message_succeeds_and_caches_translation() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let operator = client::register_user(env, canister_ids);
+    client::user_index::happy_path::add_platform_operator(env, *controller, canister_ids.user_index, operator.user_id);
+    let set_config_response = client::user_index::set_translation_provider_config(
+        env,
+        operator.principal,
+        canister_ids.user_index,
+        &user_index_canister::set_translation_provider_config::Args {
+            config: Some(TranslationProviderConfig {
+                provider: TranslationProvider::DeepL,
+                api_key: random_string(),
+            }),
+        },
+    );
+    assert!(matches!(
+        set_config_response,
+        user_index_canister::set_translation_provider_config::Response::Success
+    ));
+    tick_many(env, 5);
+
+    let user = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &user, &random_string(), false, true);
+    let message_id: MessageId = random_from_u128();
+    client::group::happy_path::send_text_message(env, &user, group_id, None, "Hello", Some(message_id));
+
+    let args = group_canister::translate_message::Args {
+        thread_root_message_index: None,
+        message_id,
+        language: "ES".to_string(),
+    };
+    let call_id = env
+        .submit_call(
+            group_id.into(),
+            user.principal,
+            "translate_message_msgpack",
+            msgpack::serialize_then_unwrap(&args),
+        )
+        .unwrap();
+
+    let request = next_translation_request(env);
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["text"], json!(["Hello"]));
+    assert_eq!(body["target_lang"], "ES");
+
+    env.mock_canister_http_response(MockCanisterHttpResponse {
+        subnet_id: request.subnet_id,
+        request_id: request.request_id,
+        response: CanisterHttpResponse::CanisterHttpReply(CanisterHttpReply {
+            status: 200,
+            headers: Vec::new(),
+            body: serde_json::to_vec(&json!({ "translations": [{ "text": "Hola" }] })).unwrap(),
+        }),
+        additional_responses: Vec::new(),
+    });
+
+    let response: group_canister::translate_message::Response = client::unwrap_msgpack_response(env.await_call(call_id));
+    assert!(
+        matches!(&response, group_canister::translate_message::Response::Success(r) if r.translation == "Hola"),
+        "{response:?}"
+    );
+
+    // Language codes are normalized, so the translation cached for "ES" is returned for "es"
+    // without calling the provider again
+    let response = client::group::translate_message(
+        env,
+        user.principal,
+        group_id.into(),
+        &group_canister::translate_message::Args {
+            language: "es".to_string(),
+            ..args
+        },
+    );
+    assert!(
+        matches!(&response, group_canister::translate_message::Response::Success(r) if r.translation == "Hola"),
+        "{response:?}"
+    );
+    assert!(env.get_canister_http().is_empty());
+
+    // The translation provider is now configured, so this env mustn't be reused by other tests
+    wrapper.discard();
+}
+
+#[test_case("es", OCErrorCode::TranslationFailed)]
+#[test_case("not a language", OCErrorCode::InvalidLanguage)]
+fn translate_message_without_provider_fails(language: &str, expected: OCErrorCode) {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &user, &random_string(), false, true);
+    let message_id: MessageId = random_from_u128();
+    client::group::happy_path::send_text_message(env, &user, group_id, None, "Hello", Some(message_id));
+
+    let response = client::group::translate_message(
+        env,
+        user.principal,
+        group_id.into(),
+        &group_canister::translate_message::Args {
+            thread_root_message_index: None,
+            message_id,
+            language: language.to_string(),
+        },
+    );
+
+    assert!(matches!(
+        response,
+        group_canister::translate_message::Response::Error(e) if e.matches_code(expected)
+    ));
+}
+
+#[test]
+fn translate_message_by_non_member_fails() {
+    let mut wrapper = ENV.deref().get();
+    let TestEnv { env, canister_ids, .. } = wrapper.env();
+
+    let user1 = client::register_user(env, canister_ids);
+    let user2 = client::register_user(env, canister_ids);
+    let group_id = client::user::happy_path::create_group(env, &user1, &random_string(), false, true);
+    let message_id: MessageId = random_from_u128();
+    client::group::happy_path::send_text_message(env, &user1, group_id, None, "Hello", Some(message_id));
+
+    let response = client::group::translate_message(
+        env,
+        user2.principal,
+        group_id.into(),
+        &group_canister::translate_message::Args {
+            thread_root_message_index: None,
+            message_id,
+            language: "es".to_string(),
+        },
+    );
+
+    assert!(matches!(
+        response,
+        group_canister::translate_message::Response::Error(e) if e.matches_code(OCErrorCode::InitiatorNotInChat)
+    ));
+}
+
+fn next_translation_request(env: &mut PocketIc) -> CanisterHttpRequest {
+    for _ in 0..20 {
+        if let Some(request) = env.get_canister_http().into_iter().next() {
+            return request;
+        }
+        env.advance_time(Duration::from_millis(100));
+        env.tick();
+    }
+    panic!("No request was made to the translation provider");
+}
@@ -36,6 +36,8 @@ fn update_group_name_succeeds() {
 
             gate_config: NoChange,
             messages_visible_to_non_members: None,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -80,6 +82,8 @@ fn change_casing_of_group_name_succeeds() {
 
             gate_config: NoChange,
             messages_visible_to_non_members: None,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -127,6 +131,8 @@ fn update_group_privacy_succeeds() {
 
             gate_config: NoChange,
             messages_visible_to_non_members: None,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
@@ -176,6 +182,8 @@ fn make_private_group_public_succeeds() {
             public: Some(true),
 
             messages_visible_to_non_members: None,
+            edit_history_disabled: None,
+            e2ee_enabled: None,
         },
     );
 
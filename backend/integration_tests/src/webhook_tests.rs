@@ -1,14 +1,21 @@
 use crate::env::ENV;
-use crate::utils::now_millis;
+use crate::utils::{now_millis, tick_many};
 use crate::{TestEnv, client};
 use group_canister::send_message_v2;
+use oc_error_codes::OCErrorCode;
+use pocket_ic::PocketIc;
+use pocket_ic::common::rest::{CanisterHttpReply, CanisterHttpRequest, CanisterHttpResponse, MockCanisterHttpResponse};
 use reqwest::Url;
+use serde_json::{Value, json};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::ops::Deref;
 use std::time::Duration;
 use test_case::test_case;
 use testing::rng::random_string;
-use types::{Chat, ChatEvent, ChatType, EventIndex, MessageContent, SenderContext, UserId};
+use types::{
+    Chat, ChatEvent, ChatEventType, ChatType, Empty, EventIndex, GroupRole, MessageContent, OutgoingWebhookDeliveryStatus,
+    SenderContext, UserId, WebhookRateLimits,
+};
 
 #[test_case(ChatType::Group)]
 #[test_case(ChatType::Channel)]
@@ -80,7 +87,14 @@ fn e2e_webhook_test(chat_type: ChatType) {
     // Post a message to the webhook
     let message_text = random_string();
     let gateway_url = env.make_live(None);
-    let response = post_message_to_webhook(chat, webhook_details.id, webhook_secret, message_text.clone(), gateway_url);
+    let response = post_message_to_webhook(
+        chat,
+        webhook_details.id,
+        webhook_secret,
+        "text/plain",
+        message_text.clone(),
+        gateway_url,
+    );
     env.stop_live();
 
     let response = match response {
@@ -119,11 +133,233 @@ fn e2e_webhook_test(chat_type: ChatType) {
     assert_eq!(content.text, message_text);
 }
 
+#[test]
+fn structured_webhook_message_posted_to_thread() {
+    let mut wrapper = ENV.deref().create_new();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+    let root_message = client::group::happy_path::send_text_message(env, &owner, group_id, None, random_string(), None);
+
+    let name = random_string();
+    let webhook = client::group::happy_path::register_webhook(env, owner.principal, group_id, name, None);
+
+    let message_text = random_string();
+    let body = serde_json::json!({
+        "text": message_text,
+        "thread_root_message_index": root_message.message_index,
+        "mentioned": [{ "user_id": owner.user_id, "username": owner.username() }],
+        "block_level_markdown": false,
+    });
+
+    let gateway_url = env.make_live(None);
+    let response = post_message_to_webhook(
+        Chat::Group(group_id),
+        webhook.id,
+        webhook.secret,
+        "application/json",
+        body.to_string(),
+        gateway_url,
+    );
+    env.stop_live();
+
+    let response = response.unwrap_or_else(|e| panic!("Failed to post a message to webhook: {e}"));
+    let send_message_v2::Response::Success(_) = response else {
+        panic!("Expected a success response, but got: {response:?}");
+    };
+
+    let events_response = client::group::happy_path::thread_events(
+        env,
+        &owner,
+        group_id,
+        root_message.message_index,
+        EventIndex::default(),
+        true,
+        100,
+        100,
+    );
+
+    let ChatEvent::Message(message) = &events_response.events.last().unwrap().event else {
+        panic!("Expected a message event");
+    };
+
+    assert!(matches!(message.sender_context, Some(SenderContext::Webhook)));
+    assert!(!message.block_level_markdown);
+
+    let MessageContent::Text(content) = &message.content else {
+        panic!("Expected a text message, but got: {:?}", message.content);
+    };
+
+    assert_eq!(content.text, message_text);
+}
+
+#[test]
+fn webhook_rate_limit_applied() {
+    let mut wrapper = ENV.deref().create_new();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+
+    let register_response = client::group::register_webhook(
+        env,
+        owner.principal,
+        group_id.into(),
+        &group_canister::register_webhook::Args {
+            name: random_string(),
+            avatar: None,
+            rate_limits: Some(WebhookRateLimits {
+                per_minute: Some(1),
+                per_hour: None,
+            }),
+            require_signature: None,
+        },
+    );
+    let group_canister::register_webhook::Response::Success(webhook) = register_response else {
+        panic!("'register_webhook' error: {register_response:?}");
+    };
+
+    let gateway_url = env.make_live(None);
+    let responses: Vec<_> = (0..2)
+        .map(|_| {
+            post_message_to_webhook(
+                Chat::Group(group_id),
+                webhook.id,
+                webhook.secret.clone(),
+                "text/plain",
+                random_string(),
+                gateway_url.clone(),
+            )
+        })
+        .collect();
+    env.stop_live();
+
+    assert!(matches!(responses[0], Ok(send_message_v2::Response::Success(_))));
+    assert!(
+        matches!(&responses[1], Ok(send_message_v2::Response::Error(e)) if e.matches_code(OCErrorCode::Throttled)),
+        "{:?}",
+        responses[1]
+    );
+
+    let webhooks = client::group::happy_path::selected_initial(env, owner.principal, group_id).webhooks;
+    let metrics = webhooks.iter().find(|w| w.id == webhook.id).unwrap().metrics;
+    assert_eq!(metrics.messages_sent, 1);
+    assert_eq!(metrics.messages_rejected, 1);
+}
+
+#[test]
+fn outgoing_webhook_delivers_events_with_retries() {
+    let mut wrapper = ENV.deref().create_new();
+    let TestEnv {
+        env,
+        canister_ids,
+        controller,
+        ..
+    } = wrapper.env();
+
+    let owner = client::register_diamond_user(env, canister_ids, *controller);
+    let group_id = client::user::happy_path::create_group(env, &owner, &random_string(), true, true);
+
+    let url = format!("https://example.com/{}", random_string());
+    let register_response = client::group::register_outgoing_webhook(
+        env,
+        owner.principal,
+        group_id.into(),
+        &group_canister::register_outgoing_webhook::Args {
+            name: random_string(),
+            url: url.clone(),
+            event_types: [ChatEventType::Message].into_iter().collect(),
+        },
+    );
+    let group_canister::register_outgoing_webhook::Response::Success(webhook) = register_response else {
+        panic!("'register_outgoing_webhook' error: {register_response:?}");
+    };
+
+    let message_text = random_string();
+    let send_result = client::group::happy_path::send_text_message(env, &owner, group_id, None, &message_text, None);
+    tick_many(env, 3);
+
+    // The first attempt fails with a retryable error, so the delivery is retried after a delay
+    let request = next_outgoing_webhook_request(env, &url);
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["event"]["Chat"]["event_index"], json!(send_result.event_index));
+    assert_eq!(
+        body["event"]["Chat"]["event"]["Message"]["content"]["Text"]["text"],
+        json!(message_text)
+    );
+    for header in ["x-oc-timestamp", "x-oc-nonce", "x-oc-signature"] {
+        assert!(request.headers.iter().any(|h| h.name == header && !h.value.is_empty()));
+    }
+    respond_to_outgoing_webhook_request(env, &request, 503);
+
+    env.advance_time(Duration::from_secs(10));
+    let retry = next_outgoing_webhook_request(env, &url);
+    assert_eq!(retry.body, request.body);
+    respond_to_outgoing_webhook_request(env, &retry, 200);
+    tick_many(env, 3);
+
+    // Admins can manage outgoing webhooks as well as owners
+    let admin = client::register_user(env, canister_ids);
+    client::group::happy_path::join_group(env, admin.principal, group_id);
+    client::group::happy_path::change_role(env, owner.principal, group_id, admin.user_id, GroupRole::Admin);
+
+    let response = client::group::outgoing_webhooks(env, admin.principal, group_id.into(), &Empty {});
+    let group_canister::outgoing_webhooks::Response::Success(result) = response else {
+        panic!("'outgoing_webhooks' error: {response:?}");
+    };
+    let details = result.webhooks.iter().find(|w| w.id == webhook.id).unwrap();
+    assert!(!webhook.secret.is_empty());
+    assert_eq!(details.consecutive_failures, 0);
+
+    let delivery = details.deliveries.first().unwrap();
+    assert_eq!(delivery.event_index, send_result.event_index);
+    assert_eq!(delivery.attempts, 2);
+    assert_eq!(delivery.status, OutgoingWebhookDeliveryStatus::Delivered);
+    assert_eq!(delivery.response_status, Some(200));
+}
+
+fn next_outgoing_webhook_request(env: &mut PocketIc, url: &str) -> CanisterHttpRequest {
+    for _ in 0..20 {
+        if let Some(request) = env.get_canister_http().into_iter().find(|r| r.url == url) {
+            return request;
+        }
+        env.advance_time(Duration::from_secs(1));
+        env.tick();
+    }
+    panic!("No request was made to the outgoing webhook");
+}
+
+fn respond_to_outgoing_webhook_request(env: &mut PocketIc, request: &CanisterHttpRequest, status: u16) {
+    env.mock_canister_http_response(MockCanisterHttpResponse {
+        subnet_id: request.subnet_id,
+        request_id: request.request_id,
+        response: CanisterHttpResponse::CanisterHttpReply(CanisterHttpReply {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }),
+        additional_responses: Vec::new(),
+    });
+    env.tick();
+}
+
 fn post_message_to_webhook(
     chat: Chat,
     webhook_id: UserId,
     webhook_secret: String,
-    message: String,
+    content_type: &str,
+    body: String,
     gateway_url: Url,
 ) -> Result<send_message_v2::Response, String> {
     let port = gateway_url.port_or_known_default().ok_or("Unknown port")?;
@@ -163,8 +399,8 @@ fn post_message_to_webhook(
 
     let response = client
         .post(url)
-        .header("Content-Type", "text/plain")
-        .body(message)
+        .header("Content-Type", content_type)
+        .body(body)
         .send()
         .map_err(|e| e.to_string())?;
 
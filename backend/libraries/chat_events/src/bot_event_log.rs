@@ -0,0 +1,66 @@
+use constants::DAY_IN_MS;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use types::{ChatEventType, EventIndex, MessageIndex, Milliseconds, TimestampMillis};
+
+const RETENTION_PERIOD: Milliseconds = 7 * DAY_IN_MS;
+const MAX_ENTRIES: usize = 10_000;
+pub const MAX_REPLAY_BATCH_SIZE: usize = 100;
+
+// Records the events which bots were notified about so that a bot which missed some notifications
+// can replay them from its last acknowledged cursor
+#[derive(Serialize, Deserialize, Default)]
+pub struct BotEventLog {
+    #[serde(rename = "e")]
+    entries: BTreeSet<(TimestampMillis, Option<MessageIndex>, EventIndex, ChatEventType)>,
+    #[serde(rename = "r", default)]
+    latest_entry_removed: TimestampMillis,
+}
+
+impl BotEventLog {
+    pub fn push(
+        &mut self,
+        thread_root_message_index: Option<MessageIndex>,
+        event_index: EventIndex,
+        event_type: ChatEventType,
+        now: TimestampMillis,
+    ) {
+        self.prune(now);
+        self.entries.insert((now, thread_root_message_index, event_index, event_type));
+    }
+
+    pub fn iter_since(
+        &self,
+        since: TimestampMillis,
+    ) -> impl Iterator<Item = (TimestampMillis, Option<MessageIndex>, EventIndex, ChatEventType)> + '_ {
+        self.entries
+            .range((since.saturating_add(1), None, EventIndex::default(), ChatEventType::Message)..)
+            .copied()
+    }
+
+    pub fn latest_timestamp(&self) -> TimestampMillis {
+        self.entries.last().map_or(self.latest_entry_removed, |(ts, ..)| *ts)
+    }
+
+    // Returns true if entries newer than `since` have been pruned
+    pub fn pruned_since(&self, since: TimestampMillis) -> bool {
+        self.latest_entry_removed > since
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.latest_entry_removed == 0
+    }
+
+    fn prune(&mut self, now: TimestampMillis) {
+        let cutoff = now.saturating_sub(RETENTION_PERIOD);
+
+        while let Some((ts, ..)) = self.entries.first().copied() {
+            if ts < cutoff || self.entries.len() >= MAX_ENTRIES {
+                self.entries.pop_first();
+                self.latest_entry_removed = ts;
+            } else {
+                break;
+            }
+        }
+    }
+}
@@ -1,19 +1,23 @@
 use crate::MessageContentInternal;
 use crate::metrics::{ChatMetricsInternal, MetricKey};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::DerefMut;
 use types::{
-    AccessGateConfigInternal, AvatarChanged, BotAdded, BotMessageContext, BotRemoved, BotUpdated, ChannelId, Chat, ChatEvent,
-    ChatEventCategory, ChatEventType, ChatId, CommunityId, DeletedBy, DirectChatCreated, EventIndex, EventWrapperInternal,
-    EventsTimeToLiveUpdated, ExternalUrlUpdated, GroupCreated, GroupDescriptionChanged, GroupFrozen, GroupGateUpdated,
-    GroupInviteCodeChanged, GroupNameChanged, GroupReplyContext, GroupRulesChanged, GroupUnfrozen, GroupVisibilityChanged,
-    HistoryDeleted, MemberJoinedInternal, MemberLeft, MembersAdded, MembersAddedToDefaultChannel, MembersRemoved, Message,
-    MessageContent, MessageContentType, MessageId, MessageIndex, MessagePinned, MessageUnpinned, MultiUserChat, OgPreview,
-    PermissionsChanged, PushIfNotContains, Reaction, ReplyContext, RoleChanged, SenderContext, ThreadSummary, TimestampMillis,
-    Tips, UserId, UsersBlocked, UsersInvited, UsersUnblocked, is_default,
+    AccessGateConfigInternal, AvatarChanged, BotAdded, BotCaller, BotInitiator, BotMessageContext, BotRemoved, BotUpdated,
+    ChannelId, Chat, ChatEvent, ChatEventCategory, ChatEventType, ChatId, CommunityId, DeletedBy, DirectChatCreated,
+    EventIndex, EventWrapperInternal, EventsTimeToLiveUpdated, ExternalUrlUpdated, GroupCreated, GroupDescriptionChanged,
+    GroupFrozen, GroupGateUpdated, GroupInviteCodeChanged, GroupNameChanged, GroupReplyContext, GroupRulesChanged,
+    GroupUnfrozen, GroupVisibilityChanged, HistoryDeleted, MemberJoinedInternal, MemberLeft, MembersAdded,
+    MembersAddedToDefaultChannel, MembersRemoved, Message, MessageContent, MessageContentType, MessageId, MessageIndex,
+    MessagePinned, MessageRevision, MessageUnpinned, Milliseconds, MultiUserChat, OgPreview, PermissionsChanged,
+    PushIfNotContains, Reaction, ReplyContext, RoleChanged, SenderContext, ThreadSummary, TimestampMillis, Tips, UserId,
+    UsersBlocked, UsersInvited, UsersUnblocked, is_default,
 };
 
+const MAX_EDIT_HISTORY_LENGTH: usize = 10;
+const MAX_CACHED_TRANSLATIONS: usize = 10;
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ChatEventInternal {
     #[serde(rename = "m")]
@@ -307,9 +311,53 @@ pub struct MessageInternal {
     pub og_previews: Vec<OgPreview>,
     #[serde(rename = "mf", default, skip_serializing_if = "is_default")]
     pub moderation_flags: u32,
+    #[serde(rename = "eh", default, skip_serializing_if = "Vec::is_empty")]
+    pub edit_history: Vec<MessageRevisionInternal>,
+    // Translations of the message text keyed by language code, cleared whenever the text changes
+    #[serde(rename = "tr", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MessageRevisionInternal {
+    #[serde(rename = "c")]
+    pub content: MessageContentInternal,
+    #[serde(rename = "b", default, skip_serializing_if = "is_default")]
+    pub block_level_markdown: bool,
+    #[serde(rename = "t")]
+    pub timestamp: TimestampMillis,
+}
+
+impl MessageRevisionInternal {
+    pub fn hydrate(self, my_user_id: Option<UserId>) -> MessageRevision {
+        MessageRevision {
+            content: self.content.hydrate(my_user_id),
+            block_level_markdown: self.block_level_markdown,
+            timestamp: self.timestamp,
+        }
+    }
 }
 
 impl MessageInternal {
+    // Drops any revisions which have outlived the chat's events TTL, then trims the history down to
+    // the most recent `MAX_EDIT_HISTORY_LENGTH` revisions
+    pub fn prune_edit_history(&mut self, events_ttl: Option<Milliseconds>, now: TimestampMillis) {
+        if let Some(ttl) = events_ttl {
+            self.edit_history.retain(|r| r.timestamp.saturating_add(ttl) > now);
+        }
+        if self.edit_history.len() > MAX_EDIT_HISTORY_LENGTH {
+            self.edit_history.drain(..self.edit_history.len() - MAX_EDIT_HISTORY_LENGTH);
+        }
+    }
+
+    // Once the cache is full, the translation with the lowest language code is evicted to make room
+    pub fn cache_translation(&mut self, language: String, translation: String) {
+        if self.translations.len() >= MAX_CACHED_TRANSLATIONS && !self.translations.contains_key(&language) {
+            self.translations.pop_first();
+        }
+        self.translations.insert(language, translation);
+    }
+
     pub fn hydrate(self, my_user_id: Option<UserId>) -> Message {
         Message {
             message_index: self.message_index,
@@ -402,6 +450,28 @@ impl MessageInternal {
     pub fn bot_context(&self) -> Option<&BotMessageContext> {
         if let Some(SenderContext::Bot(bc)) = &self.sender_context { Some(bc) } else { None }
     }
+
+    // Whether the bot can still overwrite this message by sending another with the same id
+    pub fn is_open_to_bot(&self, bot_caller: &BotCaller) -> bool {
+        let Some(bot_message) = self.bot_context() else {
+            return false;
+        };
+
+        if bot_caller.bot != self.sender {
+            return false;
+        }
+
+        match &bot_caller.initiator {
+            // A bot responding to an interaction can update the message which was interacted with,
+            // even after it has been finalised
+            BotInitiator::Interaction(interaction) => interaction.message_id == self.message_id,
+            initiator => {
+                initiator.user() == bot_message.command.as_ref().map(|c| c.initiator)
+                    && initiator.command() == bot_message.command.as_ref()
+                    && !bot_message.finalised
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -596,7 +666,10 @@ mod tests {
             message_index: 1.into(),
             message_id: 1u64.into(),
             sender: Principal::from_text("4bkt6-4aaaa-aaaaf-aaaiq-cai").unwrap().into(),
-            content: MessageContentInternal::Text(TextContentInternal { text: "123".to_string() }),
+            content: MessageContentInternal::Text(TextContentInternal {
+                text: "123".to_string(),
+                components: Vec::new(),
+            }),
             sender_context: None,
             replies_to: None,
             reactions: Vec::new(),
@@ -608,6 +681,8 @@ mod tests {
             block_level_markdown: false,
             og_previews: Vec::new(),
             moderation_flags: 0,
+            edit_history: Vec::new(),
+            translations: BTreeMap::new(),
         };
 
         let message_bytes_len = msgpack::serialize_then_unwrap(&message).len();
@@ -1,8 +1,9 @@
+use crate::bot_event_log::{BotEventLog, MAX_REPLAY_BATCH_SIZE};
 use crate::chat_events_list::Reader;
 use crate::expiring_events::ExpiringEvents;
 use crate::last_updated_timestamps::LastUpdatedTimestamps;
 use crate::metrics::{ChatMetricsInternal, MetricKey};
-use crate::search_index::SearchIndex;
+use crate::search_index::{SearchIndex, SearchIndexEntry, matches_content_type_filters};
 use crate::*;
 use constants::{ONE_MB, OPENCHAT_BOT_USER_ID};
 use event_store_types::EventBuilder;
@@ -17,16 +18,17 @@ use std::mem;
 use std::ops::DerefMut;
 use tracing::error;
 use types::{
-    AuthorityReportState, BlobReference, BotChatEvent, BotNotification, CallParticipant, CanisterId, Chat, ChatEvent,
-    ChatEventCategory, ChatEventType, ChatType, CompletedCryptoTransaction, DiamondMembershipStatus, DirectChatCreated,
-    EventContext, EventIndex, EventMetaData, EventWrapper, EventWrapperInternal, EventsTimeToLiveUpdated,
-    GroupCanisterThreadDetails, GroupCreated, GroupFrozen, GroupUnfrozen, HydratedMention, Mention, Message,
-    MessageEditedEventPayload, MessageEventPayload, MessageId, MessageIndex, MessageMatch, MessageTippedEventPayload,
-    Milliseconds, ModerationCategories, ModerationReportStatus, MultiUserChat, OCResult, OgPreview, OptionUpdate,
-    P2PSwapAccepted, P2PSwapCompleted, P2PSwapCompletedEventPayload, P2PSwapContent, P2PSwapStatus, PendingCryptoTransaction,
-    PollVotes, ProposalRewardStatus, ProposalUpdate, Reaction, ReactionAddedEventPayload, RegisterVoteResult,
-    ReserveP2PSwapSuccess, SenderContext, Tally, TimestampMillis, TimestampNanos, Timestamped, Tips, UserId, VideoCall,
-    VideoCallEndedEventPayload, VideoCallParticipants, VideoCallPresence, VideoCallType, VoteOperation,
+    AuthorityReportState, BlobReference, BotCaller, BotChatEvent, BotEventsSinceResult, BotInitiator, BotNotification,
+    BotReplayedChatEvent, CallParticipant, CanisterId, Chat, ChatEvent, ChatEventCategory, ChatEventType, ChatExportCursor,
+    ChatExportEvent, ChatType, CompletedCryptoTransaction, DiamondMembershipStatus, DirectChatCreated, EventContext,
+    EventIndex, EventMetaData, EventWrapper, EventWrapperInternal, EventsTimeToLiveUpdated, GroupCanisterThreadDetails,
+    GroupCreated, GroupFrozen, GroupRole, GroupUnfrozen, HydratedMention, Mention, Message, MessageEditedEventPayload,
+    MessageEventPayload, MessageId, MessageIndex, MessageMatch, MessageRevision, MessageTippedEventPayload, Milliseconds,
+    ModerationCategories, ModerationReportStatus, MultiUserChat, OCResult, OgPreview, OptionUpdate, P2PSwapAccepted,
+    P2PSwapCompleted, P2PSwapCompletedEventPayload, P2PSwapContent, P2PSwapStatus, PendingCryptoTransaction, PollVotes,
+    ProposalRewardStatus, ProposalUpdate, Reaction, ReactionAddedEventPayload, RegisterVoteResult, ReserveP2PSwapSuccess,
+    SenderContext, Tally, TimestampMillis, TimestampNanos, Timestamped, Tips, UserId, VideoCall, VideoCallEndedEventPayload,
+    VideoCallParticipants, VideoCallPresence, VideoCallType, VoteOperation,
 };
 
 // The patchable fields of a moderation-report card; each is applied when present so that
@@ -53,6 +55,10 @@ pub struct ChatEvents {
     anonymized_id: String,
     search_index: SearchIndex,
     bot_subscriptions: BTreeMap<ChatEventType, HashSet<UserId>>,
+    #[serde(rename = "bl", default, skip_serializing_if = "BotEventLog::is_empty")]
+    bot_event_log: BotEventLog,
+    #[serde(rename = "bc", default, skip_serializing_if = "BTreeMap::is_empty")]
+    bot_event_cursors: BTreeMap<UserId, TimestampMillis>,
     #[serde(rename = "pt", default, skip_serializing_if = "BTreeMap::is_empty")]
     active_proposal_tallies: BTreeMap<EventIndex, Tally>,
 }
@@ -83,6 +89,8 @@ impl ChatEvents {
             anonymized_id: hex::encode(anonymized_id.to_be_bytes()),
             search_index: SearchIndex::default(),
             bot_subscriptions: BTreeMap::new(),
+            bot_event_log: BotEventLog::default(),
+            bot_event_cursors: BTreeMap::new(),
             active_proposal_tallies: BTreeMap::new(),
         };
 
@@ -115,6 +123,8 @@ impl ChatEvents {
             anonymized_id: hex::encode(anonymized_id.to_be_bytes()),
             search_index: SearchIndex::default(),
             bot_subscriptions: BTreeMap::new(),
+            bot_event_log: BotEventLog::default(),
+            bot_event_cursors: BTreeMap::new(),
             active_proposal_tallies: BTreeMap::new(),
         };
 
@@ -219,6 +229,8 @@ impl ChatEvents {
             block_level_markdown: args.block_level_markdown,
             og_previews: args.og_previews,
             moderation_flags: 0,
+            edit_history: Vec::new(),
+            translations: BTreeMap::new(),
         };
 
         add_to_metrics(
@@ -291,6 +303,7 @@ impl ChatEvents {
         let now = args.now;
         let chat = self.chat;
         let anonymized_id = self.anonymized_id.clone();
+        let events_ttl = self.events_ttl.value;
 
         match self.update_message(
             thread_root_message_index,
@@ -299,13 +312,13 @@ impl ChatEvents {
             now,
             true,
             ChatEventType::MessageEdited,
-            |message, event| Self::edit_message_inner(message, event, args, chat, anonymized_id, event_pusher),
+            |message, event| Self::edit_message_inner(message, event, args, chat, anonymized_id, events_ttl, event_pusher),
         ) {
             Ok(result) => {
                 let bot_notification = result.bot_notification;
-                let (message_index, event, document) = result.value;
+                let (message_index, event, search_index_entry) = result.value;
                 if thread_root_message_index.is_none() {
-                    self.search_index.push(message_index, sender, document);
+                    self.search_index.push(message_index, search_index_entry);
                 }
 
                 add_to_metrics(
@@ -335,31 +348,57 @@ impl ChatEvents {
     fn edit_message_inner<P: EventPusher>(
         message: &mut MessageInternal,
         event: EventMetaData,
-        args: EditMessageArgs,
+        mut args: EditMessageArgs,
         chat: Chat,
         anonymized_id: String,
+        events_ttl: Option<Milliseconds>,
         mut event_pusher: Option<P>,
-    ) -> Result<(MessageIndex, EventMetaData, Document), UpdateEventError<OCResult<(MessageIndex, EventMetaData)>>> {
+    ) -> Result<(MessageIndex, EventMetaData, SearchIndexEntry), UpdateEventError<OCResult<(MessageIndex, EventMetaData)>>>
+    {
         if message.sender != args.sender || matches!(message.content, MessageContentInternal::Deleted(_)) {
             return Err(UpdateEventError::NoChange(Err(OCErrorCode::InitiatorNotAuthorized.into())));
         }
 
+        // Only bots can attach components to their messages
+        if message.bot_context().is_none()
+            && let MessageContentInternal::Text(t) = &mut args.content
+        {
+            t.components.clear();
+        }
+
         let existing_text = message.content.text();
         let new_text = args.content.text();
         let block_level_markdown_update = args.block_level_markdown.filter(|md| *md != message.block_level_markdown);
 
-        if new_text != existing_text || block_level_markdown_update.is_some() || args.og_previews != message.og_previews {
+        if new_text != existing_text
+            || block_level_markdown_update.is_some()
+            || args.og_previews != message.og_previews
+            || args.content.components() != message.content.components()
+        {
             let edited = new_text.map(|t| t.replace("#LINK_REMOVED", ""))
                 != existing_text.map(|t| t.replace("#LINK_REMOVED", ""))
                 || block_level_markdown_update.is_some();
 
             let old_length = message.content.text_length();
-            message.content = args.content;
+            let previous_content = mem::replace(&mut message.content, args.content);
             message.og_previews = args.og_previews;
 
-            let document = Document::from(&message.content);
+            if message.content.text() != previous_content.text() {
+                message.translations.clear();
+            }
+
+            let search_index_entry = SearchIndexEntry::new(message.sender, &message.content);
 
             if edited {
+                if args.record_edit_history {
+                    message.edit_history.push(MessageRevisionInternal {
+                        content: previous_content,
+                        block_level_markdown: message.block_level_markdown,
+                        timestamp: message.last_edited.unwrap_or(event.timestamp),
+                    });
+                    message.prune_edit_history(events_ttl, args.now);
+                }
+
                 if let Some(block_level_markdown) = block_level_markdown_update {
                     message.block_level_markdown = block_level_markdown;
                 }
@@ -394,7 +433,7 @@ impl ChatEvents {
                     );
                 }
             }
-            return Ok((message.message_index, event, document));
+            return Ok((message.message_index, event, search_index_entry));
         }
 
         Err(UpdateEventError::NoChange(Ok((message.message_index, event))))
@@ -532,7 +571,7 @@ impl ChatEvents {
             |message, _| Self::undelete_message_inner(message, &args),
         ) {
             Ok(result) => {
-                let (sender, message_index, document) = result.value;
+                let (sender, message_index, search_index_entry) = result.value;
                 if sender != args.caller {
                     add_to_metrics(
                         &mut self.metrics,
@@ -550,7 +589,7 @@ impl ChatEvents {
                     args.now,
                 );
                 if args.thread_root_message_index.is_none() {
-                    self.search_index.push(message_index, sender, document);
+                    self.search_index.push(message_index, search_index_entry);
                 }
                 Ok(result.bot_notification)
             }
@@ -562,7 +601,7 @@ impl ChatEvents {
     fn undelete_message_inner(
         message: &mut MessageInternal,
         args: &DeleteUndeleteMessageArgs,
-    ) -> Result<(UserId, MessageIndex, Document), UpdateEventError<OCErrorCode>> {
+    ) -> Result<(UserId, MessageIndex, SearchIndexEntry), UpdateEventError<OCErrorCode>> {
         let Some(deleted_by) = message.deleted_by.as_ref().map(|db| db.deleted_by) else {
             return Err(UpdateEventError::NoChange(OCErrorCode::NoChange));
         };
@@ -581,7 +620,7 @@ impl ChatEvents {
                 _ => {
                     let sender = message.sender;
                     message.deleted_by = None;
-                    Ok((sender, message.message_index, Document::from(&message.content)))
+                    Ok((sender, message.message_index, SearchIndexEntry::new(sender, &message.content)))
                 }
             }
         } else {
@@ -623,6 +662,8 @@ impl ChatEvents {
 
         let content = std::mem::replace(&mut message.content, MessageContentInternal::Deleted(deleted_by));
         let sender = message.sender;
+        message.edit_history.clear();
+        message.translations.clear();
 
         Ok((content, sender))
     }
@@ -680,7 +721,13 @@ impl ChatEvents {
             return Err(UpdateEventError::NotFound);
         };
 
-        let result = p.register_vote(args.user_id, args.option_index, args.operation);
+        let weight = p
+            .config
+            .vote_weighting
+            .as_ref()
+            .map(|w| w.weight(args.user_role, args.token_balance));
+
+        let result = p.register_vote(args.user_id, args.option_index, args.operation, weight);
 
         match result {
             RegisterVoteResult::Success(existing_vote_removed) => Ok(RegisterPollVoteSuccess {
@@ -698,6 +745,7 @@ impl ChatEvents {
             RegisterVoteResult::PollEnded => Err(UpdateEventError::NoChange(OCErrorCode::PollEnded)),
             RegisterVoteResult::OptionIndexOutOfRange => Err(UpdateEventError::NoChange(OCErrorCode::PollOptionNotFound)),
             RegisterVoteResult::UserCannotChangeVote => Err(UpdateEventError::NoChange(OCErrorCode::CannotChangeVote)),
+            RegisterVoteResult::NoVotingWeight => Err(UpdateEventError::NoChange(OCErrorCode::NoVotingWeight)),
         }
     }
 
@@ -705,6 +753,7 @@ impl ChatEvents {
         &mut self,
         thread_root_message_index: Option<MessageIndex>,
         message_index: MessageIndex,
+        final_balances: Option<HashMap<UserId, u128>>,
         now: TimestampMillis,
     ) -> EndPollResult {
         use EndPollResult::*;
@@ -716,7 +765,7 @@ impl ChatEvents {
             now,
             true,
             ChatEventType::MessagePollEnded,
-            |message, _| Self::end_poll_inner(message),
+            |message, _| Self::end_poll_inner(message, final_balances.as_ref()),
         ) {
             Ok(result) => Success(Box::new(result)),
             Err(UpdateEventError::NoChange(_)) => UnableToEndPoll,
@@ -724,16 +773,31 @@ impl ChatEvents {
         }
     }
 
-    fn end_poll_inner(message: &mut MessageInternal) -> Result<(), UpdateEventError> {
+    fn end_poll_inner(
+        message: &mut MessageInternal,
+        final_balances: Option<&HashMap<UserId, u128>>,
+    ) -> Result<(), UpdateEventError> {
         let MessageContentInternal::Poll(p) = &mut message.content else {
             return Err(UpdateEventError::NotFound);
         };
 
-        if !p.ended && p.config.end_date.is_some() {
-            p.ended = true;
-            Ok(())
+        if let Some(balances) = final_balances {
+            p.cap_token_weights(balances);
+        }
+
+        if p.end() { Ok(()) } else { Err(UpdateEventError::NoChange(())) }
+    }
+
+    pub fn token_weighted_poll_voters(
+        &self,
+        thread_root_message_index: Option<MessageIndex>,
+        message_index: MessageIndex,
+    ) -> Option<(CanisterId, Vec<UserId>)> {
+        let (message, _) = self.message_internal(EventIndex::default(), thread_root_message_index, message_index.into())?;
+        if let MessageContentInternal::Poll(p) = &message.content {
+            p.token_weighted_voters()
         } else {
-            Err(UpdateEventError::NoChange(()))
+            None
         }
     }
 
@@ -1848,7 +1912,8 @@ impl ChatEvents {
             self.threads.get_mut(&root_message_index).unwrap()
         } else {
             if let ChatEventInternal::Message(m) = &event {
-                self.search_index.push(m.message_index, m.sender, Document::from(&m.content));
+                self.search_index
+                    .push(m.message_index, SearchIndexEntry::new(m.sender, &m.content));
             }
             &mut self.main
         };
@@ -1859,10 +1924,12 @@ impl ChatEvents {
             self.expiring_events.insert(event_index, timestamp);
         }
 
-        let bots_to_notify = self.bots_to_notify(&event_type);
+        let bots_to_notify = self.bots_to_notify(&event_type, thread_root_message_index);
         let bot_notification = if bots_to_notify.is_empty() {
             None
         } else {
+            self.bot_event_log
+                .push(thread_root_message_index, event_index, event_type, now);
             let event = event.chat_event(None);
             Some(BotNotification {
                 event: types::BotEvent::Chat(BotChatEvent {
@@ -1909,18 +1976,76 @@ impl ChatEvents {
         }
     }
 
+    // Searches either the main events list or, if `thread_root_message_index` is set, a single
+    // thread. The main events list is searched via the search index, whereas threads are small
+    // enough to be scanned directly.
     pub fn search_messages(
         &self,
+        min_visible_event_index: EventIndex,
         min_visible_message_index: MessageIndex,
+        thread_root_message_index: Option<MessageIndex>,
         query: Query,
         users: HashSet<UserId>,
         max_results: u8,
-    ) -> Vec<MessageMatch> {
-        self.search_index
-            .search_messages(min_visible_message_index, query, users)
-            .map(|message_index| MessageMatch { message_index, score: 1 })
-            .take(max_results as usize)
-            .collect()
+    ) -> OCResult<Vec<MessageMatch>> {
+        let events_list = self
+            .events_list(min_visible_event_index, thread_root_message_index)
+            .ok_or(OCErrorCode::ThreadNotFound)?;
+
+        let from = query
+            .filters
+            .after
+            .map_or(MessageIndex::default(), |ts| events_list.first_message_index_after(ts));
+        let to = query.filters.before.map_or(events_list.next_message_index(), |ts| {
+            events_list.first_message_index_after(ts.saturating_sub(1))
+        });
+
+        let matches = if thread_root_message_index.is_some() {
+            events_list
+                .iter(None, false, EventIndex::default(), None)
+                .filter_map(|e| e.into_event())
+                .filter_map(|e| e.event.into_message())
+                .filter(|m| m.message_index >= from && m.message_index < to && m.deleted_by.is_none())
+                .filter(|m| users.is_empty() || users.contains(&m.sender))
+                .filter(|m| query.is_empty() || Document::from(&m.content).is_match(&query))
+                .filter_map(|m| Self::message_match(&m, &query))
+                .take(max_results as usize)
+                .collect()
+        } else {
+            self.search_index
+                .search_messages(max(min_visible_message_index, from)..to, &query, &users)
+                .filter_map(|message_index| events_list.get_event(message_index.into(), min_visible_event_index, None))
+                .filter_map(|e| e.event.into_message())
+                .filter_map(|m| Self::message_match(&m, &query))
+                .take(max_results as usize)
+                .collect()
+        };
+
+        Ok(matches)
+    }
+
+    fn message_match(message: &MessageInternal, query: &Query) -> Option<MessageMatch> {
+        if !matches_content_type_filters(&message.content.content_type(), query) {
+            return None;
+        }
+
+        let highlights = message
+            .content
+            .text()
+            .map(|text| {
+                query
+                    .highlights(text)
+                    .into_iter()
+                    .map(|(start, end)| (start as u32, end as u32))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(MessageMatch {
+            message_index: message.message_index,
+            score: 1,
+            highlights,
+        })
     }
 
     pub fn push_main_event(&mut self, event: ChatEventInternal, now: TimestampMillis) -> PushEventResultInternal {
@@ -2012,10 +2137,13 @@ impl ChatEvents {
         &self,
         thread_root_message_index: Option<MessageIndex>,
         message_id: MessageId,
-        is_v2_bot: bool,
+        bot_caller: Option<&BotCaller>,
     ) -> bool {
         if let Some((message, _)) = self.message_internal(EventIndex::default(), thread_root_message_index, message_id.into()) {
-            !is_v2_bot || message.bot_context().is_none_or(|b| b.finalised)
+            bot_caller.is_none_or(|b| {
+                let interacted_with = matches!(&b.initiator, BotInitiator::Interaction(i) if i.message_id == message_id);
+                !interacted_with && message.bot_context().is_none_or(|c| c.finalised)
+            })
         } else {
             false
         }
@@ -2424,7 +2552,7 @@ impl ChatEvents {
         permitted_categories: &HashSet<ChatEventCategory>,
     ) {
         // Remove any existing subscriptions
-        self.unsubscribe_bot_from_events(bot_id);
+        self.remove_bot_subscriptions(bot_id);
 
         // Add any permitted new subscriptions
         let permitted_event_types: HashSet<ChatEventType> = event_types
@@ -2432,18 +2560,123 @@ impl ChatEvents {
             .filter(|t| permitted_categories.contains(&ChatEventCategory::from(*t)))
             .collect();
 
+        if permitted_event_types.is_empty() {
+            self.bot_event_cursors.remove(&bot_id);
+            return;
+        }
+
         for event_type in permitted_event_types {
             self.bot_subscriptions.entry(event_type).or_default().insert(bot_id);
         }
+
+        // New subscribers start from the latest event rather than replaying older ones
+        let latest = self.bot_event_log.latest_timestamp();
+        self.bot_event_cursors.entry(bot_id).or_insert(latest);
     }
 
     pub fn unsubscribe_bot_from_events(&mut self, bot_id: UserId) {
+        self.remove_bot_subscriptions(bot_id);
+        self.bot_event_cursors.remove(&bot_id);
+    }
+
+    fn remove_bot_subscriptions(&mut self, bot_id: UserId) {
         for subscriptions in self.bot_subscriptions.values_mut() {
             subscriptions.remove(&bot_id);
         }
         self.bot_subscriptions.retain(|_, subscriptions| !subscriptions.is_empty());
     }
 
+    pub fn bot_event_cursor(&self, bot_id: &UserId) -> Option<TimestampMillis> {
+        self.bot_event_cursors.get(bot_id).copied()
+    }
+
+    // Moves the bot's cursor forward, it is never moved backwards
+    pub fn acknowledge_bot_events(&mut self, bot_id: UserId, cursor: TimestampMillis) -> OCResult {
+        if !self.bot_subscriptions.values().any(|s| s.contains(&bot_id)) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+        if cursor > self.bot_event_log.latest_timestamp() {
+            return Err(OCErrorCode::InvalidRequest.with_message("Cursor is ahead of the latest event"));
+        }
+
+        let current = self.bot_event_cursors.entry(bot_id).or_default();
+        *current = max(*current, cursor);
+        Ok(())
+    }
+
+    // Replays the events the bot is subscribed to which have occurred since `since`, defaulting to
+    // the bot's last acknowledged cursor. Events sharing a timestamp are never split across batches
+    // so that the returned cursor can safely be used to fetch the next batch.
+    pub fn bot_events_since(
+        &self,
+        bot_id: UserId,
+        since: Option<TimestampMillis>,
+        permitted_categories: &HashSet<ChatEventCategory>,
+        max_events: usize,
+    ) -> OCResult<BotEventsSinceResult> {
+        let subscribed: HashSet<ChatEventType> = self
+            .bot_subscriptions
+            .iter()
+            .filter(|(t, s)| s.contains(&bot_id) && permitted_categories.contains(&ChatEventCategory::from(**t)))
+            .map(|(t, _)| *t)
+            .collect();
+
+        if subscribed.is_empty() {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        let since = since.or_else(|| self.bot_event_cursor(&bot_id)).unwrap_or_default();
+        let max_events = max_events.clamp(1, MAX_REPLAY_BATCH_SIZE);
+        let mut events = Vec::new();
+        let mut cursor = since;
+        let mut complete = true;
+
+        for (timestamp, thread_root_message_index, event_index, event_type) in self.bot_event_log.iter_since(since) {
+            if events.len() >= max_events && timestamp > cursor {
+                complete = false;
+                break;
+            }
+            cursor = timestamp;
+
+            if !subscription_types(event_type, thread_root_message_index)
+                .iter()
+                .any(|t| subscribed.contains(t))
+            {
+                continue;
+            }
+
+            let Some(events_list) = self.events_list(EventIndex::default(), thread_root_message_index) else {
+                continue;
+            };
+            let reader = ChatEventsListReader::with_min_visible_event_index(
+                events_list,
+                &self.last_updated_timestamps,
+                EventIndex::default(),
+                Some(permitted_categories.clone()),
+            );
+            // Events which have since expired or been removed are skipped
+            if let Some(event) = reader.get_event(event_index.into()) {
+                events.push(BotReplayedChatEvent {
+                    event: BotChatEvent {
+                        event: event.event.chat_event(None),
+                        chat: self.chat,
+                        thread: thread_root_message_index,
+                        event_index,
+                        latest_event_index: events_list.latest_event_index().unwrap_or_default(),
+                    },
+                    timestamp,
+                });
+            }
+        }
+
+        Ok(BotEventsSinceResult {
+            events,
+            cursor,
+            complete,
+            events_pruned: self.bot_event_log.pruned_since(since),
+        })
+    }
+
     fn events_list(
         &self,
         min_visible_event_index: EventIndex,
@@ -2496,6 +2729,186 @@ impl ChatEvents {
             .and_then(|e| e.event.into_message().map(|m| (m, e.index)))
     }
 
+    // Returns the previous versions of the message, oldest first, followed by the current version
+    pub fn message_edit_history(
+        &self,
+        min_visible_event_index: EventIndex,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        my_user_id: Option<UserId>,
+        now: TimestampMillis,
+    ) -> OCResult<Vec<MessageRevision>> {
+        let event = self
+            .event_wrapper_internal(min_visible_event_index, thread_root_message_index, message_id.into())
+            .ok_or(OCErrorCode::MessageNotFound)?;
+
+        let Some(mut message) = event.event.into_message() else {
+            return Err(OCErrorCode::MessageNotFound.into());
+        };
+
+        if message.deleted_by.is_some() {
+            return Err(OCErrorCode::MessageNotFound.into());
+        }
+
+        message.prune_edit_history(self.events_ttl.value, now);
+
+        let current = MessageRevision {
+            content: message.content.hydrate(my_user_id),
+            block_level_markdown: message.block_level_markdown,
+            timestamp: message.last_edited.unwrap_or(event.timestamp),
+        };
+
+        Ok(message
+            .edit_history
+            .into_iter()
+            .map(|r| r.hydrate(my_user_id))
+            .chain(std::iter::once(current))
+            .collect())
+    }
+
+    // Returns the cached translation of a text message if there is one, otherwise the text which
+    // needs translating
+    pub fn message_translation(
+        &self,
+        min_visible_event_index: EventIndex,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        language: &str,
+    ) -> OCResult<MessageTranslation> {
+        let message = self
+            .event_wrapper_internal(min_visible_event_index, thread_root_message_index, message_id.into())
+            .and_then(|e| e.event.into_message())
+            .filter(|m| m.deleted_by.is_none())
+            .ok_or(OCErrorCode::MessageNotFound)?;
+
+        let MessageContentInternal::Text(content) = message.content else {
+            return Err(OCErrorCode::InvalidMessageType.into());
+        };
+
+        Ok(match message.translations.get(language) {
+            Some(translation) => MessageTranslation::Cached(translation.clone()),
+            None => MessageTranslation::Required(content.text),
+        })
+    }
+
+    // Checks that the message has a component with the given id which accepts `value`, returning
+    // the id of the bot which sent the message
+    pub fn validate_message_interaction(
+        &self,
+        min_visible_event_index: EventIndex,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        component_id: &str,
+        value: Option<&str>,
+    ) -> OCResult<UserId> {
+        let (message, _) = self
+            .message_internal(min_visible_event_index, thread_root_message_index, message_id.into())
+            .filter(|(m, _)| m.deleted_by.is_none())
+            .ok_or(OCErrorCode::MessageNotFound)?;
+
+        if message.bot_context().is_none() {
+            return Err(OCErrorCode::InvalidRequest.with_message("Message was not sent by a bot"));
+        }
+
+        let component = message
+            .content
+            .components()
+            .iter()
+            .find(|c| c.id() == component_id)
+            .ok_or_else(|| OCErrorCode::InvalidRequest.with_message("Component not found"))?;
+
+        if !component.is_valid_value(value) {
+            return Err(OCErrorCode::InvalidRequest.with_message("Invalid value for component"));
+        }
+
+        Ok(message.sender)
+    }
+
+    // Caching a translation doesn't mark the message as updated since translations aren't returned
+    // to clients as part of the message. If the message has been edited since the text was
+    // translated, the translation is stale so it is dropped.
+    pub fn cache_message_translation(
+        &mut self,
+        min_visible_event_index: EventIndex,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        source_text: &str,
+        language: String,
+        translation: String,
+    ) {
+        let _ = self.update_event(
+            thread_root_message_index,
+            message_id.into(),
+            min_visible_event_index,
+            None,
+            |event| {
+                Self::update_message_inner(event, |message, _| {
+                    if message.deleted_by.is_none() && message.content.text() == Some(source_text) {
+                        message.cache_translation(language, translation);
+                        Ok(())
+                    } else {
+                        Err(UpdateEventError::NoChange(()))
+                    }
+                })
+            },
+        );
+    }
+
+    // Returns up to `max_events` events following on from `after`, working through the main events
+    // first and then each thread in turn. If there are more events to export, the cursor to pass in
+    // to get the next page is returned alongside the events. If the cursor's thread has since been
+    // removed (eg. its root message expired), the export resumes from the next thread after it.
+    pub fn export_events(
+        &self,
+        min_visible_event_index: EventIndex,
+        after: Option<ChatExportCursor>,
+        max_events: usize,
+        my_user_id: Option<UserId>,
+    ) -> (Vec<ChatExportEvent>, Option<ChatExportCursor>) {
+        let mut events = Vec::new();
+        let after = after.and_then(|c| c.event());
+        let start_thread = after.and_then(|(thread, _)| thread);
+
+        let event_lists = start_thread
+            .is_none()
+            .then_some(None)
+            .into_iter()
+            .chain(self.threads.range(start_thread.unwrap_or_default()..).map(|(m, _)| Some(*m)));
+
+        for thread_root_message_index in event_lists {
+            let Some(reader) = self.events_reader(min_visible_event_index, thread_root_message_index, None) else {
+                continue;
+            };
+
+            let start = after
+                .filter(|(thread, _)| *thread == thread_root_message_index)
+                .map(|(_, event_index)| event_index.incr());
+
+            if let Some(start) = start
+                && reader.latest_event_index().is_none_or(|latest| start > latest)
+            {
+                continue;
+            }
+
+            for event in reader.iter_events(start.map(|e| e.into()), true) {
+                if events.len() >= max_events {
+                    let next = events.last().map(|e: &ChatExportEvent| ChatExportCursor::Event {
+                        thread_root_message_index: e.thread_root_message_index,
+                        event_index: e.event.index,
+                    });
+                    return (events, next);
+                }
+
+                events.push(ChatExportEvent {
+                    thread_root_message_index,
+                    event: reader.hydrate_event(event, my_user_id),
+                });
+            }
+        }
+
+        (events, None)
+    }
+
     fn expiry_date(&self, event: &ChatEventInternal, is_thread_event: bool, now: TimestampMillis) -> Option<TimestampMillis> {
         if !is_thread_event && let Some(ttl) = self.events_ttl.value {
             if self.can_delete_event(event) { Some(now + ttl) } else { None }
@@ -2571,8 +2984,10 @@ impl ChatEvents {
             |event| Self::update_message_inner(event, update_message_fn),
         )
         .map(|r| {
-            let bots_to_notify = self.bots_to_notify(&event_type);
+            let bots_to_notify = self.bots_to_notify(&event_type, thread_root_message_index);
             let bot_notification = if !bots_to_notify.is_empty() {
+                self.bot_event_log
+                    .push(thread_root_message_index, r.event_index, event_type, now);
                 Some(BotNotification {
                     event: types::BotEvent::Chat(BotChatEvent {
                         event: r.event,
@@ -2619,11 +3034,30 @@ impl ChatEvents {
         self.last_updated_timestamps.latest_update_removed()
     }
 
-    pub fn bots_to_notify(&self, event_type: &ChatEventType) -> Vec<UserId> {
-        self.bot_subscriptions
-            .get(event_type)
-            .map(|s| s.iter().cloned().collect())
-            .unwrap_or_default()
+    pub fn bots_to_notify(&self, event_type: &ChatEventType, thread_root_message_index: Option<MessageIndex>) -> Vec<UserId> {
+        let subscription_types = subscription_types(*event_type, thread_root_message_index);
+
+        let mut bots: Vec<_> = subscription_types
+            .iter()
+            .filter_map(|t| self.bot_subscriptions.get(t))
+            .flatten()
+            .copied()
+            .collect();
+
+        if subscription_types.len() > 1 {
+            bots.sort();
+            bots.dedup();
+        }
+        bots
+    }
+}
+
+// Thread replies are delivered to bots subscribed to either `Message` or `MessageThreadReply`
+fn subscription_types(event_type: ChatEventType, thread_root_message_index: Option<MessageIndex>) -> Vec<ChatEventType> {
+    if event_type == ChatEventType::Message && thread_root_message_index.is_some() {
+        vec![ChatEventType::Message, ChatEventType::MessageThreadReply]
+    } else {
+        vec![event_type]
     }
 }
 
@@ -2666,6 +3100,7 @@ pub struct EditMessageArgs {
     pub block_level_markdown: Option<bool>,
     pub og_previews: Vec<OgPreview>,
     pub finalise_bot_message: bool,
+    pub record_edit_history: bool,
     pub now: TimestampMillis,
 }
 
@@ -2707,6 +3142,9 @@ pub struct RegisterPollVoteArgs {
     pub message_index: MessageIndex,
     pub option_index: u32,
     pub operation: VoteOperation,
+    pub user_role: GroupRole,
+    // Only required if the poll's votes are weighted by token balance
+    pub token_balance: Option<u128>,
     pub now: TimestampMillis,
 }
 
@@ -2813,6 +3251,12 @@ pub struct UndeleteMessageSuccess {
     pub bot_notification: Option<BotNotification>,
 }
 
+pub enum MessageTranslation {
+    Cached(String),
+    // Holds the message text which needs translating
+    Required(String),
+}
+
 #[derive(Copy, Clone)]
 pub enum EventKey {
     EventIndex(EventIndex),
@@ -218,6 +218,17 @@ impl ChatEventsList {
         self.events_map.remove(event_index)
     }
 
+    // Returns the index of the first message sent after the given timestamp. Messages are
+    // only ever removed from the start of the list (eg. when they expire) so any which can't be
+    // found are treated as being older than the timestamp.
+    pub(crate) fn first_message_index_after(&self, timestamp: TimestampMillis) -> MessageIndex {
+        let index = self
+            .message_event_indexes
+            .partition_point(|e| self.events_map.get(*e).is_none_or(|event| event.timestamp <= timestamp));
+
+        (index as u32).into()
+    }
+
     pub fn latest_event_index(&self) -> Option<EventIndex> {
         self.latest_event_index
     }
@@ -597,13 +608,16 @@ impl<I: Iterator<Item = EventWrapperInternal<ChatEventInternal>>> Iterator for C
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ChatEvents, MessageContentInternal, NullEventPusher, PushMessageArgs, TextContentInternal};
+    use crate::{
+        ChatEvents, EditMessageArgs, MessageContentInternal, MessageTranslation, NullEventPusher, PushMessageArgs,
+        TextContentInternal,
+    };
     use candid::Principal;
     use ic_stable_structures::DefaultMemoryImpl;
     use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
     use rand::random;
     use std::mem::size_of;
-    use types::Milliseconds;
+    use types::{ChatExportCursor, Milliseconds};
 
     #[test]
     fn enum_size() {
@@ -795,6 +809,192 @@ mod tests {
         assert_eq!(event_indexes, (46..=70).map(|i| i.into()).collect_vec());
     }
 
+    #[test]
+    fn edit_history_keeps_most_recent_revisions() {
+        let mut events = setup_events(None);
+        let user_id = Principal::from_slice(&[2]).into();
+        let message_id = MessageId::from(2u128);
+
+        for i in 0..15 {
+            events
+                .edit_message::<NullEventPusher>(
+                    EditMessageArgs {
+                        sender: user_id,
+                        min_visible_event_index: EventIndex::default(),
+                        thread_root_message_index: None,
+                        message_id,
+                        content: MessageContentInternal::Text(TextContentInternal {
+                            text: format!("edit {i}"),
+                            components: Vec::new(),
+                        }),
+                        block_level_markdown: None,
+                        og_previews: Vec::new(),
+                        finalise_bot_message: false,
+                        record_edit_history: true,
+                        now: 1000 + i,
+                    },
+                    None,
+                )
+                .unwrap();
+        }
+
+        let history = events
+            .message_edit_history(EventIndex::default(), None, message_id, None, 2000)
+            .unwrap();
+
+        assert_eq!(history.len(), 11);
+        assert_eq!(history.first().unwrap().content.text(), Some("edit 4"));
+        assert_eq!(history.last().unwrap().content.text(), Some("edit 14"));
+        assert_eq!(history.last().unwrap().timestamp, 1014);
+    }
+
+    #[test]
+    fn translations_are_cached_until_message_edited() {
+        let mut events = setup_events(None);
+        let user_id = Principal::from_slice(&[2]).into();
+        let message_id = MessageId::from(2u128);
+
+        let Ok(MessageTranslation::Required(text)) = events.message_translation(EventIndex::default(), None, message_id, "es")
+        else {
+            panic!();
+        };
+        assert_eq!(text, "hello");
+
+        events.cache_message_translation(
+            EventIndex::default(),
+            None,
+            message_id,
+            &text,
+            "es".to_string(),
+            "hola".to_string(),
+        );
+        // A translation of text which is no longer the message's text is dropped
+        events.cache_message_translation(
+            EventIndex::default(),
+            None,
+            message_id,
+            "goodbye",
+            "fr".to_string(),
+            "au revoir".to_string(),
+        );
+
+        assert!(matches!(
+            events.message_translation(EventIndex::default(), None, message_id, "es"),
+            Ok(MessageTranslation::Cached(t)) if t == "hola"
+        ));
+        assert!(matches!(
+            events.message_translation(EventIndex::default(), None, message_id, "fr"),
+            Ok(MessageTranslation::Required(_))
+        ));
+
+        events
+            .edit_message::<NullEventPusher>(
+                EditMessageArgs {
+                    sender: user_id,
+                    min_visible_event_index: EventIndex::default(),
+                    thread_root_message_index: None,
+                    message_id,
+                    content: MessageContentInternal::Text(TextContentInternal {
+                        text: "hello there".to_string(),
+                        components: Vec::new(),
+                    }),
+                    block_level_markdown: None,
+                    og_previews: Vec::new(),
+                    finalise_bot_message: false,
+                    record_edit_history: true,
+                    now: 1000,
+                },
+                None,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            events.message_translation(EventIndex::default(), None, message_id, "es"),
+            Ok(MessageTranslation::Required(t)) if t == "hello there"
+        ));
+    }
+
+    #[test]
+    fn export_events_pages_through_main_events_then_threads() {
+        let mut events = setup_events(None);
+        push_thread_replies(&mut events, 10.into(), 1000);
+
+        let mut exported = Vec::new();
+        let mut after = None;
+        loop {
+            let (page, next) = events.export_events(EventIndex::default(), after, 30, None);
+            assert!(page.len() <= 30);
+            exported.extend(page);
+            if next.is_none() {
+                break;
+            }
+            after = next;
+        }
+
+        let main_event_count = usize::from(events.main_events_list().latest_event_index().unwrap().incr());
+        assert_eq!(exported.len(), main_event_count + 3);
+        assert!(
+            exported
+                .iter()
+                .take(main_event_count)
+                .all(|e| e.thread_root_message_index.is_none())
+        );
+        assert!(
+            exported
+                .iter()
+                .skip(main_event_count)
+                .all(|e| e.thread_root_message_index == Some(10.into()))
+        );
+    }
+
+    #[test]
+    fn export_events_resumes_from_next_thread_if_cursor_thread_removed() {
+        let mut events = setup_events(Some(1000));
+        push_events(&mut events, 5000);
+        push_thread_replies(&mut events, 10.into(), 200);
+        push_thread_replies(&mut events, 150.into(), 5200);
+
+        // The root of the first thread expires, which removes the thread
+        events.remove_expired_events(3000);
+
+        let after = ChatExportCursor::Event {
+            thread_root_message_index: Some(10.into()),
+            event_index: EventIndex::default(),
+        };
+        let (page, next) = events.export_events(EventIndex::default(), Some(after), 30, None);
+
+        assert_eq!(page.len(), 3);
+        assert!(page.iter().all(|e| e.thread_root_message_index == Some(150.into())));
+        assert!(next.is_none());
+    }
+
+    fn push_thread_replies(events: &mut ChatEvents, root_message_index: MessageIndex, now: TimestampMillis) {
+        let user_id = Principal::from_slice(&[2]).into();
+
+        for i in 0..3 {
+            events.push_message::<NullEventPusher>(
+                PushMessageArgs {
+                    sender: user_id,
+                    thread_root_message_index: Some(root_message_index),
+                    message_id: MessageId::from((now + i) as u128),
+                    content: MessageContentInternal::Text(TextContentInternal {
+                        text: "reply".to_string(),
+                        components: Vec::new(),
+                    }),
+                    sender_context: None,
+                    mentioned: Vec::new(),
+                    replies_to: None,
+                    now: now + i,
+                    forwarded: false,
+                    sender_is_bot: false,
+                    block_level_markdown: false,
+                    og_previews: Vec::new(),
+                },
+                None,
+            );
+        }
+    }
+
     fn setup_events(events_ttl: Option<Milliseconds>) -> ChatEvents {
         let memory = MemoryManager::init(DefaultMemoryImpl::default());
         stable_memory_map::init(memory.get(MemoryId::new(1)));
@@ -818,6 +1018,7 @@ mod tests {
                     message_id,
                     content: MessageContentInternal::Text(TextContentInternal {
                         text: "hello".to_string(),
+                        components: Vec::new(),
                     }),
                     sender_context: None,
                     mentioned: Vec::new(),
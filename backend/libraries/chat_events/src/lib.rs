@@ -1,5 +1,6 @@
 use event_store_types::Event;
 
+mod bot_event_log;
 mod chat_event_internal;
 mod chat_events;
 mod chat_events_list;
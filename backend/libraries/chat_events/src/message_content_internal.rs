@@ -6,22 +6,24 @@ use ledger_utils::{create_pending_transaction, format_crypto_amount};
 use search::simple::Document;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_bytes::ByteBuf;
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use types::icrc1::{Account, CryptoAccount};
 use types::{
     AudioContent, BlobReference, CallParticipant, CanisterId, CompletedCryptoTransaction, ContentValidationError,
     ContentWithCaptionEventPayload, CryptoContent, CryptoContentEventPayload, CryptoTransaction, Cryptocurrency, CustomContent,
     EncryptedContent, EncryptedContentEventPayload, EncryptedMessageContentType, EncryptionKey, FileContent,
-    FileContentEventPayload, GiphyContent, GiphyImageVariant, GovernanceProposalContentEventPayload, ImageContent,
-    ImageOrVideoContentEventPayload, MAX_TEXT_LENGTH, MAX_TEXT_LENGTH_USIZE, MessageContent, MessageContentEventPayload,
-    MessageContentInitial, MessageContentType, MessageIndex, MessageReminderContent, MessageReminderContentEventPayload,
-    MessageReminderCreatedContent, MessageReport, Milliseconds, ModerationInput, ModerationReportContent, P2PSwapAccepted,
-    P2PSwapCancelled, P2PSwapCompleted, P2PSwapContent, P2PSwapContentEventPayload, P2PSwapContentInitial, P2PSwapExpired,
-    P2PSwapReserved, P2PSwapStatus, PendingCryptoTransaction, PollConfig, PollContent, PollContentEventPayload, PollVotes,
-    PrizeContent, PrizeContentEventPayload, PrizeContentInitial, PrizeWinnerContent, PrizeWinnerContentEventPayload, Proposal,
+    FileContentEventPayload, GiphyContent, GiphyImageVariant, GovernanceProposalContentEventPayload, GroupRole, ImageContent,
+    ImageOrVideoContentEventPayload, MAX_TEXT_LENGTH, MAX_TEXT_LENGTH_USIZE, MessageComponent, MessageContent,
+    MessageContentEventPayload, MessageContentInitial, MessageContentType, MessageIndex, MessageReminderContent,
+    MessageReminderContentEventPayload, MessageReminderCreatedContent, MessageReport, Milliseconds, ModerationInput,
+    ModerationReportContent, P2PSwapAccepted, P2PSwapCancelled, P2PSwapCompleted, P2PSwapContent, P2PSwapContentEventPayload,
+    P2PSwapContentInitial, P2PSwapExpired, P2PSwapReserved, P2PSwapStatus, PendingCryptoTransaction, PollConfig, PollContent,
+    PollContentEventPayload, PollResult, PollVoteWeighting, PollVotes, PollVotingSystem, PrizeContent,
+    PrizeContentEventPayload, PrizeContentInitial, PrizeWinnerContent, PrizeWinnerContentEventPayload, Proposal,
     ProposalContent, RegisterVoteResult, ReportedMessage, ReportedMessageContentEventPayload, TextContent,
     TextContentEventPayload, ThumbnailData, TimestampMillis, TimestampNanos, TokenInfo, TotalVotes, TransactionHash, UserId,
     UserType, VideoCallContent, VideoCallPresence, VideoCallType, VideoContent, VoteOperation, is_default,
+    validate_message_components,
 };
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -70,7 +72,7 @@ pub enum MessageContentInternal {
 
 impl MessageContentInternal {
     pub fn validate_new_message(
-        content: MessageContentInitial,
+        mut content: MessageContentInitial,
         is_direct_chat: bool,
         sender_user_type: UserType,
         forwarding: bool,
@@ -90,6 +92,19 @@ impl MessageContentInternal {
             }
         }
 
+        if let MessageContentInitial::Text(t) = &mut content
+            && let Some(components) = &t.components
+        {
+            if forwarding {
+                // Components only work on the bot's original message so are dropped when forwarding
+                t.components = None;
+            } else if sender_user_type != UserType::BotV2 {
+                return ValidateNewMessageContentResult::Error(ContentValidationError::Unauthorized);
+            } else if let Err(reason) = validate_message_components(components) {
+                return ValidateNewMessageContentResult::Error(ContentValidationError::InvalidComponents(reason));
+            }
+        }
+
         // Allow GovernanceProposal messages to exceed the max length since they are collapsed on the UI
         if content.text_length() > MAX_TEXT_LENGTH_USIZE && !matches!(&content, MessageContentInitial::GovernanceProposal(_)) {
             return ValidateNewMessageContentResult::Error(ContentValidationError::TextTooLong(MAX_TEXT_LENGTH));
@@ -235,6 +250,10 @@ impl MessageContentInternal {
         }
     }
 
+    pub fn components(&self) -> &[MessageComponent] {
+        if let MessageContentInternal::Text(t) = self { &t.components } else { &[] }
+    }
+
     pub fn moderation_input(&self) -> ModerationInput {
         let mut input = ModerationInput {
             text: self.text().map(|t| t.to_string()),
@@ -560,11 +579,16 @@ pub(crate) trait MessageContentInternalSubtype {
 pub struct TextContentInternal {
     #[serde(rename = "t")]
     pub text: String,
+    #[serde(rename = "mc", default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<MessageComponent>,
 }
 
 impl From<TextContent> for TextContentInternal {
     fn from(value: TextContent) -> Self {
-        TextContentInternal { text: value.text }
+        TextContentInternal {
+            text: value.text,
+            components: value.components.unwrap_or_default(),
+        }
     }
 }
 
@@ -572,7 +596,10 @@ impl MessageContentInternalSubtype for TextContentInternal {
     type ContentType = TextContent;
 
     fn hydrate(self, _my_user_id: Option<UserId>) -> Self::ContentType {
-        TextContent { text: self.text }
+        TextContent {
+            text: self.text,
+            components: (!self.components.is_empty()).then_some(self.components),
+        }
     }
 }
 
@@ -756,6 +783,14 @@ pub struct PollContentInternal {
     pub votes: HashMap<u32, Vec<UserId>>,
     #[serde(rename = "e")]
     pub ended: bool,
+    // Each user's preferences, most preferred first. Only used by ranked-choice polls.
+    #[serde(rename = "r", default, skip_serializing_if = "HashMap::is_empty")]
+    pub rankings: HashMap<UserId, Vec<u32>>,
+    // The weight of each user's votes, recorded when they vote. Only used by weighted polls.
+    #[serde(rename = "w", default, skip_serializing_if = "HashMap::is_empty")]
+    pub weights: HashMap<UserId, u128>,
+    #[serde(rename = "rs", default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<PollResult>,
 }
 
 impl From<PollContent> for PollContentInternal {
@@ -764,6 +799,9 @@ impl From<PollContent> for PollContentInternal {
             config: value.config.into(),
             votes: HashMap::new(),
             ended: false,
+            rankings: HashMap::new(),
+            weights: HashMap::new(),
+            result: None,
         }
     }
 }
@@ -781,76 +819,291 @@ impl MessageContentInternalSubtype for PollContentInternal {
 }
 
 impl PollContentInternal {
-    pub fn register_vote(&mut self, user_id: UserId, option_index: u32, operation: VoteOperation) -> RegisterVoteResult {
+    pub fn register_vote(
+        &mut self,
+        user_id: UserId,
+        option_index: u32,
+        operation: VoteOperation,
+        weight: Option<u128>,
+    ) -> RegisterVoteResult {
         if self.ended {
             RegisterVoteResult::PollEnded
-        } else if option_index > (self.config.options.len() as u32) + 1 {
+        } else if option_index >= self.config.options.len() as u32 {
             RegisterVoteResult::OptionIndexOutOfRange
+        } else if weight == Some(0) && matches!(operation, VoteOperation::RegisterVote) {
+            RegisterVoteResult::NoVotingWeight
         } else {
-            match operation {
-                VoteOperation::RegisterVote => {
-                    let votes = self.votes.entry(option_index).or_default();
-                    if votes.contains(&user_id) {
-                        return RegisterVoteResult::SuccessNoChange;
-                    }
-                    votes.push(user_id);
-                    let mut existing_vote_removed = false;
-                    if !self.config.allow_multiple_votes_per_user {
-                        // If the user has already left a vote, remove it
-                        for (_, votes) in self.votes.iter_mut().filter(|(o, _)| **o != option_index) {
-                            if let Some((index, _)) = votes.iter().enumerate().find(|(_, u)| **u == user_id) {
-                                // if the poll does not permit users to change vote then this is an error
-                                if !self.config.allow_user_to_change_vote {
-                                    return RegisterVoteResult::UserCannotChangeVote;
-                                }
-                                votes.remove(index);
-                                existing_vote_removed = true;
-                                break;
+            let result = if self.is_ranked_choice() {
+                self.register_ranked_vote(user_id, option_index, operation)
+            } else {
+                self.register_plurality_vote(user_id, option_index, operation)
+            };
+
+            if let (RegisterVoteResult::Success(_), Some(weight)) = (&result, weight) {
+                if self.has_voted(&user_id) {
+                    // A user's token weight can only go down once they've voted, otherwise they
+                    // could vote, receive more tokens and then change their vote to increase it
+                    let weight = match self.weights.get(&user_id) {
+                        Some(existing) if self.is_token_weighted() => weight.min(*existing),
+                        _ => weight,
+                    };
+                    self.weights.insert(user_id, weight);
+                } else {
+                    self.weights.remove(&user_id);
+                }
+            }
+            result
+        }
+    }
+
+    fn register_plurality_vote(&mut self, user_id: UserId, option_index: u32, operation: VoteOperation) -> RegisterVoteResult {
+        match operation {
+            VoteOperation::RegisterVote => {
+                let votes = self.votes.entry(option_index).or_default();
+                if votes.contains(&user_id) {
+                    return RegisterVoteResult::SuccessNoChange;
+                }
+                votes.push(user_id);
+                let mut existing_vote_removed = false;
+                if !self.config.allow_multiple_votes_per_user {
+                    // If the user has already left a vote, remove it
+                    for (_, votes) in self.votes.iter_mut().filter(|(o, _)| **o != option_index) {
+                        if let Some((index, _)) = votes.iter().enumerate().find(|(_, u)| **u == user_id) {
+                            // if the poll does not permit users to change vote then this is an error
+                            if !self.config.allow_user_to_change_vote {
+                                return RegisterVoteResult::UserCannotChangeVote;
                             }
+                            votes.remove(index);
+                            existing_vote_removed = true;
+                            break;
                         }
                     }
+                }
 
-                    RegisterVoteResult::Success(existing_vote_removed)
+                RegisterVoteResult::Success(existing_vote_removed)
+            }
+            VoteOperation::DeleteVote => {
+                if let Some(votes) = self.votes.get_mut(&option_index)
+                    && let Some((index, _)) = votes.iter().enumerate().find(|(_, u)| **u == user_id)
+                {
+                    votes.remove(index);
+                    return RegisterVoteResult::Success(true);
                 }
-                VoteOperation::DeleteVote => {
-                    if let Some(votes) = self.votes.get_mut(&option_index)
-                        && let Some((index, _)) = votes.iter().enumerate().find(|(_, u)| **u == user_id)
-                    {
-                        votes.remove(index);
-                        return RegisterVoteResult::Success(true);
-                    }
-                    RegisterVoteResult::SuccessNoChange
+                RegisterVoteResult::SuccessNoChange
+            }
+        }
+    }
+
+    // Registering a vote adds the option as the user's next preference, deleting a vote removes
+    // the option from their preferences
+    fn register_ranked_vote(&mut self, user_id: UserId, option_index: u32, operation: VoteOperation) -> RegisterVoteResult {
+        match operation {
+            VoteOperation::RegisterVote => {
+                let ranking = self.rankings.entry(user_id).or_default();
+                if ranking.contains(&option_index) {
+                    return RegisterVoteResult::SuccessNoChange;
+                }
+                let already_voted = !ranking.is_empty();
+                ranking.push(option_index);
+                RegisterVoteResult::Success(already_voted)
+            }
+            VoteOperation::DeleteVote => {
+                let Some(ranking) = self.rankings.get_mut(&user_id) else {
+                    return RegisterVoteResult::SuccessNoChange;
+                };
+                let Some(index) = ranking.iter().position(|o| *o == option_index) else {
+                    return RegisterVoteResult::SuccessNoChange;
+                };
+                if !self.config.allow_user_to_change_vote {
+                    return RegisterVoteResult::UserCannotChangeVote;
+                }
+                ranking.remove(index);
+                if ranking.is_empty() {
+                    self.rankings.remove(&user_id);
                 }
+                RegisterVoteResult::Success(true)
             }
         }
     }
 
+    // Ends the poll and tallies the result, returning false if the poll has no end date or has
+    // already ended
+    pub fn end(&mut self) -> bool {
+        if self.ended || self.config.end_date.is_none() {
+            return false;
+        }
+        self.ended = true;
+        self.result = Some(self.tally());
+        true
+    }
+
+    // If the poll's votes are weighted by token balance, returns the ledger along with the users
+    // who have voted, so that their balances can be read before the poll ends
+    pub fn token_weighted_voters(&self) -> Option<(CanisterId, Vec<UserId>)> {
+        let ledger_canister_id = self.config.vote_weighting.as_ref()?.ledger_canister_id()?;
+        (!self.ended).then(|| (ledger_canister_id, self.weights.keys().copied().collect()))
+    }
+
+    // Caps each voter's weight at the weight of their balance when the poll ends. Tokens can only
+    // be held by one account at a time, so this stops the same tokens being counted more than once
+    // by voting and then transferring them to another user who also votes. Voters missing from
+    // `final_balances` (eg. because their balance couldn't be read) are treated as having no balance,
+    // otherwise a failed read would leave their weight uncapped.
+    pub fn cap_token_weights(&mut self, final_balances: &HashMap<UserId, u128>) {
+        let Some(weighting) = self
+            .config
+            .vote_weighting
+            .as_ref()
+            .filter(|w| w.ledger_canister_id().is_some())
+        else {
+            return;
+        };
+        for (user_id, weight) in self.weights.iter_mut() {
+            let balance = final_balances.get(user_id).copied().unwrap_or_default();
+            *weight = (*weight).min(weighting.weight(GroupRole::Participant, Some(balance)));
+        }
+    }
+
     pub fn votes(&self, my_user_id: Option<UserId>) -> PollVotes {
         let user_votes = if let Some(user_id) = my_user_id {
-            self.votes
-                .iter()
-                .filter(|(_, v)| v.contains(&user_id))
-                .map(|(k, _)| *k)
-                .collect()
+            if self.is_ranked_choice() {
+                self.rankings.get(&user_id).cloned().unwrap_or_default()
+            } else {
+                self.votes
+                    .iter()
+                    .filter(|(_, v)| v.contains(&user_id))
+                    .map(|(k, _)| *k)
+                    .collect()
+            }
         } else {
             Vec::new()
         };
 
+        let first_preferences = self.is_ranked_choice().then(|| self.first_preferences());
+        let votes = first_preferences.as_ref().unwrap_or(&self.votes);
+
         let total_votes: TotalVotes;
         let hide_votes = self.config.end_date.is_some() && !self.ended && !self.config.show_votes_before_end_date;
         if hide_votes {
-            total_votes = TotalVotes::Hidden(self.votes.values().map(|v| v.len() as u32).sum());
+            total_votes = TotalVotes::Hidden(votes.values().map(|v| v.len() as u32).sum());
+        } else if self.config.vote_weighting.is_some() {
+            total_votes = TotalVotes::Weighted(self.weighted_totals(votes));
         } else if self.config.anonymous {
-            total_votes = TotalVotes::Anonymous(self.votes.iter().map(|(k, v)| (*k, v.len() as u32)).collect());
+            total_votes = TotalVotes::Anonymous(votes.iter().map(|(k, v)| (*k, v.len() as u32)).collect());
         } else {
-            total_votes = TotalVotes::Visible(self.votes.clone());
+            total_votes = TotalVotes::Visible(votes.clone());
         }
 
         PollVotes {
             user: user_votes,
             total: total_votes,
+            result: self.result.clone(),
+        }
+    }
+
+    fn tally(&self) -> PollResult {
+        let voters = if self.is_ranked_choice() {
+            self.rankings.len()
+        } else {
+            self.votes.values().flatten().collect::<HashSet<_>>().len()
+        } as u32;
+
+        let quorum_reached = self.config.quorum.is_none_or(|q| voters >= q);
+        let rounds = if self.is_ranked_choice() {
+            self.instant_runoff_rounds()
+        } else {
+            vec![self.weighted_totals(&self.votes)]
+        };
+        let winners = if quorum_reached { rounds.last().map(leaders).unwrap_or_default() } else { Vec::new() };
+
+        PollResult {
+            voters,
+            quorum_reached,
+            winners,
+            rounds,
+        }
+    }
+
+    // Each round, every user's vote goes to their most preferred option which is still in the
+    // running. The option(s) with the fewest votes are then eliminated, until an option has a
+    // majority or the remaining options are tied.
+    fn instant_runoff_rounds(&self) -> Vec<HashMap<u32, u128>> {
+        let mut remaining: HashSet<u32> = (0..self.config.options.len() as u32).collect();
+        let mut rounds = Vec::new();
+
+        loop {
+            let mut tally: HashMap<u32, u128> = remaining.iter().map(|o| (*o, 0)).collect();
+            for (user_id, ranking) in self.rankings.iter() {
+                if let Some(option) = ranking.iter().find(|o| remaining.contains(o)) {
+                    *tally.entry(*option).or_default() += self.weight(user_id);
+                }
+            }
+
+            let total: u128 = tally.values().sum();
+            let max = tally.values().copied().max().unwrap_or_default();
+            let min = tally.values().copied().min().unwrap_or_default();
+            let finished = total == 0 || max > total - max || min == max;
+
+            if !finished {
+                remaining.retain(|o| tally.get(o).is_some_and(|v| *v > min));
+            }
+            rounds.push(tally);
+
+            if finished {
+                return rounds;
+            }
+        }
+    }
+
+    fn first_preferences(&self) -> HashMap<u32, Vec<UserId>> {
+        let mut votes: HashMap<u32, Vec<UserId>> = HashMap::new();
+        for (user_id, ranking) in self.rankings.iter() {
+            if let Some(option) = ranking.first() {
+                votes.entry(*option).or_default().push(*user_id);
+            }
+        }
+        votes
+    }
+
+    fn weighted_totals(&self, votes: &HashMap<u32, Vec<UserId>>) -> HashMap<u32, u128> {
+        votes
+            .iter()
+            .map(|(option, users)| (*option, users.iter().map(|u| self.weight(u)).sum()))
+            .collect()
+    }
+
+    fn weight(&self, user_id: &UserId) -> u128 {
+        self.weights.get(user_id).copied().unwrap_or(1)
+    }
+
+    fn has_voted(&self, user_id: &UserId) -> bool {
+        if self.is_ranked_choice() {
+            self.rankings.contains_key(user_id)
+        } else {
+            self.votes.values().any(|v| v.contains(user_id))
         }
     }
+
+    fn is_token_weighted(&self) -> bool {
+        self.config
+            .vote_weighting
+            .as_ref()
+            .is_some_and(|w| w.ledger_canister_id().is_some())
+    }
+
+    fn is_ranked_choice(&self) -> bool {
+        self.config.voting_system == PollVotingSystem::RankedChoice
+    }
+}
+
+fn leaders(tally: &HashMap<u32, u128>) -> Vec<u32> {
+    let max = tally.values().copied().max().unwrap_or_default();
+    if max == 0 {
+        return Vec::new();
+    }
+    let mut leaders: Vec<_> = tally.iter().filter(|(_, v)| **v == max).map(|(o, _)| *o).collect();
+    leaders.sort_unstable();
+    leaders
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -884,6 +1137,12 @@ pub struct PollConfigInternal {
         skip_serializing_if = "is_default"
     )]
     pub allow_user_to_change_vote: bool,
+    #[serde(rename = "vs", default, skip_serializing_if = "is_default")]
+    pub voting_system: PollVotingSystem,
+    #[serde(rename = "w", default, skip_serializing_if = "Option::is_none")]
+    pub vote_weighting: Option<PollVoteWeighting>,
+    #[serde(rename = "q", default, skip_serializing_if = "Option::is_none")]
+    pub quorum: Option<u32>,
 }
 
 impl From<PollConfig> for PollConfigInternal {
@@ -896,6 +1155,9 @@ impl From<PollConfig> for PollConfigInternal {
             show_votes_before_end_date: value.show_votes_before_end_date,
             allow_multiple_votes_per_user: value.allow_multiple_votes_per_user,
             allow_user_to_change_vote: value.allow_user_to_change_vote,
+            voting_system: value.voting_system,
+            vote_weighting: value.vote_weighting,
+            quorum: value.quorum,
         }
     }
 }
@@ -910,6 +1172,9 @@ impl From<PollConfigInternal> for PollConfig {
             show_votes_before_end_date: value.show_votes_before_end_date,
             allow_multiple_votes_per_user: value.allow_multiple_votes_per_user,
             allow_user_to_change_vote: value.allow_user_to_change_vote,
+            voting_system: value.voting_system,
+            vote_weighting: value.vote_weighting,
+            quorum: value.quorum,
         }
     }
 }
@@ -2195,3 +2460,161 @@ impl From<TokenInfoCombined> for TokenInfo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll(voting_system: PollVotingSystem, quorum: Option<u32>) -> PollContentInternal {
+        PollContentInternal {
+            config: PollConfigInternal {
+                text: None,
+                options: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                end_date: Some(1),
+                anonymous: false,
+                show_votes_before_end_date: false,
+                allow_multiple_votes_per_user: false,
+                allow_user_to_change_vote: true,
+                voting_system,
+                vote_weighting: None,
+                quorum,
+            },
+            votes: HashMap::new(),
+            ended: false,
+            rankings: HashMap::new(),
+            weights: HashMap::new(),
+            result: None,
+        }
+    }
+
+    fn user(index: u8) -> UserId {
+        Principal::from_slice(&[index]).into()
+    }
+
+    #[test]
+    fn ranked_choice_eliminates_until_majority() {
+        let mut poll = poll(PollVotingSystem::RankedChoice, None);
+        let ballots: [&[u32]; 5] = [&[0], &[0], &[1, 2], &[2, 1], &[2, 1]];
+        for (index, ballot) in ballots.into_iter().enumerate() {
+            for option in ballot {
+                poll.register_vote(user(index as u8), *option, VoteOperation::RegisterVote, None);
+            }
+        }
+
+        assert!(poll.end());
+
+        // Option 'b' is eliminated in the first round and its vote transfers to 'c'
+        let result = poll.result.unwrap();
+        assert_eq!(result.voters, 5);
+        assert!(result.quorum_reached);
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(result.rounds[1], HashMap::from([(0, 2), (2, 3)]));
+        assert_eq!(result.winners, vec![2]);
+    }
+
+    #[test]
+    fn no_winner_if_quorum_not_reached() {
+        let mut poll = poll(PollVotingSystem::Plurality, Some(3));
+        poll.register_vote(user(1), 0, VoteOperation::RegisterVote, None);
+        poll.register_vote(user(2), 0, VoteOperation::RegisterVote, None);
+
+        assert!(poll.end());
+
+        let result = poll.result.unwrap();
+        assert_eq!(result.voters, 2);
+        assert!(!result.quorum_reached);
+        assert!(result.winners.is_empty());
+    }
+
+    #[test]
+    fn weighted_votes_decide_winner() {
+        let mut poll = poll(PollVotingSystem::Plurality, None);
+        poll.register_vote(user(1), 0, VoteOperation::RegisterVote, Some(1));
+        poll.register_vote(user(2), 0, VoteOperation::RegisterVote, Some(1));
+        poll.register_vote(user(3), 1, VoteOperation::RegisterVote, Some(5));
+
+        assert!(poll.end());
+        assert_eq!(poll.result.unwrap().winners, vec![1]);
+    }
+
+    #[test]
+    fn option_index_must_be_in_range() {
+        let mut poll = poll(PollVotingSystem::RankedChoice, None);
+        assert!(matches!(
+            poll.register_vote(user(1), 3, VoteOperation::RegisterVote, None),
+            RegisterVoteResult::OptionIndexOutOfRange
+        ));
+        assert!(matches!(
+            poll.register_vote(user(1), 2, VoteOperation::RegisterVote, None),
+            RegisterVoteResult::Success(_)
+        ));
+    }
+
+    #[test]
+    fn token_weights_capped_at_final_balances() {
+        let mut poll = poll(PollVotingSystem::Plurality, None);
+        poll.config.vote_weighting = Some(PollVoteWeighting::TokenBalance(types::TokenBalanceVoteWeighting {
+            ledger_canister_id: Principal::from_slice(&[100]),
+            quadratic: false,
+        }));
+
+        // User 1 votes, transfers their tokens to user 2 who then votes with the same tokens
+        poll.register_vote(user(1), 0, VoteOperation::RegisterVote, Some(100));
+        poll.register_vote(user(2), 1, VoteOperation::RegisterVote, Some(100));
+        poll.register_vote(user(3), 0, VoteOperation::RegisterVote, Some(60));
+
+        let (_, mut voters) = poll.token_weighted_voters().unwrap();
+        voters.sort();
+        assert_eq!(voters, vec![user(1), user(2), user(3)]);
+
+        poll.cap_token_weights(&HashMap::from([(user(1), 0), (user(2), 100), (user(3), 60)]));
+
+        assert!(poll.end());
+        let result = poll.result.unwrap();
+        assert_eq!(result.rounds[0], HashMap::from([(0, 60), (1, 100)]));
+        assert_eq!(result.winners, vec![1]);
+    }
+
+    #[test]
+    fn token_weights_of_voters_with_unknown_final_balances_are_zeroed() {
+        let mut poll = poll(PollVotingSystem::Plurality, None);
+        poll.config.vote_weighting = Some(PollVoteWeighting::TokenBalance(types::TokenBalanceVoteWeighting {
+            ledger_canister_id: Principal::from_slice(&[100]),
+            quadratic: false,
+        }));
+
+        poll.register_vote(user(1), 0, VoteOperation::RegisterVote, Some(100));
+        poll.register_vote(user(2), 1, VoteOperation::RegisterVote, Some(60));
+
+        // User 1's balance couldn't be read
+        poll.cap_token_weights(&HashMap::from([(user(2), 60)]));
+
+        assert!(poll.end());
+        let result = poll.result.unwrap();
+        assert_eq!(result.rounds[0], HashMap::from([(0, 0), (1, 60)]));
+        assert_eq!(result.winners, vec![1]);
+    }
+
+    #[test]
+    fn token_weight_cannot_increase_after_voting() {
+        let mut poll = poll(PollVotingSystem::Plurality, None);
+        poll.config.vote_weighting = Some(PollVoteWeighting::TokenBalance(types::TokenBalanceVoteWeighting {
+            ledger_canister_id: Principal::from_slice(&[100]),
+            quadratic: false,
+        }));
+
+        poll.register_vote(user(1), 0, VoteOperation::RegisterVote, Some(10));
+        poll.register_vote(user(1), 1, VoteOperation::RegisterVote, Some(1000));
+
+        assert_eq!(poll.weights.get(&user(1)), Some(&10));
+    }
+
+    #[test]
+    fn zero_weight_cannot_vote() {
+        let mut poll = poll(PollVotingSystem::Plurality, None);
+        assert!(matches!(
+            poll.register_vote(user(1), 0, VoteOperation::RegisterVote, Some(0)),
+            RegisterVoteResult::NoVotingWeight
+        ));
+    }
+}
@@ -1,34 +1,134 @@
+use crate::MessageContentInternal;
 use search::simple::{Document, Query};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashSet};
-use types::{MessageIndex, UserId};
+use std::ops::Range;
+use types::{MessageContentType, MessageIndex, UserId};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct SearchIndex {
-    map: BTreeMap<MessageIndex, (UserId, Document)>,
+    map: BTreeMap<MessageIndex, SearchIndexEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(from = "SearchIndexEntryCombined")]
+pub struct SearchIndexEntry {
+    #[serde(rename = "s")]
+    sender: UserId,
+    #[serde(rename = "d")]
+    document: Document,
+    // Entries created before content types were indexed won't have this set
+    #[serde(rename = "c", default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<MessageContentType>,
+}
+
+impl SearchIndexEntry {
+    pub fn new(sender: UserId, content: &MessageContentInternal) -> SearchIndexEntry {
+        SearchIndexEntry {
+            sender,
+            document: Document::from(content),
+            content_type: Some(content.content_type()),
+        }
+    }
 }
 
 impl SearchIndex {
-    pub fn push(&mut self, message_index: MessageIndex, sender: UserId, document: Document) {
-        self.map.insert(message_index, (sender, document));
+    pub fn push(&mut self, message_index: MessageIndex, entry: SearchIndexEntry) {
+        self.map.insert(message_index, entry);
     }
 
     pub fn remove(&mut self, message_index: MessageIndex) {
         self.map.remove(&message_index);
     }
 
-    pub fn search_messages(
-        &self,
-        min_visible_message_index: MessageIndex,
-        query: Query,
-        users: HashSet<UserId>,
-    ) -> impl Iterator<Item = MessageIndex> + '_ {
+    // Returns the matching message indexes, latest first. Entries whose content type is unknown
+    // are returned if the query has content type filters, so the caller must check those itself.
+    pub fn search_messages<'a>(
+        &'a self,
+        range: Range<MessageIndex>,
+        query: &'a Query,
+        users: &'a HashSet<UserId>,
+    ) -> impl Iterator<Item = MessageIndex> + 'a {
+        let range = range.start..range.end.max(range.start);
+
         self.map
-            .range(min_visible_message_index..)
+            .range(range)
             .rev()
-            .filter(move |(_, (sender, doc))| {
-                (users.is_empty() || users.contains(sender)) && (query.tokens.is_empty() || doc.is_match(&query))
+            .filter(move |(_, entry)| {
+                (users.is_empty() || users.contains(&entry.sender))
+                    && (query.is_empty() || entry.document.is_match(query))
+                    && entry
+                        .content_type
+                        .as_ref()
+                        .is_none_or(|content_type| matches_content_type_filters(content_type, query))
             })
             .map(|(id, _)| *id)
     }
 }
+
+pub(crate) fn matches_content_type_filters(content_type: &MessageContentType, query: &Query) -> bool {
+    query
+        .filters
+        .has
+        .iter()
+        .all(|kind| matches_content_type_filter(content_type, kind))
+}
+
+fn matches_content_type_filter(content_type: &MessageContentType, kind: &str) -> bool {
+    match content_type {
+        MessageContentType::Text => kind == "text",
+        MessageContentType::Image => matches!(kind, "image" | "media"),
+        MessageContentType::Video => matches!(kind, "video" | "media"),
+        MessageContentType::Audio => matches!(kind, "audio" | "media"),
+        MessageContentType::File => kind == "file",
+        MessageContentType::Poll => kind == "poll",
+        MessageContentType::Crypto => kind == "crypto",
+        MessageContentType::Giphy => matches!(kind, "giphy" | "gif"),
+        MessageContentType::GovernanceProposal => kind == "proposal",
+        MessageContentType::Prize => kind == "prize",
+        MessageContentType::P2PSwap => kind == "swap",
+        MessageContentType::Custom(custom) => custom.to_lowercase() == kind,
+        MessageContentType::Deleted
+        | MessageContentType::PrizeWinner
+        | MessageContentType::MessageReminderCreated
+        | MessageContentType::MessageReminder
+        | MessageContentType::ReportedMessage
+        | MessageContentType::ModerationReport
+        | MessageContentType::VideoCall => false,
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SearchIndexEntryCombined {
+    Old(UserId, Document),
+    New {
+        #[serde(rename = "s")]
+        sender: UserId,
+        #[serde(rename = "d")]
+        document: Document,
+        #[serde(rename = "c", default)]
+        content_type: Option<MessageContentType>,
+    },
+}
+
+impl From<SearchIndexEntryCombined> for SearchIndexEntry {
+    fn from(value: SearchIndexEntryCombined) -> Self {
+        match value {
+            SearchIndexEntryCombined::Old(sender, document) => SearchIndexEntry {
+                sender,
+                document,
+                content_type: None,
+            },
+            SearchIndexEntryCombined::New {
+                sender,
+                document,
+                content_type,
+            } => SearchIndexEntry {
+                sender,
+                document,
+                content_type,
+            },
+        }
+    }
+}
@@ -23,10 +23,12 @@ use crate::{
 use constants::CHAT_SYMBOL;
 use rand::rngs::StdRng;
 use rand::{Rng, RngExt, SeedableRng};
+use std::collections::{BTreeMap, HashMap};
 use testing::rng::deterministic::{random_from_principal, random_from_u32, random_from_u128, random_principal, random_string};
 use types::{
-    EventIndex, EventWrapperInternal, MessageReport, P2PSwapCompleted, P2PSwapStatus, Proposal, ProposalDecisionStatus,
-    ProposalRewardStatus, Reaction, SnsProposal, Tally, ThumbnailData, Tips, TokenInfo, VideoCallPresence, VideoCallType,
+    EventIndex, EventWrapperInternal, MessageReport, P2PSwapCompleted, P2PSwapStatus, PollVotingSystem, Proposal,
+    ProposalDecisionStatus, ProposalRewardStatus, Reaction, SnsProposal, Tally, ThumbnailData, Tips, TokenInfo,
+    VideoCallPresence, VideoCallType,
 };
 
 mod test_values;
@@ -36,6 +38,7 @@ fn text_content() {
     let mut rng = get_deterministic_rng();
     let content = MessageContentInternal::Text(TextContentInternal {
         text: random_string(&mut rng),
+        components: Vec::new(),
     });
     let bytes = generate_then_serialize_value(content, &mut rng);
     assert_eq!(bytes, TEXT_CURRENT);
@@ -147,6 +150,9 @@ fn poll_content() {
             show_votes_before_end_date: true,
             allow_multiple_votes_per_user: true,
             allow_user_to_change_vote: true,
+            voting_system: PollVotingSystem::Plurality,
+            vote_weighting: None,
+            quorum: None,
         },
         votes: [(
             rng.random(),
@@ -155,6 +161,9 @@ fn poll_content() {
         .into_iter()
         .collect(),
         ended: true,
+        rankings: HashMap::new(),
+        weights: HashMap::new(),
+        result: None,
     });
     let bytes = generate_then_serialize_value(content, &mut rng);
     assert_eq!(bytes, POLL_CURRENT);
@@ -588,6 +597,8 @@ fn generate_value<R: Rng>(content: MessageContentInternal, rng: &mut R) -> Event
             sender_context: None,
             og_previews: Vec::new(),
             moderation_flags: 0,
+            edit_history: Vec::new(),
+            translations: BTreeMap::new(),
         })),
     }
 }
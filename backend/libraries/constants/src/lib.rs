@@ -26,6 +26,8 @@ pub const CYCLES_REQUIRED_FOR_UPGRADE: Cycles = 300 * B; // 0.3T cycles
 
 pub const OPENCHAT_BOT_USER_ID: UserId = UserId::new(Principal::from_slice(&[228, 104, 142, 9, 133, 211, 135, 217, 129, 1]));
 pub const DELETED_USER_ID: UserId = UserId::new(Principal::from_slice(&[139, 36, 200, 58, 72, 145, 241, 66, 97, 1]));
+// Placeholder sender of messages imported from other platforms, ie. Slack, Discord and Telegram
+pub const IMPORTED_USER_ID: UserId = UserId::new(Principal::from_slice(&[73, 77, 80, 79, 82, 84, 69, 68, 0, 1]));
 pub const OPENCHAT_BOT_USERNAME: &str = "OpenChatBot";
 pub const OPENCHAT_TREASURY_CANISTER_ID: CanisterId = Principal::from_slice(&[0, 0, 0, 0, 2, 48, 2, 238, 1, 1]);
 
@@ -191,6 +191,12 @@ pub enum OCErrorCode {
     LedgerNotFound = 347,
     PrizeUserNotElligible = 348,
     NoEventsToDelete = 349,
+    MaxScheduledMessagesReached = 350,
+    MaxOutgoingWebhooksReached = 351,
+    EditHistoryDisabled = 352,
+    NoVotingWeight = 353,
+    TranslationFailed = 354,
+    EndToEndEncrypted = 355,
 
     // InternalError
     C2CError = 500,
@@ -1,30 +1,34 @@
 use chat_events::{
     AddRemoveReactionArgs, ChatEventInternal, ChatEvents, ChatEventsListReader, DeleteMessageSuccess,
     DeleteUndeleteMessagesArgs, EditMessageArgs, EventPusher, ExpiredThread, GroupGateUpdatedInternal, MessageContentInternal,
-    MessageInternal, NullEventPusher, PushEventResultInternal, PushMessageArgs, Reader, RegisterPollVoteArgs,
-    RegisterPollVoteSuccess, RemoveEventsResult, ReservePrizeSuccess, TipMessageArgs, UndeleteMessageSuccess,
-    UpdateMessageSuccess,
+    MessageInternal, MessageTranslation, NullEventPusher, PushEventResultInternal, PushMessageArgs, Reader,
+    RegisterPollVoteArgs, RegisterPollVoteSuccess, RemoveEventsResult, ReservePrizeSuccess, TextContentInternal,
+    TipMessageArgs, UndeleteMessageSuccess, UpdateMessageSuccess,
 };
+use constants::IMPORTED_USER_ID;
 use group_community_common::MemberUpdate;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use oc_error_codes::{OCError, OCErrorCode};
+use rand::rngs::StdRng;
 use regex_lite::Regex;
 use search::simple::Query;
 use serde::{Deserialize, Serialize};
 use std::cmp::{Reverse, max, min};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Bound;
 use types::{
-    AccessGateConfig, AccessGateConfigInternal, AvatarChanged, BotMessageContext, BotNotification, Caller, Chat,
-    CustomPermission, DiamondMembershipStatus, Document, EventIndex, EventOrExpiredRange, EventWrapper, EventsCaller,
-    EventsResponse, ExternalUrlUpdated, GroupDescriptionChanged, GroupMember, GroupNameChanged, GroupPermissions,
-    GroupReplyContext, GroupRole, GroupRulesChanged, GroupSubtype, GroupVisibilityChanged, HydratedMention,
-    MAX_RETURNED_MENTIONS, MemberLeft, MembersRemoved, Message, MessageContent, MessageId, MessageIndex, MessageMatch,
-    MessagePermissions, MessagePinned, MessageUnpinned, MessagesResponse, Milliseconds, MultiUserChat, OCResult, OgPreview,
-    OptionUpdate, OptionalGroupPermissions, OptionalMessagePermissions, PermissionsChanged, Reaction, ReserveP2PSwapSuccess,
-    RoleChanged, Rules, SelectedGroupUpdates, SenderContext, ThreadPreview, TimestampMillis, Timestamped, UpdatedRules, UserId,
-    UserType, UsersBlocked, UsersInvited, Version, Versioned, VersionedRules, VideoCall, VideoCallPresence, VoteOperation,
-    WebhookDetails,
+    AccessGateConfig, AccessGateConfigInternal, AvatarChanged, BotEvent, BotMessageContext, BotNotification, Caller,
+    CanisterId, Chat, ChatEventCategory, ChatEventType, ChatExportCursor, ChatExportMember, ChatExportPage, CustomPermission,
+    DiamondMembershipStatus, Document, EventIndex, EventOrExpiredRange, EventWrapper, EventsCaller, EventsResponse,
+    ExternalUrlUpdated, GroupDescriptionChanged, GroupMember, GroupNameChanged, GroupPermissions, GroupReplyContext, GroupRole,
+    GroupRulesChanged, GroupSubtype, GroupVisibilityChanged, HydratedMention, ImportedMessage, MAX_RETURNED_MENTIONS,
+    MAX_TEXT_LENGTH_USIZE, MemberLeft, MembersRemoved, Message, MessageContent, MessageId, MessageIndex, MessageMatch,
+    MessagePermissions, MessagePinned, MessageRevision, MessageUnpinned, MessagesResponse, Milliseconds, MultiUserChat,
+    OCResult, OgPreview, OptionUpdate, OptionalGroupPermissions, OptionalMessagePermissions, PermissionsChanged, Reaction,
+    ReserveP2PSwapSuccess, RoleChanged, Rules, SelectedGroupUpdates, SenderContext, ThreadPreview, TimestampMillis,
+    Timestamped, UpdatedRules, UserId, UserType, UsersBlocked, UsersInvited, Version, Versioned, VersionedRules, VideoCall,
+    VideoCallPresence, VoteOperation, WebhookDetails,
 };
 use utils::document::validate_avatar;
 use utils::text_validation::{
@@ -34,15 +38,20 @@ use utils::text_validation::{
 mod invited_users;
 mod members;
 mod mentions;
+mod outgoing_webhooks;
 mod roles;
 mod webhooks;
 
 pub use invited_users::*;
 pub use members::*;
 pub use mentions::*;
+pub use outgoing_webhooks::*;
 pub use roles::*;
 pub use webhooks::*;
 
+const MAX_KEYWORD_ALERTS: usize = 20;
+const MAX_KEYWORD_ALERT_LENGTH: usize = 50;
+
 #[derive(Serialize, Deserialize)]
 pub struct GroupChatCore {
     pub is_public: Timestamped<bool>,
@@ -67,6 +76,16 @@ pub struct GroupChatCore {
     pub external_url: Timestamped<Option<String>>,
     at_everyone_mentions: BTreeMap<TimestampMillis, AtEveryoneMention>,
     pub webhooks: Webhooks,
+    #[serde(default)]
+    pub outgoing_webhooks: OutgoingWebhooks,
+    #[serde(default)]
+    pub edit_history_disabled: Timestamped<bool>,
+    #[serde(default)]
+    pub e2ee_key_epoch: Timestamped<Option<u32>>,
+    // When each key epoch started, starting from epoch 1, used to determine which epoch was
+    // current when each member joined
+    #[serde(default)]
+    e2ee_key_epoch_starts: Vec<TimestampMillis>,
 }
 
 #[expect(clippy::too_many_arguments)]
@@ -124,6 +143,68 @@ impl GroupChatCore {
             external_url: Timestamped::new(external_url, now),
             at_everyone_mentions: BTreeMap::new(),
             webhooks: Webhooks::default(),
+            outgoing_webhooks: OutgoingWebhooks::default(),
+            edit_history_disabled: Timestamped::default(),
+            e2ee_key_epoch: Timestamped::default(),
+            e2ee_key_epoch_starts: Vec::new(),
+        }
+    }
+
+    pub fn is_end_to_end_encrypted(&self) -> bool {
+        self.e2ee_key_epoch.value.is_some()
+    }
+
+    // The epoch of the key which messages and files must currently be encrypted with. Chats which
+    // aren't end-to-end encrypted are always at epoch 0.
+    pub fn key_epoch(&self) -> u32 {
+        self.e2ee_key_epoch.value.unwrap_or_default()
+    }
+
+    // Once a member leaves or is removed they must no longer be able to derive the key used for
+    // new messages, so each time this happens the chat moves on to a new key epoch. Members can
+    // still derive the keys of earlier epochs in order to decrypt older messages.
+    fn rotate_key_epoch(&mut self, now: TimestampMillis) {
+        if let Some(epoch) = self.e2ee_key_epoch.value {
+            self.e2ee_key_epoch = Timestamped::new(Some(epoch + 1), now);
+            self.e2ee_key_epoch_starts.push(now);
+        }
+    }
+
+    // The earliest key epoch the member may derive keys for. Unless the chat's history is visible
+    // to new joiners, members can only derive keys from the epoch which was current when they
+    // joined, so that they can't decrypt messages sent in earlier epochs.
+    pub fn min_key_epoch(&self, member: &GroupMemberInternal) -> u32 {
+        if self.history_visible_to_new_joiners {
+            0
+        } else {
+            self.e2ee_key_epoch_starts
+                .iter()
+                .take_while(|start| **start <= member.date_added())
+                .count() as u32
+        }
+    }
+
+    // Removes a member whose account has been deleted, moving the chat on to a new key epoch in
+    // the same way as if they had left
+    pub fn remove_deleted_user(&mut self, user_id: UserId, now: TimestampMillis) {
+        if self.members.remove(user_id, now).is_some() {
+            self.rotate_key_epoch(now);
+        }
+    }
+
+    // In end-to-end encrypted chats the canister must never receive plaintext, so all message
+    // content must be encrypted client side. Video call messages are the exception since they
+    // contain no user content.
+    pub fn validate_message_content(&self, content: &MessageContentInternal) -> OCResult {
+        if self.is_end_to_end_encrypted()
+            && !matches!(
+                content,
+                MessageContentInternal::Encrypted(_) | MessageContentInternal::VideoCall(_)
+            )
+        {
+            Err(OCErrorCode::EndToEndEncrypted.with_message("Message content must be encrypted"))
+        } else {
+            Ok(())
         }
     }
 
@@ -169,6 +250,8 @@ impl GroupChatCore {
             self.invited_users.last_updated(),
             self.members.last_updated().unwrap_or_default(),
             self.webhooks.last_updated(),
+            self.edit_history_disabled.timestamp,
+            self.e2ee_key_epoch.timestamp,
         ]
         .into_iter()
         .max()
@@ -263,6 +346,8 @@ impl GroupChatCore {
                 .if_set_after(since)
                 .cloned()
                 .map_or(OptionUpdate::NoChange, OptionUpdate::from_update),
+            edit_history_disabled: self.edit_history_disabled.if_set_after(since).copied(),
+            e2ee_key_epoch: self.e2ee_key_epoch.if_set_after(since).copied().flatten(),
             any_updates_missed: self.members.any_updates_removed(since)
                 || member.as_ref().map(|m| m.any_updates_removed(since)).unwrap_or_default()
                 || self.events.latest_event_update_removed() > since,
@@ -308,16 +393,7 @@ impl GroupChatCore {
         };
 
         if self.webhooks.last_updated() > since {
-            result.webhooks = Some(
-                self.webhooks
-                    .iter()
-                    .map(|(id, webhook)| WebhookDetails {
-                        id: *id,
-                        name: webhook.name.clone(),
-                        avatar_id: webhook.avatar.as_ref().map(|avatar| avatar.id),
-                    })
-                    .collect(),
-            );
+            result.webhooks = Some(self.webhooks.details());
         }
 
         let mut users_added_updated_or_removed = HashSet::new();
@@ -499,6 +575,133 @@ impl GroupChatCore {
         }
     }
 
+    pub fn message_edit_history(
+        &self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        now: TimestampMillis,
+    ) -> OCResult<Vec<MessageRevision>> {
+        let member = self.members.get(&user_id).ok_or(OCErrorCode::InitiatorNotInChat)?;
+
+        if self.edit_history_disabled.value {
+            return Err(OCErrorCode::EditHistoryDisabled.into());
+        }
+
+        self.events.message_edit_history(
+            member.min_visible_event_index(),
+            thread_root_message_index,
+            message_id,
+            Some(user_id),
+            now,
+        )
+    }
+
+    pub fn message_translation(
+        &self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        language: &str,
+    ) -> OCResult<MessageTranslation> {
+        let member = self.members.get(&user_id).ok_or(OCErrorCode::InitiatorNotInChat)?;
+
+        self.events.message_translation(
+            member.min_visible_event_index(),
+            thread_root_message_index,
+            message_id,
+            language,
+        )
+    }
+
+    pub fn validate_message_interaction(
+        &self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        component_id: &str,
+        value: Option<&str>,
+    ) -> OCResult<UserId> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        self.events.validate_message_interaction(
+            member.min_visible_event_index(),
+            thread_root_message_index,
+            message_id,
+            component_id,
+            value,
+        )
+    }
+
+    pub fn cache_message_translation(
+        &mut self,
+        thread_root_message_index: Option<MessageIndex>,
+        message_id: MessageId,
+        source_text: &str,
+        language: String,
+        translation: String,
+    ) {
+        self.events.cache_message_translation(
+            EventIndex::default(),
+            thread_root_message_index,
+            message_id,
+            source_text,
+            language,
+            translation,
+        );
+    }
+
+    pub fn export_history(
+        &self,
+        user_id: UserId,
+        after: Option<ChatExportCursor>,
+        max_events: u32,
+    ) -> OCResult<ChatExportPage> {
+        const MAX_EVENTS_PER_PAGE: u32 = 1000;
+
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().can_export_history(&self.permissions) {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        let min_visible_event_index = self.min_visible_event_index(Some(user_id))?;
+        let max_records = max_events.clamp(1, MAX_EVENTS_PER_PAGE) as usize;
+
+        // The members are exported first, paged in the same way as the events
+        let mut members = Vec::new();
+        let members_after = match after {
+            None => Some(Bound::Unbounded),
+            Some(ChatExportCursor::Member { user_id: last_exported }) => Some(Bound::Excluded(last_exported)),
+            Some(ChatExportCursor::Event { .. }) => None,
+        };
+        if let Some(members_after) = members_after {
+            let mut last = None;
+            for member_id in self.members.member_ids().range((members_after, Bound::Unbounded)) {
+                if members.len() >= max_records {
+                    break;
+                }
+                if let Some(role) = self.members.role(member_id) {
+                    members.push(ChatExportMember {
+                        user_id: *member_id,
+                        role,
+                    });
+                }
+                last = Some(*member_id);
+            }
+            if members.len() >= max_records {
+                let next = last.map(|last_exported| ChatExportCursor::Member { user_id: last_exported });
+                return Ok(ChatExportPage::new(members, Vec::new(), next));
+            }
+        }
+
+        let (events, next) =
+            self.events
+                .export_events(min_visible_event_index, after, max_records - members.len(), Some(user_id));
+
+        Ok(ChatExportPage::new(members, events, next))
+    }
+
     pub fn thread_previews(&self, user_id: UserId, threads: Vec<MessageIndex>) -> OCResult<Vec<ThreadPreview>> {
         let member = self.members.get(&user_id).ok_or(OCErrorCode::InitiatorNotInChat)?;
 
@@ -513,14 +716,19 @@ impl GroupChatCore {
     pub fn search(
         &self,
         user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
         search_term: String,
         users: Option<HashSet<UserId>>,
         max_results: u8,
     ) -> OCResult<Vec<MessageMatch>> {
         const MIN_TERM_LENGTH: u8 = 3;
-        const MAX_TERM_LENGTH: u8 = 30;
+        const MAX_TERM_LENGTH: u8 = 100;
         const MAX_USERS: u8 = 5;
 
+        if self.is_end_to_end_encrypted() {
+            return Err(OCErrorCode::EndToEndEncrypted.with_message("Messages can't be searched"));
+        }
+
         let term_length = search_term.len() as u8;
         let users = users.unwrap_or_default();
 
@@ -543,11 +751,14 @@ impl GroupChatCore {
 
         let query = Query::new(&search_term);
 
-        let matches = self
-            .events
-            .search_messages(member.min_visible_message_index(), query, users, max_results);
-
-        Ok(matches)
+        self.events.search_messages(
+            member.min_visible_event_index(),
+            member.min_visible_message_index(),
+            thread_root_message_index,
+            query,
+            users,
+            max_results,
+        )
     }
 
     pub fn send_message<P: EventPusher>(
@@ -574,15 +785,14 @@ impl GroupChatCore {
                 .message_internal(EventIndex::default(), thread_root_message_index, message_id.into())
         {
             if let Caller::BotV2(bot_now) = &caller
-                && let Some(bot_message) = message.bot_context()
-                && bot_now.bot == message.sender
-                && bot_now.initiator.user() == bot_message.command.as_ref().map(|c| c.initiator)
-                && bot_now.initiator.command() == bot_message.command.as_ref()
-                && !bot_message.finalised
+                && message.is_open_to_bot(bot_now)
             {
+                // Don't notify members again if the message was already finalised
+                let finalise = finalised && message.bot_context().is_some_and(|b| !b.finalised);
+
                 return self.update_bot_message(
                     caller,
-                    finalised,
+                    finalise,
                     thread_root_message_index,
                     message_id,
                     content,
@@ -691,6 +901,7 @@ impl GroupChatCore {
             block_level_markdown: Some(block_level_markdown),
             og_previews,
             finalise_bot_message: finalise,
+            record_edit_history: false,
             now,
         };
 
@@ -776,7 +987,7 @@ impl GroupChatCore {
                                     m.mentions.add(thread_root_message_index, message_index, message_id, now);
                                 }
 
-                                if mentioned || !m.notifications_muted().value {
+                                if mentioned || (!m.notifications_muted().value && !m.mentions_only().value) {
                                     users_to_notify.insert(user_id);
                                 }
                             }
@@ -803,12 +1014,29 @@ impl GroupChatCore {
                         self.members
                             .member_ids()
                             .difference(self.members.at_everyone_muted())
+                            .filter(|u| !self.members.mentions_only().contains(u))
                             .copied(),
                     );
                 }
 
-                // Notify everyone who has notifications unmuted
-                users_to_notify.extend(self.members.notifications_unmuted().iter().copied());
+                // Notify everyone who has notifications unmuted, other than those who only want
+                // to be notified of mentions and replies
+                users_to_notify.extend(
+                    self.members
+                        .notifications_unmuted()
+                        .difference(self.members.mentions_only())
+                        .copied(),
+                );
+            }
+
+            // Notify anyone who has a keyword alert which matches the message, even if muted
+            if let Some(text) = message.content.text().filter(|_| !self.members.keyword_alerts().is_empty()) {
+                let text = text.to_lowercase();
+                for (user_id, keywords) in self.members.keyword_alerts() {
+                    if !users_to_notify.contains(user_id) && keywords.iter().any(|k| contains_keyword(&text, k)) {
+                        users_to_notify.insert(*user_id);
+                    }
+                }
             }
         }
 
@@ -843,6 +1071,8 @@ impl GroupChatCore {
             });
         }
 
+        self.validate_message_content(content)?;
+
         if let Some(version) = rules_accepted {
             self.members.update_member(&caller.agent(), |m| {
                 m.accept_rules(min(version, self.rules.text.version), now);
@@ -880,6 +1110,81 @@ impl GroupChatCore {
         })
     }
 
+    // Pushes messages imported from another platform, sent by the placeholder imported user. Only
+    // the owner can import messages. Messages which have already been imported are skipped so that
+    // an import can safely be retried. Imported messages aren't pushed to the event store since
+    // they aren't activity on OpenChat. Messages are pushed with the current time, so that expiry,
+    // thread summaries and metrics behave as for any other message, while the time they were
+    // originally sent is kept in their sender context. Returns the message index of each message.
+    pub fn import_messages(
+        &mut self,
+        user_id: UserId,
+        messages: Vec<ImportedMessage>,
+        now: TimestampMillis,
+    ) -> OCResult<Vec<MessageIndex>> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        if !member.role().is_owner() {
+            return Err(OCErrorCode::InitiatorNotAuthorized.into());
+        }
+
+        // Imported messages are plaintext, which end-to-end encrypted chats must never contain
+        if self.is_end_to_end_encrypted() {
+            return Err(
+                OCErrorCode::EndToEndEncrypted.with_message("Messages can't be imported into end-to-end encrypted chats")
+            );
+        }
+
+        if messages
+            .iter()
+            .any(|m| m.text.is_empty() || m.text.len() > MAX_TEXT_LENGTH_USIZE)
+        {
+            return Err(OCErrorCode::InvalidMessageContent.into());
+        }
+
+        let mut message_indexes = Vec::with_capacity(messages.len());
+        for message in messages {
+            if let Some((_, message_index, _)) = self
+                .events
+                .message_ids(message.thread_root_message_index, message.message_id.into())
+            {
+                message_indexes.push(message_index);
+                continue;
+            }
+
+            if let Some(root_message_index) = message.thread_root_message_index
+                && self.events.message_ids(None, root_message_index.into()).is_none()
+            {
+                return Err(OCErrorCode::ThreadNotFound.into());
+            }
+
+            let (message_event, _) = self.events.push_message::<NullEventPusher>(
+                PushMessageArgs {
+                    sender: IMPORTED_USER_ID,
+                    thread_root_message_index: message.thread_root_message_index,
+                    message_id: message.message_id,
+                    content: MessageContentInternal::Text(TextContentInternal {
+                        text: message.text,
+                        components: Vec::new(),
+                    }),
+                    sender_context: Some(SenderContext::Imported(message.context)),
+                    mentioned: Vec::new(),
+                    replies_to: None,
+                    forwarded: false,
+                    sender_is_bot: false,
+                    block_level_markdown: false,
+                    og_previews: Vec::new(),
+                    now,
+                },
+                None,
+            );
+
+            message_indexes.push(message_event.event.message_index);
+        }
+
+        Ok(message_indexes)
+    }
+
     pub fn add_reaction<P: EventPusher>(
         &mut self,
         caller: Caller,
@@ -1340,6 +1645,7 @@ impl GroupChatCore {
         self.can_leave(user_id)?;
 
         let removed = self.members.remove(user_id, now).unwrap();
+        self.rotate_key_epoch(now);
 
         let result = self
             .events
@@ -1393,6 +1699,10 @@ impl GroupChatCore {
         // Try to block the user if requested
         let blocked = block && self.members.block(target_user_id, now);
 
+        if removed {
+            self.rotate_key_epoch(now);
+        }
+
         // Push relevant event
         let event = if blocked {
             let event = UsersBlocked {
@@ -1432,9 +1742,20 @@ impl GroupChatCore {
         messages_visible_to_non_members: Option<bool>,
         events_ttl: OptionUpdate<Milliseconds>,
         external_url: OptionUpdate<String>,
+        edit_history_disabled: Option<bool>,
+        e2ee_enabled: Option<bool>,
         now: TimestampMillis,
     ) -> OCResult<UpdateSuccessResult> {
-        self.can_update(user_id, &name, &description, &rules, &avatar, permissions.as_ref(), &public)?;
+        self.can_update(
+            user_id,
+            &name,
+            &description,
+            &rules,
+            &avatar,
+            permissions.as_ref(),
+            &public,
+            &e2ee_enabled,
+        )?;
 
         Ok(self.do_update(
             user_id,
@@ -1448,6 +1769,8 @@ impl GroupChatCore {
             messages_visible_to_non_members,
             events_ttl,
             external_url,
+            edit_history_disabled,
+            e2ee_enabled,
             now,
         ))
     }
@@ -1461,6 +1784,7 @@ impl GroupChatCore {
         avatar: &OptionUpdate<Document>,
         permissions: Option<&OptionalGroupPermissions>,
         public: &Option<bool>,
+        e2ee_enabled: &Option<bool>,
     ) -> OCResult {
         let avatar_update = avatar.as_ref().expand();
 
@@ -1493,12 +1817,20 @@ impl GroupChatCore {
             return Err(OCErrorCode::AvatarTooBig.with_json(&error));
         }
 
+        if *e2ee_enabled == Some(false) && self.is_end_to_end_encrypted() {
+            return Err(OCErrorCode::EndToEndEncrypted.with_message("End-to-end encryption can't be disabled"));
+        }
+
+        if (*e2ee_enabled == Some(true) || self.is_end_to_end_encrypted()) && public.unwrap_or(self.is_public.value) {
+            return Err(OCErrorCode::EndToEndEncrypted.with_message("End-to-end encrypted chats must be private"));
+        }
+
         let member = self.members.get_verified_member(user_id)?;
 
         let group_permissions = &self.permissions;
         if !member.role().can_update_group(group_permissions)
             || (permissions.is_some() && !member.role().can_change_permissions())
-            || (public.is_some() && !member.role().can_change_group_visibility())
+            || ((public.is_some() || e2ee_enabled.is_some()) && !member.role().can_change_group_visibility())
         {
             Err(OCErrorCode::InitiatorNotAuthorized.into())
         } else {
@@ -1519,6 +1851,8 @@ impl GroupChatCore {
         messages_visible_to_non_members: Option<bool>,
         events_ttl: OptionUpdate<Milliseconds>,
         external_url: OptionUpdate<String>,
+        edit_history_disabled: Option<bool>,
+        e2ee_enabled: Option<bool>,
         now: TimestampMillis,
     ) -> UpdateSuccessResult {
         let mut result = UpdateSuccessResult {
@@ -1702,6 +2036,19 @@ impl GroupChatCore {
             result.bot_notifications.push(push_result.and_then(|r| r.bot_notification));
         }
 
+        if let Some(edit_history_disabled) = edit_history_disabled
+            && self.edit_history_disabled.value != edit_history_disabled
+        {
+            self.edit_history_disabled = Timestamped::new(edit_history_disabled, now);
+        }
+
+        // Epoch 0 is the implicit epoch of chats which aren't end-to-end encrypted, so encryption
+        // starts at epoch 1 to ensure no former member can derive the new keys.
+        if e2ee_enabled == Some(true) && !self.is_end_to_end_encrypted() {
+            self.e2ee_key_epoch = Timestamped::new(Some(1), now);
+            self.e2ee_key_epoch_starts = vec![now];
+        }
+
         result
     }
 
@@ -1743,6 +2090,24 @@ impl GroupChatCore {
         Ok(())
     }
 
+    // Returns true if the member's notification filters were updated
+    pub fn set_notification_filters(
+        &mut self,
+        user_id: UserId,
+        mentions_only: Option<bool>,
+        keyword_alerts: Option<Vec<String>>,
+        now: TimestampMillis,
+    ) -> OCResult<bool> {
+        self.members.get_verified_member(user_id)?;
+
+        let keyword_alerts = keyword_alerts.map(normalise_keyword_alerts).transpose()?;
+
+        Ok(self
+            .members
+            .set_notification_filters(user_id, mentions_only, keyword_alerts, now)
+            .unwrap_or_default())
+    }
+
     pub fn register_poll_vote(
         &mut self,
         user_id: UserId,
@@ -1750,10 +2115,12 @@ impl GroupChatCore {
         message_index: MessageIndex,
         option_index: u32,
         operation: VoteOperation,
+        token_balance: Option<u128>,
         now: TimestampMillis,
     ) -> OCResult<UpdateMessageSuccess<RegisterPollVoteSuccess>> {
         let member = self.members.get_verified_member(user_id)?;
         let min_visible_event_index = member.min_visible_event_index();
+        let user_role = member.role().value.into();
 
         self.events.register_poll_vote(RegisterPollVoteArgs {
             user_id,
@@ -1762,10 +2129,37 @@ impl GroupChatCore {
             message_index,
             option_index,
             operation,
+            user_role,
+            token_balance,
             now,
         })
     }
 
+    // If votes on the poll are weighted by token balance, returns the ledger which the user's
+    // balance must be read from before their vote can be registered
+    pub fn poll_vote_weighting_ledger(
+        &self,
+        user_id: UserId,
+        thread_root_message_index: Option<MessageIndex>,
+        message_index: MessageIndex,
+    ) -> OCResult<Option<CanisterId>> {
+        let member = self.members.get_verified_member(user_id)?;
+
+        let Some((message, _)) = self.events.message_internal(
+            member.min_visible_event_index(),
+            thread_root_message_index,
+            message_index.into(),
+        ) else {
+            return Err(OCErrorCode::PollNotFound.into());
+        };
+
+        if let MessageContentInternal::Poll(p) = &message.content {
+            Ok(p.config.vote_weighting.as_ref().and_then(|w| w.ledger_canister_id()))
+        } else {
+            Err(OCErrorCode::PollNotFound.into())
+        }
+    }
+
     pub fn reserve_prize(
         &mut self,
         user_id: UserId,
@@ -1982,6 +2376,7 @@ impl GroupChatCore {
             react_to_messages: new.react_to_messages.unwrap_or(old.react_to_messages),
             mention_all_members: new.mention_all_members.unwrap_or(old.mention_all_members),
             start_video_call: new.start_video_call.unwrap_or(old.start_video_call),
+            export_history: new.export_history.unwrap_or(old.export_history),
             message_permissions,
             thread_permissions,
         }
@@ -2049,12 +2444,72 @@ impl GroupChatCore {
     }
 
     pub fn webhooks(&self) -> Vec<WebhookDetails> {
-        self.webhooks
-            .iter()
-            .map(|(id, webhook)| WebhookDetails {
-                id: *id,
-                name: webhook.name.clone(),
-                avatar_id: webhook.avatar.as_ref().map(|avatar| avatar.id),
+        self.webhooks.details()
+    }
+
+    // Outgoing webhooks receive events through the same subscriptions as bots, but since they are
+    // registered by the chat's owners they may subscribe to events of any category
+    pub fn register_outgoing_webhook(
+        &mut self,
+        name: String,
+        url: String,
+        event_types: HashSet<ChatEventType>,
+        rng: &mut StdRng,
+    ) -> OCResult<UserId> {
+        if self.is_end_to_end_encrypted() {
+            return Err(OCErrorCode::EndToEndEncrypted.with_message("Outgoing webhooks can't be registered"));
+        }
+
+        let id = self.outgoing_webhooks.register(name, url, event_types.clone(), rng)?;
+        self.events
+            .subscribe_bot_to_events(id, event_types, &ALL_CHAT_EVENT_CATEGORIES);
+        Ok(id)
+    }
+
+    pub fn update_outgoing_webhook(
+        &mut self,
+        id: UserId,
+        url: Option<String>,
+        event_types: Option<HashSet<ChatEventType>>,
+        enabled: Option<bool>,
+    ) -> OCResult {
+        self.outgoing_webhooks.update(id, url, event_types.clone(), enabled)?;
+        if let Some(event_types) = event_types {
+            self.events
+                .subscribe_bot_to_events(id, event_types, &ALL_CHAT_EVENT_CATEGORIES);
+        }
+        Ok(())
+    }
+
+    pub fn remove_outgoing_webhook(&mut self, id: UserId) -> OCResult {
+        self.outgoing_webhooks.remove(&id).ok_or(OCErrorCode::WebhookNotFound)?;
+        self.events.unsubscribe_bot_from_events(id);
+        Ok(())
+    }
+
+    // Removes the outgoing webhooks from the notification's recipients (leaving only bots) and
+    // adds a pending delivery to the log of each enabled webhook, returning the delivery ids
+    pub fn start_outgoing_webhook_deliveries(
+        &mut self,
+        notification: &mut BotNotification,
+        now: TimestampMillis,
+    ) -> Vec<(UserId, u64)> {
+        let (webhook_ids, bot_ids): (Vec<_>, Vec<_>) = std::mem::take(&mut notification.recipients)
+            .into_iter()
+            .partition(|id| self.outgoing_webhooks.contains(id));
+
+        notification.recipients = bot_ids;
+
+        let BotEvent::Chat(event) = &notification.event else {
+            return Vec::new();
+        };
+
+        webhook_ids
+            .into_iter()
+            .filter_map(|id| {
+                self.outgoing_webhooks
+                    .start_delivery(id, event.event_index, event.thread, now)
+                    .map(|delivery_id| (id, delivery_id))
             })
             .collect()
     }
@@ -2116,6 +2571,8 @@ pub struct SummaryUpdates {
     pub rules_changed: bool,
     pub video_call_in_progress: OptionUpdate<VideoCall>,
     pub external_url: OptionUpdate<String>,
+    pub edit_history_disabled: Option<bool>,
+    pub e2ee_key_epoch: Option<u32>,
     pub any_updates_missed: bool,
 }
 
@@ -2177,6 +2634,13 @@ impl From<AccessRulesInternal> for VersionedRules {
 
 lazy_static! {
     static ref EVERYONE_REGEX: Regex = Regex::new(r"(^|\W)(@everyone)($|\W)").unwrap();
+    static ref ALL_CHAT_EVENT_CATEGORIES: HashSet<ChatEventCategory> = [
+        ChatEventCategory::Message,
+        ChatEventCategory::Membership,
+        ChatEventCategory::Details
+    ]
+    .into_iter()
+    .collect();
 }
 
 fn is_everyone_mentioned(content: &MessageContentInternal) -> bool {
@@ -2185,6 +2649,39 @@ fn is_everyone_mentioned(content: &MessageContentInternal) -> bool {
         .is_some_and(|text| text.contains("@everyone") && EVERYONE_REGEX.is_match(text))
 }
 
+pub fn normalise_keyword_alerts(keywords: Vec<String>) -> OCResult<Vec<String>> {
+    let mut normalised: Vec<String> = Vec::new();
+    for keyword in keywords {
+        let keyword = keyword.trim().to_lowercase();
+        if keyword.is_empty() || normalised.contains(&keyword) {
+            continue;
+        }
+        if keyword.chars().count() > MAX_KEYWORD_ALERT_LENGTH {
+            return Err(OCErrorCode::InvalidRequest.with_message(format!(
+                "Keyword alerts must be at most {MAX_KEYWORD_ALERT_LENGTH} characters"
+            )));
+        }
+        normalised.push(keyword);
+    }
+
+    if normalised.len() > MAX_KEYWORD_ALERTS {
+        return Err(
+            OCErrorCode::InvalidRequest.with_message(format!("At most {MAX_KEYWORD_ALERTS} keyword alerts are allowed"))
+        );
+    }
+    Ok(normalised)
+}
+
+// Keywords only match whole words (or phrases), so "cat" doesn't match "concatenate". The text is
+// expected to already be lowercase.
+fn contains_keyword(text: &str, keyword: &str) -> bool {
+    text.match_indices(keyword).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + keyword.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
 struct PrepareSendMessageSuccess {
     min_visible_event_index: EventIndex,
     everyone_mentioned: bool,
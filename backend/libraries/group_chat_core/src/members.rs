@@ -36,6 +36,12 @@ pub struct GroupMembers {
     notifications_unmuted: BTreeSet<UserId>,
     #[serde(default)]
     at_everyone_muted: BTreeSet<UserId>,
+    #[serde(default)]
+    mentions_only: BTreeSet<UserId>,
+    // Each member's keywords are duplicated here so that matching them against each new message
+    // doesn't require reading every watching member from stable memory
+    #[serde(default)]
+    keyword_alerts: BTreeMap<UserId, Vec<String>>,
     lapsed: BTreeSet<UserId>,
     blocked: BTreeSet<UserId>,
     suspended: BTreeSet<UserId>,
@@ -53,6 +59,8 @@ impl GroupMembers {
             min_visible_message_index: MessageIndex::default(),
             notifications_muted: Timestamped::new(false, now),
             at_everyone_muted: Timestamped::new(false, now),
+            mentions_only: Timestamped::default(),
+            keyword_alerts: Timestamped::default(),
             mentions: Mentions::default(),
             followed_threads: TimestampedSet::new(),
             unfollowed_threads: TimestampedSet::new(),
@@ -78,6 +86,8 @@ impl GroupMembers {
             },
             notifications_unmuted: [creator_user_id].into_iter().collect(),
             at_everyone_muted: BTreeSet::new(),
+            mentions_only: BTreeSet::new(),
+            keyword_alerts: BTreeMap::new(),
             lapsed: BTreeSet::new(),
             suspended: BTreeSet::new(),
             updates: BTreeSet::new(),
@@ -119,6 +129,8 @@ impl GroupMembers {
                 min_visible_message_index,
                 notifications_muted: Timestamped::new(notifications_muted, 0),
                 at_everyone_muted: Timestamped::new(false, 0),
+                mentions_only: Timestamped::default(),
+                keyword_alerts: Timestamped::default(),
                 mentions: Mentions::default(),
                 followed_threads: TimestampedSet::default(),
                 unfollowed_threads: TimestampedSet::default(),
@@ -164,6 +176,12 @@ impl GroupMembers {
         if member.at_everyone_muted.value {
             self.at_everyone_muted.remove(&user_id);
         }
+        if member.mentions_only.value {
+            self.mentions_only.remove(&user_id);
+        }
+        if !member.keyword_alerts.value.is_empty() {
+            self.keyword_alerts.remove(&user_id);
+        }
         if member.lapsed.value {
             self.lapsed.remove(&user_id);
         }
@@ -374,6 +392,51 @@ impl GroupMembers {
         }
     }
 
+    pub fn set_notification_filters(
+        &mut self,
+        user_id: UserId,
+        mentions_only: Option<bool>,
+        keyword_alerts: Option<Vec<String>>,
+        now: TimestampMillis,
+    ) -> Option<bool> {
+        let member = self.get(&user_id)?;
+
+        let mentions_only = mentions_only.filter(|m| *m != member.mentions_only.value);
+        let keyword_alerts = keyword_alerts.filter(|k| *k != member.keyword_alerts.value);
+
+        if let Some(mentions_only) = mentions_only {
+            if mentions_only {
+                self.mentions_only.insert(user_id);
+            } else {
+                self.mentions_only.remove(&user_id);
+            }
+        }
+
+        if let Some(keyword_alerts) = &keyword_alerts {
+            if keyword_alerts.is_empty() {
+                self.keyword_alerts.remove(&user_id);
+            } else {
+                self.keyword_alerts.insert(user_id, keyword_alerts.clone());
+            }
+        }
+
+        let updated = mentions_only.is_some() || keyword_alerts.is_some();
+
+        if updated {
+            self.update_member(&user_id, |m| {
+                if let Some(mentions_only) = mentions_only {
+                    m.mentions_only = Timestamped::new(mentions_only, now);
+                }
+                if let Some(keyword_alerts) = keyword_alerts {
+                    m.keyword_alerts = Timestamped::new(keyword_alerts, now);
+                }
+                true
+            });
+        }
+
+        Some(updated)
+    }
+
     pub fn register_proposal_vote(&mut self, user_id: &UserId, message_index: MessageIndex, now: TimestampMillis) {
         self.update_member(user_id, |m| {
             m.prune_proposal_votes(now);
@@ -466,6 +529,14 @@ impl GroupMembers {
         &self.at_everyone_muted
     }
 
+    pub fn mentions_only(&self) -> &BTreeSet<UserId> {
+        &self.mentions_only
+    }
+
+    pub fn keyword_alerts(&self) -> &BTreeMap<UserId, Vec<String>> {
+        &self.keyword_alerts
+    }
+
     pub fn lapsed(&self) -> &BTreeSet<UserId> {
         &self.lapsed
     }
@@ -523,6 +594,8 @@ impl GroupMembers {
         let mut moderators = BTreeSet::new();
         let mut notifications_unmuted = BTreeSet::new();
         let mut at_everyone_muted = BTreeSet::new();
+        let mut mentions_only = BTreeSet::new();
+        let mut keyword_alerts = BTreeMap::new();
         let mut lapsed = BTreeSet::new();
         let mut suspended = BTreeSet::new();
 
@@ -546,6 +619,14 @@ impl GroupMembers {
                 at_everyone_muted.insert(member.user_id);
             }
 
+            if member.mentions_only.value {
+                mentions_only.insert(member.user_id);
+            }
+
+            if !member.keyword_alerts.value.is_empty() {
+                keyword_alerts.insert(member.user_id, member.keyword_alerts.value.clone());
+            }
+
             if member.lapsed.value {
                 lapsed.insert(member.user_id);
             }
@@ -561,6 +642,8 @@ impl GroupMembers {
         assert_eq!(moderators, self.moderators);
         assert_eq!(notifications_unmuted, self.notifications_unmuted);
         assert_eq!(at_everyone_muted, self.at_everyone_muted);
+        assert_eq!(mentions_only, self.mentions_only);
+        assert_eq!(keyword_alerts, self.keyword_alerts);
         assert_eq!(lapsed, self.lapsed);
         assert_eq!(suspended, self.suspended);
     }
@@ -607,6 +690,8 @@ pub struct GroupMemberInternal {
     role: Timestamped<GroupRoleInternal>,
     notifications_muted: Timestamped<bool>,
     at_everyone_muted: Timestamped<bool>,
+    mentions_only: Timestamped<bool>,
+    keyword_alerts: Timestamped<Vec<String>>,
     pub mentions: Mentions,
     pub followed_threads: TimestampedSet<MessageIndex>,
     pub unfollowed_threads: TimestampedSet<MessageIndex>,
@@ -645,6 +730,14 @@ impl GroupMemberInternal {
         &self.at_everyone_muted
     }
 
+    pub fn mentions_only(&self) -> &Timestamped<bool> {
+        &self.mentions_only
+    }
+
+    pub fn keyword_alerts(&self) -> &Timestamped<Vec<String>> {
+        &self.keyword_alerts
+    }
+
     pub fn lapsed(&self) -> &Timestamped<bool> {
         &self.lapsed
     }
@@ -659,6 +752,8 @@ impl GroupMemberInternal {
             self.role.timestamp,
             self.notifications_muted.timestamp,
             self.at_everyone_muted.timestamp,
+            self.mentions_only.timestamp,
+            self.keyword_alerts.timestamp,
             self.suspended.timestamp,
             self.rules_accepted.as_ref().map(|r| r.timestamp).unwrap_or_default(),
             self.lapsed.timestamp,
@@ -837,6 +932,10 @@ pub struct GroupMemberStableStorage {
     notifications_muted: Timestamped<bool>,
     #[serde(rename = "em", default, skip_serializing_if = "is_default")]
     pub at_everyone_muted: Timestamped<bool>,
+    #[serde(rename = "mo", default, skip_serializing_if = "is_default")]
+    mentions_only: Timestamped<bool>,
+    #[serde(rename = "ka", default, skip_serializing_if = "is_default")]
+    keyword_alerts: Timestamped<Vec<String>>,
     #[serde(rename = "m", default, skip_serializing_if = "Mentions::is_empty")]
     pub mentions: Mentions,
     #[serde(rename = "tf", default, skip_serializing_if = "TimestampedSet::is_empty")]
@@ -869,6 +968,8 @@ impl GroupMemberStableStorage {
             role: self.role,
             notifications_muted: self.notifications_muted,
             at_everyone_muted: self.at_everyone_muted,
+            mentions_only: self.mentions_only,
+            keyword_alerts: self.keyword_alerts,
             mentions: self.mentions,
             followed_threads: self.followed_threads,
             unfollowed_threads: self.unfollowed_threads,
@@ -891,6 +992,8 @@ impl From<GroupMemberInternal> for GroupMemberStableStorage {
             role: value.role,
             notifications_muted: value.notifications_muted,
             at_everyone_muted: value.at_everyone_muted,
+            mentions_only: value.mentions_only,
+            keyword_alerts: value.keyword_alerts,
             mentions: value.mentions,
             followed_threads: value.followed_threads,
             unfollowed_threads: value.unfollowed_threads,
@@ -931,6 +1034,8 @@ mod tests {
             role: Timestamped::default(),
             notifications_muted: default_notifications_muted(),
             at_everyone_muted: Timestamped::default(),
+            mentions_only: Timestamped::default(),
+            keyword_alerts: Timestamped::default(),
             mentions: Mentions::default(),
             followed_threads: TimestampedSet::default(),
             unfollowed_threads: TimestampedSet::default(),
@@ -967,6 +1072,8 @@ mod tests {
             role: Timestamped::new(GroupRoleInternal::Owner, 1),
             notifications_muted: Timestamped::new(true, 1),
             at_everyone_muted: Timestamped::new(true, 1),
+            mentions_only: Timestamped::new(true, 1),
+            keyword_alerts: Timestamped::new(vec!["a".to_string()], 1),
             mentions,
             followed_threads: [(1.into(), 1)].into_iter().collect(),
             unfollowed_threads: [(1.into(), 1)].into_iter().collect(),
@@ -983,7 +1090,7 @@ mod tests {
         let member_bytes = msgpack::serialize_then_unwrap(&member);
         let member_bytes_len = member_bytes.len();
 
-        assert_eq!(member_bytes_len, 167);
+        assert_eq!(member_bytes_len, 191);
 
         let _deserialized: GroupMemberStableStorage = msgpack::deserialize_then_unwrap(&member_bytes);
     }
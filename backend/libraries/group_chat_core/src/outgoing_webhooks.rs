@@ -0,0 +1,308 @@
+use candid::Principal;
+use constants::SECOND_IN_MS;
+use group_community_common::outgoing_webhooks::OutgoingWebhookAttempt;
+use oc_error_codes::OCErrorCode;
+use rand::{RngExt, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use types::{
+    ChatEventType, EventIndex, MessageIndex, Milliseconds, OCResult, OutgoingWebhookDelivery, OutgoingWebhookDeliveryStatus,
+    OutgoingWebhookDetails, TimestampMillis, UserId,
+};
+
+const MAX_OUTGOING_WEBHOOKS: usize = 10;
+const MAX_URL_LENGTH: usize = 1000;
+const MAX_DELIVERY_LOG_LENGTH: usize = 100;
+const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+const INITIAL_RETRY_DELAY: Milliseconds = 10 * SECOND_IN_MS;
+// Once this many deliveries in a row have failed the webhook is disabled until an admin re-enables it
+const MAX_CONSECUTIVE_FAILURES: u32 = 20;
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct OutgoingWebhooks {
+    map: BTreeMap<UserId, OutgoingWebhook>,
+    next_delivery_id: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OutgoingWebhook {
+    pub name: String,
+    pub url: String,
+    pub secret: String,
+    pub event_types: HashSet<ChatEventType>,
+    pub enabled: bool,
+    pub consecutive_failures: u32,
+    deliveries: VecDeque<OutgoingWebhookDelivery>,
+}
+
+impl OutgoingWebhooks {
+    pub fn register(
+        &mut self,
+        name: String,
+        url: String,
+        event_types: HashSet<ChatEventType>,
+        rng: &mut StdRng,
+    ) -> OCResult<UserId> {
+        if self.map.len() >= MAX_OUTGOING_WEBHOOKS {
+            return Err(OCErrorCode::MaxOutgoingWebhooksReached.into());
+        }
+
+        if self.map.values().any(|webhook| webhook.name == name) {
+            return Err(OCErrorCode::NameTaken.into());
+        }
+
+        validate_url(&url)?;
+
+        let id: UserId = Principal::from_slice(&rng.random::<[u8; 8]>()).into();
+
+        self.map.insert(
+            id,
+            OutgoingWebhook {
+                name,
+                url,
+                secret: hex::encode(rng.random::<[u8; 16]>()),
+                event_types,
+                enabled: true,
+                consecutive_failures: 0,
+                deliveries: VecDeque::new(),
+            },
+        );
+
+        Ok(id)
+    }
+
+    pub fn update(
+        &mut self,
+        id: UserId,
+        url: Option<String>,
+        event_types: Option<HashSet<ChatEventType>>,
+        enabled: Option<bool>,
+    ) -> OCResult {
+        let webhook = self.map.get_mut(&id).ok_or(OCErrorCode::WebhookNotFound)?;
+
+        if let Some(url) = url {
+            validate_url(&url)?;
+            webhook.url = url;
+        }
+
+        if let Some(event_types) = event_types {
+            webhook.event_types = event_types;
+        }
+
+        if let Some(enabled) = enabled {
+            webhook.enabled = enabled;
+            if enabled {
+                webhook.consecutive_failures = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: &UserId) -> Option<OutgoingWebhook> {
+        self.map.remove(id)
+    }
+
+    pub fn get(&self, id: &UserId) -> Option<&OutgoingWebhook> {
+        self.map.get(id)
+    }
+
+    pub fn is_enabled(&self, id: &UserId) -> bool {
+        self.map.get(id).is_some_and(|webhook| webhook.enabled)
+    }
+
+    pub fn contains(&self, id: &UserId) -> bool {
+        self.map.contains_key(id)
+    }
+
+    // Adds a pending entry to the webhook's delivery log, returning its id if the webhook exists and is enabled
+    pub fn start_delivery(
+        &mut self,
+        id: UserId,
+        event_index: EventIndex,
+        thread: Option<MessageIndex>,
+        now: TimestampMillis,
+    ) -> Option<u64> {
+        let webhook = self.map.get_mut(&id).filter(|webhook| webhook.enabled)?;
+
+        let delivery_id = self.next_delivery_id;
+        self.next_delivery_id += 1;
+
+        webhook.deliveries.push_back(OutgoingWebhookDelivery {
+            id: delivery_id,
+            event_index,
+            thread,
+            queued: now,
+            attempts: 0,
+            last_attempt: None,
+            status: OutgoingWebhookDeliveryStatus::Pending,
+            response_status: None,
+            error: None,
+        });
+        while webhook.deliveries.len() > MAX_DELIVERY_LOG_LENGTH {
+            webhook.deliveries.pop_front();
+        }
+
+        Some(delivery_id)
+    }
+
+    // Records the outcome of a delivery attempt, returning the delay before the next attempt if
+    // the delivery should be retried
+    pub fn record_attempt(
+        &mut self,
+        id: UserId,
+        delivery_id: u64,
+        attempt: OutgoingWebhookAttempt,
+        now: TimestampMillis,
+    ) -> Option<Milliseconds> {
+        let webhook = self.map.get_mut(&id)?;
+        let delivery = webhook.deliveries.iter_mut().find(|d| d.id == delivery_id)?;
+
+        delivery.attempts += 1;
+        delivery.last_attempt = Some(now);
+        delivery.response_status = attempt.response_status;
+        delivery.error = attempt.error;
+
+        if delivery.error.is_none() {
+            delivery.status = OutgoingWebhookDeliveryStatus::Delivered;
+            webhook.consecutive_failures = 0;
+            None
+        } else if attempt.retryable && delivery.attempts < MAX_DELIVERY_ATTEMPTS && webhook.enabled {
+            Some(retry_delay(delivery.attempts))
+        } else {
+            delivery.status = OutgoingWebhookDeliveryStatus::Failed;
+            webhook.consecutive_failures += 1;
+            if webhook.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                webhook.enabled = false;
+            }
+            None
+        }
+    }
+
+    pub fn details(&self) -> Vec<OutgoingWebhookDetails> {
+        self.map.iter().map(|(id, webhook)| webhook.to_details(*id)).collect()
+    }
+}
+
+impl OutgoingWebhook {
+    pub fn to_details(&self, id: UserId) -> OutgoingWebhookDetails {
+        OutgoingWebhookDetails {
+            id,
+            name: self.name.clone(),
+            url: self.url.clone(),
+            event_types: self.event_types.clone(),
+            enabled: self.enabled,
+            consecutive_failures: self.consecutive_failures,
+            deliveries: self.deliveries.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+fn validate_url(url: &str) -> OCResult {
+    if url.len() > MAX_URL_LENGTH || !url.starts_with("https://") || url.len() == "https://".len() {
+        Err(OCErrorCode::InvalidRequest.with_message("url must be a valid https url"))
+    } else {
+        Ok(())
+    }
+}
+
+// Backs off exponentially, ie. 10s, 40s, 160s, 640s
+fn retry_delay(attempts: u32) -> Milliseconds {
+    INITIAL_RETRY_DELAY * 4u64.pow(attempts.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    const NOW: TimestampMillis = 1_700_000_000_000;
+
+    fn setup() -> (OutgoingWebhooks, UserId) {
+        let mut webhooks = OutgoingWebhooks::default();
+        let mut rng = StdRng::seed_from_u64(1);
+        let id = webhooks
+            .register(
+                "ci".to_string(),
+                "https://example.com/hook".to_string(),
+                [ChatEventType::Message].into_iter().collect(),
+                &mut rng,
+            )
+            .unwrap();
+        (webhooks, id)
+    }
+
+    fn failure(retryable: bool) -> OutgoingWebhookAttempt {
+        OutgoingWebhookAttempt {
+            response_status: Some(503),
+            error: Some("Service unavailable".to_string()),
+            retryable,
+        }
+    }
+
+    #[test]
+    fn only_https_urls_accepted() {
+        let mut webhooks = OutgoingWebhooks::default();
+        let mut rng = StdRng::seed_from_u64(1);
+        for url in ["http://example.com", "https://", "ftp://example.com"] {
+            assert!(
+                webhooks
+                    .register("ci".to_string(), url.to_string(), HashSet::new(), &mut rng)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn retryable_failures_retried_with_backoff() {
+        let (mut webhooks, id) = setup();
+        let delivery_id = webhooks.start_delivery(id, 10.into(), None, NOW).unwrap();
+
+        let mut delays = Vec::new();
+        while let Some(delay) = webhooks.record_attempt(id, delivery_id, failure(true), NOW) {
+            delays.push(delay);
+        }
+        assert_eq!(delays, vec![10_000, 40_000, 160_000, 640_000]);
+
+        let details = webhooks.get(&id).unwrap().to_details(id);
+        assert_eq!(details.deliveries[0].attempts, MAX_DELIVERY_ATTEMPTS);
+        assert_eq!(details.deliveries[0].status, OutgoingWebhookDeliveryStatus::Failed);
+        assert_eq!(details.consecutive_failures, 1);
+    }
+
+    #[test]
+    fn success_resets_consecutive_failures() {
+        let (mut webhooks, id) = setup();
+        let first = webhooks.start_delivery(id, 10.into(), None, NOW).unwrap();
+        assert!(webhooks.record_attempt(id, first, failure(false), NOW).is_none());
+        assert_eq!(webhooks.get(&id).unwrap().consecutive_failures, 1);
+
+        let second = webhooks.start_delivery(id, 11.into(), None, NOW).unwrap();
+        let success = OutgoingWebhookAttempt {
+            response_status: Some(200),
+            error: None,
+            retryable: false,
+        };
+        assert!(webhooks.record_attempt(id, second, success, NOW).is_none());
+
+        let details = webhooks.get(&id).unwrap().to_details(id);
+        assert_eq!(details.consecutive_failures, 0);
+        assert_eq!(details.deliveries[0].status, OutgoingWebhookDeliveryStatus::Delivered);
+        assert_eq!(details.deliveries[1].status, OutgoingWebhookDeliveryStatus::Failed);
+    }
+
+    #[test]
+    fn webhook_disabled_after_too_many_failures() {
+        let (mut webhooks, id) = setup();
+        for i in 0..MAX_CONSECUTIVE_FAILURES {
+            let delivery_id = webhooks.start_delivery(id, i.into(), None, NOW).unwrap();
+            webhooks.record_attempt(id, delivery_id, failure(false), NOW);
+        }
+
+        assert!(!webhooks.is_enabled(&id));
+        assert!(webhooks.start_delivery(id, 100.into(), None, NOW).is_none());
+
+        webhooks.update(id, None, None, Some(true)).unwrap();
+        assert!(webhooks.start_delivery(id, 100.into(), None, NOW).is_some());
+    }
+}
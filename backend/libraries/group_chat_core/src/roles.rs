@@ -158,10 +158,18 @@ impl GroupRoleInternal {
         self.is_permitted(permissions.mention_all_members)
     }
 
+    pub fn can_export_history(&self, permissions: &GroupPermissions) -> bool {
+        self.is_permitted(permissions.export_history)
+    }
+
     pub fn can_delete_history(&self) -> bool {
         self.is_owner()
     }
 
+    pub fn can_manage_outgoing_webhooks(&self) -> bool {
+        self.has_admin_rights()
+    }
+
     pub fn is_permitted(&self, permission_role: GroupPermissionRole) -> bool {
         match permission_role {
             GroupPermissionRole::None => false,
@@ -194,6 +202,7 @@ impl GroupRoleInternal {
             (role_permissions.add_members, ChatPermission::AddMembers),
             (role_permissions.change_roles, ChatPermission::ChangeRoles),
             (role_permissions.delete_messages, ChatPermission::DeleteMessages),
+            (role_permissions.export_history, ChatPermission::ExportHistory),
             (role_permissions.invite_users, ChatPermission::InviteUsers),
             (role_permissions.mention_all_members, ChatPermission::MentionAllMembers),
             (role_permissions.pin_messages, ChatPermission::PinMessages),
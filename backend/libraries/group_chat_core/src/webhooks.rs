@@ -1,8 +1,16 @@
 use candid::Principal;
+use constants::{HOUR_IN_MS, MINUTE_IN_MS};
+use oc_error_codes::OCErrorCode;
 use rand::{RngExt, rngs::StdRng};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-use types::{Document, OptionUpdate, TimestampMillis, UserId};
+use std::collections::{BTreeMap, VecDeque};
+use types::{
+    Document, Milliseconds, OCResult, OptionUpdate, TimestampMillis, UserId, WebhookDetails, WebhookMetrics, WebhookRateLimits,
+    WebhookSignature,
+};
+
+const SIGNATURE_VALIDITY_WINDOW: Milliseconds = 5 * MINUTE_IN_MS;
+const MAX_NONCE_LENGTH: usize = 64;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct Webhooks {
@@ -15,6 +23,18 @@ pub struct Webhook {
     pub name: String,
     pub avatar: Option<Document>,
     pub secret: String,
+    #[serde(default)]
+    pub rate_limits: WebhookRateLimits,
+    #[serde(default)]
+    pub require_signature: bool,
+    #[serde(default)]
+    pub metrics: WebhookMetrics,
+    // The timestamps of the messages sent within the last hour, used to apply the rate limits
+    #[serde(default)]
+    recent_messages: VecDeque<TimestampMillis>,
+    // The nonces of the signed requests which could still be replayed, along with when they were received
+    #[serde(default)]
+    recent_nonces: VecDeque<(TimestampMillis, String)>,
 }
 
 impl Webhooks {
@@ -22,6 +42,8 @@ impl Webhooks {
         &mut self,
         name: String,
         avatar: Option<Document>,
+        rate_limits: WebhookRateLimits,
+        require_signature: bool,
         rng: &mut StdRng,
         now: TimestampMillis,
     ) -> Option<UserId> {
@@ -37,6 +59,9 @@ impl Webhooks {
                 name,
                 avatar,
                 secret: Self::generate_secret(rng),
+                rate_limits,
+                require_signature,
+                ..Default::default()
             },
         );
 
@@ -55,12 +80,28 @@ impl Webhooks {
         }
     }
 
-    pub fn update(&mut self, id: UserId, name: Option<String>, avatar: OptionUpdate<Document>, now: TimestampMillis) -> bool {
+    pub fn update(
+        &mut self,
+        id: UserId,
+        name: Option<String>,
+        avatar: OptionUpdate<Document>,
+        rate_limits: Option<WebhookRateLimits>,
+        require_signature: Option<bool>,
+        now: TimestampMillis,
+    ) -> bool {
         if let Some(webhook) = self.map.get_mut(&id) {
             if let Some(name) = name {
                 webhook.name = name;
             }
 
+            if let Some(rate_limits) = rate_limits {
+                webhook.rate_limits = rate_limits;
+            }
+
+            if let Some(require_signature) = require_signature {
+                webhook.require_signature = require_signature;
+            }
+
             match avatar {
                 OptionUpdate::SetToNone => webhook.avatar = None,
                 OptionUpdate::SetToSome(avatar) => webhook.avatar = Some(avatar),
@@ -87,6 +128,38 @@ impl Webhooks {
         self.map.get(id)
     }
 
+    // Checks that the request is authorized and within the webhook's rate limits. Rejected
+    // requests are counted in the webhook's metrics.
+    // Note: Metrics changes don't bump `last_updated`, otherwise every webhook message would
+    // trigger a chat summary update for every member.
+    pub fn verify_request(
+        &mut self,
+        id: UserId,
+        secret: Option<&str>,
+        signature: Option<&WebhookSignature>,
+        now: TimestampMillis,
+    ) -> OCResult {
+        let webhook = self.map.get_mut(&id).ok_or(OCErrorCode::WebhookNotFound)?;
+
+        let result = webhook.verify_request(secret, signature, now);
+        if result.is_err() {
+            webhook.metrics.messages_rejected += 1;
+        }
+        result
+    }
+
+    pub fn record_message(&mut self, id: UserId, success: bool, now: TimestampMillis) {
+        if let Some(webhook) = self.map.get_mut(&id) {
+            if success {
+                webhook.metrics.messages_sent += 1;
+                webhook.metrics.last_used = Some(now);
+                webhook.recent_messages.push_back(now);
+            } else {
+                webhook.metrics.messages_rejected += 1;
+            }
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&UserId, &Webhook)> {
         self.map.iter()
     }
@@ -95,6 +168,10 @@ impl Webhooks {
         self.map.keys()
     }
 
+    pub fn details(&self) -> Vec<WebhookDetails> {
+        self.map.iter().map(|(id, webhook)| webhook.to_details(*id)).collect()
+    }
+
     pub fn last_updated(&self) -> TimestampMillis {
         self.last_updated
     }
@@ -108,3 +185,228 @@ impl Webhooks {
         hex::encode(secret_bytes)
     }
 }
+
+impl Webhook {
+    pub fn to_details(&self, id: UserId) -> WebhookDetails {
+        WebhookDetails {
+            id,
+            name: self.name.clone(),
+            avatar_id: self.avatar.as_ref().map(|avatar| avatar.id),
+            rate_limits: self.rate_limits,
+            require_signature: self.require_signature,
+            metrics: self.metrics,
+        }
+    }
+
+    // Requests must be signed if the webhook requires it, or if the secret is omitted from the URL,
+    // otherwise the secret in the URL is sufficient
+    fn verify_request(&mut self, secret: Option<&str>, signature: Option<&WebhookSignature>, now: TimestampMillis) -> OCResult {
+        let signed = match secret {
+            Some(secret) if !self.require_signature => {
+                if !constant_time_eq(secret.as_bytes(), self.secret.as_bytes()) {
+                    return Err(OCErrorCode::InvalidWebhook.into());
+                }
+                false
+            }
+            _ => {
+                self.verify_signature(signature, now)?;
+                true
+            }
+        };
+
+        self.check_rate_limits(now)?;
+
+        if let Some(signature) = signature.filter(|_| signed) {
+            self.recent_nonces.push_back((now, signature.nonce.clone()));
+        }
+        Ok(())
+    }
+
+    fn verify_signature(&mut self, signature: Option<&WebhookSignature>, now: TimestampMillis) -> OCResult {
+        let Some(signature) = signature else {
+            return Err(OCErrorCode::InvalidWebhook.with_message("Signature required"));
+        };
+
+        if signature.timestamp.abs_diff(now) > SIGNATURE_VALIDITY_WINDOW {
+            return Err(OCErrorCode::InvalidWebhook.with_message("Signature timestamp outside of validity window"));
+        }
+
+        if signature.nonce.is_empty() || signature.nonce.len() > MAX_NONCE_LENGTH {
+            return Err(OCErrorCode::InvalidWebhook.with_message("Invalid nonce"));
+        }
+
+        // A nonce can only be replayed while its timestamp is within the validity window, and
+        // that timestamp is itself within the window of when the nonce was first received
+        while self
+            .recent_nonces
+            .front()
+            .is_some_and(|(ts, _)| *ts + 2 * SIGNATURE_VALIDITY_WINDOW < now)
+        {
+            self.recent_nonces.pop_front();
+        }
+
+        if self.recent_nonces.iter().any(|(_, nonce)| *nonce == signature.nonce) {
+            return Err(OCErrorCode::InvalidWebhook.with_message("Nonce already used"));
+        }
+
+        let expected = compute_signature(&self.secret, signature.timestamp, &signature.nonce, &signature.body);
+        if !constant_time_eq(signature.signature.to_ascii_lowercase().as_bytes(), expected.as_bytes()) {
+            return Err(OCErrorCode::InvalidWebhook.with_message("Invalid signature"));
+        }
+
+        Ok(())
+    }
+
+    fn check_rate_limits(&mut self, now: TimestampMillis) -> OCResult {
+        while self.recent_messages.front().is_some_and(|ts| *ts + HOUR_IN_MS <= now) {
+            self.recent_messages.pop_front();
+        }
+
+        if let Some(per_hour) = self.rate_limits.per_hour
+            && self.recent_messages.len() >= per_hour as usize
+        {
+            return Err(OCErrorCode::Throttled.with_message("Hourly rate limit reached"));
+        }
+
+        if let Some(per_minute) = self.rate_limits.per_minute {
+            let last_minute = self
+                .recent_messages
+                .iter()
+                .rev()
+                .take_while(|ts| **ts + MINUTE_IN_MS > now)
+                .count();
+            if last_minute >= per_minute as usize {
+                return Err(OCErrorCode::Throttled.with_message("Per minute rate limit reached"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn compute_signature(secret: &str, timestamp: TimestampMillis, nonce: &str, body: &[u8]) -> String {
+    let mut input = format!("{timestamp}.{nonce}.").into_bytes();
+    input.extend_from_slice(body);
+    hex::encode(hmac_sha256::HMAC::mac(input, secret.as_bytes()))
+}
+
+// Compares the values without short-circuiting so that the time taken doesn't reveal how much of a
+// guessed secret or signature is correct. Only the length can be inferred, which isn't secret.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use serde_bytes::ByteBuf;
+
+    const NOW: TimestampMillis = 1_700_000_000_000;
+
+    fn setup(rate_limits: WebhookRateLimits, require_signature: bool) -> (Webhooks, UserId, String) {
+        let mut webhooks = Webhooks::default();
+        let mut rng = StdRng::seed_from_u64(1);
+        let id = webhooks
+            .register("ci".to_string(), None, rate_limits, require_signature, &mut rng, NOW)
+            .unwrap();
+        let secret = webhooks.get(&id).unwrap().secret.clone();
+        (webhooks, id, secret)
+    }
+
+    fn sign(secret: &str, timestamp: TimestampMillis, nonce: &str) -> WebhookSignature {
+        let body = b"hello".to_vec();
+        WebhookSignature {
+            timestamp,
+            nonce: nonce.to_string(),
+            signature: compute_signature(secret, timestamp, nonce, &body),
+            body: ByteBuf::from(body),
+        }
+    }
+
+    #[test]
+    fn per_minute_rate_limit_applied() {
+        let (mut webhooks, id, secret) = setup(
+            WebhookRateLimits {
+                per_minute: Some(2),
+                per_hour: None,
+            },
+            false,
+        );
+
+        for i in 0..2 {
+            assert!(webhooks.verify_request(id, Some(&secret), None, NOW + i).is_ok());
+            webhooks.record_message(id, true, NOW + i);
+        }
+        assert!(webhooks.verify_request(id, Some(&secret), None, NOW + 2).is_err());
+        assert!(webhooks.verify_request(id, Some(&secret), None, NOW + MINUTE_IN_MS).is_ok());
+
+        let metrics = webhooks.get(&id).unwrap().metrics;
+        assert_eq!(metrics.messages_sent, 2);
+        assert_eq!(metrics.messages_rejected, 1);
+        assert_eq!(metrics.last_used, Some(NOW + 1));
+    }
+
+    #[test]
+    fn per_hour_rate_limit_applied() {
+        let (mut webhooks, id, secret) = setup(
+            WebhookRateLimits {
+                per_minute: None,
+                per_hour: Some(1),
+            },
+            false,
+        );
+
+        assert!(webhooks.verify_request(id, Some(&secret), None, NOW).is_ok());
+        webhooks.record_message(id, true, NOW);
+        assert!(
+            webhooks
+                .verify_request(id, Some(&secret), None, NOW + 30 * MINUTE_IN_MS)
+                .is_err()
+        );
+        assert!(webhooks.verify_request(id, Some(&secret), None, NOW + HOUR_IN_MS).is_ok());
+    }
+
+    #[test]
+    fn signed_requests_verified() {
+        let (mut webhooks, id, secret) = setup(WebhookRateLimits::default(), true);
+
+        // The URL secret alone is not sufficient
+        assert!(webhooks.verify_request(id, Some(&secret), None, NOW).is_err());
+
+        let signature = sign(&secret, NOW, "abc");
+        assert!(webhooks.verify_request(id, None, Some(&signature), NOW).is_ok());
+
+        // Replaying the same request is rejected
+        assert!(webhooks.verify_request(id, None, Some(&signature), NOW + 1).is_err());
+
+        // As are stale requests and those signed with the wrong secret
+        let stale = sign(&secret, NOW, "def");
+        assert!(
+            webhooks
+                .verify_request(id, None, Some(&stale), NOW + 2 * SIGNATURE_VALIDITY_WINDOW)
+                .is_err()
+        );
+        let wrong_secret = sign("wrong", NOW, "ghi");
+        assert!(webhooks.verify_request(id, None, Some(&wrong_secret), NOW).is_err());
+
+        assert_eq!(webhooks.get(&id).unwrap().metrics.messages_rejected, 4);
+    }
+
+    #[test]
+    fn url_secret_can_be_omitted_if_request_signed() {
+        let (mut webhooks, id, secret) = setup(WebhookRateLimits::default(), false);
+
+        assert!(webhooks.verify_request(id, Some("wrong"), None, NOW).is_err());
+        assert!(webhooks.verify_request(id, None, None, NOW).is_err());
+
+        let mut signature = sign(&secret, NOW, "abc");
+        signature.signature = signature.signature.to_uppercase();
+        assert!(webhooks.verify_request(id, None, Some(&signature), NOW).is_ok());
+
+        let wrong_secret = sign("wrong", NOW, "def");
+        assert!(webhooks.verify_request(id, None, Some(&wrong_secret), NOW).is_err());
+
+        assert_eq!(webhooks.get(&id).unwrap().metrics.messages_rejected, 3);
+    }
+}
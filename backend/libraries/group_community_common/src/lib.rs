@@ -3,9 +3,11 @@ mod expiring_member_actions;
 mod expiring_members;
 mod member;
 pub mod openai_moderation;
+pub mod outgoing_webhooks;
 mod payment_locks;
 mod payment_receipts;
 mod pending_payments_queue;
+pub mod translation;
 mod user_cache;
 
 pub use achievements::*;
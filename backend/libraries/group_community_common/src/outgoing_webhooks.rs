@@ -0,0 +1,105 @@
+use candid::Principal;
+use ic_cdk::call::Call;
+use ic_cdk_management_canister::{HttpHeader, HttpMethod, HttpRequestArgs, HttpRequestResult};
+use types::{BotEvent, BotEventPayload, BotEventWrapper, CanisterId, TimestampMillis};
+
+// The same headers are used to sign requests to incoming webhooks, so integrations can verify
+// both directions with the same code
+pub const TIMESTAMP_HEADER: &str = "x-oc-timestamp";
+pub const NONCE_HEADER: &str = "x-oc-nonce";
+pub const SIGNATURE_HEADER: &str = "x-oc-signature";
+
+const MAX_RESPONSE_BYTES: u64 = 4 * 1024;
+
+// The result of a single attempt to POST an event to an outgoing webhook
+pub struct OutgoingWebhookAttempt {
+    pub response_status: Option<u32>,
+    pub error: Option<String>,
+    pub retryable: bool,
+}
+
+// Outgoing webhooks receive the same envelope as bots, JSON encoded
+pub fn serialize_event(api_gateway: CanisterId, event: BotEvent, timestamp: TimestampMillis) -> Vec<u8> {
+    serde_json::to_vec(&BotEventWrapper {
+        api_gateway,
+        event,
+        timestamp,
+    })
+    .unwrap()
+}
+
+// POSTs the JSON encoded event to the webhook's endpoint. Any 2xx response counts as delivered.
+pub async fn post_event(
+    url: String,
+    payload: BotEventPayload,
+    timestamp: TimestampMillis,
+    nonce: String,
+) -> OutgoingWebhookAttempt {
+    // As with the moderation API, the request is made from a single replica so that the endpoint
+    // receives one request per delivery rather than one per replica
+    let args = HttpRequestArgs {
+        url,
+        max_response_bytes: Some(MAX_RESPONSE_BYTES),
+        method: HttpMethod::POST,
+        headers: vec![
+            HttpHeader {
+                name: "content-type".to_string(),
+                value: "application/json".to_string(),
+            },
+            HttpHeader {
+                name: TIMESTAMP_HEADER.to_string(),
+                value: timestamp.to_string(),
+            },
+            HttpHeader {
+                name: NONCE_HEADER.to_string(),
+                value: nonce,
+            },
+            HttpHeader {
+                name: SIGNATURE_HEADER.to_string(),
+                value: payload.signature,
+            },
+        ],
+        body: Some(payload.data.into_vec()),
+        transform: None,
+        is_replicated: Some(false),
+    };
+
+    let request_size = (args.url.len()
+        + args.headers.iter().map(|h| h.name.len() + h.value.len()).sum::<usize>()
+        + args.body.as_ref().map_or(0, |b| b.len())) as u64;
+    let cycles = ic_cdk::api::cost_http_request(request_size, MAX_RESPONSE_BYTES);
+
+    let response: HttpRequestResult = match Call::unbounded_wait(Principal::management_canister(), "http_request")
+        .with_arg(&args)
+        .with_cycles(cycles)
+        .await
+    {
+        Ok(response) => match response.candid() {
+            Ok(response) => response,
+            Err(error) => return failure(None, format!("Failed to decode response: {error:?}"), true),
+        },
+        Err(error) => return failure(None, format!("HTTPS outcall failed: {error:?}"), true),
+    };
+
+    let status = u32::try_from(response.status.0).unwrap_or_default();
+    if (200..300).contains(&status) {
+        OutgoingWebhookAttempt {
+            response_status: Some(status),
+            error: None,
+            retryable: false,
+        }
+    } else {
+        let body: String = String::from_utf8_lossy(&response.body).chars().take(200).collect();
+        // Throttling and server errors may be transient, anything else is treated as a rejection
+        let retryable = status == 408 || status == 429 || status >= 500;
+        failure(Some(status), format!("Endpoint returned status {status}: {body}"), retryable)
+    }
+}
+
+fn failure(response_status: Option<u32>, error: String, retryable: bool) -> OutgoingWebhookAttempt {
+    OutgoingWebhookAttempt {
+        response_status,
+        error: Some(error),
+        retryable,
+    }
+}
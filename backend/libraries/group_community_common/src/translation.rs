@@ -0,0 +1,172 @@
+use candid::Principal;
+use ic_cdk::call::Call;
+use ic_cdk_management_canister::{HttpHeader, HttpMethod, HttpRequestArgs, HttpRequestResult};
+use serde::Deserialize;
+use types::{TranslationProvider, TranslationProviderConfig};
+
+const DEEPL_URL: &str = "https://api.deepl.com/v2/translate";
+const GOOGLE_URL: &str = "https://translation.googleapis.com/language/translate/v2";
+const MAX_RESPONSE_BYTES: u64 = 100 * 1024;
+
+// Translates the text into the target language using the configured provider, letting the
+// provider detect the source language
+pub async fn translate_text(config: &TranslationProviderConfig, text: &str, target_language: &str) -> Result<String, String> {
+    let (url, auth_header, body) = match &config.provider {
+        TranslationProvider::DeepL => (
+            DEEPL_URL.to_string(),
+            Some(("authorization", format!("DeepL-Auth-Key {}", config.api_key))),
+            serde_json::json!({
+                "text": [text],
+                "target_lang": target_language.to_uppercase(),
+            }),
+        ),
+        TranslationProvider::Google => (
+            GOOGLE_URL.to_string(),
+            Some(("x-goog-api-key", config.api_key.clone())),
+            serde_json::json!({
+                "q": text,
+                "target": target_language,
+                "format": "text",
+            }),
+        ),
+        TranslationProvider::LibreTranslate(base_url) => (
+            format!("{}/translate", base_url.trim_end_matches('/')),
+            None,
+            serde_json::json!({
+                "q": text,
+                "source": "auto",
+                "target": target_language,
+                "format": "text",
+                "api_key": config.api_key,
+            }),
+        ),
+    };
+
+    let mut headers = vec![HttpHeader {
+        name: "content-type".to_string(),
+        value: "application/json".to_string(),
+    }];
+    if let Some((name, value)) = auth_header {
+        headers.push(HttpHeader {
+            name: name.to_string(),
+            value,
+        });
+    }
+
+    // As with the moderation API, the outcall is made from a single replica so that the provider
+    // is only charged for one request. A bad translation from a misbehaving replica is harmless.
+    let args = HttpRequestArgs {
+        url,
+        max_response_bytes: Some(MAX_RESPONSE_BYTES),
+        method: HttpMethod::POST,
+        headers,
+        body: Some(serde_json::to_vec(&body).unwrap()),
+        transform: None,
+        is_replicated: Some(false),
+    };
+
+    let request_size = (args.url.len()
+        + args.headers.iter().map(|h| h.name.len() + h.value.len()).sum::<usize>()
+        + args.body.as_ref().map_or(0, |b| b.len())) as u64;
+    let cycles = ic_cdk::api::cost_http_request(request_size, MAX_RESPONSE_BYTES);
+
+    let response: HttpRequestResult = Call::unbounded_wait(Principal::management_canister(), "http_request")
+        .with_arg(&args)
+        .with_cycles(cycles)
+        .await
+        .map_err(|error| format!("HTTPS outcall failed: {error:?}"))?
+        .candid()
+        .map_err(|error| format!("Failed to decode response: {error:?}"))?;
+
+    let status = u32::try_from(response.status.clone().0).unwrap_or_default();
+    if status != 200 {
+        let body: String = String::from_utf8_lossy(&response.body).chars().take(500).collect();
+        return Err(format!("Translation API returned status {status}: {body}"));
+    }
+
+    extract_translation(&config.provider, &response.body).ok_or("Failed to parse translation API response".to_string())
+}
+
+fn extract_translation(provider: &TranslationProvider, body: &[u8]) -> Option<String> {
+    match provider {
+        TranslationProvider::DeepL => {
+            let response: DeepLResponse = serde_json::from_slice(body).ok()?;
+            response.translations.into_iter().next().map(|t| t.text)
+        }
+        TranslationProvider::Google => {
+            let response: GoogleResponse = serde_json::from_slice(body).ok()?;
+            response.data.translations.into_iter().next().map(|t| t.translated_text)
+        }
+        TranslationProvider::LibreTranslate(_) => {
+            let response: LibreTranslateResponse = serde_json::from_slice(body).ok()?;
+            Some(response.translated_text)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DeepLResponse {
+    translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeepLTranslation {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct GoogleResponse {
+    data: GoogleData,
+}
+
+#[derive(Deserialize)]
+struct GoogleData {
+    translations: Vec<GoogleTranslation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleTranslation {
+    translated_text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LibreTranslateResponse {
+    translated_text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_translation_from_each_provider() {
+        let deepl = br#"{"translations":[{"detected_source_language":"EN","text":"Hola"}]}"#;
+        let google = br#"{"data":{"translations":[{"translatedText":"Hola","detectedSourceLanguage":"en"}]}}"#;
+        let libre = br#"{"translatedText":"Hola"}"#;
+
+        assert_eq!(
+            extract_translation(&TranslationProvider::DeepL, deepl).as_deref(),
+            Some("Hola")
+        );
+        assert_eq!(
+            extract_translation(&TranslationProvider::Google, google).as_deref(),
+            Some("Hola")
+        );
+        assert_eq!(
+            extract_translation(
+                &TranslationProvider::LibreTranslate("https://libre.example".to_string()),
+                libre
+            )
+            .as_deref(),
+            Some("Hola")
+        );
+    }
+
+    #[test]
+    fn malformed_response_returns_none() {
+        assert!(extract_translation(&TranslationProvider::DeepL, br#"{"translations":[]}"#).is_none());
+        assert!(extract_translation(&TranslationProvider::Google, b"not json").is_none());
+    }
+}
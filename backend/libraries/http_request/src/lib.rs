@@ -1,6 +1,7 @@
 mod document_handler;
 mod logs_handler;
 mod router;
+mod webhook_handler;
 
 use serde::Serialize;
 use types::{HeaderField, HttpResponse};
@@ -8,6 +9,7 @@ use types::{HeaderField, HttpResponse};
 pub use document_handler::*;
 pub use logs_handler::*;
 pub use router::*;
+pub use webhook_handler::*;
 
 pub fn build_json_response<T: Serialize>(body: &T) -> HttpResponse {
     let bytes = serde_json::to_string(body).unwrap().into_bytes();
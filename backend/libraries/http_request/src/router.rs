@@ -10,6 +10,7 @@ pub enum Route {
     Banner(Option<u128>),
     ProfileBackground(Option<u128>),
     File(u128),
+    FileThumbnail(u128, Option<u32>),
     Logs(Option<TimestampMillis>),
     Errors(Option<TimestampMillis>),
     Traces(Option<TimestampMillis>),
@@ -28,7 +29,8 @@ pub struct AvatarRoute {
 pub struct WebhookRoute {
     pub channel_id: Option<ChannelId>,
     pub webhook_id: UserId,
-    pub secret: String,
+    // Optional since signed requests needn't include the secret in the URL
+    pub secret: Option<String>,
 }
 
 pub fn extract_route(path: &str) -> Route {
@@ -50,7 +52,12 @@ pub fn extract_route(path: &str) -> Route {
         }
         "blobs" | "files" if !parts.is_empty() => {
             if let Ok(file_id) = FileId::from_str(parts[0]) {
-                return Route::File(file_id);
+                return if parts.get(1) == Some(&"thumb") {
+                    let size = parse_query(qs).get("w").and_then(|w| u32::from_str(w).ok());
+                    Route::FileThumbnail(file_id, size)
+                } else {
+                    Route::File(file_id)
+                };
             }
         }
         "channel" => {
@@ -114,20 +121,16 @@ fn parse_avatar(parts: &mut VecDeque<&str>, channel_id: Option<ChannelId>) -> Ro
 }
 
 fn parse_webhook(parts: &mut VecDeque<&str>, channel_id: Option<ChannelId>) -> Option<Route> {
-    let mut next = parts.pop_front();
-
-    if let Some(webhook_id) = next.and_then(|p| Principal::from_text(p).ok()).map(UserId::from) {
-        next = parts.pop_front();
-        if let Some(secret) = next {
-            return Some(Route::Webhook(WebhookRoute {
-                channel_id,
-                webhook_id,
-                secret: secret.to_string(),
-            }));
-        }
-    }
+    let webhook_id = parts
+        .pop_front()
+        .and_then(|p| Principal::from_text(p).ok())
+        .map(UserId::from)?;
 
-    None
+    Some(Route::Webhook(WebhookRoute {
+        channel_id,
+        webhook_id,
+        secret: parts.pop_front().filter(|s| !s.is_empty()).map(|s| s.to_string()),
+    }))
 }
 
 fn parse_query(query: &str) -> HashMap<String, String> {
@@ -190,13 +193,39 @@ mod tests {
         match extract_route(&format!("/webhook/{webhook_id}/{secret}")) {
             Route::Webhook(w) => {
                 assert_eq!(webhook_id, w.webhook_id);
-                assert_eq!(secret, w.secret);
+                assert_eq!(Some(secret.to_string()), w.secret);
                 assert!(w.channel_id.is_none());
             }
             _ => panic!(),
         }
     }
 
+    #[test]
+    fn webhook_without_secret() {
+        let webhook_id = UserId::from(Principal::from_text("3e3x2-xyaaa-aaaaq-aaalq-cai").unwrap());
+        match extract_route(&format!("/webhook/{webhook_id}")) {
+            Route::Webhook(w) => {
+                assert_eq!(webhook_id, w.webhook_id);
+                assert!(w.secret.is_none());
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn file_thumbnail() {
+        const FILE_ID: u128 = 367253521351235123;
+        assert!(matches!(extract_route(&format!("/files/{FILE_ID}")), Route::File(FILE_ID)));
+        assert!(matches!(
+            extract_route(&format!("/files/{FILE_ID}/thumb")),
+            Route::FileThumbnail(FILE_ID, None)
+        ));
+        assert!(matches!(
+            extract_route(&format!("/files/{FILE_ID}/thumb?w=320")),
+            Route::FileThumbnail(FILE_ID, Some(320))
+        ));
+    }
+
     #[test]
     fn logs() {
         assert!(matches!(extract_route("/logs/1633649663014109000"), Route::Logs(_)));
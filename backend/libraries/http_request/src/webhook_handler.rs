@@ -0,0 +1,136 @@
+use serde_bytes::ByteBuf;
+use types::{HttpRequest, WebhookMessage, WebhookSignature};
+
+const TIMESTAMP_HEADER: &str = "X-OC-Timestamp";
+const NONCE_HEADER: &str = "X-OC-Nonce";
+const SIGNATURE_HEADER: &str = "X-OC-Signature";
+
+// Webhook requests with a JSON content type are parsed as a structured `WebhookMessage`, anything
+// else is treated as plain text, which is how webhooks originally worked
+pub fn extract_webhook_message(request: &HttpRequest) -> Result<WebhookMessage, String> {
+    let is_json = request
+        .header("Content-Type")
+        .is_some_and(|c| c.trim_start().to_lowercase().starts_with("application/json"));
+
+    if is_json {
+        serde_json::from_slice(&request.body).map_err(|error| format!("Invalid JSON: {error}"))
+    } else {
+        String::from_utf8(request.body.clone())
+            .map(WebhookMessage::from)
+            .map_err(|_| "Invalid UTF-8".to_string())
+    }
+}
+
+// Extracts the signature headers used by webhooks which require signed requests. Returns `None` if
+// none of the headers are present, and an error if only some of them are.
+pub fn extract_webhook_signature(request: &HttpRequest) -> Result<Option<WebhookSignature>, String> {
+    let timestamp = request.header(TIMESTAMP_HEADER);
+    let nonce = request.header(NONCE_HEADER);
+    let signature = request.header(SIGNATURE_HEADER);
+
+    match (timestamp, nonce, signature) {
+        (None, None, None) => Ok(None),
+        (Some(timestamp), Some(nonce), Some(signature)) => Ok(Some(WebhookSignature {
+            timestamp: timestamp
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid {TIMESTAMP_HEADER} header"))?,
+            nonce: nonce.trim().to_string(),
+            signature: signature.trim().to_string(),
+            body: ByteBuf::from(request.body.clone()),
+        })),
+        _ => Err(format!(
+            "{TIMESTAMP_HEADER}, {NONCE_HEADER} and {SIGNATURE_HEADER} headers must all be provided"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{EventIndex, MessageContentInitial, MessageIndex, WebhookAttachmentKind};
+
+    fn request(content_type: Option<&str>, body: &str) -> HttpRequest {
+        HttpRequest {
+            method: "POST".to_string(),
+            url: "/webhook".to_string(),
+            headers: content_type
+                .map(|c| ("content-type".to_string(), c.to_string()))
+                .into_iter()
+                .collect(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn plain_text() {
+        let mut message = extract_webhook_message(&request(None, "{\"text\":\"hello\"}")).unwrap();
+
+        let MessageContentInitial::Text(content) = message.take_content().unwrap() else {
+            panic!()
+        };
+        assert_eq!(content.text, "{\"text\":\"hello\"}");
+        assert!(message.block_level_markdown());
+    }
+
+    #[test]
+    fn structured() {
+        let body = r#"{
+            "text": "Build failed",
+            "thread_root_message_index": 5,
+            "replies_to": 12,
+            "block_level_markdown": false,
+            "attachment": {
+                "kind": "image",
+                "url": "https://3e3x2-xyaaa-aaaaq-aaalq-cai.raw.icp0.io/files/1234",
+                "mime_type": "image/png"
+            }
+        }"#;
+        let mut message = extract_webhook_message(&request(Some("application/json; charset=utf-8"), body)).unwrap();
+
+        assert_eq!(message.thread_root_message_index, Some(MessageIndex::from(5)));
+        assert_eq!(message.replies_to().map(|r| r.event_index), Some(EventIndex::from(12)));
+        assert!(!message.block_level_markdown());
+        assert_eq!(
+            message.attachment.as_ref().map(|a| a.kind),
+            Some(WebhookAttachmentKind::Image)
+        );
+
+        let MessageContentInitial::Image(content) = message.take_content().unwrap() else {
+            panic!()
+        };
+        assert_eq!(content.caption.as_deref(), Some("Build failed"));
+        assert_eq!(content.blob_reference.map(|b| b.blob_id), Some(1234));
+    }
+
+    #[test]
+    fn content_and_text_rejected() {
+        let body = r#"{ "text": "hello", "content": { "Text": { "text": "hello" } } }"#;
+        let mut message = extract_webhook_message(&request(Some("application/json"), body)).unwrap();
+
+        assert!(message.take_content().is_err());
+    }
+
+    #[test]
+    fn signature_headers() {
+        let mut request = request(None, "hello");
+        assert!(extract_webhook_signature(&request).unwrap().is_none());
+
+        request
+            .headers
+            .push(("x-oc-timestamp".to_string(), "1700000000000".to_string()));
+        assert!(extract_webhook_signature(&request).is_err());
+
+        request.headers.push(("x-oc-nonce".to_string(), "abc".to_string()));
+        request.headers.push(("x-oc-signature".to_string(), "def".to_string()));
+        let signature = extract_webhook_signature(&request).unwrap().unwrap();
+        assert_eq!(signature.timestamp, 1700000000000);
+        assert_eq!(signature.nonce, "abc");
+        assert_eq!(signature.body.as_slice(), b"hello");
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(extract_webhook_message(&request(Some("application/json"), "hello")).is_err());
+    }
+}
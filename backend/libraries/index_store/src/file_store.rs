@@ -0,0 +1,123 @@
+use crate::IndexStore;
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use types::{CanisterId, Error};
+
+// Persists the indexes to a JSON file so that they survive restarts without depending on any
+// external service. Each write goes to a temporary file which is fsync'd and then renamed over the
+// original, so a crash mid-write leaves either the old or the new file, never a partial one.
+#[derive(Clone)]
+pub struct FileStore {
+    path: Arc<PathBuf>,
+    indexes_processed_up_to: Arc<Mutex<BTreeMap<String, u64>>>,
+}
+
+impl FileStore {
+    pub fn open(path: impl Into<PathBuf>) -> Result<FileStore, Error> {
+        let path = path.into();
+        let indexes = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Failed to parse index store file {}: {e}", path.display()))?,
+            Err(error) if error.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(FileStore {
+            path: Arc::new(path),
+            indexes_processed_up_to: Arc::new(Mutex::new(indexes)),
+        })
+    }
+}
+
+#[async_trait]
+impl IndexStore for FileStore {
+    async fn get(&self, canister_id: CanisterId) -> Result<Option<u64>, Error> {
+        Ok(self
+            .indexes_processed_up_to
+            .lock()
+            .await
+            .get(&canister_id.to_string())
+            .copied())
+    }
+
+    async fn set(&self, canister_id: CanisterId, index: u64) -> Result<(), Error> {
+        // The lock is held while writing so that concurrent writes can't reorder on disk
+        let mut indexes = self.indexes_processed_up_to.lock().await;
+        indexes.insert(canister_id.to_string(), index);
+        let bytes = serde_json::to_vec_pretty(&*indexes)?;
+
+        // The write and fsyncs block, so run them off the async worker threads
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || write_atomically(&path, &bytes)).await?
+    }
+}
+
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    let temp_path = temp_path(path);
+
+    let mut file = File::create(&temp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+
+    std::fs::rename(&temp_path, path)?;
+
+    // Sync the directory so that the rename itself is durable
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+// Appends a suffix rather than replacing the extension, so that the temporary file can never be
+// the target file itself (eg. if the target file's extension is already "tmp")
+fn temp_path(path: &Path) -> PathBuf {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    PathBuf::from(temp_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn indexes_survive_reopening_the_store() {
+        let path = std::env::temp_dir().join(format!("index_store_test_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let canister_id = CanisterId::from_slice(&[1, 2, 3]);
+
+        let store = FileStore::open(&path).unwrap();
+        assert_eq!(store.get(canister_id).await.unwrap(), None);
+        store.set(canister_id, 5).await.unwrap();
+        store.set(canister_id, 10).await.unwrap();
+
+        let reopened = FileStore::open(&path).unwrap();
+        assert_eq!(reopened.get(canister_id).await.unwrap(), Some(10));
+        assert!(!temp_path(&path).exists());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn store_file_with_tmp_extension_is_not_overwritten_by_temp_file() {
+        let path = std::env::temp_dir().join(format!("index_store_test_{}.tmp", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let canister_id = CanisterId::from_slice(&[1, 2, 3]);
+
+        let store = FileStore::open(&path).unwrap();
+        store.set(canister_id, 7).await.unwrap();
+
+        assert_ne!(temp_path(&path), path);
+        let reopened = FileStore::open(&path).unwrap();
+        assert_eq!(reopened.get(canister_id).await.unwrap(), Some(7));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
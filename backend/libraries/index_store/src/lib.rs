@@ -1,6 +1,8 @@
 mod dummy_store;
+mod file_store;
 
 pub use dummy_store::DummyStore;
+pub use file_store::FileStore;
 
 use async_trait::async_trait;
 use types::{CanisterId, Error};
@@ -2,8 +2,11 @@ use candid::Principal;
 use constants::calculate_summary_updates_data_removal_cutoff;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use std::collections::{btree_map::Entry, BTreeMap, BTreeSet};
-use types::{BotDefinitionUpdate, BotPermissions, BotSubscriptions, OptionUpdate, TimestampMillis, UserId};
+use std::collections::{BTreeMap, BTreeSet, btree_map::Entry};
+use types::{
+    BotChannelPermissions, BotDefinitionUpdate, BotPermissions, BotSubscriptions, ChannelId, OptionUpdate, TimestampMillis,
+    UserId,
+};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct InstalledBots {
@@ -20,6 +23,7 @@ impl InstalledBots {
         permissions: BotPermissions,
         autonomous_permissions: Option<BotPermissions>,
         default_subscriptions: Option<BotSubscriptions>,
+        channel_permissions: Option<Vec<BotChannelPermissions>>,
         now: TimestampMillis,
     ) -> bool {
         if self.bots.contains_key(&bot_id) {
@@ -33,6 +37,7 @@ impl InstalledBots {
                 permissions,
                 autonomous_permissions,
                 default_subscriptions,
+                channel_permissions: channel_permissions.map(channel_permissions_map),
             },
         );
         self.prune_then_insert_member_update(bot_id, BotUpdate::Added, now);
@@ -48,6 +53,12 @@ impl InstalledBots {
         // The stored bot permissions are _granted_ permissions, whereas the incoming permissions are
         // those requested by the bot. So intersect the requested permissions with the stored permissions.
 
+        if let Some(channel_permissions) = bot.channel_permissions.as_mut() {
+            for channel in channel_permissions.values_mut() {
+                channel.apply_requested(&update.command_permissions, &update.autonomous_permissions);
+            }
+        }
+
         match update.command_permissions {
             OptionUpdate::NoChange => (),
             OptionUpdate::SetToNone => bot.permissions = BotPermissions::default(),
@@ -80,6 +91,7 @@ impl InstalledBots {
         bot_id: UserId,
         command_permissions: BotPermissions,
         autonomous_permissions: Option<BotPermissions>,
+        channel_permissions: OptionUpdate<Vec<BotChannelPermissions>>,
         now: TimestampMillis,
     ) -> bool {
         match self.bots.entry(bot_id) {
@@ -88,12 +100,33 @@ impl InstalledBots {
                 let bot = o.get_mut();
                 bot.permissions = command_permissions;
                 bot.autonomous_permissions = autonomous_permissions;
+                if let Some(channel_permissions) = channel_permissions.expand() {
+                    bot.channel_permissions = channel_permissions.map(channel_permissions_map);
+                }
                 self.prune_then_insert_member_update(bot_id, BotUpdate::Updated, now);
                 true
             }
         }
     }
 
+    // Removes the channel from the restrictions of any bots which are permitted to access it
+    pub fn remove_channel(&mut self, channel_id: ChannelId, now: TimestampMillis) {
+        let updated: Vec<_> = self
+            .bots
+            .iter_mut()
+            .filter_map(|(bot_id, bot)| {
+                bot.channel_permissions
+                    .as_mut()
+                    .and_then(|c| c.remove(&channel_id))
+                    .map(|_| *bot_id)
+            })
+            .collect();
+
+        for bot_id in updated {
+            self.prune_then_insert_member_update(bot_id, BotUpdate::Updated, now);
+        }
+    }
+
     pub fn remove(&mut self, bot_id: UserId, now: TimestampMillis) -> bool {
         let removed = self.bots.remove(&bot_id).is_some();
 
@@ -165,4 +198,85 @@ pub struct BotInternal {
     pub autonomous_permissions: Option<BotPermissions>,
     #[serde(default)]
     pub default_subscriptions: Option<BotSubscriptions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_permissions: Option<BTreeMap<ChannelId, ChannelPermissionsInternal>>,
+}
+
+impl BotInternal {
+    // The command permissions granted in the given channel, or community-wide if no channel is
+    // specified. Returns None if the bot is restricted to other channels, or if no channel is
+    // specified and the bot is restricted to specific channels.
+    pub fn permissions_in(&self, channel_id: Option<ChannelId>) -> Option<&BotPermissions> {
+        match (channel_id, &self.channel_permissions) {
+            (Some(channel_id), Some(channel_permissions)) => channel_permissions.get(&channel_id).map(|c| &c.permissions),
+            (None, Some(_)) => None,
+            (_, None) => Some(&self.permissions),
+        }
+    }
+
+    pub fn autonomous_permissions_in(&self, channel_id: Option<ChannelId>) -> Option<&BotPermissions> {
+        match (channel_id, &self.channel_permissions) {
+            (Some(channel_id), Some(channel_permissions)) => channel_permissions
+                .get(&channel_id)
+                .and_then(|c| c.autonomous_permissions.as_ref()),
+            (None, Some(_)) => None,
+            (_, None) => self.autonomous_permissions.as_ref(),
+        }
+    }
+
+    pub fn channel_permissions(&self) -> Option<Vec<BotChannelPermissions>> {
+        self.channel_permissions.as_ref().map(|channel_permissions| {
+            channel_permissions
+                .iter()
+                .map(|(channel_id, c)| BotChannelPermissions {
+                    channel_id: *channel_id,
+                    permissions: c.permissions.clone(),
+                    autonomous_permissions: c.autonomous_permissions.clone(),
+                })
+                .collect()
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ChannelPermissionsInternal {
+    pub permissions: BotPermissions,
+    #[serde(default)]
+    pub autonomous_permissions: Option<BotPermissions>,
+}
+
+impl ChannelPermissionsInternal {
+    fn apply_requested(&mut self, command: &OptionUpdate<BotPermissions>, autonomous: &OptionUpdate<BotPermissions>) {
+        match command {
+            OptionUpdate::NoChange => (),
+            OptionUpdate::SetToNone => self.permissions = BotPermissions::default(),
+            OptionUpdate::SetToSome(requested) => self.permissions = self.permissions.intersect(requested),
+        };
+
+        match autonomous {
+            OptionUpdate::NoChange => (),
+            OptionUpdate::SetToNone => self.autonomous_permissions = None,
+            OptionUpdate::SetToSome(requested) => {
+                self.autonomous_permissions = self
+                    .autonomous_permissions
+                    .as_ref()
+                    .map(|existing| existing.intersect(requested))
+            }
+        };
+    }
+}
+
+fn channel_permissions_map(channel_permissions: Vec<BotChannelPermissions>) -> BTreeMap<ChannelId, ChannelPermissionsInternal> {
+    channel_permissions
+        .into_iter()
+        .map(|c| {
+            (
+                c.channel_id,
+                ChannelPermissionsInternal {
+                    permissions: c.permissions,
+                    autonomous_permissions: c.autonomous_permissions,
+                },
+            )
+        })
+        .collect()
 }
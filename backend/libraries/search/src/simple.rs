@@ -1,7 +1,26 @@
 use serde::{Deserialize, Serialize};
 
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+// A query is made up of free text terms plus optional filters. The supported syntax is -
+// `word` matches any field containing `word`
+// `word*` matches any field containing a word which starts with `word`
+// `"some phrase"` matches any field containing the exact phrase
+// `before:<date>` / `after:<date>` where date is either `YYYY-MM-DD` or a timestamp in millis
+// `has:<kind>` eg. `has:image`, `has:file`, `has:poll`
+#[derive(Default)]
 pub struct Query {
     pub tokens: Vec<Token>,
+    pub prefixes: Vec<Token>,
+    pub phrases: Vec<Token>,
+    pub filters: QueryFilters,
+}
+
+#[derive(Default)]
+pub struct QueryFilters {
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+    pub has: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -15,8 +34,78 @@ impl Token {
 
 impl Query {
     pub fn new(text: &str) -> Query {
-        Query {
-            tokens: parse_tokens(text),
+        let mut query = Query::default();
+
+        for term in split_terms(text) {
+            match term {
+                Term::Phrase(phrase) => {
+                    let normalized = normalize_whitespace(&phrase.to_lowercase());
+                    if !normalized.is_empty() {
+                        query.phrases.push(Token(normalized));
+                    }
+                }
+                Term::Word(word) => query.push_word(&word),
+            }
+        }
+
+        query
+    }
+
+    // Returns true if the query contains no free text terms (it may still contain filters)
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty() && self.prefixes.is_empty() && self.phrases.is_empty()
+    }
+
+    // Returns the (start, end) character offsets within `text` of each part of the text which
+    // matches the query. The ranges are sorted and do not overlap.
+    pub fn highlights(&self, text: &str) -> Vec<(usize, usize)> {
+        let chars: Vec<char> = text.chars().map(lowercase_char).collect();
+        let mut ranges = Vec::new();
+
+        for token in self.tokens.iter() {
+            let needle: Vec<char> = token.0.chars().collect();
+            ranges.extend(find_all(&chars, &needle).map(|start| (start, start + needle.len())));
+        }
+
+        for prefix in self.prefixes.iter() {
+            let needle: Vec<char> = prefix.0.chars().collect();
+            ranges.extend(
+                find_all(&chars, &needle)
+                    .filter(|&start| is_word_start(&chars, start))
+                    .map(|start| (start, word_end(&chars, start + needle.len()))),
+            );
+        }
+
+        for phrase in self.phrases.iter() {
+            let words: Vec<Vec<char>> = phrase.0.split(' ').map(|w| w.chars().collect()).collect();
+            ranges.extend((0..chars.len()).filter_map(|start| match_phrase_at(&chars, start, &words).map(|end| (start, end))));
+        }
+
+        merge_ranges(ranges)
+    }
+
+    fn push_word(&mut self, word: &str) {
+        if let Some((key, value)) = word.split_once(':') {
+            let handled = match key.to_lowercase().as_str() {
+                "before" => parse_timestamp(value, false)
+                    .map(|ts| self.filters.before = Some(ts))
+                    .is_some(),
+                "after" => parse_timestamp(value, true).map(|ts| self.filters.after = Some(ts)).is_some(),
+                "has" if !value.is_empty() => {
+                    self.filters.has.push(value.to_lowercase());
+                    true
+                }
+                _ => false,
+            };
+            if handled {
+                return;
+            }
+        }
+
+        if let Some(prefix) = word.strip_suffix('*').filter(|p| !p.is_empty() && !p.ends_with('*')) {
+            self.prefixes.push(Token::new(prefix));
+        } else {
+            self.tokens.push(Token::new(word));
         }
     }
 }
@@ -33,18 +122,148 @@ impl Document {
         self
     }
 
-    // Returns true if every token in the query matches the document, else false
+    // Returns true if every term in the query matches the document, else false
     pub fn is_match(&self, query: &Query) -> bool {
-        if query.tokens.is_empty() {
+        if query.is_empty() {
             false
         } else {
             query.tokens.iter().all(|t| self.0.iter().any(|f| f.0.contains(&t.0)))
+                && query
+                    .prefixes
+                    .iter()
+                    .all(|p| self.0.iter().any(|f| contains_word_starting_with(&f.0, &p.0)))
+                && query
+                    .phrases
+                    .iter()
+                    .all(|p| self.0.iter().any(|f| normalize_whitespace(&f.0).contains(&p.0)))
+        }
+    }
+}
+
+enum Term {
+    Word(String),
+    Phrase(String),
+}
+
+// Splits the text on whitespace, treating anything within double quotes as a single phrase.
+// An unterminated quote runs to the end of the text.
+fn split_terms(text: &str) -> Vec<Term> {
+    let mut terms = Vec::new();
+    let mut remaining = text;
+
+    while let Some(quote_start) = remaining.find('"') {
+        terms.extend(remaining[..quote_start].split_whitespace().map(|w| Term::Word(w.to_string())));
+
+        let after_quote = &remaining[quote_start + 1..];
+        if let Some(quote_end) = after_quote.find('"') {
+            terms.push(Term::Phrase(after_quote[..quote_end].to_string()));
+            remaining = &after_quote[quote_end + 1..];
+        } else {
+            terms.push(Term::Phrase(after_quote.to_string()));
+            remaining = "";
+        }
+    }
+
+    terms.extend(remaining.split_whitespace().map(|w| Term::Word(w.to_string())));
+    terms
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn contains_word_starting_with(field: &str, prefix: &str) -> bool {
+    field
+        .match_indices(prefix)
+        .any(|(i, _)| field[..i].chars().next_back().is_none_or(|c| !c.is_alphanumeric()))
+}
+
+// Parses either a date in the format `YYYY-MM-DD` or a timestamp in millis. For dates, `after`
+// resolves to the end of the day and `before` resolves to the start of the day, so that both
+// filters exclude the date given.
+fn parse_timestamp(value: &str, end_of_day: bool) -> Option<u64> {
+    if let Ok(timestamp) = value.parse::<u64>() {
+        return Some(timestamp);
+    }
+
+    let mut parts = value.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+
+    if !(1970..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let start_of_day = days_from_civil(year, month, day) as u64 * MILLIS_PER_DAY;
+    Some(if end_of_day { start_of_day + MILLIS_PER_DAY - 1 } else { start_of_day })
+}
+
+// Howard Hinnant's algorithm for converting a date into the number of days since 1970-01-01
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn lowercase_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn find_all<'a>(haystack: &'a [char], needle: &'a [char]) -> impl Iterator<Item = usize> + 'a {
+    let count = if needle.is_empty() { 0 } else { (haystack.len() + 1).saturating_sub(needle.len()) };
+    (0..count).filter(move |&i| haystack[i..].starts_with(needle))
+}
+
+fn is_word_start(chars: &[char], index: usize) -> bool {
+    index == 0 || !chars[index - 1].is_alphanumeric()
+}
+
+fn word_end(chars: &[char], from: usize) -> usize {
+    chars[from..]
+        .iter()
+        .position(|c| !c.is_alphanumeric())
+        .map_or(chars.len(), |p| from + p)
+}
+
+// Returns the end offset if the phrase matches at `start`, allowing any run of whitespace
+// between the words of the phrase
+fn match_phrase_at(chars: &[char], start: usize, words: &[Vec<char>]) -> Option<usize> {
+    let mut index = start;
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            let whitespace = chars[index..].iter().take_while(|c| c.is_whitespace()).count();
+            if whitespace == 0 {
+                return None;
+            }
+            index += whitespace;
         }
+        if !chars[index..].starts_with(word) {
+            return None;
+        }
+        index += word.len();
     }
+    Some(index)
 }
 
-fn parse_tokens(text: &str) -> Vec<Token> {
-    text.split_whitespace().map(Token::new).collect()
+fn merge_ranges(mut ranges: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        if let Some(last) = merged.last_mut()
+            && start <= last.1
+        {
+            last.1 = last.1.max(end);
+        } else {
+            merged.push((start, end));
+        }
+    }
+    merged
 }
 
 #[cfg(test)]
@@ -66,4 +285,50 @@ mod tests {
 
         assert_eq!(doc.is_match(&Query::new(query)), should_match);
     }
+
+    #[test_case(vec!["Deploying to prod"], "deploy*", true)]
+    #[test_case(vec!["Redeploying to prod"], "deploy*", false)]
+    #[test_case(vec!["Redeploying to prod"], "deploy", true)]
+    #[test_case(vec!["the quick  brown fox"], "\"quick brown\"", true)]
+    #[test_case(vec!["the quick red fox"], "\"quick brown\"", false)]
+    #[test_case(vec!["the Quick brown fox"], "\"quick brown\" fox", true)]
+    #[test_case(vec!["the quick brown fox"], "\"quick brown", true)]
+    fn phrase_and_prefix_matches_found_correctly(doc_fields: Vec<&str>, query: &str, should_match: bool) {
+        let mut doc = Document::default();
+        for field in doc_fields {
+            doc.add_field(field);
+        }
+
+        assert_eq!(doc.is_match(&Query::new(query)), should_match);
+    }
+
+    #[test]
+    fn filters_parsed_correctly() {
+        let query = Query::new("outage has:Image after:2024-01-31 before:1717200000000 other:value");
+
+        assert_eq!(
+            query.tokens.iter().map(|t| t.0.as_str()).collect::<Vec<_>>(),
+            vec!["outage", "other:value"]
+        );
+        assert_eq!(query.filters.has, vec!["image".to_string()]);
+        assert_eq!(query.filters.after, Some(1706745599999));
+        assert_eq!(query.filters.before, Some(1717200000000));
+    }
+
+    #[test]
+    fn filters_only_query_is_empty() {
+        let query = Query::new("has:poll before:2024-01-01");
+
+        assert!(query.is_empty());
+        assert_eq!(query.filters.before, Some(1704067200000));
+    }
+
+    #[test_case("Deploy the deployment", "deploy*", vec![(0, 6), (11, 21)])]
+    #[test_case("Deploy the deployment", "loy", vec![(3, 6), (14, 17)])]
+    #[test_case("The quick\nbrown fox", "\"quick brown\"", vec![(4, 15)])]
+    #[test_case("abcabc", "abc bca", vec![(0, 6)])]
+    #[test_case("nothing here", "xyz", vec![])]
+    fn highlights_found_correctly(text: &str, query: &str, expected: Vec<(usize, usize)>) {
+        assert_eq!(Query::new(query).highlights(text), expected);
+    }
 }
@@ -8,7 +8,7 @@ use std::ops::DerefMut;
 use std::rc::Rc;
 use std::sync::Mutex;
 use std::time::Duration;
-use types::Milliseconds;
+use types::TimestampMillis;
 
 // Use this to process events where events are grouped into batches based on their key.
 // Eg. sending events to User canisters (in this case the User canisterId would be the key).
@@ -35,9 +35,11 @@ impl<T: TimerJobItemGroup> GroupedTimerJobQueue<T> {
                 queue: VecDeque::new(),
                 items_map: BTreeMap::new(),
                 in_progress: BTreeSet::new(),
+                retry_at: BTreeMap::new(),
                 max_concurrency,
                 defer_processing,
                 timer: None,
+                timer_due: None,
             })),
             phantom: PhantomData,
         }
@@ -64,7 +66,10 @@ impl<T: TimerJobItemGroup> GroupedTimerJobQueue<T> {
     }
 
     pub fn clear(&mut self) {
-        self.within_lock(|i| i.queue.clear())
+        self.within_lock(|i| {
+            i.queue.clear();
+            i.retry_at.clear();
+        })
     }
 
     pub fn len(&self) -> usize {
@@ -91,10 +96,25 @@ struct GroupedTimerJobQueueInner<S: Clone, K: Clone + Ord, I> {
     queue: VecDeque<K>,
     items_map: BTreeMap<K, VecDeque<I>>,
     in_progress: BTreeSet<K>,
+    // Keys whose most recent batch failed and is to be retried, mapped to when the retry is due
+    #[serde(default)]
+    retry_at: BTreeMap<K, TimestampMillis>,
     max_concurrency: usize,
     defer_processing: bool,
     #[serde(skip)]
     timer: Option<PerRoundTimer>,
+    #[serde(skip)]
+    timer_due: Option<TimestampMillis>,
+}
+
+impl<S: Clone, K: Clone + Ord, I> GroupedTimerJobQueueInner<S, K, I> {
+    // Returns when the next key in the queue becomes due to be processed, if there are any
+    fn next_due(&self, now: TimestampMillis) -> Option<TimestampMillis> {
+        self.queue
+            .iter()
+            .map(|key| self.retry_at.get(key).map_or(now, |retry_at| (*retry_at).max(now)))
+            .min()
+    }
 }
 
 impl<T: TimerJobItemGroup + 'static> GroupedTimerJobQueue<T>
@@ -124,7 +144,7 @@ where
         });
 
         if defer_processing {
-            self.set_timer_if_required(0);
+            self.set_timer_if_required();
         } else {
             self.flush();
         }
@@ -132,11 +152,20 @@ where
 
     pub fn flush(&self) {
         let mut batches = Vec::new();
+        let now = now_millis();
 
         self.within_lock(|i| {
             let max_to_start = i.max_concurrency.saturating_sub(i.in_progress.len());
+            let mut backing_off = Vec::new();
             while batches.len() < max_to_start {
                 if let Some(grouping_key) = i.queue.pop_front() {
+                    // If this key's last batch failed, skip it until its retry is due
+                    if i.retry_at.get(&grouping_key).is_some_and(|retry_at| *retry_at > now) {
+                        backing_off.push(grouping_key);
+                        continue;
+                    }
+                    i.retry_at.remove(&grouping_key);
+
                     if let Occupied(mut e) = i.items_map.entry(grouping_key.clone()) {
                         // If this key is already being processed, skip it
                         if !i.in_progress.insert(grouping_key.clone()) {
@@ -171,28 +200,44 @@ where
                 }
             }
 
-            if i.queue.is_empty() {
-                i.timer = None;
+            // Put the keys which are backing off back at the front of the queue to maintain ordering
+            for grouping_key in backing_off.into_iter().rev() {
+                i.queue.push_front(grouping_key);
             }
+
+            // The timer is re-armed below based on when the remaining keys are next due
+            i.timer = None;
+            i.timer_due = None;
         });
 
+        self.set_timer_if_required();
+
         if !batches.is_empty() {
             let clone = self.clone();
             ic_cdk::futures::spawn_migratory(clone.process_all_batches(batches));
         }
     }
 
-    fn set_timer_if_required(&self, delay: Milliseconds) -> bool {
+    // Arms the timer to fire when the next key in the queue is due, unless it is already set to
+    // fire at or before then
+    fn set_timer_if_required(&self) -> bool {
         let clone = self.clone();
+        let now = now_millis();
         self.within_lock(|i| {
-            if !i.queue.is_empty() && i.timer.is_none() {
-                i.timer = Some(PerRoundTimer::new_with_interval(Duration::from_millis(delay), move || {
-                    clone.flush()
-                }));
-                true
-            } else {
-                false
+            let Some(due) = i.next_due(now) else {
+                i.timer = None;
+                i.timer_due = None;
+                return false;
+            };
+            if i.timer.is_some() && i.timer_due.is_some_and(|timer_due| timer_due <= due) {
+                return false;
             }
+            i.timer = Some(PerRoundTimer::new_with_interval(
+                Duration::from_millis(due.saturating_sub(now)),
+                move || clone.flush(),
+            ));
+            i.timer_due = Some(due);
+            true
         })
     }
 
@@ -202,12 +247,14 @@ where
 
     async fn process_batch(&self, batch: T) {
         let result = batch.process().await;
-        let retry = matches!(result, Err(Some(_)));
+        let retry_delay = result.err().flatten();
         let key = batch.key();
+        let now = now_millis();
 
         self.within_lock(|i| {
             i.in_progress.remove(&key);
-            if retry {
+            if let Some(delay) = retry_delay {
+                i.retry_at.insert(key.clone(), now.saturating_add(delay));
                 let queue = i.items_map.entry(key.clone()).or_default();
                 // Prepend the items to the front of the queue such that ordering is maintained
                 for item in batch.into_items().into_iter().rev() {
@@ -219,7 +266,7 @@ where
                 i.queue.push_back(key);
             }
         });
-        self.set_timer_if_required(result.err().flatten().unwrap_or_default());
+        self.set_timer_if_required();
     }
 }
 
@@ -255,11 +302,15 @@ where
             inner: Rc::new(Mutex::new(inner)),
             phantom: PhantomData,
         };
-        value.set_timer_if_required(0);
+        value.set_timer_if_required();
         Ok(value)
     }
 }
 
+fn now_millis() -> TimestampMillis {
+    ic_cdk::api::time() / 1_000_000
+}
+
 #[macro_export]
 macro_rules! grouped_timer_job_batch {
     ($name:ident, $key_type:ty, $item_type:ty, $batch_size:literal) => {
@@ -303,3 +354,47 @@ macro_rules! grouped_timer_job_batch {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: TimestampMillis = 1_700_000_000_000;
+
+    fn inner(queue: Vec<u32>, retry_at: Vec<(u32, TimestampMillis)>) -> GroupedTimerJobQueueInner<(), u32, ()> {
+        GroupedTimerJobQueueInner {
+            state: (),
+            queue: queue.into_iter().collect(),
+            items_map: BTreeMap::new(),
+            in_progress: BTreeSet::new(),
+            retry_at: retry_at.into_iter().collect(),
+            max_concurrency: 1,
+            defer_processing: false,
+            timer: None,
+            timer_due: None,
+        }
+    }
+
+    #[test]
+    fn next_due_none_if_queue_empty() {
+        assert_eq!(inner(Vec::new(), vec![(1, NOW + 1000)]).next_due(NOW), None);
+    }
+
+    #[test]
+    fn next_due_now_if_any_key_not_backing_off() {
+        assert_eq!(inner(vec![1, 2], vec![(1, NOW + 1000)]).next_due(NOW), Some(NOW));
+    }
+
+    #[test]
+    fn next_due_is_earliest_retry() {
+        assert_eq!(
+            inner(vec![1, 2], vec![(1, NOW + 40_000), (2, NOW + 10_000)]).next_due(NOW),
+            Some(NOW + 10_000)
+        );
+    }
+
+    #[test]
+    fn overdue_retries_due_now() {
+        assert_eq!(inner(vec![1], vec![(1, NOW - 1000)]).next_due(NOW), Some(NOW));
+    }
+}
@@ -1,9 +1,9 @@
 use crate::bitflags::{decode_from_bitflags, encode_as_bitflags};
 use crate::{
-    AudioContent, CanisterId, Chat, ChatEventCategory, ChatEventType, ChatId, ChatPermission, CommunityEventCategory,
-    CommunityEventType, CommunityId, CommunityOrGroup, CommunityPermission, FileContent, GiphyContent, GroupRole, ImageContent,
-    MessageContentInitial, MessageId, MessagePermission, OgPreview, OptionUpdate, PollContent, TextContent, TimestampMillis,
-    UserId, VideoContent, is_default,
+    AudioContent, CanisterId, ChannelId, Chat, ChatEventCategory, ChatEventType, ChatId, ChatPermission,
+    CommunityEventCategory, CommunityEventType, CommunityId, CommunityOrGroup, CommunityPermission, FileContent, GiphyContent,
+    GroupRole, ImageContent, MessageContentInitial, MessageId, MessagePermission, OgPreview, OptionUpdate, PollContent,
+    TextContent, TimestampMillis, UserId, VideoContent, WebhookMetrics, WebhookRateLimits, is_default,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -62,6 +62,16 @@ pub enum BotCommandParamType {
     DateTimeParam(DateTimeParam),
 }
 
+impl BotCommandParamType {
+    pub fn supports_autocomplete(&self) -> bool {
+        match self {
+            BotCommandParamType::StringParam(p) => p.autocomplete.unwrap_or_default(),
+            BotCommandParamType::IntegerParam(p) => p.autocomplete.unwrap_or_default(),
+            _ => false,
+        }
+    }
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
 pub struct StringParam {
@@ -70,6 +80,8 @@ pub struct StringParam {
     #[ts(as = "Vec<BotCommandOptionChoiceString>")]
     pub choices: Vec<BotCommandOptionChoice<String>>,
     pub multi_line: bool,
+    // If true, suggestions are fetched from the bot's autocomplete endpoint as the user types
+    pub autocomplete: Option<bool>,
 }
 
 #[ts_export]
@@ -79,6 +91,8 @@ pub struct IntegerParam {
     pub max_value: i128,
     #[ts(as = "Vec<BotCommandOptionChoiceI128>")]
     pub choices: Vec<BotCommandOptionChoice<i128>>,
+    // If true, suggestions are fetched from the bot's autocomplete endpoint as the user types
+    pub autocomplete: Option<bool>,
 }
 
 #[ts_export]
@@ -220,6 +234,7 @@ impl BotPermissions {
                 ChatPermission::ReadMessages,
                 ChatPermission::ReadMembership,
                 ChatPermission::ReadSummary,
+                ChatPermission::ExportHistory,
             ]))
             .with_message(&HashSet::from_iter([
                 MessagePermission::Text,
@@ -315,6 +330,44 @@ pub struct BotMatch {
     pub avatar_id: Option<u128>,
     pub commands: Vec<BotCommandDefinition>,
     pub autonomous_config: Option<AutonomousConfig>,
+    #[serde(default)]
+    pub categories: Vec<BotCategory>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // The number of locations the bot is currently installed in
+    #[serde(default)]
+    pub install_count: u32,
+    // The number of times the bot has been installed, including locations it has since been removed from
+    #[serde(default)]
+    pub lifetime_install_count: u32,
+    pub average_rating: Option<f32>,
+    #[serde(default)]
+    pub rating_count: u32,
+    pub published_at: Option<TimestampMillis>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BotCategory {
+    AI,
+    Community,
+    Finance,
+    Fun,
+    Games,
+    Moderation,
+    News,
+    Productivity,
+    Utilities,
+    Other,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BotReview {
+    pub reviewer: UserId,
+    pub rating: u8,
+    pub text: Option<String>,
+    pub timestamp: TimestampMillis,
 }
 
 macro_rules! slash_command_option_choice {
@@ -339,6 +392,19 @@ pub struct InstalledBotDetails {
     pub added_by: UserId,
     pub permissions: BotPermissions,
     pub autonomous_permissions: Option<BotPermissions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub channel_permissions: Option<Vec<BotChannelPermissions>>,
+}
+
+// Only applies to bots installed in a community. If specified, the bot is restricted to the listed
+// channels and within each of them these permissions are used in place of the community-wide ones.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BotChannelPermissions {
+    pub channel_id: ChannelId,
+    pub permissions: BotPermissions,
+    pub autonomous_permissions: Option<BotPermissions>,
 }
 
 #[ts_export]
@@ -347,6 +413,12 @@ pub struct WebhookDetails {
     pub id: UserId,
     pub name: String,
     pub avatar_id: Option<u128>,
+    #[serde(default)]
+    pub rate_limits: WebhookRateLimits,
+    #[serde(default)]
+    pub require_signature: bool,
+    #[serde(default)]
+    pub metrics: WebhookMetrics,
 }
 
 #[ts_export]
@@ -458,12 +530,24 @@ pub enum BotMessageContent {
 pub enum BotInitiator {
     Command(BotCommand),
     Autonomous,
+    Interaction(BotInteraction),
+}
+
+// A user pressing a button or choosing an option on one of the bot's messages
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotInteraction {
+    pub message_id: MessageId,
+    pub component_id: String,
+    pub value: Option<String>,
+    pub initiator: UserId,
 }
 
 impl BotInitiator {
     pub fn user(&self) -> Option<UserId> {
         match self {
             BotInitiator::Command(bot_command) => Some(bot_command.initiator),
+            BotInitiator::Interaction(interaction) => Some(interaction.initiator),
             _ => None,
         }
     }
@@ -7,6 +7,7 @@ pub enum AccessTypeArgs {
     JoinVideoCall(JoinVideoCallArgs),
     MarkVideoCallAsEnded(MarkVideoCallAsEndedArgs),
     BotActionByCommand(BotActionByCommandArgs),
+    ChatEncryptionKey(ChatEncryptionKeyArgs),
 }
 
 impl AccessTypeArgs {
@@ -23,6 +24,7 @@ impl AccessTypeArgs {
             AccessTypeArgs::JoinVideoCall(args) => args.initiator,
             AccessTypeArgs::MarkVideoCallAsEnded(args) => args.initiator,
             AccessTypeArgs::BotActionByCommand(args) => args.initiator,
+            AccessTypeArgs::ChatEncryptionKey(args) => args.initiator,
         }
     }
 }
@@ -51,6 +53,13 @@ pub struct MarkVideoCallAsEndedArgs {
     pub initiator: UserId,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatEncryptionKeyArgs {
+    pub initiator: UserId,
+    #[serde(default)]
+    pub key_epoch: u32,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BotActionByCommandArgs {
     pub bot_id: UserId,
@@ -25,6 +25,13 @@ pub struct CommunityCanisterChannelSummary {
     #[ts(as = "Option<bool>", optional)]
     pub messages_visible_to_non_members: bool,
     #[serde(default, skip_serializing_if = "is_default")]
+    #[ts(as = "Option<bool>", optional)]
+    pub edit_history_disabled: bool,
+    // The current key epoch if the chat is end-to-end encrypted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub e2ee_key_epoch: Option<u32>,
+    #[serde(default, skip_serializing_if = "is_default")]
     #[ts(as = "Option<EventIndex>", optional)]
     pub min_visible_event_index: EventIndex,
     #[serde(default, skip_serializing_if = "is_default")]
@@ -65,6 +72,8 @@ pub struct CommunityCanisterChannelSummaryUpdates {
     pub avatar_id: OptionUpdate<u128>,
     pub is_public: Option<bool>,
     pub messages_visible_to_non_members: Option<bool>,
+    pub edit_history_disabled: Option<bool>,
+    pub e2ee_key_epoch: Option<u32>,
     #[ts(as = "Option<crate::EventWrapperMessage>")]
     pub latest_message: Option<EventWrapper<Message>>,
     pub latest_message_sender_display_name: Option<String>,
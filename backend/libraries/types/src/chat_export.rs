@@ -0,0 +1,87 @@
+use crate::{BlobReference, ChatEvent, EventIndex, EventWrapper, GroupRole, MessageIndex, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+
+// A chat is exported by repeatedly calling `export_history`, passing in the `next` cursor from the
+// previous page until it is `None`. Each `ChatExportRecord` is written out as a single line of
+// JSON (JSON-lines), members first, then the main chat events, then each thread's events ordered
+// by root message index. The `blobs` of every page are collected into a separate manifest so that
+// files, images, etc can be downloaded from the storage buckets alongside the archive. See
+// `docs/chat-export-format.md` for the full format.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatExportPage {
+    pub records: Vec<ChatExportRecord>,
+    pub blobs: Vec<BlobReference>,
+    pub next: Option<ChatExportCursor>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum ChatExportRecord {
+    Member(ChatExportMember),
+    Event(ChatExportEvent),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatExportMember {
+    pub user_id: UserId,
+    pub role: GroupRole,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatExportEvent {
+    pub thread_root_message_index: Option<MessageIndex>,
+    #[ts(as = "crate::EventWrapperChatEvent")]
+    pub event: EventWrapper<ChatEvent>,
+}
+
+// Points at the last record included in a page, the next page starts from the record after it
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ChatExportCursor {
+    Member {
+        user_id: UserId,
+    },
+    Event {
+        thread_root_message_index: Option<MessageIndex>,
+        event_index: EventIndex,
+    },
+}
+
+impl ChatExportCursor {
+    // The (thread, event index) of the last event exported, or `None` if the export hasn't reached
+    // the events yet
+    pub fn event(&self) -> Option<(Option<MessageIndex>, EventIndex)> {
+        match self {
+            ChatExportCursor::Member { .. } => None,
+            ChatExportCursor::Event {
+                thread_root_message_index,
+                event_index,
+            } => Some((*thread_root_message_index, *event_index)),
+        }
+    }
+}
+
+impl ChatExportPage {
+    pub fn new(members: Vec<ChatExportMember>, events: Vec<ChatExportEvent>, next: Option<ChatExportCursor>) -> Self {
+        let blobs = events
+            .iter()
+            .filter_map(|e| if let ChatEvent::Message(m) = &e.event.event { Some(m.content.blob_references()) } else { None })
+            .flatten()
+            .collect();
+
+        ChatExportPage {
+            records: members
+                .into_iter()
+                .map(ChatExportRecord::Member)
+                .chain(events.into_iter().map(ChatExportRecord::Event))
+                .collect(),
+            blobs,
+            next,
+        }
+    }
+}
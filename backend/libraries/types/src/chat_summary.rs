@@ -114,6 +114,13 @@ pub struct GroupCanisterGroupChatSummary {
     #[ts(as = "Option<bool>", optional)]
     pub messages_visible_to_non_members: bool,
     #[serde(default, skip_serializing_if = "is_default")]
+    #[ts(as = "Option<bool>", optional)]
+    pub edit_history_disabled: bool,
+    // The current key epoch if the chat is end-to-end encrypted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub e2ee_key_epoch: Option<u32>,
+    #[serde(default, skip_serializing_if = "is_default")]
     #[ts(as = "Option<EventIndex>", optional)]
     pub min_visible_event_index: EventIndex,
     #[serde(default, skip_serializing_if = "is_default")]
@@ -170,6 +177,8 @@ pub struct GroupCanisterGroupChatSummaryUpdates {
     pub metrics: Option<ChatMetrics>,
     pub is_public: Option<bool>,
     pub messages_visible_to_non_members: Option<bool>,
+    pub edit_history_disabled: Option<bool>,
+    pub e2ee_key_epoch: Option<u32>,
     #[serde(default, skip_serializing_if = "OptionUpdate::is_empty")]
     #[ts(as = "Option<crate::OptionUpdateFrozenGroupInfo>", optional)]
     pub frozen: OptionUpdate<FrozenGroupInfo>,
@@ -208,6 +217,12 @@ pub struct GroupMembership {
     #[serde(default, skip_serializing_if = "is_default")]
     #[ts(as = "Option<bool>", optional)]
     pub at_everyone_muted: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    #[ts(as = "Option<bool>", optional)]
+    pub mentions_only: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<String>>", optional)]
+    pub keyword_alerts: Vec<String>,
     pub my_metrics: ChatMetrics,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     #[ts(as = "Option<Vec<GroupCanisterThreadDetails>>", optional)]
@@ -229,6 +244,8 @@ pub struct GroupMembershipUpdates {
     pub mentions: Vec<HydratedMention>,
     pub notifications_muted: Option<bool>,
     pub at_everyone_muted: Option<bool>,
+    pub mentions_only: Option<bool>,
+    pub keyword_alerts: Option<Vec<String>>,
     pub my_metrics: Option<ChatMetrics>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     #[ts(as = "Option<Vec<GroupCanisterThreadDetails>>", optional)]
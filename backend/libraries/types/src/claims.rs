@@ -1,4 +1,4 @@
-use crate::{BotActionScope, BotCommand, BotPermissions, CanisterId, Chat, UserId, VideoCallType};
+use crate::{BotActionScope, BotCommand, BotInteraction, BotPermissions, CanisterId, Chat, UserId, VideoCallType};
 use serde::{Deserialize, Serialize};
 
 // The `claim_type` values written into the JWTs we sign. Each token must be verified against the
@@ -12,6 +12,9 @@ pub const CLAIM_TYPE_START_VIDEO_CALL: &str = "StartVideoCall";
 pub const CLAIM_TYPE_JOIN_VIDEO_CALL: &str = "JoinVideoCall";
 pub const CLAIM_TYPE_MARK_VIDEO_CALL_AS_ENDED: &str = "MarkVideoCallAsEnded";
 pub const CLAIM_TYPE_BOT_ACTION_BY_COMMAND: &str = "BotActionByCommand";
+pub const CLAIM_TYPE_BOT_AUTOCOMPLETE: &str = "BotAutocomplete";
+pub const CLAIM_TYPE_BOT_INTERACTION: &str = "BotInteraction";
+pub const CLAIM_TYPE_CHAT_ENCRYPTION_KEY: &str = "ChatEncryptionKey";
 
 #[derive(Serialize, Deserialize)]
 pub struct JoinOrEndVideoCallClaims {
@@ -19,6 +22,16 @@ pub struct JoinOrEndVideoCallClaims {
     pub chat_id: Chat,
 }
 
+// Proves to the Identity canister that `user_id` is a member of `chat_id` and so may derive the
+// chat's encryption key for `key_epoch`
+#[derive(Serialize, Deserialize)]
+pub struct ChatEncryptionKeyClaims {
+    pub user_id: UserId,
+    pub chat_id: Chat,
+    #[serde(default)]
+    pub key_epoch: u32,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct StartVideoCallClaims {
     pub user_id: UserId,
@@ -40,3 +53,26 @@ pub struct BotActionByCommandClaims {
     pub granted_permissions: BotPermissions,
     pub command: BotCommand,
 }
+
+// Grants no permissions, it only proves to the bot that `initiator` is requesting suggestions for
+// the given command parameter from within `scope`
+#[derive(Serialize, Deserialize)]
+pub struct BotAutocompleteClaims {
+    pub bot_api_gateway: CanisterId,
+    pub bot: UserId,
+    pub scope: BotActionScope,
+    pub initiator: UserId,
+    pub command_name: String,
+    pub param_name: String,
+}
+
+// Issued when a user presses a button or picks an option on one of the bot's messages. The scope's
+// message id is that of the message interacted with, which the bot may then edit in response.
+#[derive(Serialize, Deserialize)]
+pub struct BotInteractionClaims {
+    pub bot_api_gateway: CanisterId,
+    pub bot: UserId,
+    pub scope: BotActionScope,
+    pub granted_permissions: BotPermissions,
+    pub interaction: BotInteraction,
+}
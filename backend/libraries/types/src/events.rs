@@ -57,6 +57,7 @@ pub enum ChatEventCategory {
 pub enum ChatEventType {
     // Message category
     Message,
+    MessageThreadReply, // Bots subscribed to `Message` receive thread replies too
     MessageEdited,
     MessageReaction,
     MessageTipped,
@@ -104,6 +105,7 @@ impl From<ChatEventType> for ChatEventCategory {
     fn from(value: ChatEventType) -> Self {
         match value {
             ChatEventType::Message
+            | ChatEventType::MessageThreadReply
             | ChatEventType::MessageEdited
             | ChatEventType::MessageReaction
             | ChatEventType::MessageTipped
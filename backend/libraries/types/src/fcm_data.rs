@@ -257,9 +257,10 @@ impl FcmData {
     }
 }
 
-impl From<UserNotificationPayload> for FcmData {
+// Returns None for a digest with no chats, since there is then no chat for the notification to open
+impl From<UserNotificationPayload> for Option<FcmData> {
     fn from(value: UserNotificationPayload) -> Self {
-        match value {
+        let data = match value {
             // Direct Notifications
             UserNotificationPayload::DirectMessage(n) => FcmData::for_direct_chat(n.sender)
                 .set_sender_name(n.sender_display_name, n.sender_name)
@@ -346,6 +347,12 @@ impl From<UserNotificationPayload> for FcmData {
                 .set_sender_name(n.tipped_by_display_name, n.tipped_by_name)
                 .set_thread(n.thread_root_message_index)
                 .set_tip(n.tip),
-        }
+
+            // Digest of the notifications held back during quiet hours, opening the busiest chat
+            UserNotificationPayload::Digest(n) => {
+                FcmData::default(n.chats.first()?.chat).set_message(Some(format!("{} new notifications", n.notification_count)))
+            }
+        };
+        Some(data)
     }
 }
@@ -1,4 +1,4 @@
-use crate::{FileId, Hash, TimestampMillis};
+use crate::{AccessorId, Chat, FileId, Hash, TimestampMillis};
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 use ts_export::ts_export;
@@ -10,6 +10,12 @@ pub struct FileAdded {
     pub hash: Hash,
     pub size: u64,
     pub meta_data: FileMetaData,
+    // These are optional so that files synced by buckets which predate them can still be read
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    #[ts(as = "Option<Vec<ts_export::TSPrincipal>>", optional)]
+    pub accessors: Option<Vec<AccessorId>>,
 }
 
 #[ts_export]
@@ -24,6 +30,29 @@ pub struct FileRemoved {
 pub struct FileMetaData {
     pub owner: Principal,
     pub created: TimestampMillis,
+    // If true the blob is ciphertext which only the file's chats can decrypt (see `FileEncryption`)
+    #[serde(default)]
+    pub encrypted: bool,
+}
+
+// The file's content is encrypted client side using a random content key, which is then wrapped
+// (encrypted) using the key of each chat the file is shared with. Chat keys are derived via
+// `identity::get_encryption_key`, so only members of those chats can unwrap the content key.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct FileEncryption {
+    pub wrapped_keys: Vec<WrappedFileKey>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct WrappedFileKey {
+    pub chat: Chat,
+    // The epoch of the chat key which wrapped this file's key
+    #[serde(default)]
+    pub key_epoch: u32,
+    #[serde(with = "serde_bytes")]
+    pub wrapped_key: Vec<u8>,
 }
 
 #[ts_export]
@@ -26,6 +26,8 @@ pub struct GroupPermissions {
     pub react_to_messages: GroupPermissionRole,
     pub mention_all_members: GroupPermissionRole,
     pub start_video_call: GroupPermissionRole,
+    #[serde(default = "default_export_history_role")]
+    pub export_history: GroupPermissionRole,
     pub message_permissions: MessagePermissions,
     pub thread_permissions: Option<MessagePermissions>,
 }
@@ -68,6 +70,8 @@ pub struct OptionalGroupPermissions {
     pub react_to_messages: Option<GroupPermissionRole>,
     pub mention_all_members: Option<GroupPermissionRole>,
     pub start_video_call: Option<GroupPermissionRole>,
+    #[serde(default)]
+    pub export_history: Option<GroupPermissionRole>,
     pub message_permissions: Option<OptionalMessagePermissions>,
     #[ts(as = "crate::OptionUpdateOptionalMessagePermissions")]
     pub thread_permissions: OptionUpdate<OptionalMessagePermissions>,
@@ -116,12 +120,17 @@ impl Default for GroupPermissions {
             invite_users: GroupPermissionRole::Admins,
             react_to_messages: GroupPermissionRole::Members,
             start_video_call: GroupPermissionRole::Admins,
+            export_history: default_export_history_role(),
             message_permissions: MessagePermissions::default(),
             thread_permissions: None,
         }
     }
 }
 
+fn default_export_history_role() -> GroupPermissionRole {
+    GroupPermissionRole::Admins
+}
+
 impl Default for MessagePermissions {
     fn default() -> Self {
         MessagePermissions {
@@ -183,6 +192,7 @@ pub enum ChatPermission {
     ReadMessages = 10,
     ReadMembership = 11,
     ReadSummary = 12,
+    ExportHistory = 13,
 }
 
 impl From<ChatPermission> for u8 {
@@ -209,6 +219,7 @@ impl TryFrom<u8> for ChatPermission {
             10 => Ok(ChatPermission::ReadMessages),
             11 => Ok(ChatPermission::ReadMembership),
             12 => Ok(ChatPermission::ReadSummary),
+            13 => Ok(ChatPermission::ExportHistory),
             _ => Err(()),
         }
     }
@@ -17,6 +17,7 @@ mod canister_wasm;
 mod channel_id;
 mod channel_summary;
 mod chat;
+mod chat_export;
 mod chat_id;
 mod chat_summary;
 mod chit;
@@ -61,6 +62,7 @@ mod media_scan;
 mod members;
 mod mention;
 mod message;
+mod message_components;
 mod message_content;
 mod message_id;
 mod message_index;
@@ -76,6 +78,7 @@ mod premium_items;
 mod prize_claim_response;
 mod proof_of_uniqueness;
 mod proposals;
+mod quiet_hours;
 mod range_set;
 mod reactions;
 mod referrals;
@@ -87,6 +90,7 @@ mod suspension;
 mod thread_preview;
 mod thread_summary;
 mod timestamped;
+mod translation;
 mod update_user_principal;
 mod user;
 mod user_groups;
@@ -94,6 +98,7 @@ mod user_summary;
 mod version;
 mod versioned;
 mod video_calls;
+mod webhooks;
 
 pub use access_tokens::*;
 pub use achievement::*;
@@ -106,6 +111,7 @@ pub use canister_wasm::*;
 pub use channel_id::*;
 pub use channel_summary::*;
 pub use chat::*;
+pub use chat_export::*;
 pub use chat_id::*;
 pub use chat_summary::*;
 pub use chit::*;
@@ -148,6 +154,7 @@ pub use media_scan::*;
 pub use members::*;
 pub use mention::*;
 pub use message::*;
+pub use message_components::*;
 pub use message_content::*;
 pub use message_id::*;
 pub use message_index::*;
@@ -164,6 +171,7 @@ pub use premium_items::*;
 pub use prize_claim_response::*;
 pub use proof_of_uniqueness::*;
 pub use proposals::*;
+pub use quiet_hours::*;
 pub use range_set::*;
 pub use reactions::*;
 pub use referrals::*;
@@ -175,6 +183,7 @@ pub use suspension::*;
 pub use thread_preview::*;
 pub use thread_summary::*;
 pub use timestamped::*;
+pub use translation::*;
 use ts_export::ts_export;
 pub use update_user_principal::*;
 pub use user::*;
@@ -183,6 +192,7 @@ pub use user_summary::*;
 pub use version::*;
 pub use versioned::*;
 pub use video_calls::*;
+pub use webhooks::*;
 
 pub type AccessorId = Principal;
 pub type CanisterId = Principal;
@@ -1,6 +1,6 @@
 use crate::{
     Achievement, BotCaller, BotCommand, CanisterId, Chat, EventIndex, MessageContent, MessageId, MessageIndex, OgPreview,
-    Reaction, ThreadSummary, UserId, is_default,
+    Reaction, ThreadSummary, TimestampMillis, UserId, is_default,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -74,6 +74,16 @@ impl Message {
     }
 }
 
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MessageRevision {
+    pub content: MessageContent,
+    #[serde(default, skip_serializing_if = "is_default")]
+    #[ts(as = "Option<bool>", optional)]
+    pub block_level_markdown: bool,
+    pub timestamp: TimestampMillis,
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct ReplyContext {
@@ -283,6 +293,7 @@ pub type CustomContentEventPayload = ();
 pub enum SenderContext {
     Bot(BotMessageContext),
     Webhook,
+    Imported(ImportedMessageContext),
 }
 
 impl SenderContext {
@@ -306,3 +317,30 @@ impl BotMessageContext {
         }
     }
 }
+
+// Messages imported from other platforms are all sent by the placeholder imported user, so the
+// original author and timestamp are kept here
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ImportedMessageContext {
+    pub source: ImportSource,
+    pub author_name: String,
+    pub original_timestamp: TimestampMillis,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImportSource {
+    Slack,
+    Discord,
+    Telegram,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ImportedMessage {
+    pub message_id: MessageId,
+    pub thread_root_message_index: Option<MessageIndex>,
+    pub text: String,
+    pub context: ImportedMessageContext,
+}
@@ -0,0 +1,134 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_export::ts_export;
+
+pub const MAX_MESSAGE_COMPONENTS: usize = 10;
+pub const MAX_SELECT_OPTIONS: usize = 25;
+pub const MAX_COMPONENT_ID_LENGTH: usize = 100;
+pub const MAX_COMPONENT_LABEL_LENGTH: usize = 80;
+
+// Interactive elements which bots can attach to their messages. When a user presses a button or
+// picks an option the bot is sent a token identifying the interaction (see `BotInteraction`).
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum MessageComponent {
+    Button(MessageButton),
+    Select(MessageSelect),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MessageButton {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub style: MessageButtonStyle,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MessageButtonStyle {
+    #[default]
+    Primary,
+    Secondary,
+    Danger,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MessageSelect {
+    pub id: String,
+    pub placeholder: Option<String>,
+    pub options: Vec<MessageSelectOption>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MessageSelectOption {
+    pub value: String,
+    pub label: String,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum InvalidComponentsReason {
+    TooManyComponents(u32),
+    TooFewOptions,
+    TooManyOptions(u32),
+    IdTooLong(u32),
+    LabelTooLong(u32),
+    EmptyId,
+    DuplicateIds,
+    DuplicateOptions,
+}
+
+impl MessageComponent {
+    pub fn id(&self) -> &str {
+        match self {
+            MessageComponent::Button(b) => &b.id,
+            MessageComponent::Select(s) => &s.id,
+        }
+    }
+
+    // Buttons don't carry a value, selects must be given one of their options
+    pub fn is_valid_value(&self, value: Option<&str>) -> bool {
+        match self {
+            MessageComponent::Button(_) => value.is_none(),
+            MessageComponent::Select(s) => value.is_some_and(|v| s.options.iter().any(|o| o.value == v)),
+        }
+    }
+
+    fn validate(&self) -> Result<(), InvalidComponentsReason> {
+        let id = self.id();
+        if id.is_empty() {
+            return Err(InvalidComponentsReason::EmptyId);
+        }
+        if id.len() > MAX_COMPONENT_ID_LENGTH {
+            return Err(InvalidComponentsReason::IdTooLong(MAX_COMPONENT_ID_LENGTH as u32));
+        }
+
+        match self {
+            MessageComponent::Button(b) => {
+                if b.label.len() > MAX_COMPONENT_LABEL_LENGTH {
+                    return Err(InvalidComponentsReason::LabelTooLong(MAX_COMPONENT_LABEL_LENGTH as u32));
+                }
+            }
+            MessageComponent::Select(s) => {
+                if s.options.is_empty() {
+                    return Err(InvalidComponentsReason::TooFewOptions);
+                }
+                if s.options.len() > MAX_SELECT_OPTIONS {
+                    return Err(InvalidComponentsReason::TooManyOptions(MAX_SELECT_OPTIONS as u32));
+                }
+                let too_long = s.placeholder.as_ref().is_some_and(|p| p.len() > MAX_COMPONENT_LABEL_LENGTH)
+                    || s.options
+                        .iter()
+                        .any(|o| o.label.len() > MAX_COMPONENT_LABEL_LENGTH || o.value.len() > MAX_COMPONENT_ID_LENGTH);
+                if too_long {
+                    return Err(InvalidComponentsReason::LabelTooLong(MAX_COMPONENT_LABEL_LENGTH as u32));
+                }
+                let mut values = HashSet::new();
+                if s.options.iter().any(|o| !values.insert(&o.value)) {
+                    return Err(InvalidComponentsReason::DuplicateOptions);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn validate_message_components(components: &[MessageComponent]) -> Result<(), InvalidComponentsReason> {
+    if components.len() > MAX_MESSAGE_COMPONENTS {
+        return Err(InvalidComponentsReason::TooManyComponents(MAX_MESSAGE_COMPONENTS as u32));
+    }
+
+    let mut ids = HashSet::new();
+    for component in components {
+        component.validate()?;
+        if !ids.insert(component.id()) {
+            return Err(InvalidComponentsReason::DuplicateIds);
+        }
+    }
+    Ok(())
+}
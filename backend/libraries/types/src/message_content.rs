@@ -1,8 +1,9 @@
 use crate::polls::{InvalidPollReason, PollConfig, PollVotes};
 use crate::{
     Achievement, CanisterId, Chat, CompletedCryptoTransaction, CryptoTransaction, CryptoTransferDetails, EncryptionKey,
-    MessageId, MessageIndex, MessagePermission, Milliseconds, ModerationInput, P2PSwapStatus, PendingCryptoTransaction,
-    ProposalContent, TimestampMillis, TokenInfo, TotalVotes, User, UserId, VideoCallType,
+    InvalidComponentsReason, MessageComponent, MessageId, MessageIndex, MessagePermission, Milliseconds, ModerationInput,
+    P2PSwapStatus, PendingCryptoTransaction, ProposalContent, TimestampMillis, TokenInfo, TotalVotes, User, UserId,
+    VideoCallType,
 };
 use candid::CandidType;
 use oc_error_codes::{OCError, OCErrorCode};
@@ -124,6 +125,7 @@ pub enum ContentValidationError {
     InvalidTypeForForwarding,
     PrizeEndDateInThePast,
     Unauthorized,
+    InvalidComponents(InvalidComponentsReason),
 }
 
 impl From<ContentValidationError> for OCError {
@@ -594,11 +596,18 @@ impl From<&MessageContent> for MessageContentType {
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct TextContent {
     pub text: String,
+    // Buttons and select menus, only bots can attach these to their messages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub components: Option<Vec<MessageComponent>>,
 }
 
 impl From<String> for TextContent {
     fn from(value: String) -> Self {
-        TextContent { text: value }
+        TextContent {
+            text: value,
+            components: None,
+        }
     }
 }
 
@@ -689,6 +698,8 @@ impl PollContent {
         let total_votes: TotalVotes;
         if self.config.end_date.is_some() && !self.config.show_votes_before_end_date {
             total_votes = TotalVotes::Hidden(0);
+        } else if self.config.vote_weighting.is_some() {
+            total_votes = TotalVotes::Weighted(HashMap::new());
         } else if self.config.anonymous {
             total_votes = TotalVotes::Anonymous(HashMap::new());
         } else {
@@ -698,6 +709,7 @@ impl PollContent {
         self.votes = PollVotes {
             total: total_votes,
             user: Vec::new(),
+            result: None,
         }
     }
 }
@@ -708,6 +720,7 @@ pub enum RegisterVoteResult {
     PollEnded,
     UserCannotChangeVote,
     OptionIndexOutOfRange,
+    NoVotingWeight,
 }
 
 #[ts_export]
@@ -950,6 +963,20 @@ impl BlobReference {
     pub fn url(&self) -> String {
         format!("https://{}.raw.icp0.io/files/{}", self.canister_id, self.blob_id)
     }
+
+    // The inverse of `url`, also accepting URLs which aren't on the `raw` subdomain. Returns None
+    // for any URL which isn't served directly by a canister on the IC.
+    pub fn from_url(url: &str) -> Option<BlobReference> {
+        let (host, path) = url.strip_prefix("https://")?.split_once('/')?;
+        let (canister_id, domain) = host.split_once('.')?;
+        if !matches!(domain.strip_prefix("raw.").unwrap_or(domain), "icp0.io" | "ic0.app") {
+            return None;
+        }
+        let canister_id = CanisterId::from_text(canister_id).ok()?;
+        let blob_id = path.strip_prefix("files/")?.trim_end_matches('/').parse().ok()?;
+
+        Some(BlobReference { canister_id, blob_id })
+    }
 }
 
 #[ts_export]
@@ -8,4 +8,8 @@ use ts_export::ts_export;
 pub struct MessageMatch {
     pub message_index: MessageIndex,
     pub score: u32,
+    // The (start, end) character offsets within the message text of each part which matched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(as = "Option<Vec<(u32, u32)>>", optional)]
+    pub highlights: Vec<(u32, u32)>,
 }
@@ -90,6 +90,24 @@ pub struct BotChatEvent {
     pub latest_event_index: EventIndex,
 }
 
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BotEventsSinceResult {
+    pub events: Vec<BotReplayedChatEvent>,
+    // Pass as `since` to fetch the next batch, then acknowledge it once the events have been processed
+    pub cursor: TimestampMillis,
+    pub complete: bool,
+    // Some events since the requested cursor are no longer retained so could not be replayed
+    pub events_pruned: bool,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BotReplayedChatEvent {
+    pub event: BotChatEvent,
+    pub timestamp: TimestampMillis,
+}
+
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct BotCommunityEvent {
@@ -243,6 +261,74 @@ pub enum UserNotificationPayload {
     #[subenum(ChannelUserNotificationPayload)]
     #[serde(rename = "ct")]
     ChannelMessageTipped(ChannelMessageTipped),
+    #[serde(rename = "dg")]
+    Digest(NotificationDigest),
+}
+
+impl UserNotificationPayload {
+    // The chat the notification relates to along with the name to show for it in a digest
+    pub fn chat_and_name(&self) -> Option<(Chat, String)> {
+        fn channel_name(community_name: &str, channel_name: &str) -> String {
+            format!("{community_name} / {channel_name}")
+        }
+
+        match self {
+            UserNotificationPayload::AddedToChannel(n) => Some((
+                Chat::Channel(n.community_id, n.channel_id),
+                channel_name(&n.community_name, &n.channel_name),
+            )),
+            UserNotificationPayload::DirectMessage(n) => Some((
+                Chat::Direct(n.sender.into()),
+                n.sender_display_name.clone().unwrap_or_else(|| n.sender_name.clone()),
+            )),
+            UserNotificationPayload::GroupMessage(n) => Some((Chat::Group(n.chat_id), n.group_name.clone())),
+            UserNotificationPayload::ChannelMessage(n) => Some((
+                Chat::Channel(n.community_id, n.channel_id),
+                channel_name(&n.community_name, &n.channel_name),
+            )),
+            UserNotificationPayload::DirectReactionAdded(n) => Some((
+                Chat::Direct(n.them.into()),
+                n.display_name.clone().unwrap_or_else(|| n.username.clone()),
+            )),
+            UserNotificationPayload::GroupReactionAdded(n) => Some((Chat::Group(n.chat_id), n.group_name.clone())),
+            UserNotificationPayload::ChannelReactionAdded(n) => Some((
+                Chat::Channel(n.community_id, n.channel_id),
+                channel_name(&n.community_name, &n.channel_name),
+            )),
+            UserNotificationPayload::DirectMessageTipped(n) => Some((
+                Chat::Direct(n.them.into()),
+                n.display_name.clone().unwrap_or_else(|| n.username.clone()),
+            )),
+            UserNotificationPayload::GroupMessageTipped(n) => Some((Chat::Group(n.chat_id), n.group_name.clone())),
+            UserNotificationPayload::ChannelMessageTipped(n) => Some((
+                Chat::Channel(n.community_id, n.channel_id),
+                channel_name(&n.community_name, &n.channel_name),
+            )),
+            UserNotificationPayload::Digest(_) => None,
+        }
+    }
+}
+
+// Summarises the notifications which were held back during the user's quiet hours. Chats are
+// ordered by the number of notifications, most first, and always contains at least one chat.
+#[ts_export]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NotificationDigest {
+    #[serde(rename = "n")]
+    pub notification_count: u32,
+    #[serde(rename = "c")]
+    pub chats: Vec<NotificationDigestChat>,
+}
+
+#[ts_export]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NotificationDigestChat {
+    #[serde(rename = "c")]
+    pub chat: Chat,
+    #[serde(rename = "n")]
+    pub name: String,
+    #[serde(rename = "t")]
+    pub notification_count: u32,
 }
 
 #[ts_export]
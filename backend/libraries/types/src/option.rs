@@ -101,11 +101,13 @@ option_update!(OptionUpdateU128, u128);
 option_update!(OptionUpdateAccessGate, crate::AccessGate);
 option_update!(OptionUpdateAccessGateConfig, crate::AccessGateConfig);
 option_update!(OptionUpdateAirdropConfig, crate::AirdropConfig);
+option_update!(OptionUpdateBotChannelPermissions, Vec<crate::BotChannelPermissions>);
 option_update!(OptionUpdateDocument, crate::Document);
 option_update!(OptionUpdateFrozenGroupInfo, crate::FrozenGroupInfo);
 option_update!(OptionUpdateGroupPermissionRole, crate::GroupPermissionRole);
 option_update!(OptionUpdateGroupSubtype, crate::GroupSubtype);
 option_update!(OptionUpdateOptionalMessagePermissions, crate::OptionalMessagePermissions);
 option_update!(OptionUpdatePinNumberSettings, crate::PinNumberSettings);
+option_update!(OptionUpdateQuietHours, crate::QuietHours);
 option_update!(OptionUpdateStreakInsurance, crate::StreakInsurance);
 option_update!(OptionUpdateVideoCall, crate::VideoCall);
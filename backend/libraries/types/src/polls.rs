@@ -1,4 +1,4 @@
-use crate::{TimestampMillis, UserId};
+use crate::{CanisterId, GroupRole, TimestampMillis, UserId};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -14,13 +14,106 @@ pub struct PollConfig {
     pub show_votes_before_end_date: bool,
     pub allow_multiple_votes_per_user: bool,
     pub allow_user_to_change_vote: bool,
+    #[serde(default)]
+    pub voting_system: PollVotingSystem,
+    #[serde(default)]
+    pub vote_weighting: Option<PollVoteWeighting>,
+    // The minimum number of users who must vote for a result to be declared when the poll ends
+    #[serde(default)]
+    pub quorum: Option<u32>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum PollVotingSystem {
+    #[default]
+    Plurality,
+    // Each vote registered by a user adds the option as their next preference. When the poll ends
+    // the result is decided by instant runoff.
+    RankedChoice,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum PollVoteWeighting {
+    Role(RoleVoteWeights),
+    TokenBalance(TokenBalanceVoteWeighting),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RoleVoteWeights {
+    pub owner: u32,
+    pub admin: u32,
+    pub moderator: u32,
+    pub member: u32,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TokenBalanceVoteWeighting {
+    pub ledger_canister_id: CanisterId,
+    // If true, each vote is weighted by the square root of the user's balance
+    pub quadratic: bool,
+}
+
+impl PollVoteWeighting {
+    pub fn weight(&self, role: GroupRole, token_balance: Option<u128>) -> u128 {
+        match self {
+            PollVoteWeighting::Role(weights) => match role {
+                GroupRole::Owner => weights.owner,
+                GroupRole::Admin => weights.admin,
+                GroupRole::Moderator => weights.moderator,
+                GroupRole::Participant => weights.member,
+            }
+            .into(),
+            PollVoteWeighting::TokenBalance(weighting) => {
+                let balance = token_balance.unwrap_or_default();
+                if weighting.quadratic { balance.isqrt() } else { balance }
+            }
+        }
+    }
+
+    pub fn ledger_canister_id(&self) -> Option<CanisterId> {
+        if let PollVoteWeighting::TokenBalance(weighting) = self {
+            Some(weighting.ledger_canister_id)
+        } else {
+            None
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            PollVoteWeighting::Role(w) => {
+                let weights = [w.owner, w.admin, w.moderator, w.member];
+                weights.iter().any(|w| *w > 0) && weights.iter().all(|w| *w <= MAX_ROLE_VOTE_WEIGHT)
+            }
+            PollVoteWeighting::TokenBalance(_) => true,
+        }
+    }
 }
 
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct PollVotes {
     pub total: TotalVotes,
+    // For ranked-choice polls these are in order of preference
     pub user: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub result: Option<PollResult>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PollResult {
+    pub voters: u32,
+    pub quorum_reached: bool,
+    // Empty if the quorum wasn't reached, contains multiple options if there was a tie
+    pub winners: Vec<u32>,
+    // The (weighted) votes for each option in each round. Plurality polls have a single round,
+    // ranked-choice polls have one round per instant runoff elimination.
+    pub rounds: Vec<HashMap<u32, u128>>,
 }
 
 impl PollConfig {
@@ -38,6 +131,10 @@ impl PollConfig {
             Err(InvalidPollReason::DuplicateOptions)
         } else if self.end_date.unwrap_or(u64::MAX) < now {
             Err(InvalidPollReason::EndDateInThePast)
+        } else if self.end_date.is_none() && (self.voting_system == PollVotingSystem::RankedChoice || self.quorum.is_some()) {
+            Err(InvalidPollReason::EndDateRequired)
+        } else if self.quorum == Some(0) || self.vote_weighting.as_ref().is_some_and(|w| !w.is_valid()) {
+            Err(InvalidPollReason::InvalidVoteWeighting)
         } else {
             Ok(())
         }
@@ -55,6 +152,9 @@ pub enum TotalVotes {
     Visible(HashMap<u32, Vec<UserId>>),
     Anonymous(HashMap<u32, u32>),
     Hidden(u32),
+    // The total weight of the votes for each option, used by weighted polls. For ranked-choice
+    // polls only first preferences are included.
+    Weighted(HashMap<u32, u128>),
 }
 
 #[ts_export]
@@ -67,6 +167,7 @@ pub enum VoteOperation {
 const MIN_POLL_OPTIONS: usize = 2;
 const MAX_POLL_OPTIONS: usize = 10;
 const MAX_POLL_OPTION_LENGTH: usize = 100;
+const MAX_ROLE_VOTE_WEIGHT: u32 = 1000;
 
 #[ts_export]
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -77,4 +178,6 @@ pub enum InvalidPollReason {
     DuplicateOptions,
     EndDateInThePast,
     PollsNotValidForDirectChats,
+    EndDateRequired,
+    InvalidVoteWeighting,
 }
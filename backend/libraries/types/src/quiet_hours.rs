@@ -0,0 +1,119 @@
+use crate::{Milliseconds, TimestampMillis};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use ts_export::ts_export;
+
+const MINUTE_IN_MS: Milliseconds = 60 * 1000;
+const DAY_IN_MS: Milliseconds = 24 * 60 * MINUTE_IN_MS;
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+// A daily window, in the user's local time, during which notifications are held back. If `digest`
+// is set then the notifications held back are summarised in a single notification which is sent
+// when the window ends, otherwise they are dropped.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuietHours {
+    // Minutes after midnight in the user's local time
+    pub start: u16,
+    pub end: u16,
+    pub digest: bool,
+}
+
+// The user's quiet hours along with their UTC offset, as synced to each local user index
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UserQuietHours {
+    #[serde(rename = "q")]
+    pub quiet_hours: QuietHours,
+    #[serde(rename = "o")]
+    pub utc_offset_mins: i16,
+}
+
+impl QuietHours {
+    pub fn is_valid(&self) -> bool {
+        self.start < MINUTES_PER_DAY && self.end < MINUTES_PER_DAY && self.start != self.end
+    }
+
+    // If `now` falls within the quiet window, returns the time at which the window ends
+    pub fn window_end(&self, now: TimestampMillis, utc_offset_mins: i16) -> Option<TimestampMillis> {
+        let offset_ms = utc_offset_mins as i64 * MINUTE_IN_MS as i64;
+        let local_ms = now as i64 + offset_ms;
+        let ms_into_day = local_ms.rem_euclid(DAY_IN_MS as i64);
+        let minute_of_day = (ms_into_day / MINUTE_IN_MS as i64) as u16;
+
+        let in_window = if self.start < self.end {
+            minute_of_day >= self.start && minute_of_day < self.end
+        } else {
+            // The window spans midnight
+            minute_of_day >= self.start || minute_of_day < self.end
+        };
+
+        if !in_window {
+            return None;
+        }
+
+        let mut end_local = local_ms - ms_into_day + self.end as i64 * MINUTE_IN_MS as i64;
+        if end_local <= local_ms {
+            end_local += DAY_IN_MS as i64;
+        }
+        Some((end_local - offset_ms) as TimestampMillis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_IN_MS: Milliseconds = 60 * MINUTE_IN_MS;
+    // 2024-01-01T00:00:00Z
+    const MIDNIGHT_UTC: TimestampMillis = 1_704_067_200_000;
+
+    #[test]
+    fn window_within_a_single_day() {
+        let quiet_hours = QuietHours {
+            start: 13 * 60,
+            end: 14 * 60,
+            digest: false,
+        };
+
+        assert_eq!(quiet_hours.window_end(MIDNIGHT_UTC + 12 * HOUR_IN_MS, 0), None);
+        assert_eq!(
+            quiet_hours.window_end(MIDNIGHT_UTC + 13 * HOUR_IN_MS, 0),
+            Some(MIDNIGHT_UTC + 14 * HOUR_IN_MS)
+        );
+        assert_eq!(quiet_hours.window_end(MIDNIGHT_UTC + 14 * HOUR_IN_MS, 0), None);
+    }
+
+    #[test]
+    fn window_spanning_midnight() {
+        let quiet_hours = QuietHours {
+            start: 22 * 60,
+            end: 7 * 60,
+            digest: true,
+        };
+
+        let next_morning = MIDNIGHT_UTC + DAY_IN_MS + 7 * HOUR_IN_MS;
+        assert_eq!(quiet_hours.window_end(MIDNIGHT_UTC + 23 * HOUR_IN_MS, 0), Some(next_morning));
+        assert_eq!(
+            quiet_hours.window_end(MIDNIGHT_UTC + DAY_IN_MS + 2 * HOUR_IN_MS, 0),
+            Some(next_morning)
+        );
+        assert_eq!(quiet_hours.window_end(MIDNIGHT_UTC + 12 * HOUR_IN_MS, 0), None);
+    }
+
+    #[test]
+    fn window_respects_utc_offset() {
+        let quiet_hours = QuietHours {
+            start: 22 * 60,
+            end: 7 * 60,
+            digest: false,
+        };
+
+        // 20:00 UTC is 23:00 at UTC+3, so within the window, which ends at 04:00 UTC
+        assert_eq!(
+            quiet_hours.window_end(MIDNIGHT_UTC + 20 * HOUR_IN_MS, 180),
+            Some(MIDNIGHT_UTC + DAY_IN_MS + 4 * HOUR_IN_MS)
+        );
+        // 20:00 UTC is 15:00 at UTC-5, so outside the window
+        assert_eq!(quiet_hours.window_end(MIDNIGHT_UTC + 20 * HOUR_IN_MS, -300), None);
+    }
+}
@@ -8,6 +8,30 @@ use ts_export::ts_export;
 pub enum NotificationSubscription {
     WebPush(SubscriptionInfo),
     FcmPush(FcmToken),
+    UnifiedPush(SubscriptionInfo),
+    HttpsPush(HttpsPushSubscription),
+}
+
+// Determines how notifications are delivered to an endpoint based subscription. In each case the
+// payload is encrypted for the subscription's keys (RFC 8291), the backends only differ in how the
+// request to the endpoint is authenticated.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub enum PushBackend {
+    // Standard web push, authenticated using VAPID
+    #[default]
+    WebPush,
+    // UnifiedPush distributors (eg. ntfy), which don't require VAPID
+    UnifiedPush,
+    // A generic HTTPS endpoint, each request is signed with an HMAC-SHA256 using this shared secret
+    HttpsPush(String),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct HttpsPushSubscription {
+    pub subscription: SubscriptionInfo,
+    pub secret: String,
 }
 
 #[ts_export]
@@ -24,6 +48,45 @@ pub struct SubscriptionKeys {
     pub auth: String,
 }
 
+impl NotificationSubscription {
+    pub fn new(subscription: SubscriptionInfo, backend: PushBackend) -> NotificationSubscription {
+        match backend {
+            PushBackend::WebPush => NotificationSubscription::WebPush(subscription),
+            PushBackend::UnifiedPush => NotificationSubscription::UnifiedPush(subscription),
+            PushBackend::HttpsPush(secret) => {
+                NotificationSubscription::HttpsPush(HttpsPushSubscription { subscription, secret })
+            }
+        }
+    }
+
+    pub fn approx_size(&self) -> usize {
+        match self {
+            NotificationSubscription::WebPush(s) | NotificationSubscription::UnifiedPush(s) => s.approx_size(),
+            NotificationSubscription::FcmPush(t) => t.0.len() + 24,
+            NotificationSubscription::HttpsPush(s) => s.subscription.approx_size() + s.secret.len() + 24,
+        }
+    }
+}
+
+impl PushBackend {
+    const MIN_SECRET_LENGTH: usize = 32;
+    const MAX_SECRET_LENGTH: usize = 256;
+
+    // HTTPS push secrets must be long enough to resist guessing, and are limited to the characters
+    // used by hex / base64 / base64url encodings so that receivers can store them easily
+    pub fn is_valid(&self) -> bool {
+        match self {
+            PushBackend::WebPush | PushBackend::UnifiedPush => true,
+            PushBackend::HttpsPush(secret) => {
+                (Self::MIN_SECRET_LENGTH..=Self::MAX_SECRET_LENGTH).contains(&secret.len())
+                    && secret
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=' | b'-' | b'_'))
+            }
+        }
+    }
+}
+
 impl SubscriptionInfo {
     pub fn approx_size(&self) -> usize {
         self.endpoint.len() + self.keys.approx_size() + 24
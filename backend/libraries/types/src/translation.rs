@@ -0,0 +1,94 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use ts_export::ts_export;
+
+// The HTTPS API used to translate message text. Each provider is called from the local user
+// indexes, which hold the API key, so the key never needs to be pushed down to the group,
+// community and user canisters which request the translations.
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum TranslationProvider {
+    DeepL,
+    Google,
+    LibreTranslate(String),
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct TranslationProviderConfig {
+    pub provider: TranslationProvider,
+    pub api_key: String,
+}
+
+// The API key must never end up in the logs
+impl Debug for TranslationProviderConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranslationProviderConfig")
+            .field("provider", &self.provider)
+            .field("api_key", &"<redacted>")
+            .finish()
+    }
+}
+
+// Accepts language codes such as "en", "pt-BR" or "zh-Hant", in any case and with either '-' or '_'
+// separators, and returns them in their canonical form (eg. "EN" -> "en", "en_us" -> "en-US") so
+// that equivalent codes share the same cached translation. Returns None if the code is invalid.
+pub fn normalize_language_code(language: &str) -> Option<String> {
+    if !(2..=10).contains(&language.len()) {
+        return None;
+    }
+
+    let mut subtags = language.split(['-', '_']);
+    let primary = subtags.next()?;
+    if !(2..=3).contains(&primary.len()) || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut normalized = primary.to_ascii_lowercase();
+    for subtag in subtags {
+        if subtag.is_empty() || !subtag.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        normalized.push('-');
+        if subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+            // Script, eg. "Hant"
+            normalized.push_str(&subtag[..1].to_ascii_uppercase());
+            normalized.push_str(&subtag[1..].to_ascii_lowercase());
+        } else if subtag.len() == 2 || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit())) {
+            // Region, eg. "BR" or "419"
+            normalized.push_str(&subtag.to_ascii_uppercase());
+        } else {
+            normalized.push_str(&subtag.to_ascii_lowercase());
+        }
+    }
+
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_language_codes_normalized() {
+        for (language, expected) in [
+            ("en", "en"),
+            ("EN", "en"),
+            ("en-us", "en-US"),
+            ("en_US", "en-US"),
+            ("PT-br", "pt-BR"),
+            ("zh-hant", "zh-Hant"),
+            ("es-419", "es-419"),
+        ] {
+            assert_eq!(normalize_language_code(language).as_deref(), Some(expected), "{language}");
+        }
+    }
+
+    #[test]
+    fn invalid_language_codes_rejected() {
+        for language in ["", "e", "not a language", "en-", "en--US", "1n", "english"] {
+            assert!(normalize_language_code(language).is_none(), "{language}");
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{
+    BlobReference, ChatEventType, EventIndex, FileContent, GroupReplyContext, ImageContent, MessageContentInitial,
+    MessageIndex, OCResult, TextContent, ThumbnailData, TimestampMillis, User, UserId,
+};
+use candid::CandidType;
+use oc_error_codes::OCErrorCode;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use std::collections::HashSet;
+use ts_export::ts_export;
+
+// The payload which can be posted to a webhook. A plain text body is treated as `text` with every
+// other field left empty, so existing integrations continue to work unchanged.
+//
+// Either provide `text` and optionally an `attachment` (in which case `text` becomes the caption),
+// or provide the full message `content` (eg. to send a poll or custom content).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct WebhookMessage {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub attachment: Option<WebhookAttachment>,
+    #[serde(default)]
+    pub content: Option<MessageContentInitial>,
+    #[serde(default)]
+    pub thread_root_message_index: Option<MessageIndex>,
+    #[serde(default)]
+    pub replies_to: Option<EventIndex>,
+    #[serde(default)]
+    pub mentioned: Vec<User>,
+    #[serde(default)]
+    pub block_level_markdown: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WebhookAttachment {
+    pub kind: WebhookAttachmentKind,
+    // Either a `url` pointing at a file stored in an OpenChat storage bucket, or the `blob_reference`
+    // itself. Files hosted elsewhere must be uploaded to OpenChat first, or linked to from `text`.
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub blob_reference: Option<BlobReference>,
+    pub mime_type: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub file_size: u32,
+    #[serde(default)]
+    pub width: u32,
+    #[serde(default)]
+    pub height: u32,
+    #[serde(default)]
+    pub thumbnail_data: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookAttachmentKind {
+    Image,
+    File,
+}
+
+impl WebhookMessage {
+    pub fn replies_to(&self) -> Option<GroupReplyContext> {
+        self.replies_to.map(|event_index| GroupReplyContext { event_index })
+    }
+
+    pub fn block_level_markdown(&self) -> bool {
+        self.block_level_markdown.unwrap_or(true)
+    }
+
+    // Builds the message content from the payload. The content is then validated by the regular
+    // `send_message` flow in the same way as for any other message.
+    pub fn take_content(&mut self) -> OCResult<MessageContentInitial> {
+        let text = self.text.take();
+
+        if let Some(content) = self.content.take() {
+            return if text.is_some() || self.attachment.is_some() {
+                Err(OCErrorCode::InvalidRequest.with_message("`content` can't be combined with `text` or `attachment`"))
+            } else {
+                Ok(content)
+            };
+        }
+
+        if let Some(attachment) = self.attachment.take() {
+            let blob_reference = match (attachment.blob_reference, attachment.url) {
+                (Some(blob_reference), _) => blob_reference,
+                (None, Some(url)) => BlobReference::from_url(&url).ok_or_else(|| {
+                    OCErrorCode::InvalidRequest.with_message(format!(
+                        "Attachment URL must be the URL of a file stored in OpenChat (https://<bucket>.raw.icp0.io/files/<file id>), \
+                        external URLs aren't supported so either upload the file to OpenChat first or include the link in `text`: {url}"
+                    ))
+                })?,
+                (None, None) => {
+                    return Err(OCErrorCode::InvalidRequest.with_message("Attachment must have a `url` or `blob_reference`"));
+                }
+            };
+
+            return Ok(match attachment.kind {
+                WebhookAttachmentKind::Image => MessageContentInitial::Image(ImageContent {
+                    width: attachment.width,
+                    height: attachment.height,
+                    thumbnail_data: ThumbnailData(attachment.thumbnail_data.unwrap_or_default()),
+                    caption: text,
+                    mime_type: attachment.mime_type,
+                    blob_reference: Some(blob_reference),
+                }),
+                WebhookAttachmentKind::File => MessageContentInitial::File(FileContent {
+                    name: attachment.name.unwrap_or_else(|| blob_reference.blob_id.to_string()),
+                    caption: text,
+                    mime_type: attachment.mime_type,
+                    file_size: attachment.file_size,
+                    blob_reference: Some(blob_reference),
+                }),
+            });
+        }
+
+        match text {
+            Some(text) => Ok(MessageContentInitial::Text(TextContent { text, components: None })),
+            None => Err(OCErrorCode::InvalidRequest.with_message("Message is empty")),
+        }
+    }
+}
+
+impl From<String> for WebhookMessage {
+    fn from(text: String) -> Self {
+        WebhookMessage {
+            text: Some(text),
+            ..Default::default()
+        }
+    }
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WebhookRateLimits {
+    pub per_minute: Option<u32>,
+    pub per_hour: Option<u32>,
+}
+
+impl WebhookRateLimits {
+    pub fn is_valid(&self) -> bool {
+        self.per_minute != Some(0) && self.per_hour != Some(0)
+    }
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct WebhookMetrics {
+    pub messages_sent: u64,
+    pub messages_rejected: u64,
+    pub last_used: Option<TimestampMillis>,
+}
+
+// When a webhook requires signed requests, the caller must include a timestamp, a nonce which is
+// unique within the signature validity window, and the hex encoded HMAC-SHA256 (keyed with the
+// webhook secret) of `{timestamp}.{nonce}.{body}`. This means the secret itself never needs to
+// appear in the webhook URL, and a captured request can't be replayed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WebhookSignature {
+    pub timestamp: TimestampMillis,
+    pub nonce: String,
+    pub signature: String,
+    pub body: ByteBuf,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct OutgoingWebhookDetails {
+    pub id: UserId,
+    pub name: String,
+    pub url: String,
+    pub event_types: HashSet<ChatEventType>,
+    pub enabled: bool,
+    pub consecutive_failures: u32,
+    pub deliveries: Vec<OutgoingWebhookDelivery>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct OutgoingWebhookDelivery {
+    pub id: u64,
+    pub event_index: EventIndex,
+    pub thread: Option<MessageIndex>,
+    pub queued: TimestampMillis,
+    pub attempts: u32,
+    pub last_attempt: Option<TimestampMillis>,
+    pub status: OutgoingWebhookDeliveryStatus,
+    pub response_status: Option<u32>,
+    pub error: Option<String>,
+}
+
+#[ts_export]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutgoingWebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CanisterId;
+
+    #[test]
+    fn attachment_url_of_openchat_file_is_accepted() {
+        let blob_reference = BlobReference {
+            canister_id: CanisterId::from_text("rturd-qaaaa-aaaaf-aabaq-cai").unwrap(),
+            blob_id: 123,
+        };
+
+        for url in [
+            blob_reference.url(),
+            "https://rturd-qaaaa-aaaaf-aabaq-cai.icp0.io/files/123".to_string(),
+            "https://rturd-qaaaa-aaaaf-aabaq-cai.raw.ic0.app/files/123/".to_string(),
+        ] {
+            let mut message = attachment_message(url);
+            let Ok(MessageContentInitial::Image(content)) = message.take_content() else {
+                panic!();
+            };
+            assert_eq!(content.blob_reference, Some(blob_reference.clone()));
+            assert_eq!(content.caption.as_deref(), Some("caption"));
+        }
+    }
+
+    #[test]
+    fn attachment_url_of_external_file_is_rejected() {
+        for url in [
+            "https://example.com/files/123",
+            "https://rturd-qaaaa-aaaaf-aabaq-cai.example.com/files/123",
+            "http://rturd-qaaaa-aaaaf-aabaq-cai.raw.icp0.io/files/123",
+            "https://rturd-qaaaa-aaaaf-aabaq-cai.raw.icp0.io/images/123",
+        ] {
+            let mut message = attachment_message(url.to_string());
+            let error = message.take_content().unwrap_err();
+            assert!(error.matches_code(OCErrorCode::InvalidRequest));
+            assert!(error.message().unwrap().contains("external URLs aren't supported"));
+        }
+    }
+
+    fn attachment_message(url: String) -> WebhookMessage {
+        WebhookMessage {
+            text: Some("caption".to_string()),
+            attachment: Some(WebhookAttachment {
+                kind: WebhookAttachmentKind::Image,
+                url: Some(url),
+                blob_reference: None,
+                mime_type: "image/png".to_string(),
+                name: None,
+                file_size: 0,
+                width: 100,
+                height: 100,
+                thumbnail_data: None,
+            }),
+            ..Default::default()
+        }
+    }
+}
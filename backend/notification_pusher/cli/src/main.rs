@@ -1,9 +1,10 @@
-use index_store::DummyStore;
+use async_trait::async_trait;
+use index_store::{DummyStore, FileStore, IndexStore};
 use notification_pusher_core::config::Config;
 use notification_pusher_core::{run_notifications_pusher, write_metrics};
 use tokio::time;
 use tracing::info;
-use types::Error;
+use types::{CanisterId, Error};
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
@@ -11,7 +12,16 @@ async fn main() -> Result<(), Error> {
 
     info!("Initializing notification pusher");
 
-    let config = Config::init_with_store(DummyStore::default()).await?;
+    let config = Config::init(|env_config| {
+        Ok(match &env_config.index_store_path {
+            Some(path) => {
+                info!(path, "Using file index store");
+                CliIndexStore::File(FileStore::open(path)?)
+            }
+            None => CliIndexStore::InMemory(DummyStore::default()),
+        })
+    })
+    .await?;
 
     info!("Initialization complete");
 
@@ -34,3 +44,26 @@ async fn write_metrics_to_file() {
         std::fs::write("metrics.md", bytes).unwrap();
     }
 }
+
+#[derive(Clone)]
+enum CliIndexStore {
+    File(FileStore),
+    InMemory(DummyStore),
+}
+
+#[async_trait]
+impl IndexStore for CliIndexStore {
+    async fn get(&self, canister_id: CanisterId) -> Result<Option<u64>, Error> {
+        match self {
+            CliIndexStore::File(store) => store.get(canister_id).await,
+            CliIndexStore::InMemory(store) => store.get(canister_id).await,
+        }
+    }
+
+    async fn set(&self, canister_id: CanisterId, index: u64) -> Result<(), Error> {
+        match self {
+            CliIndexStore::File(store) => store.set(canister_id, index).await,
+            CliIndexStore::InMemory(store) => store.set(canister_id, index).await,
+        }
+    }
+}
@@ -28,6 +28,11 @@ pub struct EnvConfig {
 
     #[envconfig(from = "GCLOUD_SA_JSON_PATH")]
     pub gcloud_sa_json_path: String,
+
+    // Used by the CLI to persist the processed notification indexes to a file. If not set, the
+    // indexes are only held in memory and are lost on restart.
+    #[envconfig(from = "INDEX_STORE_PATH")]
+    pub index_store_path: Option<String>,
 }
 
 pub struct Config<I> {
@@ -42,6 +47,10 @@ pub struct Config<I> {
 
 impl<I: IndexStore + 'static> Config<I> {
     pub async fn init_with_store(index_store: I) -> Result<Self, Error> {
+        Self::init(|_| Ok(index_store)).await
+    }
+
+    pub async fn init<F: FnOnce(&EnvConfig) -> Result<I, Error>>(build_index_store: F) -> Result<Self, Error> {
         // Load environment variables from .env file
         dotenv::dotenv()?;
 
@@ -50,6 +59,7 @@ impl<I: IndexStore + 'static> Config<I> {
 
         // Initialize the IC agent and index store
         let ic_agent = IcAgent::build(&env_config.ic_url, &env_config.ic_identity_pem, !env_config.is_production).await?;
+        let index_store = build_index_store(&env_config)?;
 
         Ok(Self {
             ic_agent,
@@ -9,7 +9,7 @@ use std::io::Write;
 use std::sync::Arc;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tracing::info;
-use types::{BotEventPayload, CanisterId, FcmData, FcmToken, SubscriptionInfo, TimestampMillis, UserId};
+use types::{BotEventPayload, CanisterId, FcmData, FcmToken, PushBackend, SubscriptionInfo, TimestampMillis, UserId};
 use web_push::WebPushMessage;
 
 mod bot_notifications;
@@ -83,6 +83,7 @@ pub struct FcmNotification {
 pub struct UserNotification {
     payload: Arc<Vec<u8>>,
     subscription_info: SubscriptionInfo,
+    backend: PushBackend,
     metadata: NotificationMetadata,
 }
 
@@ -1,6 +1,6 @@
 use prometheus::core::Collector;
 use prometheus::proto::MetricFamily;
-use prometheus::{HistogramOpts, HistogramVec, IntGaugeVec, Opts, Registry};
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry};
 use std::sync::OnceLock;
 use types::{CanisterId, Milliseconds};
 
@@ -18,6 +18,8 @@ pub struct Metrics {
     processing_duration_ms: HistogramVec,
     http_post_notification_duration_ms: HistogramVec,
     notification_payload_sizes: HistogramVec,
+    user_notifications_pushed: IntCounterVec,
+    user_notification_push_duration_ms: HistogramVec,
 }
 
 static METRICS: OnceLock<Metrics> = OnceLock::new();
@@ -94,6 +96,19 @@ impl Metrics {
         )
         .unwrap();
 
+        let user_notifications_pushed = IntCounterVec::new(
+            Opts::new("user_notifications_pushed", "Per push backend"),
+            &["backend", "success"],
+        )
+        .unwrap();
+
+        let user_notification_push_duration_ms = HistogramVec::new(
+            HistogramOpts::new("user_notification_push_duration", "In milliseconds. Per push backend")
+                .buckets(calc_buckets(10.0)),
+            &["backend"],
+        )
+        .unwrap();
+
         registry.register(Box::new(latest_notification_index_read.clone())).unwrap();
         registry
             .register(Box::new(latest_notification_index_processed.clone()))
@@ -106,6 +121,10 @@ impl Metrics {
             .register(Box::new(http_post_notification_duration_ms.clone()))
             .unwrap();
         registry.register(Box::new(notification_payload_sizes.clone())).unwrap();
+        registry.register(Box::new(user_notifications_pushed.clone())).unwrap();
+        registry
+            .register(Box::new(user_notification_push_duration_ms.clone()))
+            .unwrap();
 
         Metrics {
             registry,
@@ -117,6 +136,8 @@ impl Metrics {
             processing_duration_ms,
             http_post_notification_duration_ms,
             notification_payload_sizes,
+            user_notifications_pushed,
+            user_notification_push_duration_ms,
         }
     }
 
@@ -171,6 +192,15 @@ impl Metrics {
             .with_label_values(&[type_label(user_notification)])
             .observe(size as f64);
     }
+
+    pub fn observe_user_notification_pushed(&self, backend: &str, duration: Milliseconds, success: bool) {
+        self.user_notifications_pushed
+            .with_label_values(&[backend, &success.to_string()])
+            .inc();
+        self.user_notification_push_duration_ms
+            .with_label_values(&[backend])
+            .observe(duration as f64);
+    }
 }
 
 fn calc_buckets(multiplication_factor: f64) -> Vec<f64> {
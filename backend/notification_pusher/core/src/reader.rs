@@ -8,7 +8,7 @@ use std::sync::Arc;
 use std::time::Instant;
 use tokio::time;
 use tracing::{error, info};
-use types::{BotDataEncoding, CanisterId, Error, NotificationEnvelope, Timestamped};
+use types::{BotDataEncoding, CanisterId, Error, NotificationEnvelope, PushBackend, Timestamped};
 
 pub struct Reader<I: IndexStore> {
     ic_agent: IcAgent,
@@ -107,6 +107,23 @@ impl<I: IndexStore> Reader<I> {
                                         PushNotification::UserNotification(UserNotification {
                                             payload: payload.clone(),
                                             subscription_info,
+                                            backend: PushBackend::WebPush,
+                                            metadata,
+                                        })
+                                    }
+                                    types::NotificationSubscription::UnifiedPush(subscription_info) => {
+                                        PushNotification::UserNotification(UserNotification {
+                                            payload: payload.clone(),
+                                            subscription_info,
+                                            backend: PushBackend::UnifiedPush,
+                                            metadata,
+                                        })
+                                    }
+                                    types::NotificationSubscription::HttpsPush(https_push) => {
+                                        PushNotification::UserNotification(UserNotification {
+                                            payload: payload.clone(),
+                                            subscription_info: https_push.subscription,
+                                            backend: PushBackend::HttpsPush(https_push.secret),
                                             metadata,
                                         })
                                     }
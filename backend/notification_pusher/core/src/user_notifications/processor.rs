@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 use tracing::info;
-use types::{SubscriptionInfo, TimestampMillis};
+use types::{PushBackend, SubscriptionInfo, TimestampMillis};
 use web_push::{
     ContentEncoding, PartialVapidSignatureBuilder, Urgency, VapidSignature, VapidSignatureBuilder, WebPushError,
     WebPushMessage, WebPushMessageBuilder,
@@ -97,11 +97,19 @@ impl Processor {
             }
         }
         let payload_bytes = notification.payload.as_ref();
-        let vapid_signature = self
-            .build_vapid_signature(&notification.subscription_info)
-            .map_err(ProcessNotificationError::FailedToBuildSignature)?;
 
-        let message = build_web_push_message(payload_bytes, &notification.subscription_info, vapid_signature.clone())
+        // Only web push endpoints require a VAPID signature, UnifiedPush endpoints accept
+        // unauthenticated requests and HTTPS endpoints are sent an HMAC signature by the pusher
+        let vapid_signature = if notification.backend == PushBackend::WebPush {
+            Some(
+                self.build_vapid_signature(&notification.subscription_info)
+                    .map_err(ProcessNotificationError::FailedToBuildSignature)?,
+            )
+        } else {
+            None
+        };
+
+        let message = build_web_push_message(payload_bytes, &notification.subscription_info, vapid_signature)
             .map_err(ProcessNotificationError::FailedToBuildMessage)?;
 
         let length = message.payload.as_ref().map_or(0, |p| p.content.len()) as u32;
@@ -132,12 +140,14 @@ enum ProcessNotificationError {
 fn build_web_push_message(
     payload: &[u8],
     subscription: &SubscriptionInfo,
-    vapid_signature: VapidSignature,
+    vapid_signature: Option<VapidSignature>,
 ) -> Result<WebPushMessage, WebPushError> {
     let subscription: web_push::SubscriptionInfo = to_web_push_subscription_info(subscription);
     let mut message_builder = WebPushMessageBuilder::new(&subscription);
     message_builder.set_payload(ContentEncoding::Aes128Gcm, payload);
-    message_builder.set_vapid_signature(vapid_signature);
+    if let Some(vapid_signature) = vapid_signature {
+        message_builder.set_vapid_signature(vapid_signature);
+    }
     message_builder.set_ttl(3600); // 1 hour
     message_builder.set_urgency(Urgency::High);
     message_builder.build()
@@ -2,18 +2,21 @@ use crate::metrics::write_metrics;
 use crate::{FcmNotification, NotificationToPush, UserNotificationToPush, timestamp};
 use async_channel::{Receiver, Sender};
 use fcm_service::{AndroidConfig, FcmMessage, FcmService, Target};
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use reqwest::{Client, StatusCode, Url};
 use std::collections::{BinaryHeap, HashMap};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 use tracing::{error, info};
-use types::{FcmToken, Milliseconds, TimestampMillis, UserId};
-use web_push::{HyperWebPushClient, WebPushClient, WebPushError};
+use types::{FcmToken, Milliseconds, PushBackend, TimestampMillis, UserId};
+use web_push::{HyperWebPushClient, WebPushClient, WebPushError, WebPushMessage};
 
 const ONE_MINUTE: Milliseconds = 60 * 1000;
 
 pub struct Pusher {
     receiver: Receiver<NotificationToPush>,
     web_push_client: HyperWebPushClient,
+    http_client: Client,
     subscriptions_to_remove_sender: Sender<(UserId, String)>,
     fcm_tokens_to_remove_sender: Sender<(UserId, FcmToken)>,
     invalid_subscriptions: Arc<RwLock<HashMap<String, TimestampMillis>>>,
@@ -33,6 +36,7 @@ impl Pusher {
         Self {
             receiver,
             web_push_client: HyperWebPushClient::new(),
+            http_client: Client::new(),
             subscriptions_to_remove_sender,
             fcm_tokens_to_remove_sender,
             invalid_subscriptions,
@@ -45,9 +49,10 @@ impl Pusher {
         while let Ok(notification_to_push) = self.receiver.recv().await {
             let start = Instant::now();
 
-            let (metadata, payload_size, success) = match notification_to_push {
+            let (metadata, backend, payload_size, success) = match notification_to_push {
                 NotificationToPush::UserNotificationToPush(user_notification_to_push) => {
                     let metadata = user_notification_to_push.notification.metadata.clone();
+                    let backend = backend_label(&user_notification_to_push.notification.backend);
                     let payload_size = user_notification_to_push
                         .message
                         .payload
@@ -57,13 +62,13 @@ impl Pusher {
                     // Push the notification to the user
                     let success = self.process_user_notification_to_push(user_notification_to_push).await;
 
-                    (metadata, Some(payload_size), success)
+                    (metadata, backend, Some(payload_size), success)
                 }
                 NotificationToPush::FcmNotificationToPush(fcm_notification_to_push) => {
                     let metadata = fcm_notification_to_push.metadata.clone();
                     let success = self.process_fcm_notification_to_push(fcm_notification_to_push).await;
 
-                    (metadata, None, success)
+                    (metadata, "fcm", None, success)
                 }
             };
 
@@ -83,18 +88,28 @@ impl Pusher {
                 m.observe_end_to_end_latency(end_to_end_latency, true, metadata.notifications_canister);
                 m.observe_end_to_end_internal_latency(end_to_end_internal_latency, true);
                 m.observe_http_post_notification_duration(push_duration, true, success);
+                m.observe_user_notification_pushed(backend, push_duration, success);
             });
         }
     }
 
     async fn process_user_notification_to_push(&self, user_notification_to_push: Box<UserNotificationToPush>) -> bool {
         let UserNotificationToPush { notification, message } = *user_notification_to_push;
-        let push_result = self.web_push_client.send(message).await;
+        let push_result = match &notification.backend {
+            PushBackend::WebPush => self.web_push_client.send(message).await.map_err(|error| match error {
+                WebPushError::EndpointNotValid(_) | WebPushError::InvalidUri | WebPushError::EndpointNotFound(_) => {
+                    PushError::SubscriptionInvalid(format!("{error:?}"))
+                }
+                _ => PushError::Other(format!("{error:?}")),
+            }),
+            PushBackend::UnifiedPush => self.post_message(message, None).await,
+            PushBackend::HttpsPush(secret) => self.post_message(message, Some(secret.as_str())).await,
+        };
         let success = push_result.is_ok();
 
         if let Err(error) = push_result {
             match error {
-                WebPushError::EndpointNotValid(_) | WebPushError::InvalidUri | WebPushError::EndpointNotFound(_) => {
+                PushError::SubscriptionInvalid(error) => {
                     let _ = self.subscriptions_to_remove_sender.try_send((
                         notification.metadata.recipient,
                         notification.subscription_info.endpoint.clone(),
@@ -112,13 +127,16 @@ impl Pusher {
                         "Failed to push notification, subscription queued to be removed"
                     );
                 }
-                _ => {
+                PushError::Other(error) => {
                     if let Ok(mut map) = self.throttled_subscriptions.write() {
                         let timestamp = timestamp();
                         if map.len() > 100 {
                             map.retain(|_, ts| *ts > timestamp);
                         }
-                        info!(notification.subscription_info.endpoint, "Subscription throttled for 1 minute");
+                        info!(
+                            error,
+                            notification.subscription_info.endpoint, "Subscription throttled for 1 minute"
+                        );
                         map.insert(notification.subscription_info.endpoint.clone(), timestamp + ONE_MINUTE);
                     }
                 }
@@ -128,6 +146,49 @@ impl Pusher {
         success
     }
 
+    // UnifiedPush and HTTPS endpoints are sent the same encrypted payload as web push endpoints, but
+    // rather than a VAPID signature, requests to HTTPS endpoints include an HMAC-SHA256 signature of
+    // the timestamp and body so that the receiver can verify they came from us
+    async fn post_message(&self, message: WebPushMessage, secret: Option<&str>) -> Result<(), PushError> {
+        let url = Url::parse(&message.endpoint.to_string()).map_err(|e| PushError::SubscriptionInvalid(e.to_string()))?;
+        let Some(payload) = message.payload else {
+            return Err(PushError::Other("Message has no payload".to_string()));
+        };
+
+        let mut request = self
+            .http_client
+            .post(url)
+            .header("ttl", message.ttl)
+            .header("urgency", "high")
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(CONTENT_ENCODING, payload.content_encoding.to_str());
+
+        for (name, value) in payload.crypto_headers {
+            request = request.header(name, value);
+        }
+
+        if let Some(secret) = secret {
+            let timestamp = timestamp();
+            request = request
+                .header("x-oc-timestamp", timestamp)
+                .header("x-oc-signature", compute_signature(secret, timestamp, &payload.content));
+        }
+
+        let response = request
+            .body(payload.content)
+            .send()
+            .await
+            .map_err(|e| PushError::Other(e.to_string()))?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            status @ (StatusCode::NOT_FOUND | StatusCode::GONE) => {
+                Err(PushError::SubscriptionInvalid(format!("Endpoint returned status {status}")))
+            }
+            status => Err(PushError::Other(format!("Endpoint returned status {status}"))),
+        }
+    }
+
     async fn process_fcm_notification_to_push(&self, fcm_notification_to_push: Box<FcmNotification>) -> bool {
         let FcmNotification {
             fcm_data,
@@ -164,6 +225,25 @@ impl Pusher {
     }
 }
 
+enum PushError {
+    SubscriptionInvalid(String),
+    Other(String),
+}
+
+fn backend_label(backend: &PushBackend) -> &'static str {
+    match backend {
+        PushBackend::WebPush => "web_push",
+        PushBackend::UnifiedPush => "unified_push",
+        PushBackend::HttpsPush(_) => "https_push",
+    }
+}
+
+fn compute_signature(secret: &str, timestamp: TimestampMillis, body: &[u8]) -> String {
+    let mut input = format!("{timestamp}.").into_bytes();
+    input.extend_from_slice(body);
+    hex::encode(hmac_sha256::HMAC::mac(input, secret.as_bytes()))
+}
+
 // FCM surfaces a stale-token failure with the stable error code `UNREGISTERED`. The
 // fcm-service crate currently returns errors as a stringified JSON body, so we match on
 // that code. If that crate ever exposes a typed error, switch to matching the variant.
@@ -209,3 +289,27 @@ fn oldest_subscriptions_are_pruned() {
         assert_eq!(map.contains_key(i.to_string().as_str()), i >= 1000, "{i}");
     }
 }
+
+#[test]
+fn signature_matches_hmac_sha256_of_timestamp_and_body() {
+    assert_eq!(
+        compute_signature("secret", 1700000000000, b"hello"),
+        "34e93906d48eb7d7b2bf2fee809e41149b8a3030b5459f29c5ca880f4cc626a6"
+    );
+}
+
+#[test]
+fn signature_depends_on_timestamp() {
+    assert_eq!(
+        compute_signature("secret", 1700000000001, b"hello"),
+        "998ac6ec2b01df4463a56d4562a0f8135dbb4261474ee732fc246f8de2b1b916"
+    );
+}
+
+#[test]
+fn signature_of_empty_body() {
+    assert_eq!(
+        compute_signature("secret", 1700000000000, b""),
+        "a7494f803b9c6508c0576777e04b19e49501e8f2ef43081ad6d470867ded87f4"
+    );
+}
@@ -0,0 +1,203 @@
+use crate::snapshots::{self, ManagementTarget, SnapshotContents};
+use candid::{Decode, Encode, Principal};
+use ic_agent::Agent;
+use pocket_ic::PocketIcBuilder;
+use pocket_ic::nonblocking::PocketIc;
+use std::path::Path;
+use types::{CanisterId, HttpRequest, HttpResponse};
+
+const LOCAL_CANISTER_CYCLES: u128 = 100_000_000_000_000;
+
+pub struct SmokeTestQuery {
+    pub method: String,
+    pub args: Vec<u8>,
+}
+
+pub struct DryRunReport {
+    // The snapshot taken for the dry run, which the upgrade then replaces with a fresh snapshot
+    pub snapshot_id: Vec<u8>,
+    pub post_upgrade_instructions: Option<u64>,
+    pub smoke_test_results: Vec<(String, Result<usize, String>)>,
+}
+
+impl DryRunReport {
+    pub fn passed(&self) -> bool {
+        self.smoke_test_results.iter().all(|(_, r)| r.is_ok())
+    }
+
+    pub fn print(&self) {
+        match self.post_upgrade_instructions {
+            Some(instructions) => println!("Post-upgrade instructions: {instructions}"),
+            None => println!("Post-upgrade instructions: unknown (not found in canister logs)"),
+        }
+        for (method, result) in self.smoke_test_results.iter() {
+            match result {
+                Ok(bytes) => println!("Smoke test '{method}' passed ({bytes} bytes returned)"),
+                Err(error) => println!("Smoke test '{method}' FAILED: {error}"),
+            }
+        }
+    }
+}
+
+// Reads the smoke test queries from a file containing one query per line, in the form
+// `<method> [hex encoded candid args]`. If no args are given the query is called with `()`.
+// Blank lines and lines starting with '#' are ignored.
+pub fn read_smoke_test_queries(path: &Path) -> Vec<SmokeTestQuery> {
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|error| panic!("Failed to read smoke test queries from {}: {error}", path.display()));
+
+    parse_smoke_test_queries(&contents)
+}
+
+fn parse_smoke_test_queries(contents: &str) -> Vec<SmokeTestQuery> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            let method = parts.next().unwrap().to_string();
+            let args = match parts.next() {
+                Some(hex_args) => hex::decode(hex_args)
+                    .unwrap_or_else(|error| panic!("Invalid args for smoke test query '{method}': {error}")),
+                None => Encode!().unwrap(),
+            };
+            SmokeTestQuery { method, args }
+        })
+        .collect()
+}
+
+// Snapshots the canister, restores the snapshot into a canister on a local PocketIC instance,
+// then upgrades that canister to the new wasm and runs the smoke test queries against it.
+// The snapshot is left on the canister for the caller to either replace with the upgrade's
+// snapshot or delete, unless the rehearsal itself errors, in which case it is deleted here. A new
+// snapshot is always taken rather than replacing an existing one, since an existing snapshot may
+// be the only way to roll back an earlier upgrade.
+pub async fn dry_run_upgrade(
+    agent: &Agent,
+    canister_id: CanisterId,
+    wasm_module: &[u8],
+    upgrade_args: Vec<u8>,
+    smoke_test_queries: &[SmokeTestQuery],
+) -> Result<DryRunReport, String> {
+    println!("Taking snapshot of canister {canister_id}");
+    let snapshot = snapshots::take_snapshot(&ManagementTarget::Ic(agent), canister_id, None).await?;
+    let contents = snapshots::download_snapshot(agent, canister_id, snapshot.id.clone()).await?;
+
+    println!("Restoring snapshot into a local PocketIC instance");
+    let pic = PocketIcBuilder::new().with_application_subnet().build_async().await;
+    let result = rehearse_upgrade(&pic, &contents, wasm_module, upgrade_args, smoke_test_queries).await;
+    pic.drop().await;
+    if result.is_err() {
+        let _ = snapshots::delete_snapshot(&ManagementTarget::Ic(agent), canister_id, snapshot.id.clone()).await;
+    }
+    result.map(|(post_upgrade_instructions, smoke_test_results)| DryRunReport {
+        snapshot_id: snapshot.id,
+        post_upgrade_instructions,
+        smoke_test_results,
+    })
+}
+
+async fn rehearse_upgrade(
+    pic: &PocketIc,
+    contents: &SnapshotContents,
+    wasm_module: &[u8],
+    upgrade_args: Vec<u8>,
+    smoke_test_queries: &[SmokeTestQuery],
+) -> Result<(Option<u64>, Vec<(String, Result<usize, String>)>), String> {
+    let target = ManagementTarget::PocketIc(pic);
+    let canister_id = pic.create_canister().await;
+    pic.add_cycles(canister_id, LOCAL_CANISTER_CYCLES).await;
+
+    let snapshot_id = snapshots::upload_snapshot(&target, canister_id, contents).await?;
+    pic.stop_canister(canister_id, None)
+        .await
+        .map_err(|error| format!("Failed to stop local canister: {error:?}"))?;
+    snapshots::load_snapshot(&target, canister_id, snapshot_id).await?;
+
+    println!("Upgrading local copy of the canister");
+    pic.upgrade_canister(canister_id, wasm_module.to_vec(), upgrade_args, None)
+        .await
+        .map_err(|error| format!("Upgrade failed: {error:?}"))?;
+    pic.start_canister(canister_id, None)
+        .await
+        .map_err(|error| format!("Failed to start local canister: {error:?}"))?;
+
+    let mut smoke_test_results = Vec::new();
+    for query in smoke_test_queries {
+        let result = pic
+            .query_call(canister_id, Principal::anonymous(), &query.method, query.args.clone())
+            .await
+            .map(|response| response.len())
+            .map_err(|error| format!("{error:?}"));
+
+        smoke_test_results.push((query.method.clone(), result));
+    }
+
+    Ok((post_upgrade_instructions(pic, canister_id).await, smoke_test_results))
+}
+
+// Each canister logs the instructions used by `post_upgrade` once it completes, so read them
+// back out of the logs exposed by the canister's `http_request` endpoint
+async fn post_upgrade_instructions(pic: &PocketIc, canister_id: CanisterId) -> Option<u64> {
+    let request = HttpRequest {
+        method: "GET".to_string(),
+        url: "/logs".to_string(),
+        headers: Vec::new(),
+        body: Vec::new(),
+    };
+    let response = pic
+        .query_call(
+            canister_id,
+            Principal::anonymous(),
+            "http_request",
+            Encode!(&request).unwrap(),
+        )
+        .await
+        .ok()?;
+    let response = Decode!(&response, HttpResponse).ok()?;
+
+    parse_post_upgrade_instructions(&String::from_utf8(response.body).ok()?)
+}
+
+fn parse_post_upgrade_instructions(logs: &str) -> Option<u64> {
+    logs.lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|log| log["fields"]["message"] == "Post-upgrade complete")
+        .and_then(|log| log["fields"]["total_instructions"].as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoke_test_queries_parsed() {
+        let args = Encode!(&1u32).unwrap();
+        let contents = format!("# comment\n\nmetrics\n  user {}  \n", hex::encode(&args));
+
+        let queries = parse_smoke_test_queries(&contents);
+
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].method, "metrics");
+        assert_eq!(queries[0].args, Encode!().unwrap());
+        assert_eq!(queries[1].method, "user");
+        assert_eq!(queries[1].args, args);
+    }
+
+    #[test]
+    fn latest_post_upgrade_instructions_returned() {
+        let logs = [
+            r#"{"fields":{"message":"Post-upgrade complete","total_instructions":100}}"#,
+            r#"{"fields":{"message":"Something else","total_instructions":5}}"#,
+            "not json",
+            r#"{"fields":{"message":"Post-upgrade complete","total_instructions":200}}"#,
+            r#"{"fields":{"message":"Something else"}}"#,
+        ]
+        .join("\n");
+
+        assert_eq!(parse_post_upgrade_instructions(&logs), Some(200));
+        assert_eq!(parse_post_upgrade_instructions("not json"), None);
+    }
+}
@@ -1,6 +1,7 @@
-use candid::CandidType;
+use crate::snapshots::ManagementTarget;
+use candid::{CandidType, Encode};
 use canister_agent_utils::{CanisterName, build_ic_agent, get_canister_wasm};
-use ic_agent::Identity;
+use ic_agent::{Agent, Identity};
 use ic_utils::call::AsyncCall;
 use ic_utils::interfaces::ManagementCanister;
 use ic_utils::interfaces::management_canister::CanisterStatusType;
@@ -8,23 +9,43 @@ use ic_utils::interfaces::management_canister::builders::CanisterInstallMode;
 use sha256::sha256;
 use types::{BuildVersion, CanisterId, CanisterWasm, UpgradeCanisterWasmArgs, UpgradeChunkedCanisterWasmArgs};
 
+mod dry_run;
+mod snapshots;
+
+pub use dry_run::{SmokeTestQuery, read_smoke_test_queries};
+
+// Only applies to canisters which are upgraded directly, rather than via the canister which
+// controls them (eg. the user index or the OpenChat installer). Those canisters are always
+// rehearsed against a local copy first and are only upgraded if the rehearsal succeeds.
+#[derive(Default)]
+pub struct UpgradeOptions {
+    // Rehearse the upgrade against a local copy of the canister, then exit without upgrading it
+    pub dry_run_only: bool,
+    // Upgrade the canister without rehearsing the upgrade first
+    pub skip_dry_run: bool,
+    pub smoke_test_queries: Vec<SmokeTestQuery>,
+}
+
 pub async fn upgrade_openchat_installer_canister(
     identity: Box<dyn Identity>,
     url: String,
     openchat_installer_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         openchat_installer_canister_id,
         version,
         openchat_installer_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::OpenChatInstaller,
+        options,
     )
-    .await;
-
-    println!("OpenChat installer canister upgraded");
+    .await
+    {
+        println!("OpenChat installer canister upgraded");
+    }
 }
 
 pub async fn upgrade_group_index_canister(
@@ -98,18 +119,21 @@ pub async fn upgrade_translations_canister(
     url: String,
     translations_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         translations_canister_id,
         version,
         translations_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::Translations,
+        options,
     )
-    .await;
-
-    println!("Translations canister upgraded");
+    .await
+    {
+        println!("Translations canister upgraded");
+    }
 }
 
 pub async fn upgrade_online_users_canister(
@@ -117,18 +141,21 @@ pub async fn upgrade_online_users_canister(
     url: String,
     online_users_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         online_users_canister_id,
         version,
         online_users_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::OnlineUsers,
+        options,
     )
-    .await;
-
-    println!("Online users canister upgraded");
+    .await
+    {
+        println!("Online users canister upgraded");
+    }
 }
 
 pub async fn upgrade_proposals_bot_canister(
@@ -136,18 +163,21 @@ pub async fn upgrade_proposals_bot_canister(
     url: String,
     proposals_bot_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         proposals_bot_canister_id,
         version,
         proposals_bot_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::ProposalsBot,
+        options,
     )
-    .await;
-
-    println!("Proposals bot canister upgraded");
+    .await
+    {
+        println!("Proposals bot canister upgraded");
+    }
 }
 
 pub async fn upgrade_airdrop_bot_canister(
@@ -155,18 +185,21 @@ pub async fn upgrade_airdrop_bot_canister(
     url: String,
     airdrop_bot_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         airdrop_bot_canister_id,
         version,
         airdrop_bot_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::AirdropBot,
+        options,
     )
-    .await;
-
-    println!("Airdrop bot canister upgraded");
+    .await
+    {
+        println!("Airdrop bot canister upgraded");
+    }
 }
 
 pub async fn upgrade_storage_index_canister(
@@ -174,18 +207,21 @@ pub async fn upgrade_storage_index_canister(
     url: String,
     storage_index_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         storage_index_canister_id,
         version,
         storage_index_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::StorageIndex,
+        options,
     )
-    .await;
-
-    println!("Storage index canister upgraded");
+    .await
+    {
+        println!("Storage index canister upgraded");
+    }
 }
 
 pub async fn upgrade_cycles_dispenser_canister(
@@ -193,18 +229,21 @@ pub async fn upgrade_cycles_dispenser_canister(
     url: String,
     cycles_dispenser_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         cycles_dispenser_canister_id,
         version,
         cycles_dispenser_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::CyclesDispenser,
+        options,
     )
-    .await;
-
-    println!("Cycles dispenser canister upgraded");
+    .await
+    {
+        println!("Cycles dispenser canister upgraded");
+    }
 }
 
 pub async fn upgrade_registry_canister(
@@ -212,18 +251,21 @@ pub async fn upgrade_registry_canister(
     url: String,
     registry_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         registry_canister_id,
         version,
         registry_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::Registry,
+        options,
     )
-    .await;
-
-    println!("Registry canister upgraded");
+    .await
+    {
+        println!("Registry canister upgraded");
+    }
 }
 
 pub async fn upgrade_market_maker_canister(
@@ -231,18 +273,21 @@ pub async fn upgrade_market_maker_canister(
     url: String,
     market_maker_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         market_maker_canister_id,
         version,
         market_maker_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::MarketMaker,
+        options,
     )
-    .await;
-
-    println!("Market maker canister upgraded");
+    .await
+    {
+        println!("Market maker canister upgraded");
+    }
 }
 
 pub async fn upgrade_neuron_controller_canister(
@@ -250,18 +295,21 @@ pub async fn upgrade_neuron_controller_canister(
     url: String,
     neuron_controller_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         neuron_controller_canister_id,
         version,
         neuron_controller_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::NeuronController,
+        options,
     )
-    .await;
-
-    println!("Neuron controller canister upgraded");
+    .await
+    {
+        println!("Neuron controller canister upgraded");
+    }
 }
 
 pub async fn upgrade_escrow_canister(
@@ -269,18 +317,21 @@ pub async fn upgrade_escrow_canister(
     url: String,
     escrow_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         escrow_canister_id,
         version,
         escrow_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::Escrow,
+        options,
     )
-    .await;
-
-    println!("Escrow canister upgraded");
+    .await
+    {
+        println!("Escrow canister upgraded");
+    }
 }
 
 pub async fn upgrade_event_relay_canister(
@@ -288,18 +339,21 @@ pub async fn upgrade_event_relay_canister(
     url: String,
     event_relay_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         event_relay_canister_id,
         version,
         event_relay_canister::post_upgrade::Args { wasm_version: version },
         CanisterName::EventRelay,
+        options,
     )
-    .await;
-
-    println!("Event relay canister upgraded");
+    .await
+    {
+        println!("Event relay canister upgraded");
+    }
 }
 
 pub async fn upgrade_event_store_canister(
@@ -307,10 +361,21 @@ pub async fn upgrade_event_store_canister(
     url: String,
     event_store_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(identity, url, event_store_canister_id, version, (), CanisterName::EventStore).await;
-
-    println!("Event store canister upgraded");
+    if upgrade_top_level_canister(
+        identity,
+        url,
+        event_store_canister_id,
+        version,
+        (),
+        CanisterName::EventStore,
+        options,
+    )
+    .await
+    {
+        println!("Event store canister upgraded");
+    }
 }
 
 pub async fn upgrade_group_canister(
@@ -489,8 +554,9 @@ pub async fn upgrade_sign_in_with_email_canister(
     url: String,
     sign_in_with_email_canister_id: CanisterId,
     version: BuildVersion,
+    options: &UpgradeOptions,
 ) {
-    upgrade_top_level_canister(
+    if upgrade_top_level_canister(
         identity,
         url,
         sign_in_with_email_canister_id,
@@ -500,12 +566,49 @@ pub async fn upgrade_sign_in_with_email_canister(
             email_sender_config: None,
         }),
         CanisterName::SignInWithEmail,
+        options,
     )
-    .await;
+    .await
+    {
+        println!("sign_in_with_email_canister upgraded");
+    }
+}
+
+pub async fn rollback_canister(
+    identity: Box<dyn Identity>,
+    url: String,
+    canister_id: CanisterId,
+    snapshot_id: Option<Vec<u8>>,
+) {
+    let agent = build_ic_agent(url, identity).await;
+    let management_canister = ManagementCanister::create(&agent);
+    let target = ManagementTarget::Ic(&agent);
+
+    let snapshot_id = match snapshot_id {
+        Some(id) => id,
+        None => {
+            snapshots::latest_snapshot(&target, canister_id)
+                .await
+                .expect("Failed to list snapshots")
+                .unwrap_or_else(|| panic!("No snapshots found for canister {canister_id}"))
+                .id
+        }
+    };
 
-    println!("sign_in_with_email_canister upgraded");
+    stop_canister(&management_canister, &canister_id).await;
+
+    println!("Loading snapshot {} into canister {canister_id}", hex::encode(&snapshot_id));
+    let result = snapshots::load_snapshot(&target, canister_id, snapshot_id).await;
+
+    start_canister(&management_canister, &canister_id).await;
+
+    match result {
+        Ok(_) => println!("Canister {canister_id} rolled back"),
+        Err(error) => panic!("Rollback failed: {error}"),
+    }
 }
 
+// Returns true if the canister was upgraded
 async fn upgrade_top_level_canister<A: CandidType + Send + Sync>(
     identity: Box<dyn Identity>,
     url: String,
@@ -513,12 +616,60 @@ async fn upgrade_top_level_canister<A: CandidType + Send + Sync>(
     version: BuildVersion,
     args: A,
     canister_name: CanisterName,
-) {
+    options: &UpgradeOptions,
+) -> bool {
     let agent = build_ic_agent(url, identity).await;
     let management_canister = ManagementCanister::create(&agent);
     let canister_wasm = get_canister_wasm(canister_name, version);
 
-    upgrade_wasm(&management_canister, &canister_id, &canister_wasm.module, args).await;
+    let mut dry_run_snapshot_id = None;
+    if !options.skip_dry_run {
+        let report = dry_run::dry_run_upgrade(
+            &agent,
+            canister_id,
+            &canister_wasm.module,
+            Encode!(&args).unwrap(),
+            &options.smoke_test_queries,
+        )
+        .await
+        .unwrap_or_else(|error| panic!("Dry run failed: {error}"));
+
+        report.print();
+
+        // The dry run snapshot is only kept if it is about to be replaced by the upgrade's snapshot,
+        // otherwise each dry run would leave behind another snapshot on the canister
+        if !report.passed() {
+            delete_dry_run_snapshot(&agent, canister_id, report.snapshot_id).await;
+            panic!("Dry run failed, canister {canister_id} has not been upgraded");
+        }
+        if options.dry_run_only {
+            delete_dry_run_snapshot(&agent, canister_id, report.snapshot_id).await;
+            println!("Dry run succeeded");
+            return false;
+        }
+        dry_run_snapshot_id = Some(report.snapshot_id);
+    }
+
+    upgrade_wasm(
+        &agent,
+        &management_canister,
+        &canister_id,
+        &canister_wasm.module,
+        args,
+        dry_run_snapshot_id,
+    )
+    .await;
+    true
+}
+
+async fn delete_dry_run_snapshot(agent: &Agent, canister_id: CanisterId, snapshot_id: Vec<u8>) {
+    match snapshots::delete_snapshot(&ManagementTarget::Ic(agent), canister_id, snapshot_id.clone()).await {
+        Ok(_) => println!("Dry run snapshot deleted"),
+        Err(error) => println!(
+            "Failed to delete dry run snapshot {} from canister {canister_id}: {error}",
+            hex::encode(&snapshot_id)
+        ),
+    }
 }
 
 async fn upgrade_canister_via_openchat_installer(
@@ -569,11 +720,42 @@ async fn upgrade_canister_via_openchat_installer(
 }
 
 async fn upgrade_wasm<A: CandidType + Send + Sync>(
+    agent: &Agent,
     management_canister: &ManagementCanister<'_>,
     canister_id: &CanisterId,
     wasm_bytes: &[u8],
     args: A,
+    dry_run_snapshot_id: Option<Vec<u8>>,
 ) {
+    stop_canister(management_canister, canister_id).await;
+
+    // Snapshot the stopped canister so that the upgrade can be reverted using the `rollback` command.
+    // This replaces the snapshot taken for the dry run (if any), but never any older snapshot.
+    // Without the snapshot there would be no way to roll back, so the upgrade is abandoned.
+    match snapshots::take_snapshot(&ManagementTarget::Ic(agent), *canister_id, dry_run_snapshot_id).await {
+        Ok(snapshot) => println!("Snapshot taken: {}", hex::encode(&snapshot.id)),
+        Err(error) => {
+            start_canister(management_canister, canister_id).await;
+            panic!("Failed to take snapshot, canister {canister_id} has not been upgraded: {error}");
+        }
+    }
+
+    println!("Upgrading wasm for canister {canister_id}");
+    match management_canister
+        .install_code(canister_id, wasm_bytes)
+        .with_mode(CanisterInstallMode::Upgrade(None))
+        .with_arg(args)
+        .call_and_wait()
+        .await
+    {
+        Ok(_) => println!("Wasm upgraded"),
+        Err(error) => println!("Upgrade failed: {error:?}"),
+    };
+
+    start_canister(management_canister, canister_id).await;
+}
+
+async fn stop_canister(management_canister: &ManagementCanister<'_>, canister_id: &CanisterId) {
     println!("Stopping canister {canister_id}");
     management_canister
         .stop_canister(canister_id)
@@ -597,19 +779,9 @@ async fn upgrade_wasm<A: CandidType + Send + Sync>(
         println!("Waiting for canister to stop");
     }
     println!("Canister stopped");
+}
 
-    println!("Upgrading wasm for canister {canister_id}");
-    match management_canister
-        .install_code(canister_id, wasm_bytes)
-        .with_mode(CanisterInstallMode::Upgrade(None))
-        .with_arg(args)
-        .call_and_wait()
-        .await
-    {
-        Ok(_) => println!("Wasm upgraded"),
-        Err(error) => println!("Upgrade failed: {error:?}"),
-    };
-
+async fn start_canister(management_canister: &ManagementCanister<'_>, canister_id: &CanisterId) {
     println!("Starting canister {canister_id}");
     management_canister
         .start_canister(canister_id)
@@ -1,49 +1,110 @@
 use canister_agent_utils::{CanisterName, get_dfx_identity};
 use canister_upgrader::*;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 use types::{BuildVersion, CanisterId};
 
 #[tokio::main]
 async fn main() {
-    let opts = Opts::parse();
+    let cli = Cli::parse();
+
+    let opts = match cli.command {
+        Some(Command::Rollback(rollback)) => {
+            let identity = get_dfx_identity(&rollback.controller);
+            let snapshot_id = rollback
+                .snapshot_id
+                .map(|id| hex::decode(id).expect("Snapshot id must be hex encoded"));
+
+            rollback_canister(identity, rollback.url, rollback.canister_id, snapshot_id).await;
+            return;
+        }
+        None => cli.upgrade.expect("Upgrade args must be specified if no command is given"),
+    };
 
     let identity = get_dfx_identity(&opts.controller);
+    let options = UpgradeOptions {
+        dry_run_only: opts.dry_run,
+        skip_dry_run: opts.skip_dry_run,
+        smoke_test_queries: opts
+            .smoke_test_queries
+            .as_deref()
+            .map(read_smoke_test_queries)
+            .unwrap_or_default(),
+    };
+
+    // Index and child canisters are upgraded by the canister which controls them, so they can't be
+    // rehearsed locally and are upgraded without a dry run
+    let dry_run_supported = !matches!(
+        opts.canister_to_upgrade,
+        CanisterName::Community
+            | CanisterName::Group
+            | CanisterName::GroupIndex
+            | CanisterName::Identity
+            | CanisterName::LocalUserIndex
+            | CanisterName::NotificationsIndex
+            | CanisterName::StorageBucket
+            | CanisterName::User
+            | CanisterName::UserIndex
+    );
+    if !dry_run_supported {
+        if options.dry_run_only {
+            eprintln!("Dry runs are only supported for canisters which are upgraded directly");
+            std::process::exit(1);
+        } else if !options.skip_dry_run {
+            eprintln!(
+                "WARNING: Dry runs are not supported for {}, it will be upgraded WITHOUT a dry run",
+                opts.canister_to_upgrade
+            );
+        }
+    }
 
     match opts.canister_to_upgrade {
-        CanisterName::AirdropBot => upgrade_airdrop_bot_canister(identity, opts.url, opts.airdrop_bot, opts.version).await,
+        CanisterName::AirdropBot => {
+            upgrade_airdrop_bot_canister(identity, opts.url, opts.airdrop_bot, opts.version, &options).await
+        }
         CanisterName::Community => upgrade_community_canister(identity, opts.url, opts.group_index, opts.version).await,
         CanisterName::CyclesDispenser => {
-            upgrade_cycles_dispenser_canister(identity, opts.url, opts.cycles_dispenser, opts.version).await
+            upgrade_cycles_dispenser_canister(identity, opts.url, opts.cycles_dispenser, opts.version, &options).await
+        }
+        CanisterName::Escrow => upgrade_escrow_canister(identity, opts.url, opts.escrow, opts.version, &options).await,
+        CanisterName::EventRelay => {
+            upgrade_event_relay_canister(identity, opts.url, opts.event_relay, opts.version, &options).await
+        }
+        CanisterName::EventStore => {
+            upgrade_event_store_canister(identity, opts.url, opts.event_store, opts.version, &options).await
         }
-        CanisterName::Escrow => upgrade_escrow_canister(identity, opts.url, opts.escrow, opts.version).await,
-        CanisterName::EventRelay => upgrade_event_relay_canister(identity, opts.url, opts.event_relay, opts.version).await,
-        CanisterName::EventStore => upgrade_event_store_canister(identity, opts.url, opts.event_store, opts.version).await,
         CanisterName::Group => upgrade_group_canister(identity, opts.url, opts.group_index, opts.version).await,
         CanisterName::GroupIndex => {
             upgrade_group_index_canister(identity, opts.url, opts.openchat_installer, opts.version).await
         }
         CanisterName::Identity => upgrade_identity_canister(identity, opts.url, opts.openchat_installer, opts.version).await,
-        CanisterName::MarketMaker => upgrade_market_maker_canister(identity, opts.url, opts.market_maker, opts.version).await,
+        CanisterName::MarketMaker => {
+            upgrade_market_maker_canister(identity, opts.url, opts.market_maker, opts.version, &options).await
+        }
         CanisterName::NeuronController => {
-            upgrade_neuron_controller_canister(identity, opts.url, opts.neuron_controller, opts.version).await
+            upgrade_neuron_controller_canister(identity, opts.url, opts.neuron_controller, opts.version, &options).await
         }
         CanisterName::NotificationsIndex => {
             upgrade_notifications_index_canister(identity, opts.url, opts.openchat_installer, opts.version).await
         }
-        CanisterName::OnlineUsers => upgrade_online_users_canister(identity, opts.url, opts.online_users, opts.version).await,
+        CanisterName::OnlineUsers => {
+            upgrade_online_users_canister(identity, opts.url, opts.online_users, opts.version, &options).await
+        }
         CanisterName::OpenChatInstaller => {
-            upgrade_openchat_installer_canister(identity, opts.url, opts.openchat_installer, opts.version).await
+            upgrade_openchat_installer_canister(identity, opts.url, opts.openchat_installer, opts.version, &options).await
         }
         CanisterName::ProposalsBot => {
-            upgrade_proposals_bot_canister(identity, opts.url, opts.proposals_bot, opts.version).await
+            upgrade_proposals_bot_canister(identity, opts.url, opts.proposals_bot, opts.version, &options).await
+        }
+        CanisterName::Registry => upgrade_registry_canister(identity, opts.url, opts.registry, opts.version, &options).await,
+        CanisterName::Translations => {
+            upgrade_translations_canister(identity, opts.url, opts.translations, opts.version, &options).await
         }
-        CanisterName::Registry => upgrade_registry_canister(identity, opts.url, opts.registry, opts.version).await,
-        CanisterName::Translations => upgrade_translations_canister(identity, opts.url, opts.translations, opts.version).await,
         CanisterName::StorageBucket => {
             upgrade_storage_bucket_canister(identity, opts.url, opts.storage_index, opts.version).await
         }
         CanisterName::StorageIndex => {
-            upgrade_storage_index_canister(identity, opts.url, opts.storage_index, opts.version).await
+            upgrade_storage_index_canister(identity, opts.url, opts.storage_index, opts.version, &options).await
         }
         CanisterName::User => upgrade_user_canister(identity, opts.url, opts.user_index, opts.version).await,
         CanisterName::LocalUserIndex => {
@@ -51,13 +112,45 @@ async fn main() {
         }
         CanisterName::UserIndex => upgrade_user_index_canister(identity, opts.url, opts.openchat_installer, opts.version).await,
         CanisterName::SignInWithEmail => {
-            upgrade_sign_in_with_email_canister(identity, opts.url, opts.sign_in_with_email, opts.version).await
+            upgrade_sign_in_with_email_canister(identity, opts.url, opts.sign_in_with_email, opts.version, &options).await
         }
         CanisterName::SignInWithEthereum | CanisterName::SignInWithSolana => unimplemented!(),
     };
 }
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    upgrade: Option<Opts>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    // Restores a canister from a snapshot, by default the one taken before its latest upgrade
+    Rollback(RollbackOpts),
+}
+
+#[derive(clap::Args)]
+struct RollbackOpts {
+    #[arg(long)]
+    url: String,
+
+    #[arg(long)]
+    controller: String,
+
+    #[arg(long)]
+    canister_id: CanisterId,
+
+    // Hex encoded, defaults to the canister's most recent snapshot
+    #[arg(long)]
+    snapshot_id: Option<String>,
+}
+
+#[derive(clap::Args)]
 struct Opts {
     #[arg(long)]
     url: String,
@@ -124,4 +217,16 @@ struct Opts {
 
     #[arg(long)]
     version: BuildVersion,
+
+    // Rehearse the upgrade against a local PocketIC copy of the canister without upgrading it
+    #[arg(long, conflicts_with = "skip_dry_run")]
+    dry_run: bool,
+
+    // Upgrade the canister without first rehearsing the upgrade against a local PocketIC copy
+    #[arg(long)]
+    skip_dry_run: bool,
+
+    // File containing the queries to run against the upgraded canister during a dry run
+    #[arg(long)]
+    smoke_test_queries: Option<PathBuf>,
 }
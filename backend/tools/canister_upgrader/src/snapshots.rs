@@ -0,0 +1,521 @@
+use candid::{CandidType, Decode, Encode, Nat, Principal};
+use ic_agent::Agent;
+use pocket_ic::common::rest::RawEffectivePrincipal;
+use pocket_ic::nonblocking::PocketIc;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::task::JoinSet;
+use types::CanisterId;
+
+// The management canister rejects snapshot reads and uploads larger than this
+const MAX_CHUNK_SIZE: u64 = 2_000_000;
+const MAX_CONCURRENT_DOWNLOADS: usize = 10;
+
+// Snapshot endpoints are called directly on the management canister rather than through
+// `ManagementCanister` so that the same code can be run against mainnet and a local PocketIC
+// instance when rehearsing an upgrade
+pub enum ManagementTarget<'a> {
+    Ic(&'a Agent),
+    PocketIc(&'a PocketIc),
+}
+
+impl ManagementTarget<'_> {
+    async fn call<A: CandidType, R: CandidType + DeserializeOwned>(
+        &self,
+        canister_id: CanisterId,
+        method: &str,
+        args: &A,
+    ) -> Result<R, String> {
+        let payload = Encode!(args).unwrap();
+        let response = match self {
+            ManagementTarget::Ic(agent) => agent
+                .update(&Principal::management_canister(), method)
+                .with_effective_canister_id(canister_id)
+                .with_arg(payload)
+                .call_and_wait()
+                .await
+                .map_err(|e| format!("'{method}' failed: {e}"))?,
+            ManagementTarget::PocketIc(pic) => pic
+                .update_call_with_effective_principal(
+                    Principal::management_canister(),
+                    RawEffectivePrincipal::CanisterId(canister_id.as_slice().to_vec()),
+                    Principal::anonymous(),
+                    method,
+                    payload,
+                )
+                .await
+                .map_err(|e| format!("'{method}' failed: {e:?}"))?,
+        };
+        Decode!(&response, R).map_err(|e| format!("Failed to decode '{method}' response: {e}"))
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Snapshot {
+    pub id: Vec<u8>,
+    pub taken_at_timestamp: u64,
+    pub total_size: u64,
+}
+
+// The full contents of a snapshot, downloaded so that it can be uploaded into another canister.
+// The memories can be hundreds of GBs so they are streamed to files rather than held in memory,
+// the files are deleted when this is dropped.
+pub struct SnapshotContents {
+    pub metadata: SnapshotMetadata,
+    pub wasm_module: PathBuf,
+    pub main_memory: PathBuf,
+    pub stable_memory: PathBuf,
+    pub wasm_chunks: Vec<Vec<u8>>,
+}
+
+impl Drop for SnapshotContents {
+    fn drop(&mut self) {
+        for path in [&self.wasm_module, &self.main_memory, &self.stable_memory] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SnapshotMetadata {
+    pub taken_at_timestamp: u64,
+    pub wasm_module_size: u64,
+    pub exported_globals: Vec<ExportedGlobal>,
+    pub wasm_memory_size: u64,
+    pub stable_memory_size: u64,
+    pub wasm_chunk_store: Vec<ChunkHash>,
+    pub certified_data: Vec<u8>,
+    pub global_timer: Option<GlobalTimer>,
+    pub on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum ExportedGlobal {
+    #[serde(rename = "i32")]
+    I32(i32),
+    #[serde(rename = "i64")]
+    I64(i64),
+    #[serde(rename = "f32")]
+    F32(f32),
+    #[serde(rename = "f64")]
+    F64(f64),
+    #[serde(rename = "v128")]
+    V128(Nat),
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ChunkHash {
+    pub hash: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum GlobalTimer {
+    Inactive,
+    Active(u64),
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum OnLowWasmMemoryHookStatus {
+    ConditionNotSatisfied,
+    Ready,
+    Executed,
+}
+
+#[derive(CandidType)]
+struct CanisterIdArgs {
+    canister_id: CanisterId,
+}
+
+#[derive(CandidType)]
+struct TakeSnapshotArgs {
+    canister_id: CanisterId,
+    replace_snapshot: Option<Vec<u8>>,
+}
+
+#[derive(CandidType)]
+struct SnapshotArgs {
+    canister_id: CanisterId,
+    snapshot_id: Vec<u8>,
+}
+
+#[derive(CandidType)]
+struct ReadSnapshotDataArgs {
+    canister_id: CanisterId,
+    snapshot_id: Vec<u8>,
+    kind: SnapshotDataKind,
+}
+
+#[derive(CandidType, Serialize, Clone)]
+#[serde(rename_all = "snake_case")]
+enum SnapshotDataKind {
+    WasmModule { offset: u64, size: u64 },
+    MainMemory { offset: u64, size: u64 },
+    StableMemory { offset: u64, size: u64 },
+    WasmChunk { hash: Vec<u8> },
+}
+
+#[derive(CandidType, Deserialize)]
+struct ReadSnapshotDataResponse {
+    chunk: Vec<u8>,
+}
+
+#[derive(CandidType)]
+struct UploadSnapshotMetadataArgs {
+    canister_id: CanisterId,
+    replace_snapshot: Option<Vec<u8>>,
+    wasm_module_size: u64,
+    exported_globals: Vec<ExportedGlobal>,
+    wasm_memory_size: u64,
+    stable_memory_size: u64,
+    certified_data: Vec<u8>,
+    global_timer: Option<GlobalTimer>,
+    on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+}
+
+#[derive(CandidType, Deserialize)]
+struct UploadSnapshotMetadataResponse {
+    snapshot_id: Vec<u8>,
+}
+
+#[derive(CandidType)]
+struct UploadSnapshotDataArgs {
+    canister_id: CanisterId,
+    snapshot_id: Vec<u8>,
+    kind: SnapshotUploadKind,
+    chunk: Vec<u8>,
+}
+
+#[derive(CandidType, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum SnapshotUploadKind {
+    WasmModule { offset: u64 },
+    MainMemory { offset: u64 },
+    StableMemory { offset: u64 },
+    WasmChunk,
+}
+
+pub async fn list_snapshots(target: &ManagementTarget<'_>, canister_id: CanisterId) -> Result<Vec<Snapshot>, String> {
+    target
+        .call(canister_id, "list_canister_snapshots", &CanisterIdArgs { canister_id })
+        .await
+}
+
+// Only pass in `replace_snapshot` if it is a snapshot taken earlier in the same run, otherwise
+// an existing snapshot, which may be needed to roll back, would be overwritten
+pub async fn take_snapshot(
+    target: &ManagementTarget<'_>,
+    canister_id: CanisterId,
+    replace_snapshot: Option<Vec<u8>>,
+) -> Result<Snapshot, String> {
+    target
+        .call(
+            canister_id,
+            "take_canister_snapshot",
+            &TakeSnapshotArgs {
+                canister_id,
+                replace_snapshot,
+            },
+        )
+        .await
+}
+
+pub async fn latest_snapshot(target: &ManagementTarget<'_>, canister_id: CanisterId) -> Result<Option<Snapshot>, String> {
+    Ok(list_snapshots(target, canister_id)
+        .await?
+        .into_iter()
+        .max_by_key(|s| s.taken_at_timestamp))
+}
+
+pub async fn delete_snapshot(
+    target: &ManagementTarget<'_>,
+    canister_id: CanisterId,
+    snapshot_id: Vec<u8>,
+) -> Result<(), String> {
+    target
+        .call(
+            canister_id,
+            "delete_canister_snapshot",
+            &SnapshotArgs {
+                canister_id,
+                snapshot_id,
+            },
+        )
+        .await
+}
+
+// The canister must be stopped before the snapshot is loaded
+pub async fn load_snapshot(target: &ManagementTarget<'_>, canister_id: CanisterId, snapshot_id: Vec<u8>) -> Result<(), String> {
+    target
+        .call(
+            canister_id,
+            "load_canister_snapshot",
+            &SnapshotArgs {
+                canister_id,
+                snapshot_id,
+            },
+        )
+        .await
+}
+
+pub async fn download_snapshot(
+    agent: &Agent,
+    canister_id: CanisterId,
+    snapshot_id: Vec<u8>,
+) -> Result<SnapshotContents, String> {
+    let metadata: SnapshotMetadata = ManagementTarget::Ic(agent)
+        .call(
+            canister_id,
+            "read_canister_snapshot_metadata",
+            &SnapshotArgs {
+                canister_id,
+                snapshot_id: snapshot_id.clone(),
+            },
+        )
+        .await?;
+
+    println!(
+        "Downloading snapshot. Wasm module: {} bytes, main memory: {} bytes, stable memory: {} bytes",
+        metadata.wasm_module_size, metadata.wasm_memory_size, metadata.stable_memory_size
+    );
+
+    // The files are owned by `contents` from the start so that they are cleaned up if a download fails
+    let file_path = |kind: &str| std::env::temp_dir().join(format!("{canister_id}_{}_{kind}", hex::encode(&snapshot_id)));
+    let mut contents = SnapshotContents {
+        metadata,
+        wasm_module: file_path("wasm_module"),
+        main_memory: file_path("main_memory"),
+        stable_memory: file_path("stable_memory"),
+        wasm_chunks: Vec::new(),
+    };
+
+    download_data(
+        agent,
+        canister_id,
+        &snapshot_id,
+        contents.metadata.wasm_module_size,
+        |offset, size| SnapshotDataKind::WasmModule { offset, size },
+        &contents.wasm_module,
+    )
+    .await?;
+    download_data(
+        agent,
+        canister_id,
+        &snapshot_id,
+        contents.metadata.wasm_memory_size,
+        |offset, size| SnapshotDataKind::MainMemory { offset, size },
+        &contents.main_memory,
+    )
+    .await?;
+    download_data(
+        agent,
+        canister_id,
+        &snapshot_id,
+        contents.metadata.stable_memory_size,
+        |offset, size| SnapshotDataKind::StableMemory { offset, size },
+        &contents.stable_memory,
+    )
+    .await?;
+
+    for chunk_hash in contents.metadata.wasm_chunk_store.iter() {
+        let response: ReadSnapshotDataResponse = ManagementTarget::Ic(agent)
+            .call(
+                canister_id,
+                "read_canister_snapshot_data",
+                &ReadSnapshotDataArgs {
+                    canister_id,
+                    snapshot_id: snapshot_id.clone(),
+                    kind: SnapshotDataKind::WasmChunk {
+                        hash: chunk_hash.hash.clone(),
+                    },
+                },
+            )
+            .await?;
+        contents.wasm_chunks.push(response.chunk);
+    }
+
+    Ok(contents)
+}
+
+// Uploads the snapshot into the target canister and returns the id of the new snapshot
+pub async fn upload_snapshot(
+    target: &ManagementTarget<'_>,
+    canister_id: CanisterId,
+    contents: &SnapshotContents,
+) -> Result<Vec<u8>, String> {
+    let metadata = &contents.metadata;
+    let response: UploadSnapshotMetadataResponse = target
+        .call(
+            canister_id,
+            "upload_canister_snapshot_metadata",
+            &UploadSnapshotMetadataArgs {
+                canister_id,
+                replace_snapshot: None,
+                wasm_module_size: metadata.wasm_module_size,
+                exported_globals: metadata.exported_globals.clone(),
+                wasm_memory_size: metadata.wasm_memory_size,
+                stable_memory_size: metadata.stable_memory_size,
+                certified_data: metadata.certified_data.clone(),
+                global_timer: metadata.global_timer.clone(),
+                on_low_wasm_memory_hook_status: metadata.on_low_wasm_memory_hook_status.clone(),
+            },
+        )
+        .await?;
+    let snapshot_id = response.snapshot_id;
+
+    upload_data(target, canister_id, &snapshot_id, &contents.wasm_module, |offset| {
+        SnapshotUploadKind::WasmModule { offset }
+    })
+    .await?;
+    upload_data(target, canister_id, &snapshot_id, &contents.main_memory, |offset| {
+        SnapshotUploadKind::MainMemory { offset }
+    })
+    .await?;
+    upload_data(target, canister_id, &snapshot_id, &contents.stable_memory, |offset| {
+        SnapshotUploadKind::StableMemory { offset }
+    })
+    .await?;
+
+    for chunk in contents.wasm_chunks.iter() {
+        target
+            .call::<_, ()>(
+                canister_id,
+                "upload_canister_snapshot_data",
+                &UploadSnapshotDataArgs {
+                    canister_id,
+                    snapshot_id: snapshot_id.clone(),
+                    kind: SnapshotUploadKind::WasmChunk,
+                    chunk: chunk.clone(),
+                },
+            )
+            .await?;
+    }
+
+    Ok(snapshot_id)
+}
+
+// Downloads the data in chunks, writing each chunk to the file as it arrives
+async fn download_data<F: Fn(u64, u64) -> SnapshotDataKind>(
+    agent: &Agent,
+    canister_id: CanisterId,
+    snapshot_id: &[u8],
+    total_size: u64,
+    kind: F,
+    path: &Path,
+) -> Result<(), String> {
+    let mut file = File::create(path)
+        .await
+        .map_err(|e| format!("Failed to create {}: {e}", path.display()))?;
+    file.set_len(total_size).await.map_err(|e| e.to_string())?;
+
+    let mut chunks = chunk_ranges(total_size).peekable();
+    while chunks.peek().is_some() {
+        let mut downloads = JoinSet::new();
+        for (offset, size) in chunks.by_ref().take(MAX_CONCURRENT_DOWNLOADS) {
+            let agent = agent.clone();
+            let args = ReadSnapshotDataArgs {
+                canister_id,
+                snapshot_id: snapshot_id.to_vec(),
+                kind: kind(offset, size),
+            };
+            downloads.spawn(async move {
+                let response: Result<ReadSnapshotDataResponse, String> = ManagementTarget::Ic(&agent)
+                    .call(canister_id, "read_canister_snapshot_data", &args)
+                    .await;
+                response.map(|r| (offset, r.chunk))
+            });
+        }
+
+        while let Some(result) = downloads.join_next().await {
+            let (offset, chunk) = result.map_err(|e| e.to_string())??;
+            write_chunk(&mut file, offset, &chunk).await?;
+        }
+    }
+
+    file.sync_all().await.map_err(|e| e.to_string())
+}
+
+async fn write_chunk(file: &mut File, offset: u64, chunk: &[u8]) -> Result<(), String> {
+    file.seek(SeekFrom::Start(offset)).await.map_err(|e| e.to_string())?;
+    file.write_all(chunk).await.map_err(|e| e.to_string())
+}
+
+// Splits the data into (offset, size) ranges no larger than the max chunk size
+fn chunk_ranges(total_size: u64) -> impl Iterator<Item = (u64, u64)> {
+    (0..total_size)
+        .step_by(MAX_CHUNK_SIZE as usize)
+        .map(move |offset| (offset, MAX_CHUNK_SIZE.min(total_size - offset)))
+}
+
+// Reads the file one chunk at a time, uploading each chunk before reading the next
+async fn upload_data<F: Fn(u64) -> SnapshotUploadKind>(
+    target: &ManagementTarget<'_>,
+    canister_id: CanisterId,
+    snapshot_id: &[u8],
+    path: &Path,
+    kind: F,
+) -> Result<(), String> {
+    let mut file = File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    let total_size = file.metadata().await.map_err(|e| e.to_string())?.len();
+
+    for (offset, size) in chunk_ranges(total_size) {
+        let mut chunk = vec![0; size as usize];
+        file.read_exact(&mut chunk).await.map_err(|e| e.to_string())?;
+
+        target
+            .call::<_, ()>(
+                canister_id,
+                "upload_canister_snapshot_data",
+                &UploadSnapshotDataArgs {
+                    canister_id,
+                    snapshot_id: snapshot_id.to_vec(),
+                    kind: kind(offset),
+                    chunk,
+                },
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_ranges_cover_data() {
+        let total_size = 2 * MAX_CHUNK_SIZE + 5;
+        let ranges: Vec<_> = chunk_ranges(total_size).collect();
+
+        assert_eq!(
+            ranges,
+            vec![(0, MAX_CHUNK_SIZE), (MAX_CHUNK_SIZE, MAX_CHUNK_SIZE), (2 * MAX_CHUNK_SIZE, 5)]
+        );
+        assert_eq!(chunk_ranges(0).count(), 0);
+        assert_eq!(chunk_ranges(MAX_CHUNK_SIZE).collect::<Vec<_>>(), vec![(0, MAX_CHUNK_SIZE)]);
+    }
+
+    #[tokio::test]
+    async fn chunks_written_out_of_order_are_placed_at_their_offsets() {
+        let path = std::env::temp_dir().join(format!("canister_upgrader_test_{}", std::process::id()));
+        let mut file = File::create(&path).await.unwrap();
+        file.set_len(6).await.unwrap();
+
+        write_chunk(&mut file, 4, &[5, 6]).await.unwrap();
+        write_chunk(&mut file, 0, &[1, 2]).await.unwrap();
+        write_chunk(&mut file, 2, &[3, 4]).await.unwrap();
+        file.sync_all().await.unwrap();
+
+        let contents = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents, vec![1, 2, 3, 4, 5, 6]);
+    }
+}
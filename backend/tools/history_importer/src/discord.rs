@@ -0,0 +1,129 @@
+use crate::{SourceChannel, SourceMessage};
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+// Types of messages which were written by a user, everything else (pins, joins, etc) is skipped
+const IMPORTED_TYPES: [&str; 2] = ["Default", "Reply"];
+
+#[derive(Deserialize)]
+struct Export {
+    channel: Channel,
+    messages: Vec<Message>,
+}
+
+#[derive(Deserialize)]
+struct Channel {
+    name: String,
+    #[serde(default)]
+    topic: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    timestamp: String,
+    #[serde(default)]
+    content: String,
+    author: Author,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    name: String,
+    #[serde(default)]
+    nickname: Option<String>,
+}
+
+// Reads the JSON format produced by DiscordChatExporter, either a single file for one channel or a
+// directory of them. Discord replies quote the message they reply to rather than starting a thread
+// so they are imported as top level messages.
+pub fn read_export(path: &str) -> Result<Vec<SourceChannel>, Box<dyn Error + Send + Sync>> {
+    let path = Path::new(path);
+    let files = if path.is_dir() {
+        let mut files: Vec<_> = fs::read_dir(path)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    files.into_iter().map(|f| parse_channel(&fs::read_to_string(f)?)).collect()
+}
+
+fn parse_channel(json: &str) -> Result<SourceChannel, Box<dyn Error + Send + Sync>> {
+    let export: Export = serde_json::from_str(json)?;
+
+    let mut messages = Vec::new();
+    for message in export.messages {
+        if !IMPORTED_TYPES.contains(&message.kind.as_str()) {
+            continue;
+        }
+
+        let timestamp = OffsetDateTime::parse(&message.timestamp, &Rfc3339)?;
+
+        messages.push(SourceMessage {
+            id: message.id,
+            thread_root_id: None,
+            author_name: message.author.nickname.unwrap_or(message.author.name),
+            text: message.content,
+            timestamp: (timestamp.unix_timestamp_nanos() / 1_000_000) as u64,
+        });
+    }
+
+    Ok(SourceChannel {
+        name: export.channel.name,
+        description: export.channel.topic.unwrap_or_default(),
+        messages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_channel_maps_messages() {
+        let json = r#"{
+            "channel": {"name": "general", "topic": "Chat about anything"},
+            "messages": [
+                {"id": "1", "type": "Default", "timestamp": "2020-09-13T12:26:40.123+00:00", "content": "hello", "author": {"name": "alice", "nickname": "Alice"}},
+                {"id": "2", "type": "ChannelPinnedMessage", "timestamp": "2020-09-13T12:26:41+00:00", "content": "", "author": {"name": "alice"}},
+                {"id": "3", "type": "Reply", "timestamp": "2020-09-13T14:26:42+02:00", "content": "hi", "author": {"name": "bob"}}
+            ]
+        }"#;
+
+        let channel = parse_channel(json).unwrap();
+
+        assert_eq!(channel.name, "general");
+        assert_eq!(channel.description, "Chat about anything");
+        assert_eq!(channel.messages.len(), 2);
+        assert_eq!(channel.messages[0].id, "1");
+        assert_eq!(channel.messages[0].author_name, "Alice");
+        assert_eq!(channel.messages[0].timestamp, 1_600_000_000_123);
+        assert_eq!(channel.messages[1].author_name, "bob");
+        assert_eq!(channel.messages[1].text, "hi");
+        assert_eq!(channel.messages[1].timestamp, 1_600_000_002_000);
+        assert!(channel.messages.iter().all(|m| m.thread_root_id.is_none()));
+    }
+
+    #[test]
+    fn parse_channel_invalid_timestamp_fails() {
+        let json = r#"{
+            "channel": {"name": "general"},
+            "messages": [
+                {"id": "1", "type": "Default", "timestamp": "yesterday", "content": "hello", "author": {"name": "alice"}}
+            ]
+        }"#;
+
+        assert!(parse_channel(json).is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use canister_agent_utils::{build_ic_agent, get_dfx_identity};
+use clap::Parser;
+use ic_agent::Agent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use types::{
+    CanisterId, ChannelId, ImportSource, ImportedMessage, ImportedMessageContext, MAX_TEXT_LENGTH_USIZE, MessageId,
+    MessageIndex, Rules, TimestampMillis,
+};
+
+mod discord;
+mod slack;
+mod telegram;
+
+const BATCH_SIZE: usize = 100;
+const MAX_ATTEMPTS_PER_BATCH: usize = 3;
+
+#[derive(Parser, Debug)]
+pub struct Config {
+    /// "slack", "discord" or "telegram"
+    #[arg(long)]
+    source: String,
+
+    /// The path to the export. For Slack this is the unzipped export directory, for Discord a
+    /// DiscordChatExporter JSON file or a directory of them, and for Telegram the `result.json`
+    /// produced by Telegram Desktop
+    #[arg(long)]
+    path: String,
+
+    /// The id of the community to import into
+    #[arg(long)]
+    community_id: CanisterId,
+
+    /// IC URL
+    #[arg(long)]
+    url: String,
+
+    /// The DFX identity of the community owner
+    #[arg(long)]
+    identity: String,
+
+    /// The file in which the channels created by the import are recorded, so that re-running the
+    /// import resumes it in those channels rather than creating new ones
+    #[arg(long, default_value = "history_import_state.json")]
+    state_file: String,
+}
+
+// Maps each source channel's name to the channel it is being imported into. Only channels created
+// by the importer are recorded, so an import is never merged into a channel created by a user.
+#[derive(Serialize, Deserialize)]
+struct ImportState {
+    community_id: CanisterId,
+    channels: HashMap<String, ChannelId>,
+}
+
+// The source-agnostic form each parser converts its export into
+pub struct SourceChannel {
+    pub name: String,
+    pub description: String,
+    pub messages: Vec<SourceMessage>,
+}
+
+pub struct SourceMessage {
+    pub id: String,
+    pub thread_root_id: Option<String>,
+    pub author_name: String,
+    pub text: String,
+    pub timestamp: TimestampMillis,
+}
+
+pub async fn run(config: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (source, channels) = match config.source.as_str() {
+        "slack" => (ImportSource::Slack, slack::read_export(&config.path)?),
+        "discord" => (ImportSource::Discord, discord::read_export(&config.path)?),
+        "telegram" => (ImportSource::Telegram, telegram::read_export(&config.path)?),
+        _ => Err("Unsupported source")?,
+    };
+
+    let identity = get_dfx_identity(&config.identity);
+    let agent = build_ic_agent(config.url, identity).await;
+
+    // Channels created by a previous run are reused so that re-running an import resumes it rather
+    // than creating duplicate channels. Messages which were already imported are skipped by the canister.
+    let mut state = read_state(&config.state_file, config.community_id)?;
+
+    for channel in channels {
+        let channel_id = match state.channels.get(&channel.name) {
+            Some(channel_id) => {
+                println!("Resuming import into channel \"{}\" ({channel_id})", channel.name);
+                *channel_id
+            }
+            None => {
+                let channel_id = create_channel(&agent, &config.community_id, &channel).await?;
+                state.channels.insert(channel.name.clone(), channel_id);
+                write_state(&config.state_file, &state)?;
+                channel_id
+            }
+        };
+        let imported = import_channel(&agent, &config.community_id, channel_id, source, channel.messages).await?;
+
+        println!(
+            "Imported {imported} messages into channel \"{}\" ({channel_id})",
+            channel.name
+        );
+    }
+
+    Ok(())
+}
+
+fn read_state(path: &str, community_id: CanisterId) -> Result<ImportState, Box<dyn Error + Send + Sync>> {
+    let state: ImportState = match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)?,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(ImportState {
+                community_id,
+                channels: HashMap::new(),
+            });
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    if state.community_id != community_id {
+        Err(format!(
+            "State file {path} is for a different community ({}), use a different --state-file",
+            state.community_id
+        ))?;
+    }
+    Ok(state)
+}
+
+fn write_state(path: &str, state: &ImportState) -> Result<(), Box<dyn Error + Send + Sync>> {
+    fs::write(path, serde_json::to_vec_pretty(state)?)?;
+    Ok(())
+}
+
+async fn create_channel(
+    agent: &Agent,
+    community_id: &CanisterId,
+    channel: &SourceChannel,
+) -> Result<ChannelId, Box<dyn Error + Send + Sync>> {
+    let args = community_canister::create_channel::Args {
+        is_public: false,
+        name: channel.name.clone(),
+        description: channel.description.clone(),
+        rules: Rules::default(),
+        subtype: None,
+        avatar: None,
+        history_visible_to_new_joiners: true,
+        messages_visible_to_non_members: None,
+        permissions_v2: None,
+        events_ttl: None,
+        gate_config: None,
+        external_url: None,
+    };
+
+    match community_canister_client::create_channel(agent, community_id, &args).await? {
+        community_canister::create_channel::Response::Success(result) => Ok(result.channel_id),
+        community_canister::create_channel::Response::Error(error) => {
+            Err(format!("Failed to create channel \"{}\": {error:?}", channel.name))?
+        }
+    }
+}
+
+// Top level messages are imported first so that the message index of every thread root is known
+// by the time its replies are imported
+async fn import_channel(
+    agent: &Agent,
+    community_id: &CanisterId,
+    channel_id: ChannelId,
+    source: ImportSource,
+    mut messages: Vec<SourceMessage>,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    messages.retain(|m| {
+        let valid = !m.text.is_empty() && m.text.len() <= MAX_TEXT_LENGTH_USIZE;
+        if !valid {
+            println!("Skipping message {} which has no text or too much text", m.id);
+        }
+        valid
+    });
+    messages.sort_by_key(|m| m.timestamp);
+
+    let (replies, top_level): (Vec<_>, Vec<_>) = messages.into_iter().partition(|m| m.thread_root_id.is_some());
+    let mut message_indexes: HashMap<String, MessageIndex> = HashMap::new();
+    let mut imported = 0;
+
+    for batch in top_level.chunks(BATCH_SIZE) {
+        let to_import = batch.iter().map(|m| to_imported_message(source, m, None)).collect();
+        let indexes = import_batch(agent, community_id, channel_id, to_import).await?;
+
+        for (message, index) in batch.iter().zip(indexes) {
+            message_indexes.insert(message.id.clone(), index);
+        }
+        imported += batch.len();
+    }
+
+    let replies: Vec<_> = replies
+        .into_iter()
+        .filter_map(|m| {
+            let root_id = m.thread_root_id.as_ref().unwrap();
+            if let Some(root_index) = message_indexes.get(root_id).copied() {
+                Some((m, root_index))
+            } else {
+                println!("Skipping reply {} whose thread root {root_id} was not imported", m.id);
+                None
+            }
+        })
+        .collect();
+
+    for batch in replies.chunks(BATCH_SIZE) {
+        let to_import = batch
+            .iter()
+            .map(|(m, root_index)| to_imported_message(source, m, Some(*root_index)))
+            .collect();
+
+        import_batch(agent, community_id, channel_id, to_import).await?;
+        imported += batch.len();
+    }
+
+    Ok(imported)
+}
+
+async fn import_batch(
+    agent: &Agent,
+    community_id: &CanisterId,
+    channel_id: ChannelId,
+    messages: Vec<ImportedMessage>,
+) -> Result<Vec<MessageIndex>, Box<dyn Error + Send + Sync>> {
+    let args = community_canister::import_history::Args { channel_id, messages };
+
+    // Message ids are derived from the source message ids and the canister skips any it has
+    // already imported, so a batch whose call failed can safely be sent again
+    let mut attempt = 1;
+    loop {
+        match community_canister_client::import_history(agent, community_id, &args).await {
+            Ok(community_canister::import_history::Response::Success(result)) => return Ok(result.message_indexes),
+            Ok(community_canister::import_history::Response::Error(error)) => {
+                return Err(format!("Failed to import messages: {error:?}"))?;
+            }
+            Err(error) if attempt < MAX_ATTEMPTS_PER_BATCH => {
+                println!("Failed to import batch, retrying. Error: {error:?}");
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn to_imported_message(
+    source: ImportSource,
+    message: &SourceMessage,
+    thread_root_message_index: Option<MessageIndex>,
+) -> ImportedMessage {
+    ImportedMessage {
+        message_id: message_id(source, message),
+        thread_root_message_index,
+        text: message.text.clone(),
+        context: ImportedMessageContext {
+            source,
+            author_name: message.author_name.clone(),
+            original_timestamp: message.timestamp,
+        },
+    }
+}
+
+fn message_id(source: ImportSource, message: &SourceMessage) -> MessageId {
+    let hash = sha256::sha256(format!("{source:?}:{}", message.id).as_bytes());
+    u64::from_be_bytes(hash[..8].try_into().unwrap()).into()
+}
@@ -0,0 +1,14 @@
+use clap::Parser;
+use history_importer::Config;
+use history_importer::run;
+use std::process;
+
+#[tokio::main]
+async fn main() {
+    let config = Config::parse();
+
+    if let Err(e) = run(config).await {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{SourceChannel, SourceMessage};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use types::TimestampMillis;
+
+// Subtypes of messages which were written by a user, everything else (joins, topic changes, etc)
+// is skipped
+const IMPORTED_SUBTYPES: [&str; 4] = ["bot_message", "file_share", "me_message", "thread_broadcast"];
+
+#[derive(Deserialize)]
+struct Channel {
+    name: String,
+    #[serde(default)]
+    purpose: Option<Purpose>,
+}
+
+#[derive(Deserialize)]
+struct Purpose {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct User {
+    id: String,
+    name: String,
+    #[serde(default)]
+    real_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    ts: String,
+    #[serde(default)]
+    thread_ts: Option<String>,
+    #[serde(default)]
+    subtype: Option<String>,
+    #[serde(default)]
+    user: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    text: String,
+}
+
+// A Slack export is a directory containing `channels.json`, `users.json` and a directory per
+// channel holding one JSON file of messages per day
+pub fn read_export(path: &str) -> Result<Vec<SourceChannel>, Box<dyn Error + Send + Sync>> {
+    let root = Path::new(path);
+    let channels: Vec<Channel> = serde_json::from_str(&fs::read_to_string(root.join("channels.json"))?)?;
+    let users: HashMap<String, String> = match fs::read_to_string(root.join("users.json")) {
+        Ok(text) => serde_json::from_str::<Vec<User>>(&text)?
+            .into_iter()
+            .map(|u| (u.id, u.real_name.filter(|n| !n.is_empty()).unwrap_or(u.name)))
+            .collect(),
+        Err(_) => HashMap::new(),
+    };
+
+    let mut result = Vec::new();
+    for channel in channels {
+        let mut day_files: Vec<_> = fs::read_dir(root.join(&channel.name))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .collect();
+        day_files.sort();
+
+        let mut messages = Vec::new();
+        for file in day_files {
+            messages.extend(parse_day(&channel.name, &users, &fs::read_to_string(file)?)?);
+        }
+
+        result.push(SourceChannel {
+            name: channel.name,
+            description: channel.purpose.map(|p| p.value).unwrap_or_default(),
+            messages,
+        });
+    }
+
+    Ok(result)
+}
+
+fn parse_day(
+    channel_name: &str,
+    users: &HashMap<String, String>,
+    json: &str,
+) -> Result<Vec<SourceMessage>, Box<dyn Error + Send + Sync>> {
+    let day: Vec<Message> = serde_json::from_str(json)?;
+
+    let mut messages = Vec::new();
+    for message in day {
+        if message
+            .subtype
+            .as_ref()
+            .is_some_and(|s| !IMPORTED_SUBTYPES.contains(&s.as_str()))
+        {
+            continue;
+        }
+
+        let author_name = message
+            .user
+            .as_ref()
+            .and_then(|u| users.get(u).cloned())
+            .or(message.username)
+            .or(message.user)
+            .unwrap_or_default();
+
+        messages.push(SourceMessage {
+            id: format!("{channel_name}/{}", message.ts),
+            thread_root_id: message
+                .thread_ts
+                .filter(|t| *t != message.ts)
+                .map(|t| format!("{channel_name}/{t}")),
+            author_name,
+            text: message.text,
+            timestamp: parse_ts(&message.ts)?,
+        });
+    }
+
+    Ok(messages)
+}
+
+// Slack timestamps are strings of the form "{seconds}.{microseconds}"
+fn parse_ts(ts: &str) -> Result<TimestampMillis, Box<dyn Error + Send + Sync>> {
+    let (seconds, micros) = ts.split_once('.').unwrap_or((ts, "0"));
+    let seconds: u64 = seconds.parse()?;
+    let micros: u64 = format!("{micros:0<6}")[..6].parse()?;
+
+    Ok(seconds * 1000 + micros / 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ts_converts_to_millis() {
+        assert_eq!(parse_ts("1600000000.123456").unwrap(), 1_600_000_000_123);
+    }
+
+    #[test]
+    fn parse_ts_pads_short_fractions() {
+        assert_eq!(parse_ts("1600000000.5").unwrap(), 1_600_000_000_500);
+    }
+
+    #[test]
+    fn parse_ts_without_fraction() {
+        assert_eq!(parse_ts("1600000000").unwrap(), 1_600_000_000_000);
+    }
+
+    #[test]
+    fn parse_ts_invalid_fails() {
+        assert!(parse_ts("abc.123").is_err());
+    }
+
+    #[test]
+    fn parse_day_maps_threads_authors_and_skips_system_messages() {
+        let users = HashMap::from([("U1".to_string(), "Alice".to_string())]);
+        let json = r#"[
+            {"ts": "1600000000.000100", "user": "U1", "text": "root", "thread_ts": "1600000000.000100"},
+            {"ts": "1600000001.000200", "user": "U2", "text": "reply", "thread_ts": "1600000000.000100"},
+            {"ts": "1600000002.000300", "subtype": "channel_join", "user": "U1", "text": "joined"},
+            {"ts": "1600000003.000400", "subtype": "bot_message", "username": "bot", "text": "beep"}
+        ]"#;
+
+        let messages = parse_day("general", &users, json).unwrap();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].id, "general/1600000000.000100");
+        assert!(messages[0].thread_root_id.is_none());
+        assert_eq!(messages[0].author_name, "Alice");
+        assert_eq!(messages[0].timestamp, 1_600_000_000_000);
+        assert_eq!(messages[1].thread_root_id.as_deref(), Some("general/1600000000.000100"));
+        assert_eq!(messages[1].author_name, "U2");
+        assert_eq!(messages[2].author_name, "bot");
+        assert_eq!(messages[2].text, "beep");
+    }
+}
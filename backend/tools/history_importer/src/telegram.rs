@@ -0,0 +1,161 @@
+use crate::{SourceChannel, SourceMessage};
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Export {
+    All { chats: ChatList },
+    Single(Chat),
+}
+
+#[derive(Deserialize)]
+struct ChatList {
+    list: Vec<Chat>,
+}
+
+#[derive(Deserialize)]
+struct Chat {
+    id: i64,
+    #[serde(default)]
+    name: Option<String>,
+    messages: Vec<Message>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    id: i64,
+    #[serde(rename = "type")]
+    kind: String,
+    date_unixtime: String,
+    #[serde(default)]
+    from: Option<String>,
+    #[serde(default)]
+    text: Text,
+}
+
+// Plain messages have their text as a string, whereas messages containing formatting, links, etc
+// have it as an array of plain strings and entity objects
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Text {
+    Plain(String),
+    Parts(Vec<TextPart>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TextPart {
+    Plain(String),
+    Entity { text: String },
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Text::Plain(String::new())
+    }
+}
+
+impl Text {
+    fn into_string(self) -> String {
+        match self {
+            Text::Plain(text) => text,
+            Text::Parts(parts) => parts
+                .into_iter()
+                .map(|p| match p {
+                    TextPart::Plain(text) | TextPart::Entity { text } => text,
+                })
+                .collect(),
+        }
+    }
+}
+
+// Reads the `result.json` produced by Telegram Desktop, which contains either a single chat or,
+// when exporting all data, a list of chats. Telegram replies quote the message they reply to
+// rather than starting a thread so they are imported as top level messages.
+pub fn read_export(path: &str) -> Result<Vec<SourceChannel>, Box<dyn Error + Send + Sync>> {
+    parse_export(&fs::read_to_string(path)?)
+}
+
+fn parse_export(json: &str) -> Result<Vec<SourceChannel>, Box<dyn Error + Send + Sync>> {
+    let chats = match serde_json::from_str(json)? {
+        Export::All { chats } => chats.list,
+        Export::Single(chat) => vec![chat],
+    };
+
+    let mut result = Vec::new();
+    for chat in chats {
+        let mut messages = Vec::new();
+        for message in chat.messages {
+            if message.kind != "message" {
+                continue;
+            }
+
+            let seconds: u64 = message.date_unixtime.parse()?;
+
+            messages.push(SourceMessage {
+                id: format!("{}/{}", chat.id, message.id),
+                thread_root_id: None,
+                author_name: message.from.unwrap_or_default(),
+                text: message.text.into_string(),
+                timestamp: seconds * 1000,
+            });
+        }
+
+        result.push(SourceChannel {
+            name: chat.name.unwrap_or_else(|| chat.id.to_string()),
+            description: String::new(),
+            messages,
+        });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single_chat() {
+        let json = r#"{
+            "id": 123,
+            "name": "My group",
+            "messages": [
+                {"id": 1, "type": "message", "date_unixtime": "1600000000", "from": "Alice", "text": "hello"},
+                {"id": 2, "type": "service", "date_unixtime": "1600000001", "actor": "Alice", "action": "pin_message"},
+                {"id": 3, "type": "message", "date_unixtime": "1600000002", "from": "Bob", "text": ["see ", {"type": "link", "text": "https://oc.app"}, "!"]}
+            ]
+        }"#;
+
+        let channels = parse_export(json).unwrap();
+
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].name, "My group");
+        assert_eq!(channels[0].messages.len(), 2);
+        assert_eq!(channels[0].messages[0].id, "123/1");
+        assert_eq!(channels[0].messages[0].author_name, "Alice");
+        assert_eq!(channels[0].messages[0].timestamp, 1_600_000_000_000);
+        assert_eq!(channels[0].messages[1].text, "see https://oc.app!");
+    }
+
+    #[test]
+    fn parse_all_chats() {
+        let json = r#"{
+            "about": "...",
+            "chats": {
+                "list": [
+                    {"id": 1, "messages": []},
+                    {"id": 2, "name": "Second", "messages": []}
+                ]
+            }
+        }"#;
+
+        let channels = parse_export(json).unwrap();
+
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0].name, "1");
+        assert_eq!(channels[1].name, "Second");
+    }
+}
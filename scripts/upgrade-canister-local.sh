@@ -12,4 +12,5 @@ SCRIPT=$(readlink -f "$0")
 SCRIPT_DIR=$(dirname "$SCRIPT")
 cd $SCRIPT_DIR
 
-./upgrade-canister.sh local http://127.0.0.1:8080/ $IDENTITY $CANISTER_NAME $VERSION $WASM_SRC
+# There is no need to rehearse upgrades against a local copy of a local canister
+./upgrade-canister.sh local http://127.0.0.1:8080/ $IDENTITY $CANISTER_NAME $VERSION "$WASM_SRC" --skip-dry-run
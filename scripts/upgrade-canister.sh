@@ -2,6 +2,7 @@
 
 # Pass in network name, IC url, identity name, canister name, and version
 # eg './upgrade-canister.sh local http://127.0.0.1:8080/ openchat user_index 1.0.0'
+# Any further args are passed through to the canister_upgrader, eg. '--dry-run' or '--skip-dry-run'
 
 NETWORK=$1
 IC_URL=$2
@@ -65,3 +66,4 @@ cargo run \
   --sign-in-with-email $SIGN_IN_WITH_EMAIL_CANISTER_ID \
   --canister-to-upgrade $CANISTER_NAME \
   --version $VERSION \
+  "${@:7}"